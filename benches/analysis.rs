@@ -0,0 +1,83 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+
+use visualization::srp_analysis::{self, *};
+
+const SIZES: [usize; 3] = [10, 100, 1000];
+const RESOURCES: usize = 8;
+
+// Without memoization exact mode recurses into every higher priority task, which is exponential
+// in the number of distinct priorities, so only the smallest set is feasible.
+#[cfg(not(feature = "perf"))]
+const EXACT_SIZES: [usize; 1] = [10];
+#[cfg(feature = "perf")]
+const EXACT_SIZES: [usize; 3] = SIZES;
+
+#[cfg(not(feature = "perf"))]
+fn exact_response_time(tasks: &Tasks) -> Result<u32, String> {
+    tasks[0].response_time(tasks, &PreemptionMode::Exact)
+}
+
+#[cfg(feature = "perf")]
+fn exact_response_time(tasks: &Tasks) -> Result<u32, String> {
+    AnalysisContext::new(tasks).response_time(0, &PreemptionMode::Exact)
+}
+
+fn blocking_time(c: &mut Criterion) {
+    let mut group = c.benchmark_group("blocking_time");
+    for n in SIZES {
+        let tasks = synthetic_task_set(n, RESOURCES);
+        group.bench_with_input(BenchmarkId::from_parameter(n), &tasks, |b, tasks| {
+            b.iter(|| black_box(&tasks[n - 1]).blocking_time(tasks))
+        });
+    }
+    group.finish();
+}
+
+fn interference(c: &mut Criterion) {
+    let mut group = c.benchmark_group("interference");
+    for n in SIZES {
+        let tasks = synthetic_task_set(n, RESOURCES);
+        group.bench_with_input(BenchmarkId::from_parameter(n), &tasks, |b, tasks| {
+            b.iter(|| black_box(&tasks[0]).interference(tasks))
+        });
+    }
+    group.finish();
+}
+
+fn response_time(c: &mut Criterion) {
+    let mut group = c.benchmark_group("response_time");
+    for n in SIZES {
+        let tasks = synthetic_task_set(n, RESOURCES);
+        group.bench_with_input(BenchmarkId::new("approximate", n), &tasks, |b, tasks| {
+            b.iter(|| black_box(&tasks[0]).response_time(tasks, &PreemptionMode::Approximate))
+        });
+    }
+    for n in EXACT_SIZES {
+        let tasks = synthetic_task_set(n, RESOURCES);
+        group.bench_with_input(BenchmarkId::new("exact", n), &tasks, |b, tasks| {
+            b.iter(|| exact_response_time(black_box(tasks)))
+        });
+    }
+    group.finish();
+}
+
+fn srp_analyze(c: &mut Criterion) {
+    let mut group = c.benchmark_group("srp_analyze");
+    group.sample_size(10);
+    for n in SIZES {
+        let tasks = synthetic_task_set(n, RESOURCES);
+        group.bench_with_input(BenchmarkId::new("approximate", n), &tasks, |b, tasks| {
+            b.iter(|| srp_analysis::srp_analyze(tasks, &AnalysisConfig::new(PreemptionMode::Approximate)))
+        });
+    }
+    for n in EXACT_SIZES {
+        let tasks = synthetic_task_set(n, RESOURCES);
+        group.bench_with_input(BenchmarkId::new("exact", n), &tasks, |b, tasks| {
+            b.iter(|| srp_analysis::srp_analyze(tasks, &AnalysisConfig::new(PreemptionMode::Exact)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, blocking_time, interference, response_time, srp_analyze);
+criterion_main!(benches);
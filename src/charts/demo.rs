@@ -0,0 +1,33 @@
+use charming::{
+    component::{Axis3D, Grid3D, VisualMap},
+    datatype::{CompositeValue, Dataset},
+    element::{AxisType, DimensionEncode, Tooltip},
+    series::Bar3d,
+    Chart,
+};
+
+use super::Theme;
+
+/// Demo of a 3D bar chart of the life expectancy per country and year.
+pub fn chart2(theme: &Theme) -> Chart {
+    let data: Vec<Vec<CompositeValue>> =
+        serde_json::from_str(include_str!("../life-expectancy-table.json")).unwrap();
+
+    theme.chart()
+        .grid3d(Grid3D::new())
+        .tooltip(Tooltip::new())
+        .x_axis3d(Axis3D::new().type_(AxisType::Category))
+        .y_axis3d(Axis3D::new().type_(AxisType::Category))
+        .z_axis3d(Axis3D::new())
+        .visual_map(VisualMap::new().max(1e8).dimension("Population"))
+        .dataset(Dataset::new().source(data))
+        .series(
+            Bar3d::new().shading("lambert").encode(
+                DimensionEncode::new()
+                    .x("Year")
+                    .y("Country")
+                    .z("Life Expectancy")
+                    .tooltip(vec![0, 1, 2, 3, 4]),
+            ),
+        )
+}
@@ -0,0 +1,905 @@
+mod demo;
+mod registry;
+mod spec;
+mod theme;
+
+pub use demo::*;
+pub use registry::*;
+pub use spec::*;
+pub use theme::*;
+
+use std::collections::BTreeMap;
+
+use charming::{
+    component::{DataZoom, DataZoomType, Grid, Title, VisualMap},
+    datatype::{CompositeValue, DataPointItem},
+    element::{
+        AxisPointer, AxisPointerType, AxisType, Emphasis, EmphasisFocus, LineStyle, LineStyleType, MarkLine,
+        MarkLineData, MarkLineVariant, Orient, SplitArea, Tooltip, Trigger,
+    },
+    series::{Bar, Heatmap, Line, Pie, Scatter},
+    Chart,
+};
+
+use crate::srp_analysis::*;
+
+// Name of the time axes, real time if there is a clock model
+fn time_unit(clock: Option<ClockModel>) -> &'static str {
+    if clock.is_some() { "µs" } else { "ticks" }
+}
+
+// Duration of `ticks` in `time_unit`
+fn duration(clock: Option<ClockModel>, ticks: u32) -> f64 {
+    clock.map_or(ticks as f64, |clock| clock.micros(ticks))
+}
+
+/// Stacked utilization per task group, next to the worst response ratio R(t) / D(t) of each group.
+pub fn group_chart(results: &[AnalysisResult], theme: &Theme) -> Chart {
+    let groups = group_analysis(results);
+
+    let labels: Vec<String> = groups
+        .iter()
+        .map(|g| g.group.clone().unwrap_or_else(|| "ungrouped".to_string()))
+        .collect();
+
+    let mut chart = theme.chart()
+        .title(theme.title("Utilization per group"))
+        .tooltip(
+            Tooltip::new()
+                .trigger(Trigger::Axis)
+                .axis_pointer(AxisPointer::new().type_(AxisPointerType::Shadow)),
+        )
+        .legend(theme.legend())
+        .grid(Grid::new().left("3%").right("4%").contain_label(true))
+        .x_axis(theme.axis().type_(AxisType::Category).data(labels))
+        .y_axis(theme.axis().type_(AxisType::Value));
+
+    // One series per task, only contributing to the bar of its own group
+    for (task, ..) in results {
+        let data: Vec<f64> = groups
+            .iter()
+            .map(|g| {
+                if g.group == task.group {
+                    task.load_factor().unwrap_or(0.0) as f64
+                } else {
+                    0.0
+                }
+            })
+            .collect();
+
+        chart = chart.series(
+            Bar::new()
+                .name(task.id.as_str())
+                .stack("utilization")
+                .emphasis(Emphasis::new().focus(EmphasisFocus::Series))
+                .data(data),
+        );
+    }
+
+    // Unbounded response ratios are drawn as zero height bars
+    chart.series(
+        Bar::new().name("worst R/D").data(
+            groups
+                .iter()
+                .map(|g| g.worst_response_ratio.unwrap_or(0.0) as f64)
+                .collect(),
+        ),
+    )
+}
+
+/// Share L(t) = C(t) / A(t) of the CPU of every task as a donut, next to the idle capacity 1 - L_tot.
+///
+/// An overloaded task set, L_tot > 1, has no idle capacity.
+pub fn utilization_chart(tasks: &Tasks, theme: &Theme) -> Chart {
+    let chart = theme.chart().tooltip(Tooltip::new().trigger(Trigger::Item)).legend(theme.legend());
+    let load_factors = match load_factors(tasks) {
+        Ok(load_factors) => load_factors,
+        Err(e) => return chart.title(theme.title("CPU utilization").subtext(e)),
+    };
+
+    let total: f32 = load_factors.iter().map(|(_, load_factor)| load_factor).sum();
+    let mut data: Vec<(f64, String)> =
+        load_factors.iter().map(|(task, load_factor)| (*load_factor as f64, task.id.clone())).collect();
+    let subtext = if total > 1.0 {
+        format!("Overloaded, L_tot = {:.3}", total)
+    } else {
+        data.push(((1.0 - total) as f64, "idle".to_string()));
+        format!("L_tot = {:.3}", total)
+    };
+
+    chart
+        .title(theme.title("CPU utilization").subtext(subtext))
+        .series(Pie::new().name("L(t)").radius(vec!["40%", "70%"]).data(data))
+}
+
+/// U(t) of every task against its slack ratio (D(t) - R(t)) / D(t), one series per `MarginQuadrant`, with the
+/// mean U(t) and `TIGHT_SLACK_RATIO` dividing the quadrants. Reads at a glance where bars of many tasks clutter.
+///
+/// Tasks without a bounded response time are not drawn but counted in the subtitle.
+pub fn margin_chart(margins: &[TaskMargin], theme: &Theme) -> Chart {
+    let quadrants = [MarginQuadrant::Healthy, MarginQuadrant::Heavy, MarginQuadrant::Starved, MarginQuadrant::Critical];
+    let mut counts: Vec<String> = quadrants
+        .iter()
+        .map(|&quadrant| {
+            format!("{} {}", margins.iter().filter(|m| m.quadrant == quadrant).count(), quadrant.label())
+        })
+        .collect();
+    let unbounded = margins.iter().filter(|m| m.slack_ratio.is_none()).count();
+    if unbounded > 0 {
+        counts.push(format!("{} unbounded", unbounded));
+    }
+    let utilizations: Vec<f32> = margins.iter().map(|m| m.utilization).collect();
+
+    let mut chart = theme.chart()
+        .title(theme.title("Utilization vs. margin").subtext(counts.join(", ")))
+        .tooltip(Tooltip::new().trigger(Trigger::Item))
+        .legend(theme.legend())
+        .grid(Grid::new().left("3%").right("4%").contain_label(true))
+        .x_axis(theme.axis().type_(AxisType::Value).name("U(t)"))
+        .y_axis(theme.axis().type_(AxisType::Value).name("(D - R) / D"));
+
+    for (i, quadrant) in quadrants.into_iter().enumerate() {
+        // The task id is a third dimension, shown in the tooltip
+        let data: Vec<Vec<CompositeValue>> = margins
+            .iter()
+            .filter(|m| m.quadrant == quadrant)
+            .filter_map(|m| {
+                Some(vec![(m.utilization as f64).into(), (m.slack_ratio? as f64).into(), m.id.as_str().into()])
+            })
+            .collect();
+        let mut series = Scatter::new().name(quadrant.label()).symbol_size(12).data(data);
+        if i == 0 {
+            let mean = mean_utilization(&utilizations) as f64;
+            series = series.mark_line(
+                MarkLine::new().line_style(LineStyle::new().type_(LineStyleType::Dashed)).data(vec![
+                    MarkLineVariant::Simple(MarkLineData::new().name("mean U(t)").x_axis(mean)),
+                    MarkLineVariant::Simple(MarkLineData::new().name("tight").y_axis(TIGHT_SLACK_RATIO as f64)),
+                ]),
+            );
+        }
+        chart = chart.series(series);
+    }
+    chart
+}
+
+/// Response time R(t) of every task as a stack of C(t), B(t) and I(t), next to its deadline D(t).
+///
+/// Hovering B(t) names the critical section blocking the task, see `Task::blocker`, and hovering I(t) the
+/// interfering tasks with their share I_h(t), see `TaskSchedulable::interference_breakdown`. I(t) is the rest of
+/// R(t), so it includes J(t) and, outside of the approximate mode, every other term of R(t).
+///
+/// Tasks without a bounded response time are drawn with a zero height response time bar. Times are in
+/// microseconds if there is a clock model, else in ticks.
+///
+/// With the `previous` analysis of the same task set, e.g. before an edit, its response times are overlaid as
+/// a line, and the tasks whose R(t) grew are counted in the subtitle.
+pub fn response_time_chart(
+    results: &[AnalysisResult],
+    previous: Option<&[TaskAnalysis]>,
+    clock: Option<ClockModel>,
+    theme: &Theme,
+) -> Chart {
+    let mut title = theme.title("Response times");
+    // R(t) of the task in the previous analysis, `None` for new tasks
+    let before = |task: &Task| previous?.iter().find(|p| p.id == task.id).map(|p| p.response_time.clone());
+    if previous.is_some() {
+        // Newly unbounded response times are regressions as well
+        let slower = results
+            .iter()
+            .filter(|(task, response_time, ..)| match (response_time, before(task)) {
+                (Ok(r), Some(Ok(p))) => *r > p,
+                (Err(_), Some(Ok(_))) => true,
+                _ => false,
+            })
+            .count();
+        title = title.subtext(format!("{} task(s) slower than before the last change", slower));
+    }
+
+    // The breakdowns need the whole task set, which the results list in order
+    let tasks: Tasks = results.iter().map(|(task, ..)| (*task).clone()).collect();
+    // C(t), B(t) and the rest of R(t) of every task, all zero if it is unbounded
+    let parts: Vec<(u32, u32, u32)> = results
+        .iter()
+        .map(|(_, response_time, blocking_time, critical_time, _)| match response_time {
+            Ok(r) => (*critical_time, *blocking_time, r.saturating_sub(blocking_time + critical_time)),
+            Err(_) => (0, 0, 0),
+        })
+        .collect();
+    let blocking: Vec<DataPointItem> = tasks
+        .iter()
+        .zip(&parts)
+        .map(|(task, (_, b, _))| {
+            let source = task.blocker(&tasks).map_or("nothing".to_string(), |blocker| blocker.to_string());
+            DataPointItem::new(duration(clock, *b)).name(format!("{} blocked by {}", task.id, source))
+        })
+        .collect();
+    let interference: Vec<DataPointItem> = tasks
+        .iter()
+        .zip(&parts)
+        .map(|(task, (.., i))| {
+            let shares: Vec<String> = task
+                .interference_breakdown(&tasks)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(h, share)| format!("{} ({})", h, share))
+                .collect();
+            let sources = if shares.is_empty() { "nothing".to_string() } else { shares.join(", ") };
+            DataPointItem::new(duration(clock, *i)).name(format!("{} interfered by {}", task.id, sources))
+        })
+        .collect();
+
+    let chart = theme.chart()
+        .title(title)
+        .tooltip(Tooltip::new().trigger(Trigger::Item).formatter("{a}: {c}<br/>{b}"))
+        .legend(theme.legend())
+        .grid(Grid::new().left("3%").right("4%").contain_label(true))
+        .x_axis(
+            theme.axis()
+                .type_(AxisType::Category)
+                .data(results.iter().map(|(task, ..)| task.id.clone()).collect()),
+        )
+        .y_axis(theme.axis().type_(AxisType::Value).name(time_unit(clock)))
+        .series(
+            Bar::new()
+                .name("C(t)")
+                .stack("R(t)")
+                .data(parts.iter().map(|(c, ..)| duration(clock, *c)).collect()),
+        )
+        .series(Bar::new().name("B(t)").stack("R(t)").data(blocking))
+        .series(Bar::new().name("I(t)").stack("R(t)").data(interference))
+        .series(
+            Bar::new()
+                .name("D(t)")
+                .data(results.iter().map(|(task, ..)| duration(clock, task.deadline)).collect()),
+        );
+    if previous.is_none() {
+        return chart;
+    }
+
+    // Unbounded or missing previous response times leave gaps in the line
+    chart.series(
+        Line::new().name("previous R(t)").data(
+            results
+                .iter()
+                .map(|(task, ..)| match before(task) {
+                    Some(Ok(r)) => CompositeValue::from(duration(clock, r)),
+                    _ => CompositeValue::from("-"),
+                })
+                .collect(),
+        ),
+    )
+}
+
+/// Composition of the approximate response time R(t) = B(t) + C(t) + I(t) of every task, with I(t) split up
+/// into the contribution I_h(t) of each interfering task h.
+pub fn response_composition_chart(tasks: &Tasks, clock: Option<ClockModel>, theme: &Theme) -> Chart {
+    let breakdowns: Vec<BTreeMap<String, u32>> =
+        tasks.iter().map(|t| t.interference_breakdown(tasks).unwrap_or_default()).collect();
+
+    let mut chart = theme
+        .chart()
+        .title(theme.title("Response time composition"))
+        .tooltip(
+            Tooltip::new()
+                .trigger(Trigger::Axis)
+                .axis_pointer(AxisPointer::new().type_(AxisPointerType::Shadow)),
+        )
+        .legend(theme.legend())
+        .grid(Grid::new().left("3%").right("4%").contain_label(true))
+        .x_axis(
+            theme
+                .axis()
+                .type_(AxisType::Category)
+                .data(tasks.iter().map(|t| t.id.clone()).collect()),
+        )
+        .y_axis(theme.axis().type_(AxisType::Value).name(time_unit(clock)))
+        .series(
+            Bar::new()
+                .name("B(t)")
+                .stack("R(t)")
+                .data(tasks.iter().map(|t| duration(clock, t.blocking_time(tasks))).collect()),
+        )
+        .series(
+            Bar::new()
+                .name("C(t)")
+                .stack("R(t)")
+                .data(tasks.iter().map(|t| duration(clock, t.wcet())).collect()),
+        );
+
+    // One series per interfering task, zero for the tasks it does not interfere with
+    for h in tasks {
+        chart = chart.series(
+            Bar::new()
+                .name(format!("I({})", h.id))
+                .stack("R(t)")
+                .emphasis(Emphasis::new().focus(EmphasisFocus::Series))
+                .data(breakdowns.iter().map(|b| duration(clock, b.get(&h.id).copied().unwrap_or(0))).collect()),
+        );
+    }
+    chart
+}
+
+// Number of bars the measured execution times are grouped into
+const HISTOGRAM_BINS: u32 = 20;
+
+/// Histogram of the measured execution times of a task, with the WCET C(t) used by the analysis marked.
+pub fn measurement_chart(task: &str, samples: &[u32], wcet: u32, theme: &Theme) -> Chart {
+    let min = samples.iter().copied().min().unwrap_or(0);
+    let max = samples.iter().copied().max().unwrap_or(0);
+    let width = ((max - min) / HISTOGRAM_BINS + 1) as f64;
+
+    let mut counts = vec![0u32; HISTOGRAM_BINS as usize + 1];
+    for &sample in samples {
+        counts[((sample - min) as f64 / width) as usize] += 1;
+    }
+    // Bars are placed at the center of their bin on a value axis, so the WCET line lines up with them
+    let data: Vec<Vec<f64>> = counts
+        .iter()
+        .enumerate()
+        .filter(|(_, &count)| count > 0)
+        .map(|(bin, &count)| vec![min as f64 + (bin as f64 + 0.5) * width, count as f64])
+        .collect();
+
+    theme
+        .chart()
+        .title(theme.title(&format!("Measured execution times of {}", task)))
+        .tooltip(Tooltip::new().trigger(Trigger::Axis))
+        .legend(theme.legend())
+        .grid(Grid::new().left("3%").right("4%").contain_label(true))
+        .x_axis(theme.axis().type_(AxisType::Value).name("execution time"))
+        .y_axis(theme.axis().type_(AxisType::Value).name("samples"))
+        .series(
+            Bar::new()
+                .name("samples")
+                .bar_width(8)
+                .mark_line(
+                    MarkLine::new()
+                        .line_style(LineStyle::new().type_(LineStyleType::Dashed))
+                        .data(vec![MarkLineVariant::Simple(MarkLineData::new().name("C(t)").x_axis(wcet as f64))]),
+                )
+                .data(data),
+        )
+}
+
+/// End-to-end latency L(c) of every chain next to its deadline D(c).
+///
+/// Chains without a bounded latency are drawn with a zero height latency bar.
+pub fn chain_chart(latencies: &[ChainLatency], theme: &Theme) -> Chart {
+    theme.chart()
+        .title(theme.title("End-to-end latencies"))
+        .tooltip(
+            Tooltip::new()
+                .trigger(Trigger::Axis)
+                .axis_pointer(AxisPointer::new().type_(AxisPointerType::Shadow)),
+        )
+        .legend(theme.legend())
+        .grid(Grid::new().left("3%").right("4%").contain_label(true))
+        .x_axis(
+            theme.axis()
+                .type_(AxisType::Category)
+                .data(latencies.iter().map(|l| l.chain.clone()).collect()),
+        )
+        .y_axis(theme.axis().type_(AxisType::Value))
+        .series(
+            Bar::new()
+                .name("L(c)")
+                .data(latencies.iter().map(|l| *l.latency.as_ref().unwrap_or(&0)).collect()),
+        )
+        .series(
+            Bar::new()
+                .name("D(c)")
+                .data(latencies.iter().map(|l| l.deadline).collect()),
+        )
+}
+
+/// Largest observed response time of every measured task next to its analytical worst case R(t).
+pub fn observed_chart(observations: &[ObservedResponseTime], theme: &Theme) -> Chart {
+    let exceeded = observations.iter().filter(|o| o.exceeds_bound).count();
+    theme.chart()
+        .title(theme.title("Observed response times").subtext(format!("{} task(s) exceed R(t)", exceeded)))
+        .tooltip(
+            Tooltip::new()
+                .trigger(Trigger::Axis)
+                .axis_pointer(AxisPointer::new().type_(AxisPointerType::Shadow)),
+        )
+        .legend(theme.legend())
+        .grid(Grid::new().left("3%").right("4%").contain_label(true))
+        .x_axis(
+            theme.axis()
+                .type_(AxisType::Category)
+                .data(observations.iter().map(|o| o.task.clone()).collect()),
+        )
+        .y_axis(theme.axis().type_(AxisType::Value))
+        .series(Bar::new().name("observed").data(observations.iter().map(|o| o.observed).collect()))
+        .series(
+            Bar::new()
+                .name("R(t)")
+                .data(observations.iter().map(|o| *o.bound.as_ref().unwrap_or(&0)).collect()),
+        )
+}
+
+/// Priority inversions found in ingested traces, one line per inversion at the priority of the waiting task,
+/// with one series per waiting task on the time axis of the device.
+pub fn inversion_chart(tasks: &Tasks, inversions: &[PriorityInversion], theme: &Theme) -> Chart {
+    let total: u64 = inversions.iter().map(PriorityInversion::duration).sum();
+    let mut chart = theme.chart()
+        .title(
+            theme
+                .title("Priority inversions")
+                .subtext(format!("{} inversion(s) over {} time units", inversions.len(), total)),
+        )
+        .tooltip(Tooltip::new().trigger(Trigger::Item))
+        .legend(theme.legend())
+        .grid(Grid::new().left("3%").right("4%").contain_label(true))
+        .x_axis(theme.axis().type_(AxisType::Value).name("t"))
+        .y_axis(theme.axis().type_(AxisType::Value).name("priority"))
+        .data_zoom(DataZoom::new().type_(DataZoomType::Inside))
+        .data_zoom(DataZoom::new().type_(DataZoomType::Slider));
+
+    for task in tasks {
+        // A "-" value interrupts the line between two inversions
+        let mut data: Vec<Vec<CompositeValue>> = vec![];
+        for inversion in inversions.iter().filter(|inversion| inversion.task == task.id) {
+            data.push(vec![(inversion.start as f64).into(), (task.prio as f64).into()]);
+            data.push(vec![(inversion.end as f64).into(), (task.prio as f64).into()]);
+            data.push(vec![(inversion.end as f64).into(), "-".into()]);
+        }
+        if !data.is_empty() {
+            chart = chart.series(Line::new().name(format!("{} waits", task.id).as_str()).data(data));
+        }
+    }
+    chart
+}
+
+/// Simulated execution of every task drawn at its priority level, together with the system ceiling Π(t).
+///
+/// A job that is released but not running while Π(t) >= P(t) is delayed by the ceiling.
+pub fn timeline_chart(tasks: &Tasks, simulation: &Simulation, clock: Option<ClockModel>, theme: &Theme) -> Chart {
+    schedule_chart(tasks, simulation, theme.title("Execution and system ceiling"), clock, theme)
+}
+
+/// Simulated critical instant of a task, see `critical_instant`, drawn like `timeline_chart` with the release
+/// and the absolute deadline of the analyzed job marked.
+pub fn scenario_chart(tasks: &Tasks, scenario: &Scenario, clock: Option<ClockModel>, theme: &Theme) -> Chart {
+    let job = &scenario.job;
+    let outcome = match job.completion {
+        Some(completion) if completion > job.deadline => {
+            format!("misses its deadline by {} {}", duration(clock, completion - job.deadline), time_unit(clock))
+        }
+        Some(_) => "meets its deadline".to_string(),
+        None => "does not complete within the simulated time".to_string(),
+    };
+    let blocking = match (&scenario.blocker, &scenario.resource) {
+        (Some(blocker), Some(resource)) => format!(", blocked by {} on {}", blocker, resource),
+        _ => String::new(),
+    };
+    let title = theme
+        .title(&format!("Critical instant of {}", scenario.task))
+        .subtext(format!("{} {}{}", scenario.task, outcome, blocking));
+
+    let mark = |name: &str, time: u32| {
+        MarkLineVariant::Simple(MarkLineData::new().name(name).x_axis(duration(clock, time)))
+    };
+    schedule_chart(tasks, &scenario.simulation, title, clock, theme).series(
+        Line::new().name(format!("job of {}", scenario.task).as_str()).data(Vec::<f64>::new()).mark_line(
+            MarkLine::new()
+                .line_style(LineStyle::new().type_(LineStyleType::Dashed))
+                .data(vec![mark("release", job.release), mark("deadline", job.deadline)]),
+        ),
+    )
+}
+
+// Execution slices of every task at its priority level and the system ceiling Π(t)
+fn schedule_chart(tasks: &Tasks, simulation: &Simulation, title: Title, clock: Option<ClockModel>, theme: &Theme) -> Chart {
+    let point = |time: u32, level: CompositeValue| vec![CompositeValue::from(duration(clock, time)), level];
+
+    let mut chart = theme.chart()
+        .title(title)
+        .tooltip(Tooltip::new().trigger(Trigger::Axis))
+        .legend(theme.legend())
+        .grid(Grid::new().left("3%").right("4%").contain_label(true))
+        .x_axis(
+            theme.axis()
+                .type_(AxisType::Value)
+                .name(format!("t [{}]", time_unit(clock)))
+                .max(duration(clock, simulation.horizon)),
+        )
+        .y_axis(theme.axis().type_(AxisType::Value).name("priority"))
+        // Zoom into the time axis with the mouse wheel or the slider below the chart
+        .data_zoom(DataZoom::new().type_(DataZoomType::Inside))
+        .data_zoom(DataZoom::new().type_(DataZoomType::Slider));
+
+    for task in tasks {
+        // A "-" value interrupts the line between two slices
+        let mut data = vec![];
+        for slice in simulation.slices.iter().filter(|slice| slice.task == task.id) {
+            data.push(point(slice.start, (task.prio as f64).into()));
+            data.push(point(slice.end, (task.prio as f64).into()));
+            data.push(point(slice.end, "-".into()));
+        }
+        chart = chart.series(Line::new().name(task.id.as_str()).show_symbol(false).data(data));
+    }
+
+    // Π(t) as a step function, each step lasting until the next one
+    let mut ceiling = vec![];
+    for (i, step) in simulation.ceiling.iter().enumerate() {
+        let end = simulation.ceiling.get(i + 1).map_or(simulation.horizon, |next| next.time);
+        ceiling.push(point(step.time, (step.ceiling as f64).into()));
+        ceiling.push(point(end, (step.ceiling as f64).into()));
+    }
+    chart.series(Line::new().name("Π(t)").show_symbol(false).data(ceiling))
+}
+
+// Sections of the trace in pre-order with their nesting depth, 0 for the trace itself
+fn nested_sections<'a>(trace: &'a Trace, depth: u32, sections: &mut Vec<(&'a Trace, u32)>) {
+    sections.push((trace, depth));
+    for inner in &trace.inner {
+        nested_sections(inner, depth + 1, sections);
+    }
+}
+
+/// Critical sections of a task over its execution, one line per section at the depth of its nesting, with
+/// the whole task at depth 0 and one series per resource.
+pub fn trace_chart(task: &Task, clock: Option<ClockModel>, theme: &Theme) -> Chart {
+    let point = |time: u32, level: CompositeValue| vec![CompositeValue::from(duration(clock, time)), level];
+    let mut sections = vec![];
+    nested_sections(&task.trace, 0, &mut sections);
+
+    let mut chart = theme.chart()
+        .title(theme.title(&format!("Trace of {}", task.id)))
+        .tooltip(Tooltip::new().trigger(Trigger::Item))
+        .legend(theme.legend())
+        .grid(Grid::new().left("3%").right("4%").contain_label(true))
+        .x_axis(theme.axis().type_(AxisType::Value).name(format!("t [{}]", time_unit(clock))))
+        .y_axis(theme.axis().type_(AxisType::Value).name("depth"));
+
+    let mut ids: Vec<&str> = vec![];
+    for (section, _) in &sections {
+        if !ids.contains(&section.id.as_str()) {
+            ids.push(&section.id);
+        }
+    }
+    for id in ids {
+        // A "-" value interrupts the line between two sections on the same resource
+        let mut data = vec![];
+        for (section, depth) in sections.iter().filter(|(section, _)| section.id == id) {
+            data.push(point(section.start, (*depth as f64).into()));
+            data.push(point(section.end, (*depth as f64).into()));
+            data.push(point(section.end, "-".into()));
+        }
+        chart = chart.series(Line::new().name(id).data(data));
+    }
+    chart
+}
+
+/// Resources a task holds over its execution as colored bands on a time axis, one band per section at the row
+/// of its nesting depth, so nested sections stack above the sections enclosing them.
+///
+/// Sections are drawn at their start and end as written in the trace, with the whole task in the bottom row.
+pub fn resource_timeline_chart(task: &Task, clock: Option<ClockModel>, theme: &Theme) -> Chart {
+    let point = |time: u32, row: CompositeValue| vec![CompositeValue::from(duration(clock, time)), row];
+    let mut sections = vec![];
+    nested_sections(&task.trace, 0, &mut sections);
+    let depth = sections.iter().map(|(_, depth)| *depth).max().unwrap_or(0);
+    let mut rows = vec![task.id.clone()];
+    rows.extend((1..=depth).map(|depth| format!("depth {}", depth)));
+
+    let mut chart = theme.chart()
+        .title(theme.title(&format!("Resource usage of {}", task.id)))
+        .tooltip(Tooltip::new().trigger(Trigger::Item))
+        .legend(theme.legend())
+        .grid(Grid::new().left("3%").right("4%").contain_label(true))
+        .x_axis(
+            theme.axis()
+                .type_(AxisType::Value)
+                .name(format!("t [{}]", time_unit(clock)))
+                .min(duration(clock, task.trace.start))
+                .max(duration(clock, task.trace.end)),
+        )
+        .y_axis(theme.axis().type_(AxisType::Category).data(rows));
+
+    let mut ids: Vec<&str> = vec![];
+    for (section, _) in &sections {
+        if !ids.contains(&section.id.as_str()) {
+            ids.push(&section.id);
+        }
+    }
+    for id in ids {
+        // A "-" value interrupts the band between two sections on the same resource
+        let mut data = vec![];
+        for (section, depth) in sections.iter().filter(|(section, _)| section.id == id) {
+            data.push(point(section.start, (*depth as f64).into()));
+            data.push(point(section.end, (*depth as f64).into()));
+            data.push(point(section.end, "-".into()));
+        }
+        chart = chart.series(
+            Line::new().name(id).show_symbol(false).line_style(LineStyle::new().width(24.0)).data(data),
+        );
+    }
+    chart
+}
+
+/// Iterates R^n of the response time recurrence of a task against its deadline, to see whether and how fast
+/// R(t) converges.
+pub fn convergence_chart(convergence: &Convergence, theme: &Theme) -> Chart {
+    let outcome = match (convergence.converged, convergence.iterates.last()) {
+        (true, Some(&r)) if r <= convergence.deadline => format!("converges to R(t) = {}", r),
+        (true, Some(&r)) => format!("converges to R(t) = {} beyond D(t)", r),
+        _ => "diverges".to_string(),
+    };
+    theme.chart()
+        .title(
+            theme.title(&format!("Response time iterates of {}", convergence.task))
+                .subtext(format!("{} after {} iterate(s)", outcome, convergence.iterates.len())),
+        )
+        .tooltip(Tooltip::new().trigger(Trigger::Axis))
+        .legend(theme.legend())
+        .grid(Grid::new().left("3%").right("4%").contain_label(true))
+        .x_axis(
+            theme.axis()
+                .type_(AxisType::Category)
+                .name("n")
+                .data((0..convergence.iterates.len()).map(|n| n.to_string()).collect()),
+        )
+        .y_axis(theme.axis().type_(AxisType::Value).name("R^n"))
+        .series(
+            Line::new()
+                .name("R^n")
+                .mark_line(
+                    MarkLine::new()
+                        .line_style(LineStyle::new().type_(LineStyleType::Dashed))
+                        .data(vec![MarkLineVariant::Simple(
+                            MarkLineData::new().name("D(t)").y_axis(convergence.deadline as f64),
+                        )]),
+                )
+                .data(convergence.iterates.clone()),
+        )
+}
+
+/// Ceiling π(r) of every resource.
+pub fn ceiling_chart(ceilings: &[ResourceCeiling], theme: &Theme) -> Chart {
+    theme.chart()
+        .title(theme.title("Resource ceilings"))
+        .tooltip(Tooltip::new().trigger(Trigger::Axis))
+        .grid(Grid::new().left("3%").right("4%").contain_label(true))
+        .x_axis(
+            theme.axis()
+                .type_(AxisType::Category)
+                .data(ceilings.iter().map(|c| c.resource.clone()).collect()),
+        )
+        .y_axis(theme.axis().type_(AxisType::Value).name("π(r)"))
+        .series(
+            Bar::new()
+                .name("π(r)")
+                .data(ceilings.iter().map(|c| c.ceiling as i32).collect()),
+        )
+}
+
+/// U(c) of every core before and after the migrations suggested by `balance_cores`, with the peak utilization of
+/// both in the subtitle.
+pub fn core_balance_chart(balancing: &CoreBalancing, theme: &Theme) -> Chart {
+    let (before, after) = balancing.peak_utilization();
+    let subtext = format!("{} move(s), max U(c) {:.3} -> {:.3}", balancing.moves.len(), before, after);
+    theme.chart()
+        .title(theme.title("Core utilization").subtext(subtext))
+        .tooltip(
+            Tooltip::new()
+                .trigger(Trigger::Axis)
+                .axis_pointer(AxisPointer::new().type_(AxisPointerType::Shadow)),
+        )
+        .legend(theme.legend())
+        .grid(Grid::new().left("3%").right("4%").contain_label(true))
+        .x_axis(
+            theme.axis()
+                .type_(AxisType::Category)
+                .data(balancing.before.iter().map(|load| format!("core {}", load.core)).collect()),
+        )
+        .y_axis(theme.axis().type_(AxisType::Value).name("U(c)"))
+        .series(Bar::new().name("before").data(balancing.before.iter().map(|load| load.utilization as f64).collect()))
+        .series(Bar::new().name("after").data(balancing.after.iter().map(|load| load.utilization as f64).collect()))
+}
+
+/// Heatmap of the longest critical section of every task on every resource, 0 if the task does not lock it.
+///
+/// Rows are the tasks in the order of `tasks`, columns the resources in order of their first use.
+pub fn resource_usage_chart(tasks: &Tasks, theme: &Theme) -> Chart {
+    let mut resources: Vec<&str> = vec![];
+    for resource in tasks.iter().flat_map(|task| task.resources()) {
+        if !resources.contains(&resource.id.as_str()) {
+            resources.push(&resource.id);
+        }
+    }
+
+    let mut data = vec![];
+    let mut longest = 0;
+    for (j, task) in tasks.iter().enumerate() {
+        for (i, resource) in resources.iter().enumerate() {
+            let hold = task.resources().filter(|r| r.id == *resource).map(Trace::wcet).max().unwrap_or(0);
+            longest = longest.max(hold);
+            data.push(vec![i as f64, j as f64, hold as f64]);
+        }
+    }
+
+    theme.chart()
+        .title(theme.title("Resource usage"))
+        .tooltip(Tooltip::new())
+        .grid(Grid::new().bottom("15%").contain_label(true))
+        .x_axis(
+            theme.axis()
+                .type_(AxisType::Category)
+                .name("resource")
+                .split_area(SplitArea::new().show(true))
+                .data(resources),
+        )
+        .y_axis(
+            theme.axis()
+                .type_(AxisType::Category)
+                .name("task")
+                .split_area(SplitArea::new().show(true))
+                .data(tasks.iter().map(|task| task.id.clone()).collect()),
+        )
+        .visual_map(
+            VisualMap::new()
+                .min(0)
+                .max(longest.max(1) as f64)
+                .calculable(true)
+                .orient(Orient::Horizontal)
+                .left("center")
+                .bottom("0%"),
+        )
+        .series(Heatmap::new().name("longest section").data(data))
+}
+
+/// Heatmap of a `SchedulabilityRegion`, 1 marking schedulable and 0 unschedulable parameter combinations.
+pub fn region_chart(region: &SchedulabilityRegion, x: &str, y: &str, theme: &Theme) -> Chart {
+    let mut data = vec![];
+    for (j, row) in region.schedulable.iter().enumerate() {
+        for (i, schedulable) in row.iter().enumerate() {
+            data.push(vec![i as f64, j as f64, if *schedulable { 1.0 } else { 0.0 }]);
+        }
+    }
+
+    theme.chart()
+        .title(theme.title("Schedulability region"))
+        .tooltip(Tooltip::new())
+        .grid(Grid::new().bottom("15%").contain_label(true))
+        .x_axis(
+            theme.axis()
+                .type_(AxisType::Category)
+                .name(x)
+                .split_area(SplitArea::new().show(true))
+                .data(region.x.iter().map(|v| v.to_string()).collect()),
+        )
+        .y_axis(
+            theme.axis()
+                .type_(AxisType::Category)
+                .name(y)
+                .split_area(SplitArea::new().show(true))
+                .data(region.y.iter().map(|v| v.to_string()).collect()),
+        )
+        .visual_map(
+            VisualMap::new()
+                .min(0)
+                .max(1)
+                .calculable(false)
+                .orient(Orient::Horizontal)
+                .left("center")
+                .bottom("0%"),
+        )
+        .series(Heatmap::new().name("schedulable").data(data))
+}
+
+/// Registers the analysis charts of `tasks` in `collection`, analyzed in approximate mode.
+///
+/// The timeline simulates one hyperperiod, at most `max_horizon` time units.
+pub fn register_analysis_charts(
+    registry: &ChartRegistry,
+    collection: &str,
+    tasks: Tasks,
+    chains: Vec<Chain>,
+    max_horizon: u32,
+) {
+    let tasks = std::sync::Arc::new(tasks);
+
+    let t = tasks.clone();
+    registry.register_fn(
+        collection,
+        "groups",
+        ChartInfo::new(
+            "Utilization per group",
+            "Utilization and worst response ratio of every task group",
+            &[ChartInput::TaskSet, ChartInput::Analysis],
+        ),
+        move |theme| group_chart(&srp_analyze(&*t, &AnalysisConfig::default()), theme),
+    );
+
+    let t = tasks.clone();
+    registry.register_fn(
+        collection,
+        "response_times",
+        ChartInfo::new(
+            "Response times",
+            "R(t) of every task as a stack of C(t), B(t) and I(t), next to its deadline D(t)",
+            &[ChartInput::TaskSet, ChartInput::Analysis],
+        ),
+        move |theme| response_time_chart(&srp_analyze(&*t, &AnalysisConfig::default()), None, None, theme),
+    );
+
+    let t = tasks.clone();
+    registry.register_fn(
+        collection,
+        "composition",
+        ChartInfo::new(
+            "Response time composition",
+            "B(t), C(t) and the interference of every higher priority task",
+            &[ChartInput::TaskSet],
+        ),
+        move |theme| response_composition_chart(&t, None, theme),
+    );
+
+    let t = tasks.clone();
+    registry.register_fn(
+        collection,
+        "timeline",
+        ChartInfo::new(
+            "Schedule",
+            "Simulated SRP schedule and system ceiling over one hyperperiod",
+            &[ChartInput::TaskSet, ChartInput::Simulation],
+        ),
+        move |theme| {
+            let horizon = hyperperiod(&t).unwrap_or(max_horizon).min(max_horizon);
+            timeline_chart(&t, &simulate(&t, horizon), None, theme)
+        },
+    );
+
+    let t = tasks.clone();
+    registry.register_fn(
+        collection,
+        "utilization",
+        ChartInfo::new(
+            "CPU utilization",
+            "Share C(t) / A(t) of every task and the idle capacity",
+            &[ChartInput::TaskSet],
+        ),
+        move |theme| utilization_chart(&t, theme),
+    );
+
+    let t = tasks.clone();
+    registry.register_fn(
+        collection,
+        "margins",
+        ChartInfo::new(
+            "Utilization vs. margin",
+            "U(t) of every task against its slack ratio (D(t) - R(t)) / D(t)",
+            &[ChartInput::TaskSet, ChartInput::Analysis],
+        ),
+        move |theme| margin_chart(&task_margins(&srp_analyze(&*t, &AnalysisConfig::default())), theme),
+    );
+
+    let t = tasks.clone();
+    registry.register_fn(
+        collection,
+        "resources",
+        ChartInfo::new(
+            "Resource usage",
+            "Longest critical section of every task on every resource",
+            &[ChartInput::TaskSet],
+        ),
+        move |theme| resource_usage_chart(&t, theme),
+    );
+
+    let t = tasks;
+    registry.register_fn(
+        collection,
+        "chains",
+        ChartInfo::new(
+            "Chain latencies",
+            "End-to-end latency of every chain next to its deadline",
+            &[ChartInput::TaskSet, ChartInput::Analysis, ChartInput::Chains],
+        ),
+        move |theme| {
+            let results = srp_analyze(&*t, &AnalysisConfig::default());
+            chain_chart(&chain_latencies(&chains, &results), theme)
+        },
+    );
+}
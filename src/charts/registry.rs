@@ -0,0 +1,125 @@
+use std::collections::BTreeMap;
+use std::sync::{Arc, RwLock};
+
+use charming::Chart;
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use super::Theme;
+
+/// Data a chart is derived from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ChartInput {
+    TaskSet,
+    /// Results of `srp_analyze`
+    Analysis,
+    /// End-to-end chains through the task set
+    Chains,
+    /// Simulated schedule of the task set
+    Simulation,
+}
+
+/// Description of a registered chart, e.g. for listing the available charts.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ChartInfo {
+    pub title: String,
+    pub description: String,
+    pub inputs: Vec<ChartInput>,
+}
+
+impl ChartInfo {
+    pub fn new(title: &str, description: &str, inputs: &[ChartInput]) -> Self {
+        ChartInfo { title: title.to_string(), description: description.to_string(), inputs: inputs.to_vec() }
+    }
+}
+
+/// Source of a single chart in a `ChartRegistry`.
+pub trait ChartProvider: Send + Sync {
+    fn info(&self) -> &ChartInfo;
+    fn chart(&self, theme: &Theme) -> Chart;
+}
+
+/// Chart provider building its chart with a closure, see `ChartRegistry::register_fn`.
+pub struct FnChartProvider<F> {
+    info: ChartInfo,
+    build: F,
+}
+
+impl<F> ChartProvider for FnChartProvider<F>
+where
+    F: Fn(&Theme) -> Chart + Send + Sync,
+{
+    fn info(&self) -> &ChartInfo {
+        &self.info
+    }
+
+    fn chart(&self, theme: &Theme) -> Chart {
+        (self.build)(theme)
+    }
+}
+
+/// Charts served by name, grouped into collections such as `ANALYSIS_SET`.
+///
+/// Chart providers register themselves at startup, so adding a chart needs no change to the routes.
+#[derive(Default)]
+pub struct ChartRegistry {
+    collections: RwLock<BTreeMap<String, BTreeMap<String, Arc<dyn ChartProvider>>>>,
+}
+
+impl ChartRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `provider` as chart `name` of `collection`, replacing any chart of the same name.
+    pub fn register<P>(&self, collection: &str, name: &str, provider: P)
+    where
+        P: ChartProvider + 'static,
+    {
+        self.collections
+            .write()
+            .unwrap()
+            .entry(collection.to_string())
+            .or_default()
+            .insert(name.to_string(), Arc::new(provider));
+    }
+
+    /// Adds a chart built by `build`, see `register`.
+    pub fn register_fn<F>(&self, collection: &str, name: &str, info: ChartInfo, build: F)
+    where
+        F: Fn(&Theme) -> Chart + Send + Sync + 'static,
+    {
+        self.register(collection, name, FnChartProvider { info, build });
+    }
+
+    /// Builds chart `name` of `collection`.
+    ///
+    /// The registry is not locked while the chart is built.
+    pub fn chart(&self, collection: &str, name: &str, theme: &Theme) -> Result<Chart, String> {
+        let provider = {
+            let collections = self.collections.read().unwrap();
+            let charts = collections
+                .get(collection)
+                .ok_or_else(|| format!("Error: Unknown chart collection '{}'", collection))?;
+            charts
+                .get(name)
+                .cloned()
+                .ok_or_else(|| format!("Error: Unknown chart '{}' in '{}'", name, collection))?
+        };
+        Ok(provider.chart(theme))
+    }
+
+    /// Descriptions of all registered charts by collection and name.
+    pub fn catalog(&self) -> BTreeMap<String, BTreeMap<String, ChartInfo>> {
+        self.collections
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(collection, charts)| {
+                let infos = charts.iter().map(|(name, provider)| (name.clone(), provider.info().clone())).collect();
+                (collection.clone(), infos)
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,277 @@
+use std::path::Path;
+use std::sync::Arc;
+
+use charming::{
+    component::Grid,
+    element::{AxisPointer, AxisPointerType, AxisType, Emphasis, EmphasisFocus, Tooltip, Trigger},
+    series::{Bar, Line, Pie},
+    Chart,
+};
+use serde::Deserialize;
+
+use super::{ChartInfo, ChartInput, ChartProvider, ChartRegistry, Theme};
+use crate::srp_analysis::*;
+
+/// Directory the chart specs are loaded from at startup, if it exists, see `load_chart_specs`.
+pub const CHART_SPEC_DIR: &str = "charts";
+
+/// Stored task set by id, for the charts of a spec with `ChartData::Analysis`.
+pub type TaskSetLookup = Arc<dyn Fn(&str) -> Option<Tasks> + Send + Sync>;
+
+// Name, stack and values of a series of a chart spec
+type SeriesData = (String, Option<String>, Vec<f64>);
+
+/// How the series of a chart spec are drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChartKind {
+    Bar,
+    Line,
+    /// Only the first series, with a slice per category
+    Pie,
+}
+
+/// Per-task quantity of an analysis, drawn as a series of a chart spec.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskMetric {
+    /// R(t), 0 if unbounded
+    ResponseTime,
+    Deadline,
+    Wcet,
+    BlockingTime,
+    Interference,
+    /// D(t) - R(t), 0 if R(t) is unbounded or beyond D(t)
+    Slack,
+    /// L(t)
+    Utilization,
+}
+
+impl TaskMetric {
+    fn label(self) -> &'static str {
+        match self {
+            TaskMetric::ResponseTime => "R(t)",
+            TaskMetric::Deadline => "D(t)",
+            TaskMetric::Wcet => "C(t)",
+            TaskMetric::BlockingTime => "B(t)",
+            TaskMetric::Interference => "I(t)",
+            TaskMetric::Slack => "D(t) - R(t)",
+            TaskMetric::Utilization => "L(t)",
+        }
+    }
+
+    fn value(self, (task, response_time, blocking_time, wcet, interference): &AnalysisResult) -> f64 {
+        match self {
+            TaskMetric::ResponseTime => *response_time.as_ref().unwrap_or(&0) as f64,
+            TaskMetric::Deadline => task.deadline as f64,
+            TaskMetric::Wcet => *wcet as f64,
+            TaskMetric::BlockingTime => *blocking_time as f64,
+            TaskMetric::Interference => *interference as f64,
+            TaskMetric::Slack => response_time.as_ref().map_or(0, |r| task.deadline.saturating_sub(*r)) as f64,
+            TaskMetric::Utilization => task.load_factor().unwrap_or(0.0) as f64,
+        }
+    }
+}
+
+/// Fixed series of a chart spec with `ChartData::Static`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct StaticSeries {
+    pub name: String,
+    /// Series with the same stack are stacked on top of each other
+    #[serde(default)]
+    pub stack: Option<String>,
+    /// One value per category
+    pub values: Vec<f64>,
+}
+
+/// Where the data of a chart spec comes from.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "source", rename_all = "snake_case")]
+pub enum ChartData {
+    /// Values given in the spec
+    Static { categories: Vec<String>, series: Vec<StaticSeries> },
+    /// A series per metric with a value per task of a stored task set, analyzed whenever the chart is requested
+    Analysis {
+        task_set: String,
+        metrics: Vec<TaskMetric>,
+        #[serde(default)]
+        config: AnalysisConfig,
+        /// Stacks all metrics instead of drawing them side by side
+        #[serde(default)]
+        stacked: bool,
+    },
+}
+
+/// Chart defined by a JSON file instead of code, e.g.
+///
+/// ```json
+/// {
+///   "collection": "SPEC_SET",
+///   "name": "slack",
+///   "title": "Slack per task",
+///   "type": "bar",
+///   "data": { "source": "analysis", "task_set": "example", "metrics": ["response_time", "slack"], "stacked": true }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ChartSpec {
+    /// Collection the chart is served in, see `ChartRegistry`
+    pub collection: String,
+    pub name: String,
+    pub title: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(rename = "type")]
+    pub kind: ChartKind,
+    pub data: ChartData,
+}
+
+impl ChartSpec {
+    /// Checks that every static series has a value per category, e.g. after editing a spec by hand.
+    pub fn validate(&self) -> Result<(), String> {
+        if let ChartData::Static { categories, series } = &self.data {
+            if let Some(series) = series.iter().find(|series| series.values.len() != categories.len()) {
+                return Err(format!(
+                    "Error: Series '{}' of chart '{}' has {} values for {} categories.",
+                    series.name,
+                    self.name,
+                    series.values.len(),
+                    categories.len()
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn info(&self) -> ChartInfo {
+        let inputs: &[ChartInput] = match self.data {
+            ChartData::Static { .. } => &[],
+            ChartData::Analysis { .. } => &[ChartInput::TaskSet, ChartInput::Analysis],
+        };
+        ChartInfo::new(&self.title, &self.description, inputs)
+    }
+
+    // Categories and every series, or why there are none
+    fn series(&self, lookup: &TaskSetLookup) -> Result<(Vec<String>, Vec<SeriesData>), String> {
+        match &self.data {
+            ChartData::Static { categories, series } => Ok((
+                categories.clone(),
+                series.iter().map(|s| (s.name.clone(), s.stack.clone(), s.values.clone())).collect(),
+            )),
+            ChartData::Analysis { task_set, metrics, config, stacked } => {
+                let tasks = lookup(task_set).ok_or_else(|| format!("Error: No task set '{}'.", task_set))?;
+                let results = srp_analyze(&tasks, config);
+                let stack = stacked.then(|| "metrics".to_string());
+                Ok((
+                    results.iter().map(|(task, ..)| task.id.clone()).collect(),
+                    metrics
+                        .iter()
+                        .map(|metric| {
+                            let values = results.iter().map(|result| metric.value(result)).collect();
+                            (metric.label().to_string(), stack.clone(), values)
+                        })
+                        .collect(),
+                ))
+            }
+        }
+    }
+
+    /// The chart of the spec, with the task sets of analysis charts looked up by `lookup`.
+    pub fn chart(&self, theme: &Theme, lookup: &TaskSetLookup) -> Chart {
+        let (categories, series) = match self.series(lookup) {
+            Ok(series) => series,
+            Err(e) => return theme.chart().title(theme.title(&self.title).subtext(e)),
+        };
+        let chart = theme.chart().title(theme.title(&self.title)).legend(theme.legend());
+        if self.kind == ChartKind::Pie {
+            let data: Vec<(f64, String)> = series
+                .first()
+                .map(|(_, _, values)| values.iter().copied().zip(categories).collect())
+                .unwrap_or_default();
+            let name = series.first().map_or(String::new(), |(name, ..)| name.clone());
+            return chart
+                .tooltip(Tooltip::new().trigger(Trigger::Item))
+                .series(Pie::new().name(name).radius(vec!["40%", "70%"]).data(data));
+        }
+
+        let mut chart = chart
+            .tooltip(
+                Tooltip::new()
+                    .trigger(Trigger::Axis)
+                    .axis_pointer(AxisPointer::new().type_(AxisPointerType::Shadow)),
+            )
+            .grid(Grid::new().left("3%").right("4%").contain_label(true))
+            .x_axis(theme.axis().type_(AxisType::Category).data(categories))
+            .y_axis(theme.axis().type_(AxisType::Value));
+        for (name, stack, values) in series {
+            chart = if self.kind == ChartKind::Line {
+                let mut line = Line::new().name(name);
+                if let Some(stack) = stack {
+                    line = line.stack(stack);
+                }
+                chart.series(line.data(values))
+            } else {
+                let mut bar = Bar::new().name(name).emphasis(Emphasis::new().focus(EmphasisFocus::Series));
+                if let Some(stack) = stack {
+                    bar = bar.stack(stack);
+                }
+                chart.series(bar.data(values))
+            };
+        }
+        chart
+    }
+}
+
+/// Chart provider of a `ChartSpec`.
+pub struct SpecChartProvider {
+    spec: ChartSpec,
+    info: ChartInfo,
+    lookup: TaskSetLookup,
+}
+
+impl SpecChartProvider {
+    pub fn new(spec: ChartSpec, lookup: TaskSetLookup) -> Self {
+        Self { info: spec.info(), spec, lookup }
+    }
+}
+
+impl ChartProvider for SpecChartProvider {
+    fn info(&self) -> &ChartInfo {
+        &self.info
+    }
+
+    fn chart(&self, theme: &Theme) -> Chart {
+        self.spec.chart(theme, &self.lookup)
+    }
+}
+
+/// Reads every `*.json` chart spec in `dir`, in the order of their file names.
+pub fn load_chart_specs(dir: &Path) -> Result<Vec<ChartSpec>, String> {
+    let entries =
+        std::fs::read_dir(dir).map_err(|e| format!("Error: Cannot read '{}': {}", dir.display(), e))?;
+    let mut paths: Vec<_> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|extension| extension == "json"))
+        .collect();
+    paths.sort();
+
+    paths
+        .iter()
+        .map(|path| {
+            let json = std::fs::read_to_string(path)
+                .map_err(|e| format!("Error: Cannot read '{}': {}", path.display(), e))?;
+            let spec: ChartSpec = serde_json::from_str(&json)
+                .map_err(|e| format!("Error: Cannot parse '{}': {}", path.display(), e))?;
+            spec.validate()?;
+            Ok(spec)
+        })
+        .collect()
+}
+
+/// Registers every spec as chart `name` of its collection, replacing built-in charts of the same name.
+pub fn register_chart_specs(registry: &ChartRegistry, specs: Vec<ChartSpec>, lookup: TaskSetLookup) {
+    for spec in specs {
+        let (collection, name) = (spec.collection.clone(), spec.name.clone());
+        registry.register(&collection, &name, SpecChartProvider::new(spec, lookup.clone()));
+    }
+}
@@ -0,0 +1,86 @@
+use charming::{
+    component::{Axis, Legend, Title},
+    element::{AxisLabel, Color, TextStyle},
+    Chart,
+};
+use serde::{Deserialize, Serialize};
+
+/// Colors and font sizes shared by every generated chart.
+///
+/// Missing fields of a theme in the config file are taken from the light theme.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Theme {
+    /// Series colors, used in order
+    pub palette: Vec<String>,
+    pub background: String,
+    pub text_color: String,
+    pub font_size: f64,
+    pub title_font_size: f64,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::light()
+    }
+}
+
+impl Theme {
+    /// ECharts' default look on a white background.
+    pub fn light() -> Self {
+        Theme {
+            palette: ["#5470c6", "#91cc75", "#fac858", "#ee6666", "#73c0de", "#3ba272", "#fc8452", "#9a60b4"]
+                .map(String::from)
+                .to_vec(),
+            background: "#ffffff".to_string(),
+            text_color: "#333333".to_string(),
+            font_size: 12.0,
+            title_font_size: 18.0,
+        }
+    }
+
+    /// Brighter colors on a dark background.
+    pub fn dark() -> Self {
+        Theme {
+            palette: ["#4992ff", "#7cffb2", "#fddd60", "#ff6e76", "#58d9f9", "#05c091", "#ff8a45", "#8d48e3"]
+                .map(String::from)
+                .to_vec(),
+            background: "#100c2a".to_string(),
+            text_color: "#eeeeee".to_string(),
+            ..Theme::light()
+        }
+    }
+
+    /// Built-in theme by name, `light` or `dark`.
+    pub fn builtin(name: &str) -> Option<Self> {
+        match name {
+            "light" => Some(Theme::light()),
+            "dark" => Some(Theme::dark()),
+            _ => None,
+        }
+    }
+
+    fn text_style(&self, font_size: f64) -> TextStyle {
+        TextStyle::new().color(self.text_color.as_str()).font_size(font_size)
+    }
+
+    /// Empty chart with the background and palette of the theme, every chart builder starts from here.
+    pub fn chart(&self) -> Chart {
+        Chart::new()
+            .background_color(self.background.as_str())
+            .color(self.palette.iter().map(|color| Color::from(color.as_str())).collect::<Vec<_>>())
+    }
+
+    pub fn title(&self, text: &str) -> Title {
+        Title::new().text(text).text_style(self.text_style(self.title_font_size))
+    }
+
+    /// Legend below the chart.
+    pub fn legend(&self) -> Legend {
+        Legend::new().top("bottom").text_style(self.text_style(self.font_size))
+    }
+
+    pub fn axis(&self) -> Axis {
+        Axis::new().axis_label(AxisLabel::new().color(self.text_color.as_str()).font_size(self.font_size))
+    }
+}
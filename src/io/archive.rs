@@ -0,0 +1,95 @@
+use std::io::Read;
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::srp_analysis::*;
+
+/// Version of the archive layout, increased on incompatible changes.
+pub const ARCHIVE_VERSION: u32 = 1;
+
+const MANIFEST: &str = "manifest.json";
+const TASKS: &str = "tasks.json";
+const MEASUREMENTS: &str = "measurements.json";
+const RESULTS: &str = "results.json";
+
+/// What an archive contains and how its results were obtained.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ArchiveManifest {
+    pub version: u32,
+    pub taskset_id: String,
+    pub config: AnalysisConfig,
+}
+
+/// Complete analysis of a task set as a tar archive, to share an analysis and reproduce it later.
+///
+/// Holds `manifest.json`, `tasks.json`, `measurements.json` and `results.json`, next to rendered documents such
+/// as `report.html` or `charts/timeline.html` that are only meant to be viewed and are not read back.
+pub struct AnalysisArchive {
+    pub manifest: ArchiveManifest,
+    pub tasks: Tasks,
+    pub measurements: Measurements,
+    pub results: Vec<TaskAnalysis>,
+    /// Rendered documents by path within the archive
+    pub documents: Vec<(String, String)>,
+}
+
+fn append(builder: &mut tar::Builder<Vec<u8>>, path: &str, contents: &[u8]) -> Result<(), String> {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(contents.len() as u64);
+    header.set_mode(0o644);
+    header.set_cksum();
+    builder
+        .append_data(&mut header, path, contents)
+        .map_err(|e| format!("Error: Cannot archive '{}': {}", path, e))
+}
+
+fn parse<T: DeserializeOwned>(path: &str, contents: Option<&Vec<u8>>) -> Result<T, String> {
+    let contents = contents.ok_or_else(|| format!("Error: The archive has no '{}'.", path))?;
+    serde_json::from_slice(contents).map_err(|e| format!("Error: Cannot parse '{}': {}", path, e))
+}
+
+impl AnalysisArchive {
+    pub fn to_tar(&self) -> Result<Vec<u8>, String> {
+        let mut builder = tar::Builder::new(vec![]);
+        append(&mut builder, MANIFEST, &serde_json::to_vec_pretty(&self.manifest).unwrap())?;
+        append(&mut builder, TASKS, &serde_json::to_vec_pretty(&self.tasks).unwrap())?;
+        append(&mut builder, MEASUREMENTS, &serde_json::to_vec_pretty(&self.measurements).unwrap())?;
+        append(&mut builder, RESULTS, &serde_json::to_vec_pretty(&self.results).unwrap())?;
+        for (path, document) in &self.documents {
+            append(&mut builder, path, document.as_bytes())?;
+        }
+        builder.into_inner().map_err(|e| format!("Error: Cannot write the archive: {}", e))
+    }
+
+    /// Reads an archive written by `to_tar`, skipping its rendered documents.
+    pub fn from_tar(bytes: &[u8]) -> Result<Self, String> {
+        let invalid = |e: std::io::Error| format!("Error: Invalid archive: {}", e);
+        let mut files = std::collections::HashMap::new();
+        let mut archive = tar::Archive::new(bytes);
+        for entry in archive.entries().map_err(invalid)? {
+            let mut entry = entry.map_err(invalid)?;
+            let path = entry.path().map_err(invalid)?.to_string_lossy().into_owned();
+            if [MANIFEST, TASKS, MEASUREMENTS, RESULTS].contains(&path.as_str()) {
+                let mut contents = vec![];
+                entry.read_to_end(&mut contents).map_err(invalid)?;
+                files.insert(path, contents);
+            }
+        }
+
+        let manifest: ArchiveManifest = parse(MANIFEST, files.get(MANIFEST))?;
+        if manifest.version != ARCHIVE_VERSION {
+            return Err(format!("Error: Unsupported archive version {}.", manifest.version));
+        }
+        let tasks: Tasks = parse(TASKS, files.get(TASKS))?;
+        validate_task_set(&tasks)?;
+
+        Ok(AnalysisArchive {
+            manifest,
+            tasks,
+            measurements: parse(MEASUREMENTS, files.get(MEASUREMENTS))?,
+            results: parse(RESULTS, files.get(RESULTS))?,
+            documents: vec![],
+        })
+    }
+}
@@ -0,0 +1,147 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use crate::srp_analysis::*;
+
+// Names of the single processor and address space all tasks run in
+const PROCESSOR: &str = "cpu";
+const ADDRESS_SPACE: &str = "memory";
+
+// Escapes text for use in XML element content and attribute values
+pub(super) fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+// Fixed priority preemptive scheduling parameters, shared by the core and the address space
+fn scheduling(xml: &mut String) {
+    xml.push_str("      <scheduling>\n        <scheduling_parameters>\n");
+    xml.push_str("          <scheduler_type>POSIX_1003_HIGHEST_PRIORITY_FIRST_PROTOCOL</scheduler_type>\n");
+    xml.push_str("          <quantum>0</quantum>\n          <preemptive_type>PREEMPTIVE</preemptive_type>\n");
+    xml.push_str("          <capacity>0</capacity>\n          <period>0</period>\n");
+    xml.push_str("          <priority>0</priority>\n          <start_time>0</start_time>\n");
+    xml.push_str("        </scheduling_parameters>\n      </scheduling>\n");
+}
+
+// (task, first, last) critical sections of every resource, ordered by resource id
+//
+// Cheddar counts critical sections in units of capacity starting at 1, relative to the start of the task,
+// so a section [s, e) becomes s - start + 1 ..= e - start.
+fn critical_sections(tasks: &Tasks) -> BTreeMap<&str, Vec<(&str, u32, u32)>> {
+    let mut sections: BTreeMap<&str, Vec<(&str, u32, u32)>> = BTreeMap::new();
+    for task in tasks {
+        for resource in task.resources() {
+            sections.entry(resource.id.as_str()).or_default().push((
+                task.id.as_str(),
+                resource.start - task.trace.start + 1,
+                resource.end - task.trace.start,
+            ));
+        }
+    }
+    sections
+}
+
+/// Cheddar XML model of a task set, for cross-checking results with the Cheddar schedulability analyzer.
+///
+/// All tasks run on a single fixed priority preemptive core. Every resource is exported with the immediate
+/// priority ceiling protocol, which for single shot tasks blocks exactly like SRP, at its ceiling π(r).
+/// Bursty tasks have no Cheddar equivalent and are rejected.
+pub fn cheddar_xml(tasks: &Tasks) -> Result<String, String> {
+    let mut xml = String::new();
+    let mut next_id = 0;
+    let mut id = || {
+        next_id += 1;
+        format!("id_{}", next_id)
+    };
+
+    // Writing into a String cannot fail, so the fmt::Results below are ignored
+    xml.push_str("<?xml version=\"1.0\" standalone=\"yes\"?>\n<cheddar>\n");
+
+    let core = id();
+    let _ = write!(xml, "  <core_units>\n    <core_unit id=\"{}\">\n", core);
+    xml.push_str("      <object_type>CORE_OBJECT_TYPE</object_type>\n      <name>core</name>\n");
+    scheduling(&mut xml);
+    xml.push_str("      <speed>1.00000</speed>\n    </core_unit>\n  </core_units>\n");
+
+    let _ = write!(xml, "  <processors>\n    <mono_core_processor id=\"{}\">\n", id());
+    xml.push_str("      <object_type>PROCESSOR_OBJECT_TYPE</object_type>\n");
+    let _ = writeln!(xml, "      <name>{}</name>", PROCESSOR);
+    xml.push_str("      <processor_type>MONOCORE_TYPE</processor_type>\n");
+    xml.push_str("      <migration_type>NO_MIGRATION_TYPE</migration_type>\n");
+    let _ = writeln!(xml, "      <core ref=\"{}\"/>", core);
+    xml.push_str("    </mono_core_processor>\n  </processors>\n");
+
+    let _ = write!(xml, "  <address_spaces>\n    <address_space id=\"{}\">\n", id());
+    xml.push_str("      <object_type>ADDRESS_SPACE_OBJECT_TYPE</object_type>\n");
+    let _ = writeln!(xml, "      <name>{}</name>\n      <cpu_name>{}</cpu_name>", ADDRESS_SPACE, PROCESSOR);
+    xml.push_str("      <text_memory_size>0</text_memory_size>\n      <stack_memory_size>0</stack_memory_size>\n");
+    xml.push_str("      <data_memory_size>0</data_memory_size>\n      <heap_memory_size>0</heap_memory_size>\n");
+    scheduling(&mut xml);
+    xml.push_str("    </address_space>\n  </address_spaces>\n");
+
+    xml.push_str("  <tasks>\n");
+    for task in tasks {
+        let (element, task_type, period) = match task.arrival {
+            ArrivalModel::Periodic { period } => ("periodic_task", "PERIODIC_TYPE", period),
+            ArrivalModel::Sporadic { min_inter_arrival } => ("sporadic_task", "SPORADIC_TYPE", min_inter_arrival),
+            ArrivalModel::Bursty { .. } => {
+                return Err(format!("Error: Bursty task '{}' cannot be exported to Cheddar", task.id))
+            }
+        };
+
+        let _ = writeln!(xml, "    <{} id=\"{}\">", element, id());
+        xml.push_str("      <object_type>TASK_OBJECT_TYPE</object_type>\n");
+        let _ = writeln!(xml, "      <name>{}</name>\n      <task_type>{}</task_type>", escape(&task.id), task_type);
+        let _ = writeln!(xml, "      <cpu_name>{}</cpu_name>", PROCESSOR);
+        let _ = writeln!(xml, "      <address_space_name>{}</address_space_name>", ADDRESS_SPACE);
+        let _ = writeln!(xml, "      <capacity>{}</capacity>", task.wcet());
+        let _ = writeln!(xml, "      <deadline>{}</deadline>", task.deadline);
+        let _ = writeln!(xml, "      <start_time>{}</start_time>", task.offset);
+        let _ = writeln!(xml, "      <priority>{}</priority>", task.prio);
+        xml.push_str("      <blocking_time>0</blocking_time>\n      <policy>SCHED_FIFO</policy>\n");
+        xml.push_str("      <text_memory_size>0</text_memory_size>\n");
+        let _ = writeln!(xml, "      <stack_memory_size>{}</stack_memory_size>", task.stack);
+        xml.push_str("      <criticality>0</criticality>\n      <context_switch_overhead>0</context_switch_overhead>\n");
+        let _ = writeln!(xml, "      <period>{}</period>", period);
+        xml.push_str("      <jitter>0</jitter>\n      <every>0</every>\n");
+        let _ = writeln!(xml, "    </{}>", element);
+    }
+    xml.push_str("  </tasks>\n");
+
+    let ceilings: BTreeMap<String, u8> = resource_ceilings(tasks)
+        .into_iter()
+        .map(|c| (c.resource, c.ceiling))
+        .collect();
+    let sections = critical_sections(tasks);
+    if !sections.is_empty() {
+        xml.push_str("  <resources>\n");
+        for (resource, sections) in sections {
+            let _ = writeln!(xml, "    <np_resource id=\"{}\">", id());
+            xml.push_str("      <object_type>RESOURCE_OBJECT_TYPE</object_type>\n");
+            let _ = writeln!(xml, "      <name>{}</name>", escape(resource));
+            xml.push_str("      <state>1</state>\n      <size>0</size>\n      <address>0</address>\n");
+            xml.push_str("      <protocol>IMMEDIATE_PRIORITY_CEILING_PROTOCOL</protocol>\n");
+            xml.push_str("      <critical_sections>\n");
+            for (task, first, last) in sections {
+                let _ = writeln!(xml, "        <task_name>{}</task_name>", escape(task));
+                let _ = writeln!(
+                    xml,
+                    "        <critical_section>\n          <task_begin>{}</task_begin>\n          <task_end>{}</task_end>\n        </critical_section>",
+                    first, last
+                );
+            }
+            xml.push_str("      </critical_sections>\n");
+            let _ = writeln!(xml, "      <cpu_name>{}</cpu_name>", PROCESSOR);
+            let _ = writeln!(xml, "      <address_space_name>{}</address_space_name>", ADDRESS_SPACE);
+            let _ = writeln!(xml, "      <priority>{}</priority>", ceilings.get(resource).copied().unwrap_or(1));
+            xml.push_str("      <priority_assignment>MANUAL_ASSIGNMENT</priority_assignment>\n");
+            xml.push_str("    </np_resource>\n");
+        }
+        xml.push_str("  </resources>\n");
+    }
+
+    xml.push_str("</cheddar>\n");
+    Ok(xml)
+}
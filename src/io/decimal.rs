@@ -0,0 +1,155 @@
+use serde_json::Value;
+
+use crate::srp_analysis::*;
+
+/// How a time that is not a whole number of ticks is converted, see `parse_decimal`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rounding {
+    /// Rejected
+    Exact,
+    /// To the next tick, for times whose growth can only lengthen response times, e.g. C(t) or J(t)
+    Up,
+    /// To the previous tick, for times whose shrinking can only lengthen response times, e.g. T(t) or D(t)
+    Down,
+}
+
+// `text` as digits * 10^shift, with the trailing zeros of the digits moved into the shift
+fn split_decimal(text: &str) -> Result<(u128, i32), String> {
+    let invalid = || format!("Error: '{}' is not a non-negative decimal time.", text);
+    let (mantissa, exponent) = match text.split_once(['e', 'E']) {
+        Some((mantissa, exponent)) => (mantissa, exponent.parse::<i32>().map_err(|_| invalid())?),
+        None => (text, 0),
+    };
+    let (whole, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    let digits = format!("{}{}", whole, fraction);
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(invalid());
+    }
+
+    let mut digits: u128 = digits.parse().map_err(|_| invalid())?;
+    let mut shift = exponent.checked_sub(fraction.len() as i32).ok_or_else(invalid)?;
+    while digits != 0 && digits.is_multiple_of(10) && shift < 0 {
+        (digits, shift) = (digits / 10, shift + 1);
+    }
+    Ok((digits, shift))
+}
+
+/// `text` * `ticks_per_unit` for a decimal `text` such as `1.25` or `2.5e-1`, rounded as given.
+///
+/// Returns an error unless the product fits into a u32, or with `Rounding::Exact` is a whole number of ticks.
+pub fn parse_decimal(text: &str, ticks_per_unit: u32, rounding: Rounding) -> Result<u32, String> {
+    let (digits, shift) = split_decimal(text)?;
+
+    // digits * 10^shift * ticks_per_unit
+    let too_large = || format!("Error: {} time units of {} ticks do not fit into a tick count.", text, ticks_per_unit);
+    let scaled = digits.checked_mul(ticks_per_unit as u128).ok_or_else(too_large)?;
+    let ticks = if shift >= 0 {
+        10u128.checked_pow(shift as u32).and_then(|factor| scaled.checked_mul(factor)).ok_or_else(too_large)?
+    } else {
+        let divisor = 10u128.checked_pow(shift.unsigned_abs()).unwrap_or(u128::MAX);
+        match (scaled % divisor, rounding) {
+            (0, _) | (_, Rounding::Down) => scaled / divisor,
+            (_, Rounding::Up) => scaled / divisor + 1,
+            (_, Rounding::Exact) => {
+                return Err(format!(
+                    "Error: {} time units are not a whole number of ticks at {} ticks per unit.",
+                    text, ticks_per_unit
+                ))
+            }
+        }
+    };
+    u32::try_from(ticks).map_err(|_| too_large())
+}
+
+// Text of the decimal number at `key` of `object`, if there is one
+fn decimal_text(object: &Value, key: &str) -> Result<Option<String>, String> {
+    match object.get(key) {
+        Some(Value::Number(number)) => Ok(Some(number.to_string())),
+        Some(Value::String(text)) => Ok(Some(text.clone())),
+        None | Some(Value::Null) => Ok(None),
+        Some(_) => Err(format!("Error: '{}' is not a time.", key)),
+    }
+}
+
+// Calls `visit` with every time of the task set, the object holding it, its key, and how it is rounded
+fn visit_times(
+    tasks: &mut Value,
+    visit: &mut impl FnMut(&mut Value, &str, Rounding) -> Result<(), String>,
+) -> Result<(), String> {
+    fn visit_trace(
+        trace: &mut Value,
+        visit: &mut impl FnMut(&mut Value, &str, Rounding) -> Result<(), String>,
+    ) -> Result<(), String> {
+        // Rounding both bounds outwards keeps nested sections nested
+        visit(trace, "start", Rounding::Down)?;
+        visit(trace, "end", Rounding::Up)?;
+        if let Some(Value::Array(inner)) = trace.get_mut("inner") {
+            for section in inner {
+                visit_trace(section, visit)?;
+            }
+        }
+        Ok(())
+    }
+
+    let Value::Array(list) = tasks else {
+        return Err("Error: A task set is an array of tasks.".to_string());
+    };
+    for task in list.iter_mut() {
+        visit(task, "deadline", Rounding::Down)?;
+        visit(task, "jitter", Rounding::Up)?;
+        visit(task, "offset", Rounding::Exact)?;
+        visit(task, "suspension", Rounding::Up)?;
+        visit(task, "wcet_hi", Rounding::Up)?;
+        if let Some(arrival) = task.get_mut("arrival") {
+            for key in ["period", "min_inter_arrival", "window"] {
+                visit(arrival, key, Rounding::Down)?;
+            }
+        }
+        if let Some(trace) = task.get_mut("trace") {
+            visit_trace(trace, visit)?;
+        }
+    }
+    Ok(())
+}
+
+// Ticks per unit that make every time of the task set a whole number of ticks, 10^n for n decimal places
+fn exact_ticks_per_unit(tasks: &mut Value) -> Result<u32, String> {
+    let mut places = 0;
+    visit_times(tasks, &mut |object, key, _| {
+        if let Some(text) = decimal_text(object, key)? {
+            let (_, shift) = split_decimal(&text)?;
+            places = places.max(shift.min(0).unsigned_abs());
+        }
+        Ok(())
+    })?;
+    10u32.checked_pow(places).ok_or_else(|| format!("Error: {} decimal places need too many ticks per unit.", places))
+}
+
+/// Reads a JSON task set whose times are decimal numbers of some unit, e.g. fractional milliseconds, as a task
+/// set in ticks with `ticks_per_unit` ticks per unit, e.g. 1000 for microsecond ticks, and returns it with the
+/// ticks per unit.
+///
+/// Without `ticks_per_unit`, the tick is the finest decimal place of the times, e.g. 100 ticks per unit for
+/// `[0.5, 1.25]`, so all times are exact. Otherwise times are rounded so the analysis stays safe: C(t), J(t) and
+/// suspensions up, T(t) and D(t) down, sections are widened to whole ticks, and offsets are rejected unless exact.
+///
+/// Times may also be given as strings, e.g. `"0.1"`, to keep all their digits. Analyze the result with a clock
+/// of `ticks_per_unit` times the units per second to report the results in real time, see `ClockModel`.
+pub fn decimal_task_set(json: &str, ticks_per_unit: Option<u32>) -> Result<(Tasks, u32), String> {
+    let mut tasks: Value = serde_json::from_str(json).map_err(|e| format!("Error: Invalid task set: {}", e))?;
+    let ticks_per_unit = match ticks_per_unit {
+        Some(0) => return Err("Error: A time unit needs at least 1 tick.".to_string()),
+        Some(ticks_per_unit) => ticks_per_unit,
+        None => exact_ticks_per_unit(&mut tasks)?,
+    };
+    visit_times(&mut tasks, &mut |object, key, rounding| {
+        let Some(text) = decimal_text(object, key)? else { return Ok(()) };
+        let ticks = parse_decimal(&text, ticks_per_unit, rounding).map_err(|e| format!("{} ({})", e, key))?;
+        object[key] = ticks.into();
+        Ok(())
+    })?;
+
+    let tasks: Tasks = serde_json::from_value(tasks).map_err(|e| format!("Error: Invalid task set: {}", e))?;
+    validate_task_set(&tasks)?;
+    Ok((tasks, ticks_per_unit))
+}
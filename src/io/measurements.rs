@@ -0,0 +1,30 @@
+use crate::srp_analysis::*;
+
+/// Parses measured execution times, either as a JSON object `{"T1": [9, 10, 8], ...}` or as CSV lines
+/// `<task>,<time>` with one sample per line.
+///
+/// In CSV, empty lines and lines starting with `#` are skipped, as is a header line such as `task,cycles`.
+pub fn parse_measurements(text: &str) -> Result<Measurements, String> {
+    if text.trim_start().starts_with('{') {
+        return serde_json::from_str(text).map_err(|e| format!("Error: Cannot parse measurements: {}", e));
+    }
+
+    let mut measurements = Measurements::new();
+    for (number, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let (task, time) = line
+            .split_once(',')
+            .ok_or_else(|| format!("Error: Line {} is not of the form <task>,<time>.", number + 1))?;
+        let time = match time.trim().parse::<u32>() {
+            Ok(time) => time,
+            Err(_) if number == 0 => continue,
+            Err(e) => return Err(format!("Error: Invalid time on line {}: {}", number + 1, e)),
+        };
+        measurements.entry(task.trim().to_string()).or_default().push(time);
+    }
+    Ok(measurements)
+}
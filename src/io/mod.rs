@@ -0,0 +1,40 @@
+mod archive;
+mod cheddar;
+mod decimal;
+mod measurements;
+mod report_xml;
+mod research;
+mod rtic;
+
+#[cfg(test)]
+mod tests;
+
+pub use archive::*;
+pub use cheddar::*;
+pub use decimal::*;
+pub use measurements::*;
+pub use report_xml::*;
+pub use research::*;
+pub use rtic::*;
+
+use std::path::Path;
+
+use crate::srp_analysis::*;
+
+/// Reads a task set from a JSON file, see `validate_task_set`.
+///
+/// The file holds either an array of tasks or a `ComponentTaskSet`, whose components are expanded into the
+/// tasks using them.
+pub fn load_task_set(path: &Path) -> Result<Tasks, String> {
+    let json = std::fs::read_to_string(path)
+        .map_err(|e| format!("Error: Cannot read '{}': {}", path.display(), e))?;
+    let parse_error = |e: serde_json::Error| format!("Error: Cannot parse '{}': {}", path.display(), e);
+    let tasks = if json.trim_start().starts_with('{') {
+        serde_json::from_str::<ComponentTaskSet>(&json).map_err(parse_error)?.expand()?
+    } else {
+        serde_json::from_str(&json).map_err(parse_error)?
+    };
+
+    validate_task_set(&tasks)?;
+    Ok(tasks)
+}
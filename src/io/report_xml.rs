@@ -0,0 +1,101 @@
+use std::fmt::Write;
+
+use serde::Serialize;
+
+use super::cheddar::escape;
+use crate::srp_analysis::*;
+
+// Serialized name of an option, e.g. `approximate`
+fn name<T: Serialize>(value: &T) -> String {
+    serde_json::to_value(value).ok().and_then(|value| value.as_str().map(str::to_string)).unwrap_or_default()
+}
+
+/// XML analysis report of a task set for toolchains that ingest XML: the options, assumptions, tasks,
+/// resources with their ceilings, the result of every task and the verdict.
+///
+/// Times are in ticks. Unbounded response times have no `response_time` attribute but an `<error>` element with
+/// the reason.
+pub fn analysis_report_xml(taskset_id: &str, tasks: &Tasks, config: &AnalysisConfig) -> String {
+    let results = srp_analyze(tasks, config);
+    let mut xml = String::new();
+
+    // Writing into a String cannot fail, so the fmt::Results below are ignored
+    xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(xml, "<analysis_report task_set=\"{}\">", escape(taskset_id));
+    let _ = write!(
+        xml,
+        "  <config mode=\"{}\" deadline_model=\"{}\" jitter_model=\"{}\" offset_model=\"{}\" \
+         blocking_refinement=\"{}\" tick_granularity=\"{}\"",
+        name(&config.mode),
+        name(&config.deadline_model),
+        name(&config.jitter_model),
+        name(&config.offset_model),
+        name(&config.blocking_refinement),
+        config.tick_granularity
+    );
+    if let Some(clock) = config.clock {
+        let _ = write!(xml, " clock_hz=\"{}\"", clock.frequency_hz);
+    }
+    xml.push_str("/>\n  <assumptions>\n");
+    for assumption in config.assumptions() {
+        let _ = writeln!(xml, "    <assumption>{}</assumption>", escape(&assumption));
+    }
+    xml.push_str("  </assumptions>\n");
+
+    xml.push_str("  <tasks>\n");
+    for task in tasks {
+        let _ = writeln!(
+            xml,
+            "    <task id=\"{}\" prio=\"{}\" kind=\"{}\" wcet=\"{}\" inter_arrival=\"{}\" deadline=\"{}\" \
+             jitter=\"{}\" offset=\"{}\"/>",
+            escape(&task.id),
+            task.prio,
+            name(&task.kind),
+            task.wcet(),
+            task.arrival.inter_arrival(),
+            task.deadline,
+            task.jitter,
+            task.offset
+        );
+    }
+    xml.push_str("  </tasks>\n");
+
+    xml.push_str("  <resources>\n");
+    for ceiling in resource_ceilings(tasks) {
+        let _ = write!(xml, "    <resource id=\"{}\" ceiling=\"{}\"", escape(&ceiling.resource), ceiling.ceiling);
+        if let Some(read_ceiling) = ceiling.read_ceiling {
+            let _ = write!(xml, " read_ceiling=\"{}\"", read_ceiling);
+        }
+        xml.push_str(">\n");
+        for task in &ceiling.tasks {
+            let _ = writeln!(xml, "      <locked_by task=\"{}\"/>", escape(task));
+        }
+        xml.push_str("    </resource>\n");
+    }
+    xml.push_str("  </resources>\n");
+
+    xml.push_str("  <results>\n");
+    for (task, response_time, blocking_time, wcet, interference) in &results {
+        let _ = write!(
+            xml,
+            "    <result task=\"{}\" wcet=\"{}\" blocking_time=\"{}\" interference=\"{}\"",
+            escape(&task.id),
+            wcet,
+            blocking_time,
+            interference
+        );
+        match response_time {
+            Ok(r) => {
+                let _ = writeln!(xml, " response_time=\"{}\" meets_deadline=\"{}\"/>", r, *r <= task.deadline);
+            }
+            Err(e) => {
+                let _ = writeln!(xml, " meets_deadline=\"false\">\n      <error>{}</error>\n    </result>", escape(e));
+            }
+        }
+    }
+    xml.push_str("  </results>\n");
+
+    let _ = writeln!(xml, "  <verdict schedulable=\"{}\"/>", is_schedulable(&results));
+    xml.push_str("</analysis_report>\n");
+    xml
+}
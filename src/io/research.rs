@@ -0,0 +1,94 @@
+use serde::Deserialize;
+
+use crate::srp_analysis::*;
+
+/// Task of a task set in the JSON layout of research tools and published benchmark sets.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ResearchTask {
+    /// Defaults to `t<index>`
+    pub id: Option<String>,
+    #[serde(rename = "C")]
+    pub wcet: u32,
+    /// Minimum inter arrival time
+    #[serde(rename = "T")]
+    pub period: u32,
+    /// Relative deadline, defaults to T
+    #[serde(rename = "D")]
+    pub deadline: Option<u32>,
+}
+
+/// Critical section of the task at index `task` on a resource.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ResearchAccess {
+    pub task: usize,
+    pub length: u32,
+}
+
+/// Resource and every access to it, defaulting to `r<index>` as id.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ResearchResource {
+    pub id: Option<String>,
+    pub accesses: Vec<ResearchAccess>,
+}
+
+/// Tasks and the resource access lists, or just an array of tasks.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum ResearchTaskSet {
+    WithResources {
+        tasks: Vec<ResearchTask>,
+        #[serde(default)]
+        resources: Vec<ResearchResource>,
+    },
+    Tasks(Vec<ResearchTask>),
+}
+
+/// Task set of a research task set in JSON, `{"tasks": [{"C", "T", "D"}], "resources": [{"accesses"}]}`.
+///
+/// Priorities are deadline monotonic, ties broken by the order of the tasks. The critical sections of a task
+/// follow each other from its start, in the order of the resources, and must fit within C.
+pub fn research_task_set(json: &str) -> Result<Tasks, String> {
+    let parsed = serde_json::from_str(json).map_err(|e| format!("Error: Cannot parse task set: {}", e))?;
+    let (tasks, resources) = match parsed {
+        ResearchTaskSet::WithResources { tasks, resources } => (tasks, resources),
+        ResearchTaskSet::Tasks(tasks) => (tasks, vec![]),
+    };
+    if tasks.len() >= u8::MAX as usize {
+        return Err(format!("Error: {} tasks exceed the number of priority levels", tasks.len()));
+    }
+
+    // The longest deadline gets priority 1
+    let deadline = |task: &ResearchTask| task.deadline.unwrap_or(task.period);
+    let mut order: Vec<usize> = (0..tasks.len()).collect();
+    order.sort_by_key(|&i| (std::cmp::Reverse(deadline(&tasks[i])), std::cmp::Reverse(i)));
+
+    let mut builders: Vec<(TaskBuilder, u32)> = tasks
+        .iter()
+        .enumerate()
+        .map(|(i, task)| {
+            let id = task.id.clone().unwrap_or_else(|| format!("t{}", i));
+            let prio = order.iter().position(|&o| o == i).unwrap() as u8 + 1;
+            let builder = TaskBuilder::new(&id)
+                .prio(prio)
+                .arrival(ArrivalModel::Sporadic { min_inter_arrival: task.period })
+                .deadline(deadline(task));
+            (builder.span(0, task.wcet), 0)
+        })
+        .collect();
+
+    for (r, resource) in resources.iter().enumerate() {
+        let id = resource.id.clone().unwrap_or_else(|| format!("r{}", r));
+        for access in &resource.accesses {
+            let (builder, start) = builders.get_mut(access.task).ok_or_else(|| {
+                format!("Error: Resource '{}' is accessed by task {}, which does not exist.", id, access.task)
+            })?;
+            let end = *start + access.length;
+            *builder = std::mem::replace(builder, TaskBuilder::new("")).section(&id, *start, end, |s| s);
+            *start = end;
+        }
+    }
+
+    let tasks = builders.into_iter().map(|(builder, _)| builder.build()).collect::<Result<Tasks, String>>()?;
+    validate_task_set(&tasks)?;
+    Ok(tasks)
+}
@@ -0,0 +1,163 @@
+use std::path::Path;
+
+use syn::{Attribute, Expr, Item, ItemFn, ItemMod, LitInt};
+
+use crate::srp_analysis::*;
+
+/// WCET of a task, and of every resource section within it, until it is replaced by a measured value.
+pub const PLACEHOLDER_WCET: u32 = 1;
+
+/// Inter arrival time of a task until it is replaced by the real one, RTIC apps do not declare it.
+pub const PLACEHOLDER_PERIOD: u32 = 1000;
+
+// Priority of tasks without a `priority` argument
+const DEFAULT_PRIORITY: u8 = 1;
+
+/// A `#[task]` declaration of an RTIC app.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RticTask {
+    pub name: String,
+    pub prio: u8,
+    /// Shared resources the task locks, in declaration order
+    pub shared: Vec<String>,
+    /// Shared resources declared as `&r`, only read and so locked read-shared
+    pub read_only: Vec<String>,
+    /// Hardware task bound to an interrupt by `binds`
+    pub interrupt: bool,
+}
+
+impl RticTask {
+    /// Task with placeholder timing, locking each shared resource once in its own `PLACEHOLDER_WCET` long section.
+    pub fn skeleton(&self) -> Result<Task, String> {
+        let sections = self.shared.len() as u32;
+        let builder = TaskBuilder::new(&self.name)
+            .prio(self.prio)
+            .period(PLACEHOLDER_PERIOD)
+            .span(0, PLACEHOLDER_WCET * sections.max(1));
+        let builder = if self.interrupt { builder.interrupt() } else { builder };
+
+        self.shared
+            .iter()
+            .zip((0..).step_by(PLACEHOLDER_WCET as usize))
+            .fold(builder, |builder, (resource, start)| {
+                if self.read_only.contains(resource) {
+                    builder.read_section(resource, start, start + PLACEHOLDER_WCET)
+                } else {
+                    builder.section(resource, start, start + PLACEHOLDER_WCET, |s| s)
+                }
+            })
+            .build()
+    }
+}
+
+// Last segment of the attribute path, e.g. `app` for `#[rtic::app(...)]`
+fn attribute_name(attr: &Attribute) -> Option<String> {
+    attr.path().segments.last().map(|segment| segment.ident.to_string())
+}
+
+// Resource name of an element of `shared = [a, &b]`, `&` marking read-only access
+fn resource_name(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Path(path) => path.path.get_ident().map(|ident| ident.to_string()),
+        Expr::Reference(reference) => resource_name(&reference.expr),
+        _ => None,
+    }
+}
+
+// Parses `#[task(binds = EXTI0, priority = 2, shared = [a, b], local = [x: u32 = 0])]`
+fn parse_task(function: &ItemFn, attr: &Attribute) -> Result<RticTask, String> {
+    let name = function.sig.ident.to_string();
+    let mut task = RticTask { name: name.clone(), prio: DEFAULT_PRIORITY, shared: vec![], read_only: vec![], interrupt: false };
+
+    // `#[task]` without arguments
+    if matches!(attr.meta, syn::Meta::Path(_)) {
+        return Ok(task);
+    }
+
+    attr.parse_nested_meta(|meta| {
+        let value = meta.value()?;
+        if meta.path.is_ident("priority") {
+            task.prio = value.parse::<LitInt>()?.base10_parse()?;
+        } else if meta.path.is_ident("binds") {
+            value.parse::<syn::Ident>()?;
+            task.interrupt = true;
+        } else if meta.path.is_ident("shared") {
+            match value.parse::<Expr>()? {
+                Expr::Array(array) => {
+                    for element in &array.elems {
+                        let resource = resource_name(element)
+                            .ok_or_else(|| meta.error("expected a shared resource name"))?;
+                        if matches!(element, Expr::Reference(_)) {
+                            task.read_only.push(resource.clone());
+                        }
+                        task.shared.push(resource);
+                    }
+                }
+                _ => return Err(meta.error("expected a list of shared resources")),
+            }
+        } else {
+            // Skip arguments that do not matter for the analysis, such as `local`
+            value.step(|cursor| {
+                let mut rest = *cursor;
+                while let Some((_, next)) = rest.token_tree() {
+                    if matches!(rest.punct(), Some((punct, _)) if punct.as_char() == ',') {
+                        break;
+                    }
+                    rest = next;
+                }
+                Ok(((), rest))
+            })?;
+        }
+        Ok(())
+    })
+    .map_err(|e| format!("Error: Cannot parse the arguments of task '{}': {}", name, e))?;
+
+    Ok(task)
+}
+
+/// Collects the `#[task]` declarations of the `#[app]` module in an RTIC application source file.
+///
+/// `#[init]` and `#[idle]` are not tasks in the sense of the analysis and are left out.
+pub fn rtic_tasks(source: &str) -> Result<Vec<RticTask>, String> {
+    let file = syn::parse_file(source).map_err(|e| format!("Error: Cannot parse RTIC source: {}", e))?;
+
+    let app: &ItemMod = file
+        .items
+        .iter()
+        .find_map(|item| match item {
+            Item::Mod(module) if module.attrs.iter().any(|attr| attribute_name(attr).as_deref() == Some("app")) => {
+                Some(module)
+            }
+            _ => None,
+        })
+        .ok_or_else(|| "Error: No #[app] module found".to_string())?;
+    let (_, items) = app
+        .content
+        .as_ref()
+        .ok_or_else(|| "Error: The #[app] module has no body".to_string())?;
+
+    let mut tasks = vec![];
+    for item in items {
+        let Item::Fn(function) = item else { continue };
+        for attr in function.attrs.iter().filter(|attr| attribute_name(attr).as_deref() == Some("task")) {
+            tasks.push(parse_task(function, attr)?);
+        }
+    }
+    Ok(tasks)
+}
+
+/// Skeleton task set of an RTIC application, see `RticTask::skeleton`.
+///
+/// Priorities and resource usage are taken from the source, WCETs and periods are placeholders.
+pub fn rtic_task_set(source: &str) -> Result<Tasks, String> {
+    let tasks = rtic_tasks(source)?.iter().map(RticTask::skeleton).collect::<Result<Tasks, String>>()?;
+    validate_task_set(&tasks)?;
+    Ok(tasks)
+}
+
+/// Reads an RTIC application source file into a skeleton task set, see `rtic_task_set`.
+pub fn load_rtic_app(path: &Path) -> Result<Tasks, String> {
+    let source = std::fs::read_to_string(path)
+        .map_err(|e| format!("Error: Cannot read '{}': {}", path.display(), e))?;
+    rtic_task_set(&source)
+}
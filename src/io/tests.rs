@@ -0,0 +1,255 @@
+use std::collections::BTreeMap;
+
+use super::*;
+
+fn decimal_task(deadline: &str, jitter: &str, wcet: &str) -> String {
+    format!(
+        r#"[{{"id": "t", "prio": 1, "deadline": {}, "jitter": {}, "arrival": {{"model": "periodic", "period": {}}},
+            "trace": {{"id": "t", "start": 0, "end": {}, "inner": []}}}}]"#,
+        deadline, jitter, deadline, wcet
+    )
+}
+
+#[test]
+fn decimal_times_are_rounded_to_the_safe_side() {
+    let (tasks, ticks_per_unit) = decimal_task_set(&decimal_task("9.99", "0.01", "1.001"), Some(10)).unwrap();
+    assert_eq!(ticks_per_unit, 10);
+    // C(t) and J(t) up, T(t) and D(t) down
+    assert_eq!((tasks[0].wcet(), tasks[0].jitter), (11, 1));
+    assert_eq!((tasks[0].arrival.inter_arrival(), tasks[0].deadline), (99, 99));
+
+    assert_eq!(parse_decimal("0.25", 10, Rounding::Up), Ok(3));
+    assert_eq!(parse_decimal("0.25", 10, Rounding::Down), Ok(2));
+    assert!(parse_decimal("0.25", 10, Rounding::Exact).is_err());
+    assert_eq!(parse_decimal("2.5e-1", 100, Rounding::Exact), Ok(25));
+}
+
+#[test]
+fn decimal_tick_is_the_finest_decimal_place() {
+    // Trailing zeros and exponents count by the places they amount to
+    let (tasks, ticks_per_unit) = decimal_task_set(&decimal_task("\"10.50\"", "2.5e-2", "1"), None).unwrap();
+    assert_eq!(ticks_per_unit, 1000);
+    assert_eq!((tasks[0].deadline, tasks[0].jitter, tasks[0].wcet()), (10500, 25, 1000));
+
+    let (tasks, ticks_per_unit) = decimal_task_set(&decimal_task("10", "0", "2"), None).unwrap();
+    assert_eq!(ticks_per_unit, 1);
+    assert_eq!(tasks[0].deadline, 10);
+}
+
+#[test]
+fn decimal_times_beyond_a_tick_count_are_rejected() {
+    assert!(parse_decimal("4294967296", 1, Rounding::Exact).is_err());
+    assert!(parse_decimal("4.294967296", 1_000_000_000, Rounding::Down).is_err());
+    assert!(parse_decimal("1e40", 1, Rounding::Exact).is_err());
+    assert_eq!(parse_decimal("4294967295", 1, Rounding::Exact), Ok(u32::MAX));
+    // More places than a u32 can count ticks for
+    assert!(decimal_task_set(&decimal_task("10", "0.0000000001", "2"), None).is_err());
+    assert!(decimal_task_set(&decimal_task("10", "0", "2"), Some(0)).is_err());
+}
+
+// (C, T, D) of every task, the task layout of a research task set
+fn research_parameters(tasks: &Tasks) -> Vec<(u32, u32, u32)> {
+    tasks.iter().map(|t| (t.wcet(), t.arrival.inter_arrival(), t.deadline)).collect()
+}
+
+// (task, length) accesses of every resource, the resource layout of a research task set
+fn research_accesses(tasks: &Tasks) -> BTreeMap<String, Vec<(usize, u32)>> {
+    let mut resources: BTreeMap<String, Vec<(usize, u32)>> = BTreeMap::new();
+    for (i, task) in tasks.iter().enumerate() {
+        for section in &task.trace.inner {
+            resources.entry(section.id.clone()).or_default().push((i, section.end - section.start));
+        }
+    }
+    resources
+}
+
+#[test]
+fn research_task_set_round_trips_its_parameters() {
+    let json = r#"{
+        "tasks": [{"C": 2, "T": 10}, {"id": "b", "C": 5, "T": 20, "D": 8}, {"C": 1, "T": 5}],
+        "resources": [
+            {"id": "bus", "accesses": [{"task": 0, "length": 1}, {"task": 1, "length": 2}]},
+            {"accesses": [{"task": 1, "length": 3}]}
+        ]
+    }"#;
+    let tasks = research_task_set(json).unwrap();
+
+    let ids: Vec<&str> = tasks.iter().map(|t| t.id.as_str()).collect();
+    assert_eq!(ids, ["t0", "b", "t2"]);
+    // Deadline monotonic: D = 5, 8, 10
+    let prios: Vec<u8> = tasks.iter().map(|t| t.prio).collect();
+    assert_eq!(prios, [1, 2, 3]);
+    let parameters = research_parameters(&tasks);
+    assert_eq!(parameters, [(2, 10, 10), (5, 20, 8), (1, 5, 5)]);
+    let accesses = [("bus".to_string(), vec![(0, 1), (1, 2)]), ("r1".to_string(), vec![(1, 3)])];
+    assert_eq!(research_accesses(&tasks), BTreeMap::from(accesses));
+    // Sections follow each other from the start of the task
+    let sections: Vec<(u32, u32)> = tasks[1].trace.inner.iter().map(|s| (s.start, s.end)).collect();
+    assert_eq!(sections, [(0, 2), (2, 5)]);
+
+    // The plain array layout reads the same tasks
+    let array = research_task_set(r#"[{"C": 2, "T": 10}, {"id": "b", "C": 5, "T": 20, "D": 8}, {"C": 1, "T": 5}]"#);
+    assert_eq!(research_parameters(&array.unwrap()), parameters);
+}
+
+#[test]
+fn malformed_research_task_sets_are_rejected() {
+    for json in [
+        "",
+        r#"{"tasks": "none"}"#,
+        r#"[{"C": 2}]"#,
+        r#"[{"C": -1, "T": 10}]"#,
+        // Access of a task that does not exist
+        r#"{"tasks": [{"C": 2, "T": 10}], "resources": [{"accesses": [{"task": 1, "length": 1}]}]}"#,
+        // Accesses longer than C
+        r#"{"tasks": [{"C": 2, "T": 10}], "resources": [{"accesses": [{"task": 0, "length": 3}]}]}"#,
+    ] {
+        assert!(research_task_set(json).is_err(), "{}", json);
+    }
+    let too_many = format!("[{}]", vec![r#"{"C": 1, "T": 1000}"#; u8::MAX as usize].join(","));
+    assert!(research_task_set(&too_many).is_err());
+}
+
+// RTIC app with a hardware task, tasks with and without priorities, and read-only and exclusive shared resources
+const RTIC_APP: &str = r#"
+#[rtic::app(device = stm32f4xx_hal::pac, dispatchers = [EXTI1])]
+mod app {
+    #[shared]
+    struct Shared { counter: u32, config: u32 }
+
+    #[local]
+    struct Local {}
+
+    #[init]
+    fn init(_: init::Context) -> (Shared, Local) { todo!() }
+
+    #[idle]
+    fn idle(_: idle::Context) -> ! { loop {} }
+
+    #[task(binds = EXTI0, priority = 3, shared = [counter, &config], local = [x: u32 = 0])]
+    fn button(_: button::Context) {}
+
+    #[task(priority = 2, shared = [counter])]
+    async fn logger(_: logger::Context) {}
+
+    #[task(shared = [&config])]
+    async fn background(_: background::Context) {}
+
+    fn helper() {}
+}
+"#;
+
+#[test]
+fn rtic_app_tasks_keep_their_priorities_and_shared_resources() {
+    let declared = rtic_tasks(RTIC_APP).unwrap();
+    let names: Vec<&str> = declared.iter().map(|t| t.name.as_str()).collect();
+    assert_eq!(names, ["button", "logger", "background"]);
+    assert_eq!(declared[0].shared, ["counter", "config"]);
+    assert_eq!(declared[0].read_only, ["config"]);
+
+    let tasks = rtic_task_set(RTIC_APP).unwrap();
+    let prios: Vec<(u8, TaskKind)> = tasks.iter().map(|t| (t.prio, t.kind)).collect();
+    assert_eq!(prios, [(3, TaskKind::Interrupt), (2, TaskKind::Software), (1, TaskKind::Software)]);
+    // One placeholder section per shared resource, one after the other
+    let sections: Vec<(&str, u32, u32)> =
+        tasks[0].trace.inner.iter().map(|s| (s.id.as_str(), s.start, s.end)).collect();
+    assert_eq!(sections, [("counter", 0, PLACEHOLDER_WCET), ("config", PLACEHOLDER_WCET, 2 * PLACEHOLDER_WCET)]);
+    assert_eq!(tasks[0].trace.inner[1].lock, LockType::Shared);
+    assert_eq!(tasks[1].trace.inner[0].lock, LockType::Exclusive);
+    assert!(tasks.iter().all(|t| t.arrival.inter_arrival() == PLACEHOLDER_PERIOD));
+
+    // `counter` is locked by the tasks at priorities 3 and 2
+    let counter = resource_ceilings(&tasks).into_iter().find(|c| c.resource == "counter").unwrap();
+    assert_eq!(counter.ceiling, 3);
+}
+
+#[test]
+fn rtic_sources_without_an_app_are_rejected() {
+    assert!(rtic_task_set("mod app {}").is_err());
+    assert!(rtic_task_set("fn main() {").is_err());
+    assert!(rtic_task_set("#[rtic::app(device = pac)]\nmod app { #[task(priority = high)] fn t() {} }").is_err());
+}
+
+// Paths of the elements of an XML document in document order, e.g. `cheddar/tasks/periodic_task`, checking that
+// every element is closed in order and that text and attributes hold no unescaped markup
+fn xml_element_paths(xml: &str) -> Vec<String> {
+    let (mut open, mut paths): (Vec<&str>, Vec<String>) = (vec![], vec![]);
+    let mut rest = xml.trim_start_matches(|c| c != '<');
+    while let Some(start) = rest.find('<') {
+        let text = &rest[..start];
+        assert!(!text.contains('>'), "unescaped '>' in {:?}", text);
+        for (i, _) in text.match_indices('&') {
+            assert!(["&amp;", "&lt;", "&gt;", "&quot;"].iter().any(|e| text[i..].starts_with(e)), "{:?}", text);
+        }
+        let end = start + rest[start..].find('>').expect("unterminated tag");
+        let tag = &rest[start + 1..end];
+        assert!(!tag.contains('<'), "unescaped '<' in <{}>", tag);
+        if let Some(name) = tag.strip_prefix('/') {
+            assert_eq!(open.pop(), Some(name), "unbalanced </{}>", name);
+        } else if !tag.starts_with('?') {
+            let name = tag.split_whitespace().next().unwrap().trim_end_matches('/');
+            open.push(name);
+            paths.push(open.join("/"));
+            if tag.ends_with('/') {
+                open.pop();
+            }
+        }
+        rest = &rest[end + 1..];
+    }
+    assert!(open.is_empty(), "unclosed {:?}", open);
+    paths
+}
+
+fn markup_task_set() -> Tasks {
+    vec![
+        TaskBuilder::new("a<b&\"c\"").prio(2).period(10).span(0, 4).section("r&1", 1, 3, |s| s).build().unwrap(),
+        TaskBuilder::new("d>e").prio(1).period(20).span(0, 6).section("r&1", 2, 5, |s| s).build().unwrap(),
+    ]
+}
+
+#[test]
+fn cheddar_xml_has_the_model_structure_and_escapes_names() {
+    let xml = cheddar_xml(&markup_task_set()).unwrap();
+    let paths = xml_element_paths(&xml);
+    for path in ["cheddar/core_units/core_unit", "cheddar/processors/mono_core_processor", "cheddar/address_spaces"] {
+        assert!(paths.iter().any(|p| p == path), "{}", path);
+    }
+    assert_eq!(paths.iter().filter(|p| *p == "cheddar/tasks/periodic_task").count(), 2);
+    assert_eq!(paths.iter().filter(|p| *p == "cheddar/resources/np_resource").count(), 1);
+    let sections = "cheddar/resources/np_resource/critical_sections/critical_section";
+    assert_eq!(paths.iter().filter(|p| *p == sections).count(), 2);
+
+    assert!(xml.contains("<name>a&lt;b&amp;&quot;c&quot;</name>"));
+    assert!(xml.contains("<task_name>d&gt;e</task_name>"));
+    assert!(xml.contains("<name>r&amp;1</name>"));
+    // [1, 3) of the first task is its capacity units 2 ..= 3, at the ceiling of both tasks
+    assert!(xml.contains("<task_begin>2</task_begin>\n          <task_end>3</task_end>"));
+    assert!(xml.contains("<priority>2</priority>\n      <priority_assignment>"));
+}
+
+#[test]
+fn cheddar_xml_rejects_bursty_tasks() {
+    let bursty = TaskBuilder::new("t").prio(1).arrival(ArrivalModel::Bursty { n: 2, window: 10 }).span(0, 1).build();
+    assert!(cheddar_xml(&vec![bursty.unwrap()]).is_err());
+}
+
+#[test]
+fn analysis_report_xml_escapes_task_and_resource_ids() {
+    let mut tasks = markup_task_set();
+    // Misses its deadline, so its result carries the error
+    tasks[1].deadline = 5;
+    let config = AnalysisConfig { mode: PreemptionMode::Exact, ..AnalysisConfig::default() };
+    let xml = analysis_report_xml("set \"<1>\"", &tasks, &config);
+
+    let paths = xml_element_paths(&xml);
+    assert_eq!(paths.iter().filter(|p| *p == "analysis_report/tasks/task").count(), 2);
+    assert_eq!(paths.iter().filter(|p| *p == "analysis_report/results/result").count(), 2);
+    assert!(paths.iter().any(|p| p == "analysis_report/results/result/error"));
+    assert!(paths.iter().any(|p| p == "analysis_report/resources/resource/locked_by"));
+
+    assert!(xml.contains("<analysis_report task_set=\"set &quot;&lt;1&gt;&quot;\">"));
+    assert!(xml.contains("<task id=\"a&lt;b&amp;&quot;c&quot;\""));
+    assert!(xml.contains("<result task=\"d&gt;e\""));
+    assert!(xml.contains("<resource id=\"r&amp;1\""));
+    assert!(xml.contains("<verdict schedulable=\"false\"/>"));
+}
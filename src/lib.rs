@@ -0,0 +1,19 @@
+//! Schedulability analysis of task sets under the Stack Resource Policy (SRP), with the charts and the web
+//! server built on it.
+//!
+//! The analysis can be used on its own, e.g. to check a task set in the tests of a firmware project:
+//!
+//! ```
+//! use visualization::srp_analysis::*;
+//!
+//! let tasks = simple_example();
+//! for (task, response_time, ..) in srp_analyze(&tasks, &AnalysisConfig::default()) {
+//!     assert!(response_time.unwrap() <= task.deadline);
+//! }
+//! ```
+
+pub mod charts;
+pub mod config;
+pub mod io;
+pub mod server;
+pub mod srp_analysis;
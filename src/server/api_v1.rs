@@ -0,0 +1,192 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::srp_analysis::*;
+
+/// Critical section of a `TaskV1`, the stable wire format of a `Trace`.
+///
+/// Fields are only ever added with a default in v1, unknown fields are rejected so a typo does not silently
+/// change the model.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct TraceV1 {
+    /// Resource locked by the section
+    pub resource: String,
+    pub start: u32,
+    pub end: u32,
+    #[serde(default)]
+    pub sections: Vec<TraceV1>,
+    /// Section that cannot be preempted at all, e.g. interrupts disabled, rather than a resource lock
+    #[serde(default)]
+    pub non_preemptible: bool,
+    #[serde(default)]
+    pub lock: LockType,
+}
+
+/// Stable wire format of a `Task`, its trace flattened into `start`, `end` and `sections`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct TaskV1 {
+    pub id: String,
+    pub prio: u8,
+    pub deadline: u32,
+    pub arrival: ArrivalModel,
+    pub start: u32,
+    pub end: u32,
+    /// Id of the trace spanning `start` to `end`, the task id if absent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace_id: Option<String>,
+    #[serde(default)]
+    pub sections: Vec<TraceV1>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+    #[serde(default)]
+    pub stack: u32,
+    #[serde(default)]
+    pub suspension: u32,
+    #[serde(default)]
+    pub jitter: u32,
+    #[serde(default)]
+    pub offset: u32,
+    #[serde(default)]
+    pub kind: TaskKind,
+    #[serde(default)]
+    pub criticality: Criticality,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wcet_hi: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weakly_hard: Option<WeaklyHard>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub threshold: Option<u8>,
+}
+
+/// Stable wire format of the analysis of a task, see `TaskAnalysis`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct TaskAnalysisV1 {
+    pub id: String,
+    pub deadline: u32,
+    /// R(t) <= D(t)
+    pub schedulable: bool,
+    /// R(t), absent if the analysis gave up, see `error`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response_time: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub blocking_time: u32,
+    pub wcet: u32,
+    pub interference: u32,
+}
+
+impl From<&Trace> for TraceV1 {
+    fn from(trace: &Trace) -> Self {
+        TraceV1 {
+            resource: trace.id.clone(),
+            start: trace.start,
+            end: trace.end,
+            sections: trace.inner.iter().map(TraceV1::from).collect(),
+            non_preemptible: trace.non_preemptible,
+            lock: trace.lock,
+        }
+    }
+}
+
+impl From<TraceV1> for Trace {
+    fn from(trace: TraceV1) -> Self {
+        Trace {
+            id: trace.resource,
+            start: trace.start,
+            end: trace.end,
+            inner: trace.sections.into_iter().map(Trace::from).collect(),
+            non_preemptible: trace.non_preemptible,
+            lock: trace.lock,
+        }
+    }
+}
+
+impl From<&Task> for TaskV1 {
+    fn from(task: &Task) -> Self {
+        TaskV1 {
+            id: task.id.clone(),
+            prio: task.prio,
+            deadline: task.deadline,
+            arrival: task.arrival,
+            start: task.trace.start,
+            end: task.trace.end,
+            trace_id: (task.trace.id != task.id).then(|| task.trace.id.clone()),
+            sections: task.trace.inner.iter().map(TraceV1::from).collect(),
+            group: task.group.clone(),
+            stack: task.stack,
+            suspension: task.suspension,
+            jitter: task.jitter,
+            offset: task.offset,
+            kind: task.kind,
+            criticality: task.criticality,
+            wcet_hi: task.wcet_hi,
+            weakly_hard: task.weakly_hard,
+            threshold: task.threshold,
+        }
+    }
+}
+
+impl TryFrom<TaskV1> for Task {
+    type Error = String;
+
+    /// Builds the task, checking it like `TaskBuilder::build`.
+    fn try_from(task: TaskV1) -> Result<Self, String> {
+        let mut builder = TaskBuilder::new(&task.id)
+            .prio(task.prio)
+            .deadline(task.deadline)
+            .arrival(task.arrival)
+            .stack(task.stack)
+            .suspension(task.suspension)
+            .jitter(task.jitter)
+            .offset(task.offset)
+            .criticality(task.criticality)
+            .span(task.start, task.end);
+        if let Some(group) = &task.group {
+            builder = builder.group(group);
+        }
+        if task.kind == TaskKind::Interrupt {
+            builder = builder.interrupt();
+        }
+        if let Some(wcet_hi) = task.wcet_hi {
+            builder = builder.wcet_hi(wcet_hi);
+        }
+        if let Some(WeaklyHard { misses, window }) = task.weakly_hard {
+            builder = builder.weakly_hard(misses, window);
+        }
+        if let Some(threshold) = task.threshold {
+            builder = builder.threshold(threshold);
+        }
+        let mut built = builder.build()?;
+        if let Some(trace_id) = task.trace_id {
+            built.trace.id = trace_id;
+        }
+        built.trace.inner = task.sections.into_iter().map(Trace::from).collect();
+        validate_nesting(&built.trace)?;
+        Ok(built)
+    }
+}
+
+impl From<&TaskAnalysis> for TaskAnalysisV1 {
+    fn from(analysis: &TaskAnalysis) -> Self {
+        TaskAnalysisV1 {
+            id: analysis.id.clone(),
+            deadline: analysis.deadline,
+            schedulable: matches!(analysis.response_time, Ok(r) if r <= analysis.deadline),
+            response_time: analysis.response_time.as_ref().ok().copied(),
+            error: analysis.response_time.as_ref().err().cloned(),
+            blocking_time: analysis.blocking_time,
+            wcet: analysis.wcet,
+            interference: analysis.interference,
+        }
+    }
+}
+
+/// Task set of a v1 request, checked like `validate_task_set`.
+pub fn tasks_from_v1(tasks: Vec<TaskV1>) -> Result<Tasks, String> {
+    let tasks = tasks.into_iter().map(Task::try_from).collect::<Result<Tasks, String>>()?;
+    validate_task_set(&tasks)?;
+    Ok(tasks)
+}
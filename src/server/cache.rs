@@ -0,0 +1,133 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::Arc;
+
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::srp_analysis::*;
+
+pub const DEFAULT_CACHE_CAPACITY: usize = 64;
+
+/// Content address of an analysis, see `task_set_key`.
+///
+/// Keys are compared in full, so task sets whose hashes collide never share a cache entry.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TaskSetKey {
+    tasks: String,
+    config: AnalysisConfig,
+}
+
+/// Content address of an analysis: the serialized task set together with the analysis options.
+pub fn task_set_key(tasks: &Tasks, config: &AnalysisConfig) -> TaskSetKey {
+    TaskSetKey { tasks: serde_json::to_string(tasks).unwrap(), config: *config }
+}
+
+#[derive(Debug, Clone, Default, Serialize, ToSchema)]
+pub struct CacheStats {
+    pub entries: usize,
+    pub capacity: usize,
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+}
+
+struct LruEntry<V> {
+    value: V,
+    last_used: u64,
+}
+
+/// Values by key, evicting the least recently used entry when full.
+pub struct Lru<K, V> {
+    entries: HashMap<K, LruEntry<V>>,
+    capacity: usize,
+    // Logical clock, incremented on every access
+    clock: u64,
+    stats: CacheStats,
+}
+
+impl<K: Clone + Eq + Hash, V: Clone> Lru<K, V> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: HashMap::new(),
+            capacity,
+            clock: 0,
+            stats: CacheStats::default(),
+        }
+    }
+
+    pub fn get(&mut self, key: &K) -> Option<V> {
+        self.clock += 1;
+        match self.entries.get_mut(key) {
+            Some(entry) => {
+                entry.last_used = self.clock;
+                self.stats.hits += 1;
+                Some(entry.value.clone())
+            }
+            None => {
+                self.stats.misses += 1;
+                None
+            }
+        }
+    }
+
+    pub fn insert(&mut self, key: K, value: V) {
+        if self.capacity == 0 {
+            return;
+        }
+        if !self.entries.contains_key(&key) && self.entries.len() >= self.capacity {
+            self.evict_least_recently_used();
+        }
+        self.clock += 1;
+        self.entries.insert(key, LruEntry { value, last_used: self.clock });
+    }
+
+    /// Drops every entry whose key does not satisfy `keep`, without counting them as evictions.
+    pub fn retain<F: FnMut(&K) -> bool>(&mut self, mut keep: F) {
+        self.entries.retain(|key, _| keep(key));
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            entries: self.entries.len(),
+            capacity: self.capacity,
+            ..self.stats.clone()
+        }
+    }
+
+    fn evict_least_recently_used(&mut self) {
+        let oldest = self
+            .entries
+            .iter()
+            .min_by_key(|(_, entry)| entry.last_used)
+            .map(|(key, _)| key.clone());
+
+        if let Some(key) = oldest {
+            self.entries.remove(&key);
+            self.stats.evictions += 1;
+        }
+    }
+}
+
+/// Analysis results keyed by `task_set_key`.
+pub type AnalysisCache = Lru<TaskSetKey, Arc<Vec<TaskAnalysis>>>;
+
+pub const DEFAULT_CHART_CACHE_CAPACITY: usize = 16;
+
+/// Identity of a rendered chart page: which chart of which data, and how it is drawn.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ChartKey {
+    /// Stored task set the chart shows, `None` for task sets posted with the request
+    pub task_set: Option<String>,
+    pub chart: String,
+    /// `task_set_key` of the data the chart shows
+    pub data: TaskSetKey,
+    pub width: u64,
+    pub height: u64,
+    pub renderer: &'static str,
+    /// Requested theme, empty for the configured one
+    pub theme: String,
+}
+
+/// Rendered HTML of the chart pages of a session, see `Session::invalidate_charts`.
+pub type ChartCache = Lru<ChartKey, Arc<String>>;
@@ -0,0 +1,41 @@
+use askama::Template;
+
+use crate::charts;
+use crate::srp_analysis::*;
+use super::filters;
+
+/// Timeline, response times and resource usage of a task set on one page, linked by task selection.
+#[derive(Template)]
+#[template(path = "dashboard.html")]
+pub struct DashboardTemplate {
+    pub taskset_id: String,
+    /// Task ids as a JSON array, in the order of the task axes of the charts
+    pub tasks: String,
+    pub timeline: String,
+    pub response_times: String,
+    pub resources: String,
+    /// Shown through a read-only share link, without links to the pages of the owner's session
+    pub shared: bool,
+}
+
+impl DashboardTemplate {
+    pub fn new(
+        taskset_id: &str,
+        tasks: &Tasks,
+        simulation: &Simulation,
+        config: &AnalysisConfig,
+        previous: Option<&[TaskAnalysis]>,
+        theme: &charts::Theme,
+    ) -> Self {
+        let results = srp_analyze(tasks, config);
+        let ids: Vec<&str> = tasks.iter().map(|task| task.id.as_str()).collect();
+        Self {
+            taskset_id: taskset_id.to_string(),
+            tasks: serde_json::to_string(&ids).unwrap(),
+            timeline: charts::timeline_chart(tasks, simulation, config.clock, theme).to_string(),
+            response_times: charts::response_time_chart(&results, previous, config.clock, theme).to_string(),
+            resources: charts::resource_usage_chart(tasks, theme).to_string(),
+            shared: false,
+        }
+    }
+}
@@ -0,0 +1,248 @@
+use askama::Template;
+use serde::Deserialize;
+
+use crate::srp_analysis::*;
+
+/// Task parameters as submitted by the editor, adding a new task or replacing the parameters of the task
+/// with the same id. Critical sections of an existing task are kept.
+#[derive(Debug, Deserialize)]
+pub struct TaskForm {
+    pub id: String,
+    pub prio: u8,
+    pub deadline: u32,
+    /// `periodic`, `sporadic` or `bursty`
+    pub arrival: String,
+    pub inter_arrival: u32,
+    /// Releases per window of a bursty task
+    #[serde(default)]
+    pub n: u32,
+    pub start: u32,
+    pub end: u32,
+    /// Empty for no group
+    #[serde(default)]
+    pub group: String,
+    #[serde(default)]
+    pub stack: u32,
+    #[serde(default)]
+    pub suspension: u32,
+    #[serde(default)]
+    pub jitter: u32,
+    /// Checkbox, present if checked
+    pub interrupt: Option<String>,
+    #[serde(default)]
+    pub criticality: Criticality,
+    /// C(HI), 0 for a LO-criticality task
+    #[serde(default)]
+    pub wcet_hi: u32,
+    /// Preemption threshold γ(t), 0 to run at P(t)
+    #[serde(default)]
+    pub threshold: u8,
+}
+
+/// Critical section added by the editor inside the section at `parent` of `task`.
+#[derive(Debug, Deserialize)]
+pub struct SectionForm {
+    pub task: String,
+    /// Section path, see `EditorRow::path`, empty for the outermost trace of the task
+    #[serde(default)]
+    pub parent: String,
+    pub resource: String,
+    pub start: u32,
+    pub end: u32,
+    /// Checkbox, present if checked
+    pub non_preemptible: Option<String>,
+    /// Checkbox, present if the section only reads the resource, see `LockType::Shared`
+    pub shared: Option<String>,
+}
+
+/// Section of a task to remove, see `EditorRow::path`.
+#[derive(Debug, Deserialize)]
+pub struct SectionRef {
+    pub task: String,
+    pub path: String,
+}
+
+fn arrival_model(arrival: &str, inter_arrival: u32, n: u32) -> Result<ArrivalModel, String> {
+    match arrival {
+        "periodic" => Ok(ArrivalModel::Periodic { period: inter_arrival }),
+        "sporadic" => Ok(ArrivalModel::Sporadic { min_inter_arrival: inter_arrival }),
+        "bursty" => Ok(ArrivalModel::Bursty { n: n.max(1), window: inter_arrival }),
+        _ => Err(format!("Error: Unknown arrival model '{}'", arrival)),
+    }
+}
+
+// Indices of a nested section, "0.1" is the second section inside the first section of a task
+fn parse_path(path: &str) -> Result<Vec<usize>, String> {
+    if path.is_empty() {
+        return Ok(vec![]);
+    }
+    path.split('.')
+        .map(|i| i.parse().map_err(|_| format!("Error: Invalid section path '{}'", path)))
+        .collect()
+}
+
+fn section_mut<'a>(trace: &'a mut Trace, path: &[usize]) -> Result<&'a mut Trace, String> {
+    path.iter().try_fold(trace, |trace, &i| {
+        trace.inner.get_mut(i).ok_or_else(|| "Error: Section does not exist".to_string())
+    })
+}
+
+fn task_mut<'a>(tasks: &'a mut Tasks, id: &str) -> Result<&'a mut Task, String> {
+    tasks
+        .iter_mut()
+        .find(|task| task.id == id)
+        .ok_or_else(|| format!("Error: Task '{}' does not exist", id))
+}
+
+impl TaskForm {
+    /// Adds the task to the set, or updates the task with the same id.
+    pub fn apply(self, tasks: &mut Tasks) -> Result<(), String> {
+        let id = self.id.trim().to_string();
+        if id.is_empty() {
+            return Err("Error: Task id must not be empty".to_string());
+        }
+        let arrival = arrival_model(&self.arrival, self.inter_arrival, self.n)?;
+        let group = Some(self.group.trim().to_string()).filter(|group| !group.is_empty());
+
+        let sections = tasks.iter().find(|task| task.id == id).map(|task| task.trace.inner.clone());
+        let builder = TaskBuilder::new(&id)
+            .prio(self.prio)
+            .deadline(self.deadline)
+            .arrival(arrival)
+            .stack(self.stack)
+            .suspension(self.suspension)
+            .jitter(self.jitter)
+            .criticality(self.criticality)
+            .span(self.start, self.end);
+        let builder = if self.wcet_hi > 0 { builder.wcet_hi(self.wcet_hi) } else { builder };
+        let builder = if self.threshold > 0 { builder.threshold(self.threshold) } else { builder };
+        let mut task = if self.interrupt.is_some() { builder.interrupt() } else { builder }.build()?;
+        task.group = group;
+        task.trace.inner = sections.unwrap_or_default();
+        validate_nesting(&task.trace)?;
+
+        match tasks.iter_mut().find(|t| t.id == id) {
+            Some(existing) => *existing = task,
+            None => tasks.push(task),
+        }
+        Ok(())
+    }
+}
+
+impl SectionForm {
+    /// Adds the section to its parent, which it has to lie within.
+    pub fn apply(self, tasks: &mut Tasks) -> Result<(), String> {
+        let resource = self.resource.trim();
+        if resource.is_empty() {
+            return Err("Error: Resource id must not be empty".to_string());
+        }
+        let task = task_mut(tasks, &self.task)?;
+        let parent = section_mut(&mut task.trace, &parse_path(&self.parent)?)?;
+        let section = Trace {
+            id: resource.to_string(),
+            start: self.start,
+            end: self.end,
+            inner: vec![],
+            non_preemptible: self.non_preemptible.is_some(),
+            lock: if self.shared.is_some() { LockType::Shared } else { LockType::Exclusive },
+        };
+
+        let mut candidate = parent.clone();
+        candidate.inner.push(section);
+        validate_nesting(&candidate)?;
+        *parent = candidate;
+        Ok(())
+    }
+}
+
+impl SectionRef {
+    /// Removes the section and everything nested inside it.
+    pub fn remove(&self, tasks: &mut Tasks) -> Result<(), String> {
+        let task = task_mut(tasks, &self.task)?;
+        let mut path = parse_path(&self.path)?;
+        let Some(index) = path.pop() else {
+            return Err("Error: The outermost trace of a task cannot be removed".to_string());
+        };
+        let parent = section_mut(&mut task.trace, &path)?;
+        if index >= parent.inner.len() {
+            return Err("Error: Section does not exist".to_string());
+        }
+        parent.inner.remove(index);
+        Ok(())
+    }
+}
+
+/// Removes the task with the given id.
+pub fn remove_task(tasks: &mut Tasks, id: &str) -> Result<(), String> {
+    let index = tasks
+        .iter()
+        .position(|task| task.id == id)
+        .ok_or_else(|| format!("Error: Task '{}' does not exist", id))?;
+    tasks.remove(index);
+    Ok(())
+}
+
+/// Critical section of a task as listed by the editor.
+pub struct EditorRow {
+    /// Indices of the section within the nested sections of the task, joined by '.'
+    pub path: String,
+    pub depth: usize,
+    pub resource: String,
+    pub start: u32,
+    pub end: u32,
+    pub non_preemptible: bool,
+    pub shared: bool,
+}
+
+/// Task as listed by the editor, with its flattened sections in pre-order.
+pub struct EditorTask {
+    pub task: Task,
+    /// `periodic`, `sporadic` or `bursty`
+    pub arrival: &'static str,
+    /// Releases per window, 1 unless bursty
+    pub n: u32,
+    pub rows: Vec<EditorRow>,
+}
+
+fn flatten(trace: &Trace, path: &str, depth: usize, rows: &mut Vec<EditorRow>) {
+    for (i, section) in trace.inner.iter().enumerate() {
+        let path = if path.is_empty() { i.to_string() } else { format!("{}.{}", path, i) };
+        rows.push(EditorRow {
+            path: path.clone(),
+            depth,
+            resource: section.id.clone(),
+            start: section.start,
+            end: section.end,
+            non_preemptible: section.non_preemptible,
+            shared: section.lock == LockType::Shared,
+        });
+        flatten(section, &path, depth + 1, rows);
+    }
+}
+
+impl From<&Task> for EditorTask {
+    fn from(task: &Task) -> Self {
+        let mut rows = vec![];
+        flatten(&task.trace, "", 0, &mut rows);
+        let arrival = match task.arrival {
+            ArrivalModel::Periodic { .. } => "periodic",
+            ArrivalModel::Sporadic { .. } => "sporadic",
+            ArrivalModel::Bursty { .. } => "bursty",
+        };
+        EditorTask { task: task.clone(), arrival, n: task.arrival.releases_per_inter_arrival(), rows }
+    }
+}
+
+/// Form-based editor of a stored task set, posting every change back to the server.
+#[derive(Template)]
+#[template(path = "editor.html")]
+pub struct EditorTemplate {
+    pub taskset_id: String,
+    pub tasks: Vec<EditorTask>,
+}
+
+impl EditorTemplate {
+    pub fn new(taskset_id: &str, tasks: &Tasks) -> Self {
+        EditorTemplate { taskset_id: taskset_id.to_string(), tasks: tasks.iter().map(EditorTask::from).collect() }
+    }
+}
@@ -0,0 +1,125 @@
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+
+use rumqttc::{AsyncClient, Event, MqttOptions, Packet, QoS};
+
+use crate::config::MqttConfig;
+use crate::srp_analysis::*;
+use super::{AppState, TaskSetCache};
+
+/// Delay before polling the broker again after a connection error, which reconnects.
+pub const MQTT_RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// Execution times of the jobs streamed from devices in the field, by task set id.
+///
+/// Unlike the events posted to /ingest, which belong to the session posting them, field events update the task
+/// sets shared by all sessions, so every live view follows the devices.
+#[derive(Default)]
+pub struct FieldMetrics {
+    // Jobs still open by topic, as every device publishes its events under its own topic with its own clock
+    recorders: Mutex<BTreeMap<String, TraceRecorder>>,
+    measurements: RwLock<BTreeMap<String, Measurements>>,
+}
+
+impl FieldMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the newline-delimited `TraceEvent`s of a message published under `topic`, and sets C(t) of every
+    /// task of the shared task set to its largest execution time in the field so far. Returns the number of
+    /// completed jobs.
+    ///
+    /// An invalid event discards the open jobs of the topic, the events before it are kept.
+    pub fn ingest(
+        &self,
+        shared: &TaskSetCache,
+        taskset_id: &str,
+        topic: &str,
+        payload: &[u8],
+    ) -> Result<usize, String> {
+        let mut tasks = shared.get(taskset_id).ok_or_else(|| format!("Error: No task set '{}'.", taskset_id))?;
+        let mut recorders = self.recorders.lock().unwrap();
+        let mut completed = Measurements::new();
+        let mut recorded = Ok(());
+        for text in payload.split(|&b| b == b'\n').filter(|text| !text.iter().all(u8::is_ascii_whitespace)) {
+            let recorder = recorders.entry(topic.to_string()).or_default();
+            recorded = record_event(recorder, &tasks, text, &mut completed);
+            if recorded.is_err() {
+                recorders.remove(topic);
+                break;
+            }
+        }
+        drop(recorders);
+
+        let jobs = completed.values().map(Vec::len).sum();
+        if jobs > 0 {
+            let mut stored = self.measurements.write().unwrap();
+            let measurements = stored.entry(taskset_id.to_string()).or_default();
+            for (task, samples) in completed {
+                measurements.entry(task).or_default().extend(samples);
+            }
+            apply_measured_wcets(&mut tasks, measurements, WcetStatistic::Max)?;
+            shared.insert(taskset_id, tasks);
+        }
+        recorded.map(|_| jobs)
+    }
+
+    /// Statistics of the execution times measured in the field for the task set, `None` before its first job.
+    pub fn stats(&self, taskset_id: &str) -> Option<Vec<ExecutionTimeStats>> {
+        self.measurements.read().unwrap().get(taskset_id).map(execution_time_stats)
+    }
+}
+
+fn record_event(
+    recorder: &mut TraceRecorder,
+    tasks: &Tasks,
+    text: &[u8],
+    completed: &mut Measurements,
+) -> Result<(), String> {
+    let event: TraceEvent = serde_json::from_slice(text).map_err(|e| format!("Error: Invalid event: {}", e))?;
+    if let TraceEventKind::Start { task } = &event.kind {
+        if !tasks.iter().any(|t| &t.id == task) {
+            return Err(format!("Error: No task with id '{}'.", task));
+        }
+    }
+    if let Some(job) = recorder.record(&event)? {
+        completed.entry(job.task).or_default().push(job.trace.end - job.trace.start);
+    }
+    Ok(())
+}
+
+/// Subscribes to the configured topic and feeds every message to `AppState::field`, reconnecting to the broker
+/// until the server stops.
+pub fn subscribe_field_events(state: Arc<AppState>, config: MqttConfig) -> tokio::task::JoinHandle<()> {
+    let client_id = config.client_id.clone().unwrap_or_else(|| "visualization".to_string());
+    let mut options = MqttOptions::new(client_id, &config.broker, config.port);
+    options.set_keep_alive(Duration::from_secs(30));
+    let (client, mut eventloop) = AsyncClient::new(options, 16);
+
+    tokio::spawn(async move {
+        loop {
+            match eventloop.poll().await {
+                // Subscribing on every connection, as a clean session forgets its subscriptions
+                Ok(Event::Incoming(Packet::ConnAck(_))) => {
+                    tracing::info!("Connected to MQTT broker {}, subscribing to {}", config.broker, config.topic);
+                    if let Err(e) = client.try_subscribe(&config.topic, QoS::AtLeastOnce) {
+                        tracing::warn!("Error: Cannot subscribe to {}: {}", config.topic, e);
+                    }
+                }
+                Ok(Event::Incoming(Packet::Publish(publish))) => {
+                    let ingested = state.field.ingest(&state.shared, &config.taskset, &publish.topic, &publish.payload);
+                    if let Err(e) = ingested {
+                        tracing::warn!("{} (topic {})", e, publish.topic);
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    tracing::warn!("Error: MQTT connection to {} failed: {}", config.broker, e);
+                    tokio::time::sleep(MQTT_RECONNECT_DELAY).await;
+                }
+            }
+        }
+    })
+}
@@ -0,0 +1,8 @@
+/// JSON, e.g. a chart option, made safe to inline into a `<script>` element of a template.
+///
+/// `<`, `>` and `&` are written as the JSON escapes `\u003c`, `\u003e` and `\u0026`, so a string such as
+/// `"</script>"` can neither close the element nor start a comment, and the value parsed by JavaScript is unchanged.
+/// Mark the result as safe, e.g. `{{ chart|script_json|safe }}`.
+pub fn script_json<T: std::fmt::Display>(json: T) -> askama::Result<String> {
+    Ok(json.to_string().replace('&', "\\u0026").replace('<', "\\u003c").replace('>', "\\u003e"))
+}
@@ -0,0 +1,213 @@
+use std::sync::Arc;
+
+use async_graphql::{Context, EmptyMutation, EmptySubscription, Json, Object, Schema, SimpleObject};
+
+use crate::srp_analysis::*;
+use super::Session;
+
+/// Schema served under /graphql, resolving task sets in the session of the request.
+pub type AnalysisSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+pub fn analysis_schema() -> AnalysisSchema {
+    Schema::build(QueryRoot, EmptyMutation, EmptySubscription).finish()
+}
+
+fn session<'a>(ctx: &Context<'a>) -> &'a Arc<Session> {
+    ctx.data_unchecked::<Arc<Session>>()
+}
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// Every task set visible in the session, analyzed with the default configuration.
+    async fn task_sets(&self, ctx: &Context<'_>) -> Vec<TaskSetNode> {
+        let session = session(ctx);
+        session
+            .task_set_ids()
+            .into_iter()
+            .filter_map(|id| Some(TaskSetNode::new(id.clone(), session.task_set(&id)?, AnalysisConfig::default())))
+            .collect()
+    }
+
+    /// Task set with the given id, analyzed with `config`, see the `AnalysisConfig` of the REST API.
+    async fn task_set(&self, ctx: &Context<'_>, id: String, config: Option<Json<AnalysisConfig>>) -> Option<TaskSetNode> {
+        let tasks = session(ctx).task_set(&id)?;
+        Some(TaskSetNode::new(id, tasks, config.map(|config| config.0).unwrap_or_default()))
+    }
+}
+
+// Task set and configuration shared by all nodes below a task set
+struct Analyzed {
+    tasks: Tasks,
+    config: AnalysisConfig,
+}
+
+impl Analyzed {
+    // Results of the (cached) analysis in the session
+    fn results(&self, ctx: &Context<'_>) -> Arc<Vec<TaskAnalysis>> {
+        session(ctx).analyze(&self.tasks, &self.config)
+    }
+}
+
+pub struct TaskSetNode {
+    id: String,
+    analyzed: Arc<Analyzed>,
+}
+
+impl TaskSetNode {
+    fn new(id: String, tasks: Tasks, config: AnalysisConfig) -> Self {
+        Self { id, analyzed: Arc::new(Analyzed { tasks, config }) }
+    }
+}
+
+#[Object]
+impl TaskSetNode {
+    async fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Tasks of the set, only those with R(t) > `response_above` * D(t) if given, unbounded R(t) included.
+    async fn tasks(&self, ctx: &Context<'_>, response_above: Option<f64>) -> Vec<TaskNode> {
+        let results = response_above.map(|_| self.analyzed.results(ctx));
+        (0..self.analyzed.tasks.len())
+            .filter(|&index| match (&results, response_above) {
+                (Some(results), Some(ratio)) => match results[index].response_time {
+                    Ok(r) => r as f64 > ratio * results[index].deadline as f64,
+                    Err(_) => true,
+                },
+                _ => true,
+            })
+            .map(|index| TaskNode { index, analyzed: self.analyzed.clone() })
+            .collect()
+    }
+
+    /// Resources locked in the task set, see `resource_ceilings`.
+    async fn resources(&self) -> Vec<ResourceNode> {
+        resource_ceilings(&self.analyzed.tasks)
+            .into_iter()
+            .map(|ceiling| ResourceNode {
+                id: ceiling.resource,
+                ceiling: ceiling.ceiling,
+                read_ceiling: ceiling.read_ceiling,
+                tasks: ceiling.tasks,
+            })
+            .collect()
+    }
+
+    /// L_tot, see `total_load_factor`.
+    async fn load_factor(&self) -> async_graphql::Result<f32> {
+        Ok(total_load_factor(&self.analyzed.tasks)?)
+    }
+
+    /// Whether every task meets its deadline.
+    async fn schedulable(&self, ctx: &Context<'_>) -> bool {
+        self.analyzed.results(ctx).iter().all(TaskAnalysis::meets_deadline)
+    }
+}
+
+pub struct TaskNode {
+    index: usize,
+    analyzed: Arc<Analyzed>,
+}
+
+impl TaskNode {
+    fn task(&self) -> &Task {
+        &self.analyzed.tasks[self.index]
+    }
+}
+
+#[Object]
+impl TaskNode {
+    async fn id(&self) -> &str {
+        &self.task().id
+    }
+
+    /// P(t)
+    async fn prio(&self) -> u8 {
+        self.task().prio
+    }
+
+    /// D(t)
+    async fn deadline(&self) -> u32 {
+        self.task().deadline
+    }
+
+    /// A(t)
+    async fn inter_arrival(&self) -> u32 {
+        self.task().arrival.inter_arrival()
+    }
+
+    /// C(t)
+    async fn wcet(&self) -> u32 {
+        self.task().wcet()
+    }
+
+    /// C(HI), null for a LO-criticality task
+    async fn wcet_hi(&self) -> Option<u32> {
+        self.task().wcet_hi
+    }
+
+    /// γ(t), the priority the task runs at once it started
+    async fn preemption_threshold(&self) -> u8 {
+        self.task().preemption_threshold()
+    }
+
+    async fn interrupt(&self) -> bool {
+        self.task().is_interrupt()
+    }
+
+    async fn group(&self) -> Option<&str> {
+        self.task().group.as_deref()
+    }
+
+    async fn stack(&self) -> u32 {
+        self.task().stack
+    }
+
+    /// Ids of the resources the task locks, in pre-order.
+    async fn resources(&self) -> Vec<&str> {
+        self.task().resources().map(|resource| resource.id.as_str()).collect()
+    }
+
+    async fn analysis(&self, ctx: &Context<'_>) -> AnalysisNode {
+        AnalysisNode::from(&self.analyzed.results(ctx)[self.index])
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct ResourceNode {
+    id: String,
+    /// π(r)
+    ceiling: u8,
+    /// π_r(r) of its read-shared sections, if there are any
+    read_ceiling: Option<u8>,
+    /// Ids of the tasks locking the resource
+    tasks: Vec<String>,
+}
+
+/// Analysis of a task, see `TaskAnalysis`.
+#[derive(SimpleObject)]
+pub struct AnalysisNode {
+    /// R(t), null if unbounded
+    response_time: Option<u32>,
+    /// Why R(t) is unbounded
+    error: Option<String>,
+    blocking_time: u32,
+    wcet: u32,
+    interference: u32,
+    meets_deadline: bool,
+}
+
+impl From<&TaskAnalysis> for AnalysisNode {
+    fn from(analysis: &TaskAnalysis) -> Self {
+        AnalysisNode {
+            response_time: analysis.response_time.as_ref().ok().copied(),
+            error: analysis.response_time.as_ref().err().cloned(),
+            blocking_time: analysis.blocking_time,
+            wcet: analysis.wcet,
+            interference: analysis.interference,
+            meets_deadline: analysis.meets_deadline(),
+        }
+    }
+}
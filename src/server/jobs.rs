@@ -0,0 +1,105 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::Instant;
+
+use serde::Serialize;
+use utoipa::ToSchema;
+
+/// Most jobs a table keeps, the oldest finished job is dropped to make room for a new one.
+pub const MAX_JOBS: usize = 64;
+
+/// Progress of a background job, see `JobTable`.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+#[serde(tag = "status", rename_all = "lowercase")]
+pub enum JobStatus {
+    Running,
+    Done {
+        /// Same body as the synchronous endpoint of the job responds with
+        #[schema(value_type = Object)]
+        result: serde_json::Value,
+    },
+    Failed {
+        error: String,
+    },
+}
+
+/// Background job as reported by `GET /jobs/:id`.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct Job {
+    pub id: String,
+    /// What the job computes, e.g. `sweep`
+    pub kind: String,
+    #[serde(flatten)]
+    pub status: JobStatus,
+    /// Milliseconds since the job started, until it finished if it did
+    pub elapsed_ms: u64,
+}
+
+struct JobEntry {
+    kind: String,
+    status: JobStatus,
+    started: Instant,
+    finished: Option<Instant>,
+}
+
+#[derive(Default)]
+struct Jobs {
+    entries: HashMap<String, JobEntry>,
+    // Ids in the order the jobs started
+    order: VecDeque<String>,
+}
+
+/// Jobs too long for a single request, e.g. large sweeps, which run in the background while clients poll for
+/// their results by id.
+///
+/// Running jobs are never dropped, so a table may hold more than `MAX_JOBS` while all of them run.
+#[derive(Default)]
+pub struct JobTable {
+    jobs: Mutex<Jobs>,
+}
+
+impl JobTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a running job of the kind and returns its new id.
+    pub fn start(&self, kind: &str) -> String {
+        let id = uuid::Uuid::new_v4().to_string();
+        let mut jobs = self.jobs.lock().unwrap();
+        if jobs.order.len() >= MAX_JOBS {
+            let finished = jobs.order.iter().position(|id| jobs.entries[id].finished.is_some());
+            if let Some(oldest) = finished.and_then(|index| jobs.order.remove(index)) {
+                jobs.entries.remove(&oldest);
+            }
+        }
+        let started = Instant::now();
+        let entry = JobEntry { kind: kind.to_string(), status: JobStatus::Running, started, finished: None };
+        jobs.entries.insert(id.clone(), entry);
+        jobs.order.push_back(id.clone());
+        id
+    }
+
+    /// Records the outcome of the job, if it is still in the table.
+    pub fn finish(&self, id: &str, result: Result<serde_json::Value, String>) {
+        if let Some(entry) = self.jobs.lock().unwrap().entries.get_mut(id) {
+            entry.status = match result {
+                Ok(result) => JobStatus::Done { result },
+                Err(error) => JobStatus::Failed { error },
+            };
+            entry.finished = Some(Instant::now());
+        }
+    }
+
+    pub fn get(&self, id: &str) -> Option<Job> {
+        let jobs = self.jobs.lock().unwrap();
+        let entry = jobs.entries.get(id)?;
+        let elapsed = entry.finished.unwrap_or_else(Instant::now).duration_since(entry.started);
+        Some(Job {
+            id: id.to_string(),
+            kind: entry.kind.clone(),
+            status: entry.status.clone(),
+            elapsed_ms: elapsed.as_millis() as u64,
+        })
+    }
+}
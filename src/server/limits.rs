@@ -0,0 +1,81 @@
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::num::NonZeroU32;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use axum::{
+    extract::{ConnectInfo, State},
+    http::{header, Request, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+/// Clients tracked at most, a new client beyond them replaces the one seen longest ago
+pub const MAX_TRACKED_CLIENTS: usize = 10_000;
+
+/// Token bucket of every client address, refilled at `requests_per_minute` a minute up to as many tokens, so a
+/// client can make a burst of requests after being idle, see `rate_limit`.
+pub struct RateLimiter {
+    requests_per_minute: NonZeroU32,
+    /// Tokens left and when they were counted, by client
+    buckets: Mutex<HashMap<IpAddr, (f64, Instant)>>,
+}
+
+impl RateLimiter {
+    pub fn new(requests_per_minute: NonZeroU32) -> Self {
+        Self { requests_per_minute, buckets: Mutex::new(HashMap::new()) }
+    }
+
+    /// Number of clients with a bucket.
+    pub fn tracked_clients(&self) -> usize {
+        self.buckets.lock().unwrap().len()
+    }
+
+    /// Takes a token of the client at `now`, or the whole seconds until it has one again.
+    ///
+    /// At most `MAX_TRACKED_CLIENTS` buckets are kept, the bucket of the client seen longest ago makes room for a
+    /// new client.
+    pub fn check(&self, client: IpAddr, now: Instant) -> Result<(), u64> {
+        let capacity = self.requests_per_minute.get() as f64;
+        let per_second = capacity / 60.0;
+        let refilled = |(tokens, at): &(f64, Instant)| (tokens + per_second * (now - *at).as_secs_f64()).min(capacity);
+
+        let mut buckets = self.buckets.lock().unwrap();
+        if buckets.len() >= MAX_TRACKED_CLIENTS && !buckets.contains_key(&client) {
+            // Its bucket has refilled the most, so forgetting it frees the fewest requests
+            let oldest = buckets.iter().min_by_key(|(_, (_, at))| *at).map(|(client, _)| *client);
+            if let Some(oldest) = oldest {
+                buckets.remove(&oldest);
+            }
+        }
+        let bucket = buckets.entry(client).or_insert((capacity, now));
+        let tokens = refilled(bucket);
+        if tokens >= 1.0 {
+            *bucket = (tokens - 1.0, now);
+            Ok(())
+        } else {
+            *bucket = (tokens, now);
+            Err(((1.0 - tokens) / per_second).ceil() as u64)
+        }
+    }
+}
+
+/// Middleware answering requests of a client beyond its rate limit with 429 Too Many Requests and `Retry-After`.
+///
+/// Requests pass without a limiter, or without a client address, i.e. unless served with
+/// `into_make_service_with_connect_info::<SocketAddr>`.
+pub async fn rate_limit<B>(
+    State(limiter): State<Option<Arc<RateLimiter>>>,
+    request: Request<B>,
+    next: Next<B>,
+) -> Response {
+    let client = request.extensions().get::<ConnectInfo<SocketAddr>>().map(|ConnectInfo(address)| address.ip());
+    if let (Some(limiter), Some(client)) = (limiter, client) {
+        if let Err(retry_after) = limiter.check(client, Instant::now()) {
+            let headers = [(header::RETRY_AFTER, retry_after.to_string())];
+            return (StatusCode::TOO_MANY_REQUESTS, headers, "Error: Too many requests, try again later.").into_response();
+        }
+    }
+    next.run(request).await
+}
@@ -0,0 +1,137 @@
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::{header, request::Parts, HeaderMap},
+};
+
+/// Language of the index and report pages, picked from the `lang` query parameter or the `Accept-Language`
+/// header of the request.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Locale {
+    #[default]
+    En,
+    Sv,
+}
+
+// Message catalog of the pages: key, English, Swedish
+const MESSAGES: &[(&str, &str, &str)] = &[
+    ("run_analysis", "Run SRP analysis", "Kör SRP-analys"),
+    ("edit_example", "Edit the example task set", "Redigera exemplets uppgiftsmängd"),
+    ("view", "View", "Visa"),
+    ("task_set", "Task set", "Uppgiftsmängd"),
+    ("chart", "Chart", "Diagram"),
+    ("mode", "Mode", "Läge"),
+    ("theme", "Theme", "Tema"),
+    ("default_theme", "default", "standard"),
+    ("width", "Width", "Bredd"),
+    ("height", "Height", "Höjd"),
+    (
+        "keys",
+        "Keys: j / k or the arrow keys move between the charts, Enter opens one, / jumps to the view form.",
+        "Tangenter: j / k eller piltangenterna flyttar mellan diagrammen, Enter öppnar ett, / hoppar till \
+         visningsformuläret.",
+    ),
+    ("report", "SRP analysis report", "SRP-analysrapport"),
+    ("verdict", "Verdict", "Utlåtande"),
+    ("schedulable", "Schedulable", "Schemaläggbar"),
+    ("all_deadlines_met", "every task meets its deadline.", "alla uppgifter håller sina deadlines."),
+    ("not_schedulable", "Not schedulable", "Inte schemaläggbar"),
+    ("deadline_missed", "at least one task misses its deadline.", "minst en uppgift missar sin deadline."),
+    ("total_load_factor", "Total load factor", "Total belastningsfaktor"),
+    ("assumptions", "Assumptions", "Antaganden"),
+    ("deadline_model", "Deadline model", "Deadlinemodell"),
+    ("jitter_model", "Jitter model", "Jittermodell"),
+    ("offset_model", "Offset model", "Offsetmodell"),
+    ("blocking_refinement", "Blocking refinement", "Förfining av blockering"),
+    ("tick_granularity", "Tick granularity", "Tickupplösning"),
+    ("clock", "Clock", "Klocka"),
+    ("tasks", "Tasks", "Uppgifter"),
+    ("sortable_results", "Sortable results", "Sorterbara resultat"),
+    ("in_ticks_also_as", "in ticks, also as", "i tick, även som"),
+    ("task", "Task", "Uppgift"),
+    ("group", "Group", "Grupp"),
+    ("blocked_by", "Blocked by", "Blockeras av"),
+    ("stack_usage", "Stack usage", "Stackanvändning"),
+    ("stack_depth", "Worst-case shared stack depth under SRP:", "Största delade stackdjup under SRP:"),
+    ("bytes", "bytes", "byte"),
+    ("stack", "Stack", "Stack"),
+    ("charts", "Charts", "Diagram"),
+    ("response_times", "Response times", "Svarstider"),
+    ("groups", "Utilization per group", "Utnyttjande per grupp"),
+    ("composition", "Response time composition", "Svarstidens sammansättning"),
+    ("utilization", "CPU utilization", "CPU-utnyttjande"),
+];
+
+impl Locale {
+    /// Locale of a language tag such as `sv-SE`, by its primary language subtag.
+    pub fn from_tag(tag: &str) -> Option<Locale> {
+        let language = tag.trim().split(['-', '_']).next()?;
+        if language.eq_ignore_ascii_case("en") {
+            Some(Locale::En)
+        } else if language.eq_ignore_ascii_case("sv") {
+            Some(Locale::Sv)
+        } else {
+            None
+        }
+    }
+
+    /// Language tag for the `lang` attribute of a page.
+    pub fn tag(self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::Sv => "sv",
+        }
+    }
+
+    /// Supported language with the highest quality in `Accept-Language`, the earliest one on equal qualities.
+    ///
+    /// Requests without an `Accept-Language` header, or without a supported language in it, get English.
+    pub fn from_headers(headers: &HeaderMap) -> Locale {
+        let Some(accept) = headers.get(header::ACCEPT_LANGUAGE).and_then(|accept| accept.to_str().ok()) else {
+            return Locale::default();
+        };
+        let mut best = (0.0, Locale::default());
+        for range in accept.split(',') {
+            let mut params = range.split(';').map(str::trim);
+            let Some(locale) = params.next().and_then(Locale::from_tag) else {
+                continue;
+            };
+            let quality = params
+                .find_map(|param| param.strip_prefix("q="))
+                .and_then(|q| q.parse().ok())
+                .unwrap_or(1.0);
+            if quality > best.0 {
+                best = (quality, locale);
+            }
+        }
+        best.1
+    }
+
+    /// Message of the catalog in this language, the key itself if it has none.
+    pub fn text(self, key: &str) -> &str {
+        match MESSAGES.iter().find(|(k, ..)| *k == key) {
+            Some((_, en, sv)) => match self {
+                Locale::En => en,
+                Locale::Sv => sv,
+            },
+            None => key,
+        }
+    }
+}
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for Locale {
+    type Rejection = std::convert::Infallible;
+
+    // `lang=sv` in the query overrides the header, e.g. for a link to a report in another language
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let requested = parts
+            .uri
+            .query()
+            .unwrap_or_default()
+            .split('&')
+            .find_map(|param| param.strip_prefix("lang="))
+            .and_then(Locale::from_tag);
+        Ok(requested.unwrap_or_else(|| Locale::from_headers(&parts.headers)))
+    }
+}
@@ -0,0 +1,108 @@
+mod api_v1;
+mod cache;
+mod dashboard;
+mod editor;
+#[cfg(feature = "mqtt")]
+mod field;
+mod filters;
+mod graphql;
+mod jobs;
+mod limits;
+mod locale;
+mod negotiate;
+mod report;
+mod results;
+mod routes;
+mod session;
+mod share;
+mod storage;
+mod task_detail;
+mod telemetry;
+mod watch;
+
+#[cfg(test)]
+mod tests;
+
+pub use api_v1::*;
+pub use cache::*;
+pub use dashboard::*;
+pub use editor::*;
+#[cfg(feature = "mqtt")]
+pub use field::*;
+pub use filters::*;
+pub use graphql::*;
+pub use jobs::*;
+pub use limits::*;
+pub use locale::*;
+pub use negotiate::*;
+pub use report::*;
+pub use results::*;
+pub use routes::*;
+pub use session::*;
+pub use share::*;
+pub use storage::*;
+pub use task_detail::*;
+pub use telemetry::*;
+pub use watch::*;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use metrics_exporter_prometheus::PrometheusHandle;
+
+use crate::charts::ChartRegistry;
+use crate::config::Config;
+use crate::srp_analysis::*;
+
+/// State shared between all request handlers.
+pub struct AppState {
+    /// Task sets stored by the server itself, visible in every session
+    pub shared: Arc<TaskSetCache>,
+    /// Backend persisting the task sets of the server and of every session, see `StorageConfig`
+    pub storage: Arc<dyn TaskSetStore>,
+    /// Task sets, measurements and analysis caches of every user, see `session_layer`
+    pub sessions: Sessions,
+    /// Charts served under /:type/:name
+    pub charts: ChartRegistry,
+    /// Schedulability tests served under /api/:taskset_id/plugins/:plugin
+    pub plugins: PluginRegistry,
+    /// Task sets shared through read-only and owner tokens under /share/:token
+    pub shares: ShareTable,
+    /// Execution times streamed from devices in the field over MQTT, see `subscribe_field_events`
+    #[cfg(feature = "mqtt")]
+    pub field: FieldMetrics,
+    /// Renders the metrics served under /metrics
+    pub metrics: PrometheusHandle,
+    pub graphql: AnalysisSchema,
+    pub config: Config,
+}
+
+impl AppState {
+    /// Opens the configured storage and loads the task sets the server stored before.
+    pub async fn new(config: Config, metrics: PrometheusHandle) -> Result<Self, String> {
+        let timeout = config.session_timeout.map_or(DEFAULT_SESSION_TIMEOUT, |minutes| Duration::from_secs(minutes * 60));
+        let storage = open_store(&config.storage).await?;
+        let writer = StoreWriter::spawn(storage.clone());
+        let stored = load_task_sets(storage.as_ref(), SHARED_NAMESPACE).await?;
+        Ok(Self {
+            shared: Arc::new(TaskSetCache::persisted(SHARED_NAMESPACE.to_string(), writer.clone(), stored)),
+            storage,
+            sessions: Sessions::new(timeout, DEFAULT_MAX_SESSIONS, writer),
+            charts: ChartRegistry::new(),
+            plugins: PluginRegistry::new(),
+            shares: ShareTable::new(),
+            #[cfg(feature = "mqtt")]
+            field: FieldMetrics::new(),
+            metrics,
+            graphql: analysis_schema(),
+            config,
+        })
+    }
+
+    /// Stores the task set under `id` for every session, replacing any earlier version, drops its chart pages and
+    /// notifies subscribers.
+    pub fn insert_task_set(&self, id: &str, tasks: Tasks) {
+        self.shared.insert(id, tasks);
+        self.sessions.invalidate_charts(id);
+    }
+}
@@ -0,0 +1,231 @@
+use askama::Template;
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::{header, request::Parts, HeaderMap, StatusCode},
+    response::{Html, IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+
+use crate::srp_analysis::*;
+
+/// Representation of a response, picked from the `format` query parameter or the `Accept` header of the request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Json,
+    /// Full page for browsers
+    Html,
+    /// Aligned table for terminals, e.g. `curl -H 'Accept: text/plain'`
+    Text,
+    /// For toolchains ingesting XML, only offered by the analysis report, other responses fall back to JSON
+    Xml,
+}
+
+impl Format {
+    /// Supported media type with the highest quality in `Accept`, the earliest one on equal qualities.
+    ///
+    /// Requests without an `Accept` header, or only accepting `*/*`, get JSON.
+    pub fn from_headers(headers: &HeaderMap) -> Format {
+        let Some(accept) = headers.get(header::ACCEPT).and_then(|accept| accept.to_str().ok()) else {
+            return Format::Json;
+        };
+        let mut best = (0.0, Format::Json);
+        for range in accept.split(',') {
+            let mut params = range.split(';').map(str::trim);
+            let format = match params.next() {
+                Some("application/json") => Format::Json,
+                Some("text/html") => Format::Html,
+                Some("text/plain") => Format::Text,
+                Some("application/xml" | "text/xml") => Format::Xml,
+                _ => continue,
+            };
+            let quality = params
+                .find_map(|param| param.strip_prefix("q="))
+                .and_then(|q| q.parse().ok())
+                .unwrap_or(1.0);
+            if quality > best.0 {
+                best = (quality, format);
+            }
+        }
+        best.1
+    }
+
+    /// Format named by the `format` query parameter: `json`, `html`, `text` or `xml`.
+    pub fn from_name(name: &str) -> Option<Format> {
+        match name {
+            "json" => Some(Format::Json),
+            "html" => Some(Format::Html),
+            "text" => Some(Format::Text),
+            "xml" => Some(Format::Xml),
+            _ => None,
+        }
+    }
+}
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for Format {
+    type Rejection = std::convert::Infallible;
+
+    // `format=xml` in the query overrides the header, e.g. for a plain link
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let requested = parts
+            .uri
+            .query()
+            .unwrap_or_default()
+            .split('&')
+            .find_map(|param| param.strip_prefix("format="))
+            .and_then(Format::from_name);
+        Ok(requested.unwrap_or_else(|| Format::from_headers(&parts.headers)))
+    }
+}
+
+/// Row of a result table in the HTML and plain-text representations.
+pub trait Table {
+    fn columns() -> Vec<&'static str>;
+    fn row(&self) -> Vec<String>;
+}
+
+// R(t), or why it is unbounded
+fn bounded(value: &Result<u32, String>) -> String {
+    match value {
+        Ok(value) => value.to_string(),
+        Err(e) => e.clone(),
+    }
+}
+
+impl Table for TaskAnalysis {
+    fn columns() -> Vec<&'static str> {
+        vec!["Task", "D(t)", "C(t)", "B(t)", "I(t)", "R(t)", "R(t) <= D(t)"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.id.clone(),
+            self.deadline.to_string(),
+            self.wcet.to_string(),
+            self.blocking_time.to_string(),
+            self.interference.to_string(),
+            bounded(&self.response_time),
+            if self.meets_deadline() { "yes" } else { "no" }.to_string(),
+        ]
+    }
+}
+
+impl Table for ResourceCeiling {
+    fn columns() -> Vec<&'static str> {
+        vec!["Resource", "π(r)", "π_r(r)", "Locked by"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.resource.clone(),
+            self.ceiling.to_string(),
+            self.read_ceiling.map_or(String::new(), |ceiling| ceiling.to_string()),
+            self.tasks.join(", "),
+        ]
+    }
+}
+
+impl Table for ChainLatency {
+    fn columns() -> Vec<&'static str> {
+        vec!["Chain", "D(c)", "L(c)", "L(c) <= D(c)"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.chain.clone(),
+            self.deadline.to_string(),
+            bounded(&self.latency),
+            if self.meets_deadline() { "yes" } else { "no" }.to_string(),
+        ]
+    }
+}
+
+impl Table for CoreMigration {
+    fn columns() -> Vec<&'static str> {
+        vec!["Task", "From", "To"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![self.task.clone(), format!("core {}", self.from), format!("core {}", self.to)]
+    }
+}
+
+#[derive(Template)]
+#[template(path = "table.html")]
+struct TableTemplate<'a> {
+    title: &'a str,
+    columns: &'a [&'static str],
+    rows: &'a [Vec<String>],
+}
+
+// Columns padded to their widest cell, separated by two spaces
+fn text_table(columns: &[&str], rows: &[Vec<String>]) -> String {
+    let header: Vec<String> = columns.iter().map(|column| column.to_string()).collect();
+    let mut widths: Vec<usize> = header.iter().map(|column| column.chars().count()).collect();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    let line = |cells: &[String]| {
+        let padded: Vec<String> = cells.iter().zip(&widths).map(|(cell, width)| format!("{:<width$}", cell)).collect();
+        padded.join("  ").trim_end().to_string() + "\n"
+    };
+
+    let rule: Vec<String> = widths.iter().map(|&width| "-".repeat(width)).collect();
+    let mut table = line(&header) + &line(&rule);
+    for row in rows {
+        table += &line(row);
+    }
+    table
+}
+
+/// Response rendered in the `Format` the request asks for: `body` as JSON, or its `rows` as an HTML page or a
+/// plain-text table.
+pub struct Negotiated<B> {
+    format: Format,
+    title: String,
+    body: B,
+    columns: Vec<&'static str>,
+    rows: Vec<Vec<String>>,
+}
+
+impl<B: Serialize> Negotiated<B> {
+    pub fn new<T: Table>(format: Format, title: &str, body: B, rows: &[T]) -> Self {
+        Self { format, title: title.to_string(), body, columns: T::columns(), rows: rows.iter().map(T::row).collect() }
+    }
+}
+
+impl<B: Serialize> IntoResponse for Negotiated<B> {
+    fn into_response(self) -> Response {
+        match self.format {
+            Format::Json | Format::Xml => Json(self.body).into_response(),
+            Format::Html => {
+                let page = TableTemplate { title: &self.title, columns: &self.columns, rows: &self.rows };
+                match page.render() {
+                    Ok(body) => Html(body).into_response(),
+                    Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, format!("Template error: {}", e)).into_response(),
+                }
+            }
+            Format::Text => (
+                [(header::CONTENT_TYPE, "text/plain; charset=utf-8")],
+                format!("{}\n\n{}", self.title, text_table(&self.columns, &self.rows)),
+            )
+                .into_response(),
+        }
+    }
+}
+
+/// `Content-Disposition` of a download named after a task set, e.g. `attachment; filename="example.csv"`.
+///
+/// Task set ids are chosen by users, so every character but ASCII letters, digits, `.`, `_` and `-` is replaced
+/// by `_`, leaving nothing that could end the quoted file name, add a parameter or an invalid header byte.
+pub fn attachment_disposition(taskset_id: &str, extension: &str) -> String {
+    let name: String = taskset_id
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-') { c } else { '_' })
+        .collect();
+    format!("attachment; filename=\"{}.{}\"", name, extension)
+}
@@ -0,0 +1,118 @@
+use askama::Template;
+
+use crate::charts;
+use crate::srp_analysis::*;
+use super::{filters, Locale};
+
+/// Row of the task table in the analysis report, times in ticks followed by microseconds if there is a clock.
+pub struct ReportRow {
+    pub id: String,
+    pub group: String,
+    pub prio: u8,
+    pub wcet: String,
+    pub inter_arrival: String,
+    pub deadline: String,
+    pub blocking_time: String,
+    /// Critical section behind B(t), see `Task::blocker`
+    pub blocker: String,
+    pub interference: String,
+    pub response_time: String,
+    pub meets_deadline: bool,
+}
+
+/// Chart embedded in the report as a serialized ECharts option.
+pub struct ReportChart {
+    pub id: String,
+    pub title: String,
+    pub option: String,
+}
+
+/// Standalone HTML report of a task set analysis, only depending on the ECharts script.
+#[derive(Template)]
+#[template(path = "report.html")]
+pub struct ReportTemplate {
+    pub taskset_id: String,
+    /// Options the analysis was run with
+    pub config: AnalysisConfig,
+    pub assumptions: Vec<String>,
+    pub rows: Vec<ReportRow>,
+    pub charts: Vec<ReportChart>,
+    pub total_load_factor: String,
+    pub stack: StackAnalysis,
+    pub schedulable: bool,
+    /// Language of the headings and verdict, the assumptions and results stay as the analysis reports them
+    pub locale: Locale,
+}
+
+impl ReportTemplate {
+    pub fn new(
+        taskset_id: &str,
+        tasks: &Tasks,
+        config: &AnalysisConfig,
+        theme: &charts::Theme,
+        locale: Locale,
+    ) -> Self {
+        let results = srp_analyze_with_progress(tasks, config, |_| {});
+        let prepared = config.prepare(tasks);
+
+        let time = |ticks: u32| config.clock.map_or(ticks.to_string(), |clock| clock.format(ticks));
+        let rows = results
+            .iter()
+            .zip(&prepared)
+            .map(|((task, response_time, blocking_time, wcet, interference), analyzed)| ReportRow {
+                id: task.id.clone(),
+                group: task.group.clone().unwrap_or_default(),
+                prio: task.prio,
+                wcet: time(*wcet),
+                inter_arrival: time(task.arrival.inter_arrival()),
+                deadline: time(task.deadline),
+                blocking_time: time(*blocking_time),
+                blocker: analyzed.blocker(&prepared).map(|blocker| blocker.to_string()).unwrap_or_default(),
+                interference: time(*interference),
+                response_time: match response_time {
+                    Ok(r) => time(*r),
+                    Err(e) => e.clone(),
+                },
+                meets_deadline: matches!(response_time, Ok(r) if *r <= task.deadline),
+            })
+            .collect();
+
+        let charts = vec![
+            ReportChart {
+                id: "response_times".to_string(),
+                title: locale.text("response_times").to_string(),
+                option: charts::response_time_chart(&results, None, config.clock, theme).to_string(),
+            },
+            ReportChart {
+                id: "groups".to_string(),
+                title: locale.text("groups").to_string(),
+                option: charts::group_chart(&results, theme).to_string(),
+            },
+            ReportChart {
+                id: "composition".to_string(),
+                title: locale.text("composition").to_string(),
+                option: charts::response_composition_chart(tasks, config.clock, theme).to_string(),
+            },
+            ReportChart {
+                id: "utilization".to_string(),
+                title: locale.text("utilization").to_string(),
+                option: charts::utilization_chart(tasks, theme).to_string(),
+            },
+        ];
+
+        Self {
+            taskset_id: taskset_id.to_string(),
+            config: *config,
+            assumptions: config.assumptions(),
+            rows,
+            charts,
+            total_load_factor: match total_load_factor(tasks) {
+                Ok(l_tot) => format!("{:.3}", l_tot),
+                Err(e) => e,
+            },
+            stack: stack_depth(tasks),
+            schedulable: is_schedulable(&results),
+            locale,
+        }
+    }
+}
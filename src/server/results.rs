@@ -0,0 +1,236 @@
+use std::cmp::Ordering;
+
+use askama::Template;
+use serde::Deserialize;
+use utoipa::ToSchema;
+
+use crate::srp_analysis::*;
+
+/// Column of the results table to sort by, e.g. `?sort=slack`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ResultColumn {
+    #[default]
+    Task,
+    Wcet,
+    Period,
+    Deadline,
+    Blocking,
+    Interference,
+    ResponseTime,
+    Slack,
+    Verdict,
+}
+
+impl ResultColumn {
+    pub const ALL: [ResultColumn; 9] = [
+        ResultColumn::Task,
+        ResultColumn::Wcet,
+        ResultColumn::Period,
+        ResultColumn::Deadline,
+        ResultColumn::Blocking,
+        ResultColumn::Interference,
+        ResultColumn::ResponseTime,
+        ResultColumn::Slack,
+        ResultColumn::Verdict,
+    ];
+
+    /// Value of the `sort` parameter selecting the column.
+    pub fn key(&self) -> &'static str {
+        match self {
+            ResultColumn::Task => "task",
+            ResultColumn::Wcet => "wcet",
+            ResultColumn::Period => "period",
+            ResultColumn::Deadline => "deadline",
+            ResultColumn::Blocking => "blocking",
+            ResultColumn::Interference => "interference",
+            ResultColumn::ResponseTime => "response_time",
+            ResultColumn::Slack => "slack",
+            ResultColumn::Verdict => "verdict",
+        }
+    }
+
+    /// Header of the column, in the notation of the analysis.
+    pub fn header(&self) -> &'static str {
+        match self {
+            ResultColumn::Task => "Task",
+            ResultColumn::Wcet => "C(t)",
+            ResultColumn::Period => "T(t)",
+            ResultColumn::Deadline => "D(t)",
+            ResultColumn::Blocking => "B(t)",
+            ResultColumn::Interference => "I(t)",
+            ResultColumn::ResponseTime => "R(t)",
+            ResultColumn::Slack => "D(t) - R(t)",
+            ResultColumn::Verdict => "Verdict",
+        }
+    }
+}
+
+/// Order of the sorted column, e.g. `?order=desc`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    #[default]
+    Asc,
+    Desc,
+}
+
+/// Analysis result of a task as listed in the results table and its CSV download, times in ticks.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResultRow {
+    pub task: String,
+    pub wcet: u32,
+    /// T(t), the minimum inter-arrival time
+    pub period: u32,
+    pub deadline: u32,
+    pub blocking_time: u32,
+    pub interference: u32,
+    pub response_time: Result<u32, String>,
+    /// D(t) - R(t), negative for a deadline miss, `None` if R(t) is unbounded
+    pub slack: Option<i64>,
+}
+
+impl ResultRow {
+    pub fn new(task: &Task, result: &TaskAnalysis) -> Self {
+        Self {
+            task: result.id.clone(),
+            wcet: result.wcet,
+            period: task.arrival.inter_arrival(),
+            deadline: result.deadline,
+            blocking_time: result.blocking_time,
+            interference: result.interference,
+            response_time: result.response_time.clone(),
+            slack: result.response_time.as_ref().ok().map(|r| result.deadline as i64 - *r as i64),
+        }
+    }
+
+    /// "meets", "misses" or "unbounded" if the analysis cannot bound R(t).
+    pub fn verdict(&self) -> &'static str {
+        match self.slack {
+            Some(slack) if slack >= 0 => "meets",
+            Some(_) => "misses",
+            None => "unbounded",
+        }
+    }
+
+    /// R(t), or why it is unbounded.
+    pub fn response_time_text(&self) -> String {
+        match &self.response_time {
+            Ok(r) => r.to_string(),
+            Err(e) => e.clone(),
+        }
+    }
+
+    /// D(t) - R(t), empty if R(t) is unbounded.
+    pub fn slack_text(&self) -> String {
+        self.slack.map_or(String::new(), |slack| slack.to_string())
+    }
+}
+
+/// Rows of the tasks of a task set, with `results` from `srp_analyze` on the tasks prepared by the same config.
+pub fn result_rows(prepared: &Tasks, results: &[TaskAnalysis]) -> Vec<ResultRow> {
+    prepared.iter().zip(results).map(|(task, result)| ResultRow::new(task, result)).collect()
+}
+
+/// Sorts the rows by the column, ties by task id. Unbounded response times and slacks sort after all bounded
+/// ones in ascending order.
+pub fn sort_results(rows: &mut [ResultRow], column: ResultColumn, sort_order: SortOrder) {
+    // `None` after `Some`, unlike the order of `Option`
+    fn bounded<T: Ord>(a: Option<T>, b: Option<T>) -> Ordering {
+        match (a, b) {
+            (Some(a), Some(b)) => a.cmp(&b),
+            (a, b) => b.is_some().cmp(&a.is_some()),
+        }
+    }
+    rows.sort_by(|a, b| {
+        let order = match column {
+            ResultColumn::Task => Ordering::Equal,
+            ResultColumn::Wcet => a.wcet.cmp(&b.wcet),
+            ResultColumn::Period => a.period.cmp(&b.period),
+            ResultColumn::Deadline => a.deadline.cmp(&b.deadline),
+            ResultColumn::Blocking => a.blocking_time.cmp(&b.blocking_time),
+            ResultColumn::Interference => a.interference.cmp(&b.interference),
+            ResultColumn::ResponseTime => bounded(a.response_time.clone().ok(), b.response_time.clone().ok()),
+            ResultColumn::Slack => bounded(a.slack, b.slack),
+            ResultColumn::Verdict => a.verdict().cmp(b.verdict()),
+        };
+        let order = order.then_with(|| a.task.cmp(&b.task));
+        if sort_order == SortOrder::Desc {
+            order.reverse()
+        } else {
+            order
+        }
+    });
+}
+
+// Quotes a CSV field containing a separator, a quote or a line break
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Rows as CSV with a header line of the column keys, e.g. `task,wcet,...`, and R(t) and the slack left empty
+/// if R(t) is unbounded.
+pub fn results_csv(rows: &[ResultRow]) -> String {
+    let header: Vec<&str> = ResultColumn::ALL.iter().map(ResultColumn::key).collect();
+    let mut csv = header.join(",") + "\n";
+    for row in rows {
+        let fields = [
+            csv_field(&row.task),
+            row.wcet.to_string(),
+            row.period.to_string(),
+            row.deadline.to_string(),
+            row.blocking_time.to_string(),
+            row.interference.to_string(),
+            row.response_time.as_ref().map_or(String::new(), |r| r.to_string()),
+            row.slack_text(),
+            row.verdict().to_string(),
+        ];
+        csv += &(fields.join(",") + "\n");
+    }
+    csv
+}
+
+/// Results table of a task set with exact numbers, sorted on the server so it works without scripts.
+#[derive(Template)]
+#[template(path = "results.html")]
+pub struct ResultsTemplate {
+    pub taskset_id: String,
+    pub rows: Vec<ResultRow>,
+    pub sort: ResultColumn,
+    pub order: SortOrder,
+    /// Parameters of the request other than the sort, e.g. `mode=exact&`, kept by the links of the page
+    pub params: String,
+}
+
+impl ResultsTemplate {
+    pub fn columns(&self) -> &'static [ResultColumn] {
+        &ResultColumn::ALL
+    }
+
+    /// Link of a column header: sorts by the column, reversing the order if the table is already sorted by it.
+    pub fn sort_link(&self, column: &ResultColumn) -> String {
+        let order = if *column == self.sort && self.order == SortOrder::Asc { "desc" } else { "asc" };
+        format!("?{}sort={}&order={}", self.params, column.key(), order)
+    }
+
+    /// Value of the `order` parameter of the current order.
+    pub fn order_key(&self) -> &'static str {
+        match self.order {
+            SortOrder::Asc => "asc",
+            SortOrder::Desc => "desc",
+        }
+    }
+
+    /// Arrow marking the column the table is sorted by.
+    pub fn marker(&self, column: &ResultColumn) -> &'static str {
+        match (*column == self.sort, self.order) {
+            (false, _) => "",
+            (true, SortOrder::Asc) => " ▲",
+            (true, SortOrder::Desc) => " ▼",
+        }
+    }
+}
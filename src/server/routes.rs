@@ -0,0 +1,3196 @@
+use std::collections::BTreeMap;
+use std::convert::Infallible;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::{
+    extract::{self, DefaultBodyLimit},
+    http::{header, HeaderMap, StatusCode},
+    middleware,
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        Html, IntoResponse, Redirect, Response,
+    },
+    routing::{get, post},
+    Form, Json, Router,
+};
+use askama::Template;
+use charming::Chart;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, OpenApi, ToSchema};
+use tower::ServiceBuilder;
+use tower_http::{timeout::TimeoutLayer, trace::TraceLayer};
+use tokio_stream::{
+    wrappers::UnboundedReceiverStream,
+    Stream, StreamExt,
+};
+
+use crate::charts::{self, ChartInfo, ChartInput, ChartRegistry, Theme};
+use crate::io;
+use crate::srp_analysis::*;
+use super::{
+    attachment_disposition, filters, rate_limit, record_analysis, remove_task, result_rows, session_layer, sort_results,
+    task_set_key, tasks_from_v1, track_metrics, AppState, CacheStats, ChartKey, DashboardTemplate, EditorTemplate,
+    Format, Job, JobStatus, Locale, Negotiated, RateLimiter, ReportTemplate, ResultColumn, ResultRow, ResultsTemplate,
+    SectionForm, SectionRef, Session, ShareAccess, ShareTokens, SortOrder, TaskAnalysisV1, TaskDetailTemplate, TaskForm,
+    TaskSetKey, TaskV1, TraceV1,
+};
+
+// Longest schedule simulated per request, the hyperperiod of a task set may be huge
+const MAX_SIMULATION_HORIZON: u32 = 100_000;
+// Most perturbed variants analyzed by a single fuzzing request
+const MAX_FUZZ_SAMPLES: u32 = 100_000;
+// Most tasks of a randomly generated task set
+const MAX_GENERATED_TASKS: usize = 1_000;
+// Longest line of a streamed trace, request bodies streamed line by line are not limited by DefaultBodyLimit
+const MAX_TRACE_LINE: usize = 64 * 1024;
+
+// Requests taking longer are answered with 408 Request Timeout
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// All routes of the server with their middleware, see `AppState`.
+pub fn router(state: Arc<AppState>) -> Router {
+    let limits = state.config.limits.clone();
+    let limiter = limits.requests_per_minute.map(|rate| Arc::new(RateLimiter::new(rate)));
+    Router::new()
+        .route("/", get(index))
+        .route("/analysis", get(analysis_page))
+        .route("/analysis/progress", get(analysis_progress))
+        .route("/analysis/:taskset_id", get(taskset_analysis_page))
+        .route("/api/analysis", get(analysis_json))
+        .route("/cache/stats", get(cache_stats))
+        .route("/cache/charts/stats", get(chart_cache_stats))
+        .route("/examples", get(example_catalog))
+        .route("/examples/:name", get(example_task_set))
+        .route("/api/generate", get(generate_task_set))
+        .route("/metrics", get(metrics))
+        .route("/graphql", get(graphiql).post(graphql))
+        .route("/region", get(region))
+        .route("/report/:taskset_id", get(report))
+        .route("/results/:taskset_id", get(results_page))
+        .route("/results/:taskset_id/results.csv", get(results_csv))
+        .route("/analysis/:taskset_id/whatif", post(what_if))
+        .route("/api/:taskset_id/ceilings", get(ceilings_json))
+        .route("/ceilings/:taskset_id", get(ceilings_page))
+        .route("/api/:taskset_id/stack", get(stack_json))
+        .route("/api/:taskset_id/chains", post(chains_json))
+        .route("/api/hierarchical", post(hierarchical_json))
+        .route("/api/traces/check", post(check_task_traces))
+        .route("/api/import/rtic", post(import_rtic))
+        .route("/api/import/research", post(import_research))
+        .route("/api/import/decimal", post(import_decimal))
+        .route("/api/import/relative", post(import_relative))
+        .route("/api/:taskset_id/export/cheddar", get(export_cheddar))
+        .route("/api/:taskset_id/export/archive", get(export_archive))
+        .route("/api/import/archive", post(import_archive))
+        .route("/api/:taskset_id/simulation", get(simulation_json))
+        .route("/api/:taskset_id/progress", get(taskset_progress))
+        .route("/simulate/:taskset_id/step", post(simulation_step))
+        .route("/api/:taskset_id/priorities/optimal", post(assign_priorities))
+        .route("/api/:taskset_id/priorities/normalized", get(priority_normalization).post(normalize_priorities))
+        .route("/api/:taskset_id/budgets", post(wcet_budgets))
+        .route("/api/:taskset_id/scale", post(scale_task_set))
+        .route("/api/:taskset_id/cores/balance", post(balance_cores_json))
+        .route("/cores/:taskset_id", get(cores_page))
+        .route("/api/plugins", get(plugin_catalog))
+        .route("/api/:taskset_id/plugins/:plugin", get(run_plugin))
+        .route("/api/:taskset_id/shedding", get(shedding))
+        .route("/api/:taskset_id/sweep", get(sweep))
+        .route("/fuzz/:taskset_id", get(fuzz))
+        .route("/jobs", post(start_job))
+        .route("/jobs/:job_id", get(job_status))
+        .route("/api/:taskset_id/weakly-hard", get(weakly_hard))
+        .route("/api/:taskset_id/measurements", post(add_measurements))
+        .route("/api/:taskset_id/wcet-check", get(wcet_check))
+        .route("/measurements/:taskset_id/:task", get(measurements_page))
+        .route("/api/:taskset_id/runs/:task", post(merge_task_runs))
+        .route("/ingest/:taskset_id/trace", post(ingest_trace))
+        .route("/api/:taskset_id/observed", get(observed_json))
+        .route("/observed/:taskset_id", get(observed_page))
+        .route("/api/:taskset_id/inversions", get(inversions_json))
+        .route("/inversions/:taskset_id", get(inversions_page))
+        .route("/api/:taskset_id/scenario/:task_id", get(scenario_json))
+        .route("/api/:taskset_id/explain/:task_id", get(explain_json))
+        .route("/api/:taskset_id/certificate", get(certificate_json))
+        .route("/api/certificate/verify", post(verify_certificate_json))
+        .route("/api/:taskset_id/offsets", get(offsets_json))
+        .route("/api/:taskset_id/sensitivity", get(sensitivity_json))
+        .route("/api/:taskset_id/refactorings", get(refactorings_json))
+        .route("/scenario/:taskset_id/:task_id", get(scenario_page))
+        .route("/timeline/:taskset_id", get(timeline_page))
+        .route("/dashboard/:taskset_id", get(dashboard_page))
+        .route("/api/:taskset_id/share", post(share_task_set))
+        .route("/share/:token", get(shared_dashboard).put(replace_shared))
+        .route("/task/:taskset_id/:task_id", get(task_page))
+        .route("/task/:taskset_id/:task_id/resources", get(task_resources_page))
+        .route("/api/:taskset_id/convergence", get(convergence_json))
+        .route("/convergence/:taskset_id/:task_id", get(convergence_page))
+        .route("/editor/:taskset_id", get(editor_page))
+        .route("/editor/:taskset_id/tasks", post(editor_save_task))
+        .route("/editor/:taskset_id/tasks/:task/delete", post(editor_remove_task))
+        .route("/editor/:taskset_id/sections", post(editor_add_section))
+        .route("/editor/:taskset_id/sections/delete", post(editor_remove_section))
+        .route("/live/:taskset_id", get(live_page))
+        .route("/live/:taskset_id/events", get(live_events))
+        .route("/api-docs/openapi.json", get(openapi_json))
+        .route("/docs", get(docs))
+        .route("/render/analysis", post(render_analysis))
+        .route("/api/charts", get(chart_catalog))
+        .route("/api/chart/:type/:name", get(chart_json))
+        .route("/embed/:type/:name", get(embed))
+        .route("/view", get(view_link))
+        .route("/view/:taskset_id/:chart", get(view))
+        .route("/:type/:name", get(render))
+        .nest("/api/v1", api_v1_routes())
+        .merge(field_routes())
+        .route_layer(middleware::from_fn(track_metrics))
+        .layer(middleware::from_fn_with_state(state.clone(), session_layer))
+        .with_state(state)
+        // Outside the session layer, so rejected requests do not start sessions
+        .layer(middleware::from_fn_with_state(limiter, rate_limit))
+        .layer(
+            ServiceBuilder::new()
+                .layer(TraceLayer::new_for_http())
+                .layer(TimeoutLayer::new(REQUEST_TIMEOUT))
+                .layer(DefaultBodyLimit::max(limits.max_body_size)),
+        )
+}
+
+// Resolves on SIGINT (Ctrl+C) or SIGTERM, letting in-flight requests finish before the server stops.
+pub async fn shutdown_signal() {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c().await.expect("failed to install Ctrl+C handler");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("failed to install SIGTERM handler")
+            .recv()
+            .await;
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+    tracing::info!("Shutting down");
+}
+
+// Registers the demo chart and the analysis charts of the example task set, the stacked bar demo is a chart spec
+// in `charts/`, see `register_chart_specs`.
+pub fn register_charts(registry: &ChartRegistry) {
+    registry.register_fn(
+        "SECOND_SET",
+        "chart2",
+        ChartInfo::new("Life expectancy", "Demo of a 3D bar chart", &[]),
+        charts::chart2,
+    );
+    charts::register_analysis_charts(
+        registry,
+        "ANALYSIS_SET",
+        simple_example(),
+        simple_example_chains(),
+        MAX_SIMULATION_HORIZON,
+    );
+}
+
+/// Registers the chart specs in `dir`, whose analysis charts are of the task sets stored by the server, returning
+/// how many there are, see `ChartSpec`.
+pub fn register_chart_specs(state: &AppState, dir: &Path) -> Result<usize, String> {
+    let specs = charts::load_chart_specs(dir)?;
+    let count = specs.len();
+    let shared = state.shared.clone();
+    charts::register_chart_specs(&state.charts, specs, Arc::new(move |id: &str| shared.get(id)));
+    Ok(count)
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct ThemeQuery {
+    /// `light`, `dark` or a theme of the config file, defaults to the configured theme
+    theme: Option<String>,
+}
+
+// Resolves the requested chart theme, or responds with 400 for unknown themes.
+fn requested_theme(state: &AppState, query: &ThemeQuery) -> Result<Theme, Response> {
+    state
+        .config
+        .theme(query.theme.as_deref())
+        .map_err(|e| (StatusCode::BAD_REQUEST, e).into_response())
+}
+
+/// How ECharts draws a chart in the browser.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+enum ChartRenderer {
+    #[default]
+    Canvas,
+    /// Scalable and selectable, e.g. for slides
+    Svg,
+}
+
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct ChartQuery {
+    /// Chart width in pixels, defaults to 1000 on chart pages
+    w: Option<u64>,
+    /// Chart height in pixels, defaults to 800 on chart pages
+    h: Option<u64>,
+    /// Defaults to canvas
+    renderer: Option<ChartRenderer>,
+}
+
+impl ChartQuery {
+    // Requested size, or the default size of the page
+    fn size(&self, width: u64, height: u64) -> ChartSize {
+        ChartSize {
+            width: self.w.unwrap_or(width),
+            height: self.h.unwrap_or(height),
+            renderer: match self.renderer.unwrap_or_default() {
+                ChartRenderer::Canvas => "canvas",
+                ChartRenderer::Svg => "svg",
+            },
+        }
+    }
+}
+
+// Size and renderer of the chart of a page, see `ChartQuery`
+struct ChartSize {
+    width: u64,
+    height: u64,
+    renderer: &'static str,
+}
+
+#[derive(Template)]
+#[template(path = "chart.html")]
+struct ChartTemplate {
+    title: String,
+    size: ChartSize,
+    chart: String,
+}
+
+// Standalone page of a chart in the requested size. Rendered by a template of our own rather than charming's
+// HtmlRenderer, which only initializes canvas charts and inlines the chart option without escaping it.
+fn chart_html(title: String, chart: &Chart, query: &ChartQuery) -> String {
+    ChartTemplate { title, size: query.size(1000, 800), chart: chart.to_string() }.render().unwrap()
+}
+
+fn chart_page(title: String, chart: &Chart, query: &ChartQuery) -> Response {
+    Html(chart_html(title, chart, query)).into_response()
+}
+
+// Identity of a chart page of a task set in the chart cache, `task_set` is `None` for task sets that are not stored.
+fn chart_key(
+    task_set: Option<&str>,
+    chart: &str,
+    data: TaskSetKey,
+    theme: &ThemeQuery,
+    query: &ChartQuery,
+) -> ChartKey {
+    let size = query.size(1000, 800);
+    ChartKey {
+        task_set: task_set.map(str::to_string),
+        chart: chart.to_string(),
+        data,
+        width: size.width,
+        height: size.height,
+        renderer: size.renderer,
+        theme: theme.theme.clone().unwrap_or_default(),
+    }
+}
+
+// Chart page from the chart cache, or built and rendered on a miss, `None` if `build` finds no such chart.
+fn cached_chart_page<F>(
+    session: &Session,
+    key: ChartKey,
+    title: String,
+    query: &ChartQuery,
+    build: F,
+) -> Option<Response>
+where
+    F: FnOnce() -> Option<Chart>,
+{
+    if let Some(html) = session.charts.lock().unwrap().get(&key) {
+        return Some(Html(html.as_ref().clone()).into_response());
+    }
+
+    // The lock is not held while building and rendering, so other requests are not blocked by a large chart
+    let html = Arc::new(chart_html(title, &build()?, query));
+    session.charts.lock().unwrap().insert(key, html.clone());
+    Some(Html(html.as_ref().clone()).into_response())
+}
+
+// Builds a registered chart, or responds with 404.
+fn lookup_chart(state: &AppState, r#type: &str, name: &str, theme: &Theme) -> Result<Chart, Response> {
+    state.charts.chart(r#type, name, theme).map_err(|e| (StatusCode::NOT_FOUND, e).into_response())
+}
+
+// Lists every registered chart with its title, description and inputs.
+#[utoipa::path(
+    get,
+    path = "/api/charts",
+    responses(
+        (status = 200, description = "Chart descriptions by collection and name", body = Object)
+    )
+)]
+async fn chart_catalog(extract::State(state): extract::State<Arc<AppState>>) -> impl IntoResponse {
+    Json(state.charts.catalog())
+}
+
+async fn render(
+    extract::State(state): extract::State<Arc<AppState>>,
+    extract::Path((r#type, name)): extract::Path<(String, String)>,
+    extract::Query(query): extract::Query<ThemeQuery>,
+    extract::Query(size): extract::Query<ChartQuery>,
+) -> impl IntoResponse {
+    let chart = match requested_theme(&state, &query).and_then(|theme| lookup_chart(&state, &r#type, &name, &theme)) {
+        Ok(chart) => chart,
+        Err(response) => return response,
+    };
+    chart_page(format!("{type} - {name}"), &chart, &size)
+}
+
+// Responds with the serialized ECharts option of a chart, for frontends rendering charts client-side.
+// The router cannot match a `.json` suffix inside a segment, so it is stripped here instead.
+#[utoipa::path(
+    get,
+    path = "/api/chart/{type}/{name}.json",
+    params(
+        ("type" = String, Path, description = "Chart collection"),
+        ("name" = String, Path, description = "Chart name"),
+        ThemeQuery
+    ),
+    responses(
+        (status = 200, description = "ECharts option", body = Object),
+        (status = 400, description = "Unknown theme"),
+        (status = 404, description = "Unknown chart")
+    )
+)]
+async fn chart_json(
+    extract::State(state): extract::State<Arc<AppState>>,
+    extract::Path((r#type, name)): extract::Path<(String, String)>,
+    extract::Query(query): extract::Query<ThemeQuery>,
+) -> impl IntoResponse {
+    let Some(name) = name.strip_suffix(".json") else {
+        return (StatusCode::NOT_FOUND, "Chart Not Found").into_response();
+    };
+
+    // Embedded charts fetch their option from the pages of other tools, see `embed`
+    match requested_theme(&state, &query).and_then(|theme| lookup_chart(&state, &r#type, name, &theme)) {
+        Ok(chart) => ([(header::ACCESS_CONTROL_ALLOW_ORIGIN, "*")], Json(chart)).into_response(),
+        Err(response) => response,
+    }
+}
+
+#[derive(Deserialize)]
+struct EmbedQuery {
+    /// Seconds between reloads of the chart option, defaults to 30, 0 never reloads
+    refresh: Option<u64>,
+}
+
+#[derive(Template)]
+#[template(path = "embed.html")]
+struct EmbedTemplate {
+    size: ChartSize,
+    /// URL of the JSON chart option as a JS string literal
+    data_url: String,
+    refresh: u64,
+}
+
+// HTML fragment of a chart that reloads its option from `chart_json`, to be embedded in an `<iframe>` or in the
+// dashboards of other tools, e.g. a Grafana text panel.
+async fn embed(
+    extract::State(state): extract::State<Arc<AppState>>,
+    extract::Path((r#type, name)): extract::Path<(String, String)>,
+    extract::Query(query): extract::Query<ThemeQuery>,
+    extract::Query(size): extract::Query<ChartQuery>,
+    extract::Query(embed): extract::Query<EmbedQuery>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    let chart = requested_theme(&state, &query).and_then(|theme| lookup_chart(&state, &r#type, &name, &theme));
+    if let Err(response) = chart {
+        return response;
+    }
+
+    // Relative to this server, not to the page the fragment is embedded in
+    let host = headers.get(header::HOST).and_then(|host| host.to_str().ok()).unwrap_or_default();
+    let theme = query.theme.map(|theme| format!("?theme={theme}")).unwrap_or_default();
+    let data_url = format!("//{host}/api/chart/{type}/{name}.json{theme}");
+    HtmlTemplate(EmbedTemplate {
+        size: size.size(600, 400),
+        data_url: serde_json::to_string(&data_url).unwrap(),
+        refresh: embed.refresh.unwrap_or(30),
+    })
+    .into_response()
+}
+
+// Names of the charts `analysis_chart` builds, in the order the index offers them.
+const ANALYSIS_CHARTS: [&str; 8] =
+    ["response_times", "groups", "composition", "utilization", "margins", "ceilings", "resources", "timeline"];
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct InlineChartQuery {
+    /// `response_times` (default), `groups`, `composition`, `utilization`, `margins`, `ceilings`, `resources` or
+    /// `timeline`
+    chart: Option<String>,
+}
+
+// Builds one of the analysis charts for a task set that is not stored on the server.
+fn analysis_chart(
+    name: &str,
+    tasks: &Tasks,
+    config: &AnalysisConfig,
+    theme: &Theme,
+) -> Option<Chart> {
+    let results = || srp_analyze(tasks, config);
+    match name {
+        "response_times" => Some(charts::response_time_chart(&results(), None, config.clock, theme)),
+        "groups" => Some(charts::group_chart(&results(), theme)),
+        "composition" => Some(charts::response_composition_chart(tasks, config.clock, theme)),
+        "ceilings" => Some(charts::ceiling_chart(&resource_ceilings(tasks), theme)),
+        "utilization" => Some(charts::utilization_chart(tasks, theme)),
+        "margins" => Some(charts::margin_chart(&task_margins(&results()), theme)),
+        "resources" => Some(charts::resource_usage_chart(tasks, theme)),
+        "timeline" => {
+            let horizon = hyperperiod(tasks).unwrap_or(MAX_SIMULATION_HORIZON).min(MAX_SIMULATION_HORIZON);
+            Some(charts::timeline_chart(tasks, &simulate(tasks, horizon), config.clock, theme))
+        }
+        _ => None,
+    }
+}
+
+// Renders a chart of the task set in the request body in one go, for scripts that do not store task sets,
+// e.g. curl -d @tasks.json -H 'Content-Type: application/json' '/render/analysis?chart=groups' > groups.html
+#[utoipa::path(
+    post,
+    path = "/render/analysis",
+    params(InlineChartQuery, AnalysisQuery, ThemeQuery, ChartQuery),
+    request_body(content = [Task], description = "Task set to analyze"),
+    responses(
+        (status = 200, description = "Standalone chart page", body = String, content_type = "text/html"),
+        (status = 400, description = "Invalid task set, unknown chart or theme")
+    )
+)]
+async fn render_analysis(
+    extract::State(state): extract::State<Arc<AppState>>,
+    extract::Extension(session): extract::Extension<Arc<Session>>,
+    extract::Query(chart): extract::Query<InlineChartQuery>,
+    extract::Query(query): extract::Query<AnalysisQuery>,
+    extract::Query(theme_query): extract::Query<ThemeQuery>,
+    extract::Query(size): extract::Query<ChartQuery>,
+    Json(tasks): Json<Tasks>,
+) -> impl IntoResponse {
+    if let Err(e) = validate_task_set(&tasks) {
+        return (StatusCode::BAD_REQUEST, e).into_response();
+    }
+    let theme = match requested_theme(&state, &theme_query) {
+        Ok(theme) => theme,
+        Err(response) => return response,
+    };
+
+    let name = chart.chart.as_deref().unwrap_or("response_times");
+    let config = query.config();
+    let key = chart_key(None, name, task_set_key(&tasks, &config), &theme_query, &size);
+    let page = cached_chart_page(&session, key, format!("Analysis - {name}"), &size, || {
+        analysis_chart(name, &tasks, &config, &theme)
+    });
+    page.unwrap_or_else(|| (StatusCode::BAD_REQUEST, format!("Unknown chart '{}'", name)).into_response())
+}
+
+// Chart page of a stored task set with all of its state in the URL, so every view can be bookmarked and shared,
+// e.g. /view/example/response_times?mode=exact&theme=dark&w=1200&h=600
+#[utoipa::path(
+    get,
+    path = "/view/{taskset_id}/{chart}",
+    params(
+        ("taskset_id" = String, Path, description = "Stored task set"),
+        ("chart" = String, Path, description = "`response_times`, `groups`, `composition`, `utilization`, `margins`, `ceilings`, `resources` or `timeline`"),
+        AnalysisQuery,
+        ThemeQuery,
+        ChartQuery
+    ),
+    responses(
+        (status = 200, description = "Standalone chart page", body = String, content_type = "text/html"),
+        (status = 400, description = "Unknown theme"),
+        (status = 404, description = "Unknown task set or chart")
+    )
+)]
+async fn view(
+    extract::State(state): extract::State<Arc<AppState>>,
+    extract::Extension(session): extract::Extension<Arc<Session>>,
+    extract::Path((taskset_id, name)): extract::Path<(String, String)>,
+    extract::Query(query): extract::Query<AnalysisQuery>,
+    extract::Query(theme_query): extract::Query<ThemeQuery>,
+    extract::Query(size): extract::Query<ChartQuery>,
+) -> impl IntoResponse {
+    let theme = match requested_theme(&state, &theme_query) {
+        Ok(theme) => theme,
+        Err(response) => return response,
+    };
+    let Some(tasks) = session.task_set(&taskset_id) else {
+        return (StatusCode::NOT_FOUND, "Task Set Not Found").into_response();
+    };
+    let config = query.config();
+    let key = chart_key(Some(&taskset_id), &name, task_set_key(&tasks, &config), &theme_query, &size);
+    let page = cached_chart_page(&session, key, format!("{name} - {taskset_id}"), &size, || {
+        analysis_chart(&name, &tasks, &config, &theme)
+    });
+    page.unwrap_or_else(|| (StatusCode::NOT_FOUND, format!("Unknown chart '{}'", name)).into_response())
+}
+
+// Target of the view form of the index, which cannot put its fields into the path: redirects
+// /view?taskset=example&chart=groups&mode=exact to /view/example/groups?mode=exact, dropping empty fields.
+async fn view_link(extract::RawQuery(raw): extract::RawQuery) -> impl IntoResponse {
+    let mut taskset_id = None;
+    let mut chart = None;
+    let mut params = vec![];
+    for param in raw.as_deref().unwrap_or_default().split('&') {
+        // Values stay percent-encoded, only the form encoding of spaces is not valid in a path
+        match param.split_once('=') {
+            Some((_, "")) | None => {}
+            Some(("taskset", value)) => taskset_id = Some(value.replace('+', "%20")),
+            Some(("chart", value)) => chart = Some(value.to_string()),
+            Some(_) => params.push(param),
+        }
+    }
+    let (Some(taskset_id), Some(chart)) = (taskset_id, chart) else {
+        return (StatusCode::BAD_REQUEST, "Missing taskset or chart").into_response();
+    };
+    let query = if params.is_empty() { String::new() } else { format!("?{}", params.join("&")) };
+    Redirect::to(&format!("/view/{}/{}{}", taskset_id, chart, query)).into_response()
+}
+
+// Make a more interactable intex with tempelates, uses render() for responses and new data.
+async fn index(
+    extract::State(state): extract::State<Arc<AppState>>,
+    extract::Extension(session): extract::Extension<Arc<Session>>,
+    locale: Locale,
+) -> impl IntoResponse {
+    let mut template = IndexTemplate::new(locale);
+    for (key, charts) in state.charts.catalog() {
+        template.collection(&key, charts);
+    }
+    template.task_sets = session.task_set_ids();
+    // Built-in themes first, unless the config file replaces them
+    let builtin = ["light", "dark"].map(str::to_string);
+    template.themes = builtin.into_iter().filter(|theme| !state.config.themes.contains_key(theme)).collect();
+    template.themes.extend(state.config.themes.keys().cloned());
+    HtmlTemplate(template)
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct AnalysisQuery {
+    /// Defaults to approximate
+    mode: Option<PreemptionMode>,
+    /// Defaults to constrained
+    deadlines: Option<DeadlineModel>,
+    /// Defaults to none
+    jitter: Option<JitterModel>,
+    /// Defaults to synchronous
+    offsets: Option<OffsetModel>,
+    /// Defaults to ceiling
+    blocking: Option<BlockingRefinement>,
+    /// Timer tick the timing parameters are rounded to, defaults to 1
+    tick: Option<u32>,
+    /// Clock frequency in Hz the timing parameters are counted in, to also report them in microseconds
+    clock_hz: Option<u64>,
+}
+
+impl AnalysisQuery {
+    // Analysis options of the request, with defaults for everything not given
+    fn config(&self) -> AnalysisConfig {
+        let defaults = AnalysisConfig::default();
+        AnalysisConfig {
+            mode: self.mode.unwrap_or(defaults.mode),
+            deadline_model: self.deadlines.unwrap_or(defaults.deadline_model),
+            jitter_model: self.jitter.unwrap_or(defaults.jitter_model),
+            offset_model: self.offsets.unwrap_or(defaults.offset_model),
+            blocking_refinement: self.blocking.unwrap_or(defaults.blocking_refinement),
+            tick_granularity: self.tick.unwrap_or(defaults.tick_granularity),
+            // A frequency of 0 counts as no clock
+            clock: self.clock_hz.filter(|hz| *hz > 0).map(|frequency_hz| ClockModel { frequency_hz }),
+        }
+    }
+}
+
+// Lists the canonical task sets with their descriptions.
+#[utoipa::path(
+    get,
+    path = "/examples",
+    responses((status = 200, description = "Canonical task sets", body = [Example]))
+)]
+async fn example_catalog() -> impl IntoResponse {
+    Json(examples())
+}
+
+// Responds with a canonical task set by name, e.g. to start editing a copy of it.
+#[utoipa::path(
+    get,
+    path = "/examples/{name}",
+    params(("name" = String, Path, description = "`simple`, `heavy-blocking`, `unschedulable` or `deep-nesting`")),
+    responses(
+        (status = 200, description = "Task set", body = [Task]),
+        (status = 404, description = "Unknown example")
+    )
+)]
+async fn example_task_set(extract::Path(name): extract::Path<String>) -> impl IntoResponse {
+    match example(&name) {
+        Some(tasks) => Json(tasks).into_response(),
+        None => (StatusCode::NOT_FOUND, "Example Not Found").into_response(),
+    }
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct GenerateQuery {
+    /// Tasks, defaults to 10, at most 1000
+    n: Option<usize>,
+    /// Resources the tasks lock, defaults to 3, 0 for none
+    resources: Option<usize>,
+    /// Total utilization, defaults to 0.7
+    utilization: Option<f64>,
+    /// Defaults to 0, the same seed draws the same task set
+    seed: Option<u64>,
+}
+
+// Random task set with the seed it was drawn from, e.g. for experiments.
+#[utoipa::path(
+    get,
+    path = "/api/generate",
+    params(GenerateQuery),
+    responses((status = 200, description = "Task set and its seed", body = GeneratedTaskSet))
+)]
+async fn generate_task_set(extract::Query(query): extract::Query<GenerateQuery>) -> impl IntoResponse {
+    let n = query.n.unwrap_or(10).min(MAX_GENERATED_TASKS);
+    let mut rng = SeededRng::new(query.seed.unwrap_or(0));
+    Json(random_task_set(n, query.resources.unwrap_or(3), query.utilization.unwrap_or(0.7), &mut rng))
+}
+
+// Page with a progress bar, listening to the `/analysis/progress` event stream of the example task set.
+async fn analysis_page() -> impl IntoResponse {
+    HtmlTemplate(AnalysisTemplate { taskset_id: "null".to_string() })
+}
+
+// Page with a progress bar, listening to the `/api/:taskset_id/progress` event stream of a stored task set.
+async fn taskset_analysis_page(
+    extract::Extension(session): extract::Extension<Arc<Session>>,
+    extract::Path(taskset_id): extract::Path<String>,
+) -> impl IntoResponse {
+    if session.task_set(&taskset_id).is_none() {
+        return (StatusCode::NOT_FOUND, "Task Set Not Found").into_response();
+    }
+    HtmlTemplate(AnalysisTemplate { taskset_id: serde_json::to_string(&taskset_id).unwrap() }).into_response()
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct ProgressQuery {
+    /// Also simulate the task set, see `SimulationQuery`
+    #[serde(default)]
+    simulate: bool,
+}
+
+// Runs the analysis on a blocking thread and streams its progress as Server-Sent Events.
+// With `simulate=true` the task set is simulated afterwards, a `simulated` event carries the largest simulated
+// response time of every task. A final `done` event carries the response times once every task is analyzed.
+fn progress_events(
+    tasks: Tasks,
+    config: AnalysisConfig,
+    progress: ProgressQuery,
+    simulation: SimulationQuery,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+
+    tokio::task::spawn_blocking(move || {
+        let started = std::time::Instant::now();
+        let send_progress = |progress| {
+            let _ = tx.send(Event::default().event("progress").json_data(progress));
+        };
+        let results = srp_analyze_with_progress(&tasks, &config, send_progress);
+        record_analysis("full", started);
+
+        if progress.simulate {
+            let simulated = simulate_with_progress(&tasks, simulation_horizon(&tasks, &simulation), send_progress);
+            let mut response_times: BTreeMap<&str, Option<u32>> = BTreeMap::new();
+            for job in &simulated.jobs {
+                let largest = response_times.entry(job.task.as_str()).or_default();
+                *largest = (*largest).max(job.response_time());
+            }
+            let _ = tx.send(Event::default().event("simulated").json_data(response_times));
+        }
+
+        let response_times: BTreeMap<&str, Result<u32, String>> = results
+            .into_iter()
+            .map(|(task, response_time, ..)| (task.id.as_str(), response_time))
+            .collect();
+        let _ = tx.send(Event::default().event("done").json_data(response_times));
+    });
+
+    let stream = UnboundedReceiverStream::new(rx).map(|event| {
+        Ok(event.unwrap_or_else(|e| Event::default().event("error").data(e.to_string())))
+    });
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
+// Progress of the analysis of the example task set, see `progress_events`.
+async fn analysis_progress(
+    extract::Query(query): extract::Query<AnalysisQuery>,
+    extract::Query(progress): extract::Query<ProgressQuery>,
+    extract::Query(simulation): extract::Query<SimulationQuery>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    progress_events(simple_example(), query.config(), progress, simulation)
+}
+
+// Progress of the analysis of a stored task set, see `progress_events`.
+#[utoipa::path(
+    get,
+    path = "/api/{taskset_id}/progress",
+    params(
+        ("taskset_id" = String, Path, description = "Stored task set"),
+        AnalysisQuery,
+        ProgressQuery,
+        SimulationQuery
+    ),
+    responses(
+        (
+            status = 200,
+            description = "`progress` events while the tasks are analyzed and simulated, a `simulated` event with the \
+                largest simulated R(t) of every task, and a final `done` event with the analyzed R(t) of every task",
+            body = String,
+            content_type = "text/event-stream"
+        ),
+        (status = 404, description = "Unknown task set")
+    )
+)]
+async fn taskset_progress(
+    extract::Extension(session): extract::Extension<Arc<Session>>,
+    extract::Path(taskset_id): extract::Path<String>,
+    extract::Query(query): extract::Query<AnalysisQuery>,
+    extract::Query(progress): extract::Query<ProgressQuery>,
+    extract::Query(simulation): extract::Query<SimulationQuery>,
+) -> impl IntoResponse {
+    let Some(tasks) = session.task_set(&taskset_id) else {
+        return (StatusCode::NOT_FOUND, "Task Set Not Found").into_response();
+    };
+    progress_events(tasks, query.config(), progress, simulation).into_response()
+}
+
+// Analysis results of the example task set, served from the analysis cache when possible.
+#[utoipa::path(
+    get,
+    path = "/api/analysis",
+    params(AnalysisQuery),
+    responses((
+        status = 200,
+        description = "Analysis of the example task set, as a table for text/html or text/plain",
+        body = [TaskAnalysis]
+    ))
+)]
+async fn analysis_json(
+    format: Format,
+    extract::Extension(session): extract::Extension<Arc<Session>>,
+    extract::Query(query): extract::Query<AnalysisQuery>,
+) -> impl IntoResponse {
+    let tasks = simple_example();
+    let config = query.config();
+    let results = session.analyze(&tasks, &config);
+    Negotiated::new(format, "Analysis of the example task set", results.to_vec(), &results)
+}
+
+#[utoipa::path(
+    get,
+    path = "/cache/stats",
+    responses((status = 200, description = "Analysis cache statistics", body = CacheStats))
+)]
+async fn cache_stats(extract::Extension(session): extract::Extension<Arc<Session>>) -> impl IntoResponse {
+    Json(session.cache.lock().unwrap().stats())
+}
+
+// Hit and eviction counts of the rendered chart pages of the session.
+#[utoipa::path(
+    get,
+    path = "/cache/charts/stats",
+    responses((status = 200, description = "Chart cache statistics", body = CacheStats))
+)]
+async fn chart_cache_stats(extract::Extension(session): extract::Extension<Arc<Session>>) -> impl IntoResponse {
+    Json(session.charts.lock().unwrap().stats())
+}
+
+// Request counts and latencies by route, and analysis durations, in the Prometheus text format.
+async fn metrics(extract::State(state): extract::State<Arc<AppState>>) -> impl IntoResponse {
+    ([(header::CONTENT_TYPE, "text/plain; version=0.0.4")], state.metrics.render())
+}
+
+// Answers a GraphQL query over the task sets of the session, see `server::QueryRoot`.
+async fn graphql(
+    extract::State(state): extract::State<Arc<AppState>>,
+    extract::Extension(session): extract::Extension<Arc<Session>>,
+    Json(request): Json<async_graphql::Request>,
+) -> impl IntoResponse {
+    Json(state.graphql.execute(request.data(session)).await)
+}
+
+// GraphiQL explorer of the GraphQL schema
+async fn graphiql() -> impl IntoResponse {
+    Html(async_graphql::http::GraphiQLSource::build().endpoint("/graphql").finish())
+}
+
+#[derive(Deserialize)]
+struct RegionQuery {
+    x: String,
+    x_from: u32,
+    x_to: u32,
+    x_step: Option<u32>,
+    y: String,
+    y_from: u32,
+    y_to: u32,
+    y_step: Option<u32>,
+}
+
+// Heatmap of schedulable combinations of two swept parameters of the example task set,
+// e.g. /region?x=T3.wcet&x_from=5&x_to=40&y=T2.period&y_from=50&y_to=300&y_step=10
+async fn region(
+    extract::State(state): extract::State<Arc<AppState>>,
+    extract::Query(query): extract::Query<RegionQuery>,
+    extract::Query(analysis): extract::Query<AnalysisQuery>,
+    extract::Query(theme): extract::Query<ThemeQuery>,
+    extract::Query(size): extract::Query<ChartQuery>,
+) -> impl IntoResponse {
+    let theme = match requested_theme(&state, &theme) {
+        Ok(theme) => theme,
+        Err(response) => return response,
+    };
+    let axis = |parameter: &str, from, to, step: Option<u32>| -> Result<SweepAxis, String> {
+        Ok(SweepAxis { parameter: parameter.parse()?, from, to, step: step.unwrap_or(1) })
+    };
+    let x = match axis(&query.x, query.x_from, query.x_to, query.x_step) {
+        Ok(x) => x,
+        Err(e) => return (StatusCode::BAD_REQUEST, e).into_response(),
+    };
+    let y = match axis(&query.y, query.y_from, query.y_to, query.y_step) {
+        Ok(y) => y,
+        Err(e) => return (StatusCode::BAD_REQUEST, e).into_response(),
+    };
+
+    let config = analysis.config();
+    let tasks = simple_example();
+    let region = match tokio::task::spawn_blocking(move || schedulability_region(&tasks, &config, &x, &y)).await {
+        Ok(Ok(region)) => region,
+        Ok(Err(e)) => return (StatusCode::BAD_REQUEST, e).into_response(),
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    };
+
+    let chart = charts::region_chart(&region, &query.x, &query.y, &theme);
+    chart_page("Schedulability region".to_string(), &chart, &size)
+}
+
+// Standalone HTML report of a stored task set, for archiving or design reviews, or the XML report for
+// `Accept: application/xml` or `?format=xml`.
+async fn report(
+    format: Format,
+    extract::State(state): extract::State<Arc<AppState>>,
+    extract::Extension(session): extract::Extension<Arc<Session>>,
+    extract::Path(taskset_id): extract::Path<String>,
+    extract::Query(query): extract::Query<AnalysisQuery>,
+    extract::Query(theme): extract::Query<ThemeQuery>,
+    locale: Locale,
+) -> impl IntoResponse {
+    let Some(tasks) = session.task_set(&taskset_id) else {
+        return (StatusCode::NOT_FOUND, "Task Set Not Found").into_response();
+    };
+    let config = query.config();
+    if format == Format::Xml {
+        let xml = io::analysis_report_xml(&taskset_id, &tasks, &config);
+        return ([(header::CONTENT_TYPE, "application/xml")], xml).into_response();
+    }
+    let theme = match requested_theme(&state, &theme) {
+        Ok(theme) => theme,
+        Err(response) => return response,
+    };
+    HtmlTemplate(ReportTemplate::new(&taskset_id, &tasks, &config, &theme, locale)).into_response()
+}
+
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct ResultsQuery {
+    /// Defaults to task
+    sort: Option<ResultColumn>,
+    /// Defaults to asc
+    order: Option<SortOrder>,
+}
+
+// Results of every task of a stored task set, sorted as requested
+fn sorted_results(
+    session: &Session,
+    taskset_id: &str,
+    query: &AnalysisQuery,
+    sort: &ResultsQuery,
+) -> Option<Vec<ResultRow>> {
+    let tasks = session.task_set(taskset_id)?;
+    let config = query.config();
+    let results = session.analyze(&tasks, &config);
+    let mut rows = result_rows(&config.prepare(&tasks), &results);
+    sort_results(&mut rows, sort.sort.unwrap_or_default(), sort.order.unwrap_or_default());
+    Some(rows)
+}
+
+// Table of the exact results of every task, sorted on the server by the column given in the query.
+async fn results_page(
+    extract::Extension(session): extract::Extension<Arc<Session>>,
+    extract::Path(taskset_id): extract::Path<String>,
+    extract::Query(query): extract::Query<AnalysisQuery>,
+    extract::Query(sort): extract::Query<ResultsQuery>,
+    extract::RawQuery(raw): extract::RawQuery,
+) -> impl IntoResponse {
+    let Some(rows) = sorted_results(&session, &taskset_id, &query, &sort) else {
+        return (StatusCode::NOT_FOUND, "Task Set Not Found").into_response();
+    };
+    // Analysis options are kept by the sort links and the CSV link
+    let params: String = raw
+        .unwrap_or_default()
+        .split('&')
+        .filter(|param| !param.is_empty() && !param.starts_with("sort=") && !param.starts_with("order="))
+        .map(|param| format!("{}&", param))
+        .collect();
+    HtmlTemplate(ResultsTemplate {
+        taskset_id,
+        rows,
+        sort: sort.sort.unwrap_or_default(),
+        order: sort.order.unwrap_or_default(),
+        params,
+    })
+    .into_response()
+}
+
+#[utoipa::path(
+    get,
+    path = "/results/{taskset_id}/results.csv",
+    params(("taskset_id" = String, Path, description = "Stored task set"), AnalysisQuery, ResultsQuery),
+    responses(
+        (status = 200, description = "task,wcet,period,deadline,blocking,interference,response_time,slack,verdict lines in ticks", body = String, content_type = "text/csv"),
+        (status = 404, description = "Unknown task set")
+    )
+)]
+async fn results_csv(
+    extract::Extension(session): extract::Extension<Arc<Session>>,
+    extract::Path(taskset_id): extract::Path<String>,
+    extract::Query(query): extract::Query<AnalysisQuery>,
+    extract::Query(sort): extract::Query<ResultsQuery>,
+) -> impl IntoResponse {
+    let Some(rows) = sorted_results(&session, &taskset_id, &query, &sort) else {
+        return (StatusCode::NOT_FOUND, "Task Set Not Found").into_response();
+    };
+    let disposition = attachment_disposition(&taskset_id, "csv");
+    (
+        [(header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()), (header::CONTENT_DISPOSITION, disposition)],
+        super::results_csv(&rows),
+    )
+        .into_response()
+}
+
+#[derive(Serialize, ToSchema)]
+struct WhatIfResponse {
+    schedulable: bool,
+    results: Vec<TaskAnalysis>,
+}
+
+// Analyzes a stored task set with parameter overrides, e.g. {"T2.wcet": 40}, leaving the stored set untouched.
+#[utoipa::path(
+    post,
+    path = "/analysis/{taskset_id}/whatif",
+    params(("taskset_id" = String, Path, description = "Stored task set"), AnalysisQuery),
+    request_body(content = BTreeMap<String, u32>, description = "Overrides such as {\"T2.wcet\": 40}"),
+    responses(
+        (status = 200, description = "Analysis with the overrides applied, as a table for text/html or text/plain", body = WhatIfResponse),
+        (status = 400, description = "Invalid override, or a task set made invalid by the overrides"),
+        (status = 404, description = "Unknown task set")
+    )
+)]
+async fn what_if(
+    format: Format,
+    extract::Extension(session): extract::Extension<Arc<Session>>,
+    extract::Path(taskset_id): extract::Path<String>,
+    extract::Query(query): extract::Query<AnalysisQuery>,
+    Json(overrides): Json<BTreeMap<String, u32>>,
+) -> impl IntoResponse {
+    let Some(base) = session.task_set(&taskset_id) else {
+        return (StatusCode::NOT_FOUND, "Task Set Not Found").into_response();
+    };
+    let mut tasks = base.clone();
+    // An override may break the task set, e.g. a WCET ending before the critical sections of the task
+    if let Err(e) = apply_overrides(&mut tasks, &overrides).and_then(|_| validate_task_set(&tasks)) {
+        return (StatusCode::BAD_REQUEST, e).into_response();
+    }
+
+    let config = query.config();
+    // Only the tasks affected by the overrides are analyzed again
+    let results = session.analyze_delta(&base, &tasks, &config);
+    let response = WhatIfResponse { schedulable: results.iter().all(TaskAnalysis::meets_deadline), results: results.to_vec() };
+    Negotiated::new(format, &format!("What-if analysis of {taskset_id}"), response, &results).into_response()
+}
+
+#[derive(Serialize, ToSchema)]
+struct PriorityAssignmentResponse {
+    /// New priority of every task by id
+    priorities: BTreeMap<String, u8>,
+    results: Vec<TaskAnalysis>,
+}
+
+// Replaces the priorities of a stored task set by a schedulable assignment, if there is one.
+#[utoipa::path(
+    post,
+    path = "/api/{taskset_id}/priorities/optimal",
+    params(("taskset_id" = String, Path, description = "Stored task set"), AnalysisQuery),
+    responses(
+        (status = 200, description = "Applied priority assignment and the resulting analysis", body = PriorityAssignmentResponse),
+        (status = 400, description = "Exact and suspension-aware mode are not supported"),
+        (status = 404, description = "Unknown task set"),
+        (status = 422, description = "No priority assignment is schedulable")
+    )
+)]
+async fn assign_priorities(
+    extract::Extension(session): extract::Extension<Arc<Session>>,
+    extract::Path(taskset_id): extract::Path<String>,
+    extract::Query(query): extract::Query<AnalysisQuery>,
+) -> impl IntoResponse {
+    let Some(tasks) = session.task_set(&taskset_id) else {
+        return (StatusCode::NOT_FOUND, "Task Set Not Found").into_response();
+    };
+
+    let config = query.config();
+    let tasks = match optimal_priority_assignment(&tasks, &config) {
+        Ok(Some(tasks)) => tasks,
+        Ok(None) => return (StatusCode::UNPROCESSABLE_ENTITY, "No Schedulable Priority Assignment").into_response(),
+        Err(e) => return (StatusCode::BAD_REQUEST, e).into_response(),
+    };
+
+    let results = session.analyze(&tasks, &config).as_ref().clone();
+    let priorities = priorities(&tasks);
+    session.insert_task_set(&taskset_id, tasks);
+    Json(PriorityAssignmentResponse { priorities, results }).into_response()
+}
+
+// Preview of the dense priority levels `normalize_priorities` would assign to a stored task set.
+#[utoipa::path(
+    get,
+    path = "/api/{taskset_id}/priorities/normalized",
+    params(("taskset_id" = String, Path, description = "Stored task set")),
+    responses(
+        (status = 200, description = "Mapping of every priority onto its dense level, and the ties", body = PriorityNormalization),
+        (status = 404, description = "Unknown task set")
+    )
+)]
+async fn priority_normalization(
+    extract::Extension(session): extract::Extension<Arc<Session>>,
+    extract::Path(taskset_id): extract::Path<String>,
+) -> impl IntoResponse {
+    match session.task_set(&taskset_id) {
+        Some(tasks) => Json(tasks.priority_normalization()).into_response(),
+        None => (StatusCode::NOT_FOUND, "Task Set Not Found").into_response(),
+    }
+}
+
+// Replaces the priorities of a stored task set by dense levels 1..=n in the same order, e.g. after an import.
+#[utoipa::path(
+    post,
+    path = "/api/{taskset_id}/priorities/normalized",
+    params(("taskset_id" = String, Path, description = "Stored task set")),
+    responses(
+        (status = 200, description = "Applied mapping of every priority onto its dense level", body = PriorityNormalization),
+        (status = 404, description = "Unknown task set")
+    )
+)]
+async fn normalize_priorities(
+    extract::Extension(session): extract::Extension<Arc<Session>>,
+    extract::Path(taskset_id): extract::Path<String>,
+) -> impl IntoResponse {
+    let Some(mut tasks) = session.task_set(&taskset_id) else {
+        return (StatusCode::NOT_FOUND, "Task Set Not Found").into_response();
+    };
+    let normalization = tasks.normalize_priorities();
+    if normalization.changed {
+        session.insert_task_set(&taskset_id, tasks);
+    }
+    Json(normalization).into_response()
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct ScaleQuery {
+    /// Every timing quantity is multiplied by it, e.g. 1000 to convert ms to µs
+    factor: u32,
+    /// Id to store the scaled task set under, defaults to replacing the task set
+    into: Option<String>,
+}
+
+// Converts a stored task set to another time unit, e.g. before combining it with task sets specified in µs.
+#[utoipa::path(
+    post,
+    path = "/api/{taskset_id}/scale",
+    params(("taskset_id" = String, Path, description = "Stored task set"), ScaleQuery),
+    responses(
+        (status = 200, description = "Scaled task set, as stored", body = [Task]),
+        (status = 400, description = "Zero factor, or a timing quantity overflows"),
+        (status = 404, description = "Unknown task set")
+    )
+)]
+async fn scale_task_set(
+    extract::Extension(session): extract::Extension<Arc<Session>>,
+    extract::Path(taskset_id): extract::Path<String>,
+    extract::Query(query): extract::Query<ScaleQuery>,
+) -> impl IntoResponse {
+    let Some(tasks) = session.task_set(&taskset_id) else {
+        return (StatusCode::NOT_FOUND, "Task Set Not Found").into_response();
+    };
+    let scaled = match tasks.scaled(query.factor) {
+        Ok(scaled) => scaled,
+        Err(e) => return (StatusCode::BAD_REQUEST, e).into_response(),
+    };
+    session.insert_task_set(query.into.as_deref().unwrap_or(&taskset_id), scaled.clone());
+    Json(scaled).into_response()
+}
+
+#[derive(Deserialize, ToSchema)]
+struct CoreBalanceRequest {
+    /// Number of cores
+    cores: u32,
+    /// Current core of every task by id, tasks missing in it run on core 0
+    #[serde(default)]
+    assignment: BTreeMap<String, u32>,
+}
+
+// Suggests migrations of tasks between the cores of a partitioned task set that lower the utilization of the
+// busiest core, leaving the stored task set untouched.
+#[utoipa::path(
+    post,
+    path = "/api/{taskset_id}/cores/balance",
+    params(("taskset_id" = String, Path, description = "Stored task set"), AnalysisQuery),
+    request_body = CoreBalanceRequest,
+    responses(
+        (status = 200, description = "Suggested moves and the cores before and after them, as a table of the moves for text/html or text/plain", body = CoreBalancing),
+        (status = 400, description = "Invalid assignment, e.g. a resource shared across cores"),
+        (status = 404, description = "Unknown task set")
+    )
+)]
+async fn balance_cores_json(
+    format: Format,
+    extract::Extension(session): extract::Extension<Arc<Session>>,
+    extract::Path(taskset_id): extract::Path<String>,
+    extract::Query(query): extract::Query<AnalysisQuery>,
+    Json(request): Json<CoreBalanceRequest>,
+) -> impl IntoResponse {
+    let Some(tasks) = session.task_set(&taskset_id) else {
+        return (StatusCode::NOT_FOUND, "Task Set Not Found").into_response();
+    };
+    match balance_cores(&tasks, &request.assignment, request.cores, &query.config()) {
+        Ok(balancing) => {
+            Negotiated::new(format, &format!("Core balancing of {taskset_id}"), &balancing, &balancing.moves)
+                .into_response()
+        }
+        Err(e) => (StatusCode::BAD_REQUEST, e).into_response(),
+    }
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct CoreQuery {
+    /// Number of cores, 2 by default
+    cores: Option<u32>,
+    /// Current core of every task, e.g. `T1:0,T2:1`, tasks missing in it run on core 0
+    assignment: Option<String>,
+}
+
+// Core of every task of a `CoreQuery` assignment
+fn parse_assignment(assignment: &str) -> Result<CoreAssignment, String> {
+    assignment
+        .split(',')
+        .filter(|entry| !entry.trim().is_empty())
+        .map(|entry| {
+            let (task, core) =
+                entry.split_once(':').ok_or_else(|| format!("Error: Expected task:core, got '{}'.", entry))?;
+            let core = core.trim().parse().map_err(|_| format!("Error: Invalid core '{}' of task '{}'.", core, task))?;
+            Ok((task.trim().to_string(), core))
+        })
+        .collect()
+}
+
+// Utilization of every core before and after the suggested migrations, e.g. /cores/example?cores=2&assignment=T1:0
+async fn cores_page(
+    extract::State(state): extract::State<Arc<AppState>>,
+    extract::Extension(session): extract::Extension<Arc<Session>>,
+    extract::Path(taskset_id): extract::Path<String>,
+    extract::Query(cores): extract::Query<CoreQuery>,
+    extract::Query(query): extract::Query<AnalysisQuery>,
+    extract::Query(theme): extract::Query<ThemeQuery>,
+    extract::Query(size): extract::Query<ChartQuery>,
+) -> impl IntoResponse {
+    let Some(tasks) = session.task_set(&taskset_id) else {
+        return (StatusCode::NOT_FOUND, "Task Set Not Found").into_response();
+    };
+    let theme = match requested_theme(&state, &theme) {
+        Ok(theme) => theme,
+        Err(response) => return response,
+    };
+    let balancing = parse_assignment(cores.assignment.as_deref().unwrap_or_default())
+        .and_then(|assignment| balance_cores(&tasks, &assignment, cores.cores.unwrap_or(2), &query.config()));
+    let balancing = match balancing {
+        Ok(balancing) => balancing,
+        Err(e) => return (StatusCode::BAD_REQUEST, e).into_response(),
+    };
+    let chart = charts::core_balance_chart(&balancing, &theme).to_string();
+    HtmlTemplate(CoresTemplate { taskset_id, balancing, chart, size: size.size(900, 500) }).into_response()
+}
+
+#[derive(Template)]
+#[template(path = "cores.html")]
+struct CoresTemplate {
+    taskset_id: String,
+    balancing: CoreBalancing,
+    size: ChartSize,
+    chart: String,
+}
+
+// Names and descriptions of the schedulability tests that can be run on stored task sets.
+#[utoipa::path(
+    get,
+    path = "/api/plugins",
+    responses((status = 200, description = "Description of every analysis plugin by name", body = Object))
+)]
+async fn plugin_catalog(extract::State(state): extract::State<Arc<AppState>>) -> impl IntoResponse {
+    Json(state.plugins.catalog())
+}
+
+// Runs a registered schedulability test instead of the SRP analysis on a stored task set.
+#[utoipa::path(
+    get,
+    path = "/api/{taskset_id}/plugins/{plugin}",
+    params(
+        ("taskset_id" = String, Path, description = "Stored task set"),
+        ("plugin" = String, Path, description = "Analysis plugin, see /api/plugins"),
+        AnalysisQuery
+    ),
+    responses(
+        (status = 200, description = "Outcome of the test", body = PluginReport),
+        (status = 404, description = "Unknown task set or plugin")
+    )
+)]
+async fn run_plugin(
+    extract::State(state): extract::State<Arc<AppState>>,
+    extract::Extension(session): extract::Extension<Arc<Session>>,
+    extract::Path((taskset_id, plugin)): extract::Path<(String, String)>,
+    extract::Query(query): extract::Query<AnalysisQuery>,
+) -> impl IntoResponse {
+    let Some(tasks) = session.task_set(&taskset_id) else {
+        return (StatusCode::NOT_FOUND, "Task Set Not Found").into_response();
+    };
+    let config = query.config();
+    match tokio::task::spawn_blocking(move || state.plugins.run(&plugin, &tasks, &config)).await {
+        Ok(Ok(report)) => Json(report).into_response(),
+        Ok(Err(e)) => (StatusCode::NOT_FOUND, e).into_response(),
+        Err(_) => (StatusCode::INTERNAL_SERVER_ERROR, "Plugin Failed").into_response(),
+    }
+}
+
+// Recommends the largest WCET budgets within the given bounds that keep a stored task set schedulable.
+#[utoipa::path(
+    post,
+    path = "/api/{taskset_id}/budgets",
+    params(("taskset_id" = String, Path, description = "Stored task set"), AnalysisQuery),
+    request_body(
+        content = [WcetBounds],
+        description = "WCET bounds of the tasks to budget, earlier tasks take precedence"
+    ),
+    responses(
+        (status = 200, description = "Recommended WCET budget of every bounded task", body = [WcetBudget]),
+        (status = 400, description = "Invalid bounds"),
+        (status = 404, description = "Unknown task set"),
+        (status = 422, description = "Not schedulable with all lower bounds")
+    )
+)]
+async fn wcet_budgets(
+    extract::Extension(session): extract::Extension<Arc<Session>>,
+    extract::Path(taskset_id): extract::Path<String>,
+    extract::Query(query): extract::Query<AnalysisQuery>,
+    Json(bounds): Json<Vec<WcetBounds>>,
+) -> impl IntoResponse {
+    let Some(tasks) = session.task_set(&taskset_id) else {
+        return (StatusCode::NOT_FOUND, "Task Set Not Found").into_response();
+    };
+
+    let config = query.config();
+    match tokio::task::spawn_blocking(move || allocate_wcet_budgets(&tasks, &bounds, &config)).await {
+        Ok(Ok(Some(budgets))) => Json(budgets).into_response(),
+        Ok(Ok(None)) => (StatusCode::UNPROCESSABLE_ENTITY, "Not Schedulable With The Lower Bounds").into_response(),
+        Ok(Err(e)) => (StatusCode::BAD_REQUEST, e).into_response(),
+        Err(_) => (StatusCode::INTERNAL_SERVER_ERROR, "Budget Allocation Failed").into_response(),
+    }
+}
+
+// Minimal sets of optional tasks to drop so that the hard tasks of a stored task set meet their deadlines.
+#[utoipa::path(
+    get,
+    path = "/api/{taskset_id}/shedding",
+    params(("taskset_id" = String, Path, description = "Stored task set"), AnalysisQuery),
+    responses(
+        (status = 200, description = "Candidate shedding plans, the best first, none if no plan exists", body = [SheddingPlan]),
+        (status = 400, description = "Too many optional tasks"),
+        (status = 404, description = "Unknown task set")
+    )
+)]
+async fn shedding(
+    extract::Extension(session): extract::Extension<Arc<Session>>,
+    extract::Path(taskset_id): extract::Path<String>,
+    extract::Query(query): extract::Query<AnalysisQuery>,
+) -> impl IntoResponse {
+    let Some(tasks) = session.task_set(&taskset_id) else {
+        return (StatusCode::NOT_FOUND, "Task Set Not Found").into_response();
+    };
+
+    let config = query.config();
+    match tokio::task::spawn_blocking(move || shedding_plans(&tasks, &config)).await {
+        Ok(Ok(plans)) => Json(plans).into_response(),
+        Ok(Err(e)) => (StatusCode::BAD_REQUEST, e).into_response(),
+        Err(_) => (StatusCode::INTERNAL_SERVER_ERROR, "Shedding Analysis Failed").into_response(),
+    }
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct FuzzQuery {
+    /// Perturbed variants to analyze, defaults to 1000, at most 100000
+    n: Option<u32>,
+    /// Largest change of a parameter in percent, defaults to 10
+    spread: Option<u32>,
+    /// Defaults to 0, the same seed draws the same variants
+    seed: Option<u64>,
+}
+
+// Robustness of a stored task set: the fraction of randomly perturbed variants that stay schedulable and the
+// parameters the failures depend on the most.
+#[utoipa::path(
+    get,
+    path = "/fuzz/{taskset_id}",
+    params(("taskset_id" = String, Path, description = "Stored task set"), FuzzQuery, AnalysisQuery),
+    responses(
+        (status = 200, description = "Fraction of schedulable variants and the most fragile parameters", body = FuzzReport),
+        (status = 404, description = "Unknown task set")
+    )
+)]
+async fn fuzz(
+    extract::Extension(session): extract::Extension<Arc<Session>>,
+    extract::Path(taskset_id): extract::Path<String>,
+    extract::Query(query): extract::Query<FuzzQuery>,
+    extract::Query(analysis): extract::Query<AnalysisQuery>,
+) -> impl IntoResponse {
+    let Some(tasks) = session.task_set(&taskset_id) else {
+        return (StatusCode::NOT_FOUND, "Task Set Not Found").into_response();
+    };
+
+    let config = analysis.config();
+    let samples = query.n.unwrap_or(1000).min(MAX_FUZZ_SAMPLES);
+    let (spread, seed) = (query.spread.unwrap_or(10), query.seed.unwrap_or(0));
+    match tokio::task::spawn_blocking(move || fuzz_task_set(&tasks, &config, samples, spread, seed)).await {
+        Ok(report) => Json(report).into_response(),
+        Err(_) => (StatusCode::INTERNAL_SERVER_ERROR, "Fuzzing Failed").into_response(),
+    }
+}
+
+/// Long-running computation on a stored task set, run in the background by `POST /jobs`.
+#[derive(Deserialize, ToSchema)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum JobRequest {
+    /// R(t) of every task, like /api/analysis
+    Analysis {
+        taskset_id: String,
+        #[serde(default)]
+        config: AnalysisConfig,
+    },
+    /// R(t) of every task at every value of a parameter, like /api/:taskset_id/sweep
+    Sweep {
+        taskset_id: String,
+        parameter: String,
+        from: u32,
+        to: u32,
+        step: Option<u32>,
+        #[serde(default)]
+        config: AnalysisConfig,
+    },
+    /// Simulated schedule, like /api/:taskset_id/simulation
+    Simulation { taskset_id: String, horizon: Option<u32> },
+    /// Perturbed variants, like /fuzz/:taskset_id
+    Fuzz {
+        taskset_id: String,
+        n: Option<u32>,
+        spread: Option<u32>,
+        seed: Option<u64>,
+        #[serde(default)]
+        config: AnalysisConfig,
+    },
+}
+
+type JobWork = Box<dyn FnOnce() -> Result<serde_json::Value, String> + Send>;
+
+// Kind and work of a job on a task set of the session, or the response rejecting it
+fn job_work(session: &Session, request: JobRequest) -> Result<(&'static str, JobWork), Response> {
+    let task_set = |id: &str| {
+        session.task_set(id).ok_or_else(|| (StatusCode::NOT_FOUND, "Task Set Not Found").into_response())
+    };
+    let json = |value: Result<serde_json::Value, serde_json::Error>| value.map_err(|e| e.to_string());
+    Ok(match request {
+        JobRequest::Analysis { taskset_id, config } => {
+            let tasks = task_set(&taskset_id)?;
+            let work = move || {
+                let results: Vec<TaskAnalysis> = srp_analyze(&tasks, &config)
+                    .iter()
+                    .map(|result| TaskAnalysis::from(result).with_clock(config.clock))
+                    .collect();
+                json(serde_json::to_value(results))
+            };
+            ("analysis", Box::new(work))
+        }
+        JobRequest::Sweep { taskset_id, parameter, from, to, step, config } => {
+            let tasks = task_set(&taskset_id)?;
+            let parameter = parameter.parse().map_err(|e: String| (StatusCode::BAD_REQUEST, e).into_response())?;
+            let axis = SweepAxis { parameter, from, to, step: step.unwrap_or(1) };
+            // Rejected on submission rather than failing once the job runs
+            axis.values().map_err(|e| (StatusCode::BAD_REQUEST, e).into_response())?;
+            let work = move || json(serde_json::to_value(response_time_sweep(&tasks, &config, &axis, true, false)?));
+            ("sweep", Box::new(work))
+        }
+        JobRequest::Simulation { taskset_id, horizon } => {
+            let tasks = task_set(&taskset_id)?;
+            let horizon = horizon
+                .or_else(|| hyperperiod(&tasks))
+                .unwrap_or(MAX_SIMULATION_HORIZON)
+                .min(MAX_SIMULATION_HORIZON);
+            ("simulation", Box::new(move || json(serde_json::to_value(simulate(&tasks, horizon)))))
+        }
+        JobRequest::Fuzz { taskset_id, n, spread, seed, config } => {
+            let tasks = task_set(&taskset_id)?;
+            let samples = n.unwrap_or(1000).min(MAX_FUZZ_SAMPLES);
+            let (spread, seed) = (spread.unwrap_or(10), seed.unwrap_or(0));
+            let work = move || json(serde_json::to_value(fuzz_task_set(&tasks, &config, samples, spread, seed)));
+            ("fuzz", Box::new(work))
+        }
+    })
+}
+
+// Starts a computation in the background and responds at once with its id, so analyses taking longer than
+// REQUEST_TIMEOUT do not time out. Poll GET /jobs/:job_id for the result.
+#[utoipa::path(
+    post,
+    path = "/jobs",
+    request_body(content = JobRequest, description = "Job and its parameters, e.g. {\"kind\": \"fuzz\", \"taskset_id\": \"example\", \"n\": 100000}"),
+    responses(
+        (status = 202, description = "Job started, with its URL in the Location header", body = Job),
+        (status = 400, description = "Invalid job"),
+        (status = 404, description = "Unknown task set")
+    )
+)]
+async fn start_job(
+    extract::Extension(session): extract::Extension<Arc<Session>>,
+    Json(request): Json<JobRequest>,
+) -> impl IntoResponse {
+    let (kind, work) = match job_work(&session, request) {
+        Ok(job) => job,
+        Err(response) => return response,
+    };
+
+    let id = session.jobs.start(kind);
+    let job = session.jobs.get(&id).unwrap();
+    let running = id.clone();
+    tokio::spawn(async move {
+        let result = tokio::task::spawn_blocking(work).await.unwrap_or_else(|_| Err("Job Failed".to_string()));
+        session.jobs.finish(&running, result);
+    });
+    (StatusCode::ACCEPTED, [(header::LOCATION, format!("/jobs/{}", id))], Json(job)).into_response()
+}
+
+#[utoipa::path(
+    get,
+    path = "/jobs/{job_id}",
+    params(("job_id" = String, Path, description = "Id returned by POST /jobs")),
+    responses(
+        (status = 200, description = "Status of the job, with its result once it is done", body = Job),
+        (status = 404, description = "Unknown job, or dropped to make room for newer ones")
+    )
+)]
+async fn job_status(
+    extract::Extension(session): extract::Extension<Arc<Session>>,
+    extract::Path(job_id): extract::Path<String>,
+) -> impl IntoResponse {
+    match session.jobs.get(&job_id) {
+        Some(job) => Json(job).into_response(),
+        None => (StatusCode::NOT_FOUND, "Job Not Found").into_response(),
+    }
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct SweepQuery {
+    /// Swept parameter, e.g. T3.wcet
+    parameter: String,
+    from: u32,
+    to: u32,
+    /// Defaults to 1, at most 10000 values are swept
+    step: Option<u32>,
+    /// Seed every iteration with the previous R(t) where possible, defaults to true
+    warm: Option<bool>,
+    /// Report the fixed-point iterations of every point, defaults to false
+    debug: Option<bool>,
+}
+
+// Response times of a stored task set while one of its parameters is swept, e.g. for a what-if slider.
+#[utoipa::path(
+    get,
+    path = "/api/{taskset_id}/sweep",
+    params(("taskset_id" = String, Path, description = "Stored task set"), SweepQuery, AnalysisQuery),
+    responses(
+        (status = 200, description = "R(t) of every task at every swept value", body = [SweepPoint]),
+        (status = 400, description = "Unknown parameter or too many swept values"),
+        (status = 404, description = "Unknown task set")
+    )
+)]
+async fn sweep(
+    extract::Extension(session): extract::Extension<Arc<Session>>,
+    extract::Path(taskset_id): extract::Path<String>,
+    extract::Query(query): extract::Query<SweepQuery>,
+    extract::Query(analysis): extract::Query<AnalysisQuery>,
+) -> impl IntoResponse {
+    let Some(tasks) = session.task_set(&taskset_id) else {
+        return (StatusCode::NOT_FOUND, "Task Set Not Found").into_response();
+    };
+    let axis = match query.parameter.parse() {
+        Ok(parameter) => SweepAxis { parameter, from: query.from, to: query.to, step: query.step.unwrap_or(1) },
+        Err(e) => return (StatusCode::BAD_REQUEST, e).into_response(),
+    };
+    // Rejected before any analysis is started
+    if let Err(e) = axis.values() {
+        return (StatusCode::BAD_REQUEST, e).into_response();
+    }
+
+    let config = analysis.config();
+    let (warm, debug) = (query.warm.unwrap_or(true), query.debug.unwrap_or(false));
+    match tokio::task::spawn_blocking(move || response_time_sweep(&tasks, &config, &axis, warm, debug)).await {
+        Ok(Ok(points)) => Json(points).into_response(),
+        Ok(Err(e)) => (StatusCode::BAD_REQUEST, e).into_response(),
+        Err(_) => (StatusCode::INTERNAL_SERVER_ERROR, "Sweep Failed").into_response(),
+    }
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct MeasurementQuery {
+    /// Statistic used as the WCET, defaults to max
+    statistic: Option<WcetStatistic>,
+}
+
+// Records measured execution times of a stored task set and sets its WCETs from all measurements so far.
+#[utoipa::path(
+    post,
+    path = "/api/{taskset_id}/measurements",
+    params(("taskset_id" = String, Path, description = "Stored task set"), MeasurementQuery),
+    request_body(
+        content = String,
+        description = "`<task>,<time>` CSV lines or a JSON object of samples by task id",
+        content_type = "text/plain"
+    ),
+    responses(
+        (status = 200, description = "Statistics of all measurements of the task set", body = [ExecutionTimeStats]),
+        (status = 400, description = "Invalid measurements"),
+        (status = 404, description = "Unknown task set")
+    )
+)]
+async fn add_measurements(
+    extract::Extension(session): extract::Extension<Arc<Session>>,
+    extract::Path(taskset_id): extract::Path<String>,
+    extract::Query(query): extract::Query<MeasurementQuery>,
+    body: String,
+) -> impl IntoResponse {
+    let Some(mut tasks) = session.task_set(&taskset_id) else {
+        return (StatusCode::NOT_FOUND, "Task Set Not Found").into_response();
+    };
+    let measurements = match io::parse_measurements(&body) {
+        Ok(measurements) => measurements,
+        Err(e) => return (StatusCode::BAD_REQUEST, e).into_response(),
+    };
+    // Samples of unknown tasks are rejected before anything is recorded
+    if let Some(task) = measurements.keys().find(|task| !tasks.iter().any(|t| &t.id == *task)) {
+        return (StatusCode::BAD_REQUEST, format!("Error: No task with id '{}'.", task)).into_response();
+    }
+
+    let measurements = session.add_measurements(&taskset_id, measurements);
+    if let Err(e) = apply_measured_wcets(&mut tasks, &measurements, query.statistic.unwrap_or_default()) {
+        return (StatusCode::BAD_REQUEST, e).into_response();
+    }
+    session.insert_task_set(&taskset_id, tasks);
+    Json(execution_time_stats(&measurements)).into_response()
+}
+
+// C(t) of every task of a stored task set against its sections and the measurements recorded for it.
+#[utoipa::path(
+    get,
+    path = "/api/{taskset_id}/wcet-check",
+    params(("taskset_id" = String, Path, description = "Stored task set")),
+    responses(
+        (status = 200, description = "Declared, derived and measured C(t) of every task", body = [WcetConsistency]),
+        (status = 404, description = "Unknown task set")
+    )
+)]
+async fn wcet_check(
+    extract::Extension(session): extract::Extension<Arc<Session>>,
+    extract::Path(taskset_id): extract::Path<String>,
+) -> impl IntoResponse {
+    let Some(tasks) = session.task_set(&taskset_id) else {
+        return (StatusCode::NOT_FOUND, "Task Set Not Found").into_response();
+    };
+    let measurements = session.measurements.read().unwrap().get(&taskset_id).cloned().unwrap_or_default();
+    Json(check_wcets(&tasks, &measurements)).into_response()
+}
+
+// Replaces the trace of a task of a stored task set by the worst-case envelope of several recorded runs.
+#[utoipa::path(
+    post,
+    path = "/api/{taskset_id}/runs/{task}",
+    params(
+        ("taskset_id" = String, Path, description = "Stored task set"),
+        ("task" = String, Path, description = "Task the runs were recorded of")
+    ),
+    request_body(content = [Trace], description = "Recorded runs of the task, all with the same sections"),
+    responses(
+        (status = 200, description = "Composite trace and the run dominating each section", body = TraceEnvelope),
+        (status = 400, description = "No runs or runs with differing sections"),
+        (status = 404, description = "Unknown task set or task")
+    )
+)]
+async fn merge_task_runs(
+    extract::Extension(session): extract::Extension<Arc<Session>>,
+    extract::Path((taskset_id, task_id)): extract::Path<(String, String)>,
+    Json(runs): Json<Vec<Trace>>,
+) -> impl IntoResponse {
+    let Some(mut tasks) = session.task_set(&taskset_id) else {
+        return (StatusCode::NOT_FOUND, "Task Set Not Found").into_response();
+    };
+    let Some(task) = tasks.iter_mut().find(|t| t.id == task_id) else {
+        return (StatusCode::NOT_FOUND, "Task Not Found").into_response();
+    };
+    let envelope = match merge_runs(&task_id, &runs) {
+        Ok(envelope) => envelope,
+        Err(e) => return (StatusCode::BAD_REQUEST, e).into_response(),
+    };
+
+    task.trace = envelope.trace.clone();
+    session.insert_task_set(&taskset_id, tasks);
+    Json(envelope).into_response()
+}
+
+#[derive(Serialize, ToSchema)]
+struct TraceIngest {
+    /// Jobs completed by the ingested events
+    jobs: usize,
+    /// Tasks of the jobs still running at the last event, the running one last
+    running: Vec<String>,
+    /// Execution times of all jobs ingested so far
+    stats: Vec<ExecutionTimeStats>,
+}
+
+// Feeds event lines to the recorder of the task set and replaces the trace of the task of every completed job
+// by the envelope of all its jobs so far. Returns the number of completed jobs.
+fn ingest_events(
+    session: &Session,
+    taskset_id: &str,
+    tasks: &mut Tasks,
+    lines: &[u8],
+    line: &mut usize,
+) -> Result<usize, String> {
+    // Parsed and checked against the task set before the recorder is locked, as the open jobs may be of tasks
+    // that were removed or replaced since the last batch
+    let mut events = vec![];
+    for text in lines.split(|&b| b == b'\n') {
+        *line += 1;
+        if text.iter().all(u8::is_ascii_whitespace) {
+            continue;
+        }
+        let event: TraceEvent =
+            serde_json::from_slice(text).map_err(|e| format!("Error: Invalid event on line {}: {}", line, e))?;
+        if let TraceEventKind::Release { task } | TraceEventKind::Start { task } | TraceEventKind::Stop { task } =
+            &event.kind
+        {
+            if !tasks.iter().any(|t| &t.id == task) {
+                return Err(format!("Error: No task with id '{}' on line {}.", task, line));
+            }
+        }
+        events.push(event);
+    }
+
+    let mut recorders = session.recorders.lock().unwrap();
+    let recorder = recorders.entry(taskset_id.to_string()).or_default();
+    let mut detectors = session.inversions.lock().unwrap();
+    let detector = detectors.entry(taskset_id.to_string()).or_default();
+    let mut jobs = 0;
+    for event in &events {
+        let completed = recorder.record(event)?;
+        detector.record(event, tasks);
+        let Some(job) = completed else { continue };
+        let Some(task) = tasks.iter_mut().find(|t| t.id == job.task) else {
+            return Err(format!("Error: No task with id '{}'.", job.task));
+        };
+        task.trace = session.record_job(taskset_id, job)?;
+        jobs += 1;
+    }
+    Ok(jobs)
+}
+
+// Forgets the jobs of the task set that started but did not stop, after events that cannot be ingested
+fn discard_open_jobs(session: &Session, taskset_id: &str) {
+    session.recorders.lock().unwrap().remove(taskset_id);
+    if let Some(detector) = session.inversions.lock().unwrap().get_mut(taskset_id) {
+        detector.discard_open_jobs();
+    }
+}
+
+// Builds traces from the events streamed by a device under test, one JSON event per line, so the analysis
+// follows the execution times observed on the target. Jobs may span several requests, e.g. one per batch of
+// events, as the open jobs of the task set are kept until they stop.
+#[utoipa::path(
+    post,
+    path = "/ingest/{taskset_id}/trace",
+    params(("taskset_id" = String, Path, description = "Stored task set")),
+    request_body(
+        content = TraceEvent,
+        description = "Newline-delimited `TraceEvent`s in the order they happened, e.g. \
+            `{\"time\": 120, \"event\": \"start\", \"task\": \"T1\"}`",
+        content_type = "application/x-ndjson"
+    ),
+    responses(
+        (status = 200, description = "Jobs completed by the events and the execution times observed so far", body = TraceIngest),
+        (status = 400, description = "Invalid event, the open jobs of the task set are discarded"),
+        (status = 404, description = "Unknown task set"),
+        (status = 413, description = "Line longer than 64 KiB, the open jobs of the task set are discarded")
+    )
+)]
+async fn ingest_trace(
+    extract::Extension(session): extract::Extension<Arc<Session>>,
+    extract::Path(taskset_id): extract::Path<String>,
+    mut body: extract::BodyStream,
+) -> impl IntoResponse {
+    let Some(mut tasks) = session.task_set(&taskset_id) else {
+        return (StatusCode::NOT_FOUND, "Task Set Not Found").into_response();
+    };
+
+    let mut pending = vec![];
+    let mut line = 0;
+    let mut jobs = 0;
+    loop {
+        let chunk = body.next().await;
+        let lines = match &chunk {
+            Some(Ok(chunk)) => {
+                pending.extend_from_slice(chunk);
+                // Only complete lines, the rest may continue in the next chunk
+                let complete = pending.iter().rposition(|&b| b == b'\n').map_or(0, |end| end + 1);
+                if pending.len() - complete > MAX_TRACE_LINE {
+                    discard_open_jobs(&session, &taskset_id);
+                    let error = format!("Error: Line {} is longer than {} bytes.", line + 1, MAX_TRACE_LINE);
+                    return (StatusCode::PAYLOAD_TOO_LARGE, error).into_response();
+                }
+                if complete == 0 {
+                    continue;
+                }
+                pending.drain(..complete).collect()
+            }
+            Some(Err(e)) => return (StatusCode::BAD_REQUEST, format!("Error: Cannot read events: {}", e)).into_response(),
+            None => std::mem::take(&mut pending),
+        };
+        match ingest_events(&session, &taskset_id, &mut tasks, &lines, &mut line) {
+            // Live views follow every chunk that completes a job
+            Ok(0) => {}
+            Ok(completed) => {
+                jobs += completed;
+                session.insert_task_set(&taskset_id, tasks.clone());
+            }
+            Err(e) => {
+                discard_open_jobs(&session, &taskset_id);
+                return (StatusCode::BAD_REQUEST, e).into_response();
+            }
+        }
+        if chunk.is_none() {
+            break;
+        }
+    }
+
+    let running = session.recorders.lock().unwrap().get(&taskset_id).map(TraceRecorder::running).unwrap_or_default();
+    let measurements = session.measurements.read().unwrap().get(&taskset_id).cloned().unwrap_or_default();
+    Json(TraceIngest { jobs, running, stats: execution_time_stats(&measurements) }).into_response()
+}
+
+// Distribution of the measured execution times of a task, behind the WCET the analysis uses.
+async fn measurements_page(
+    extract::State(state): extract::State<Arc<AppState>>,
+    extract::Extension(session): extract::Extension<Arc<Session>>,
+    extract::Path((taskset_id, task_id)): extract::Path<(String, String)>,
+    extract::Query(theme): extract::Query<ThemeQuery>,
+    extract::Query(size): extract::Query<ChartQuery>,
+) -> impl IntoResponse {
+    let theme = match requested_theme(&state, &theme) {
+        Ok(theme) => theme,
+        Err(response) => return response,
+    };
+    let Some(task) = session.task_set(&taskset_id).and_then(|tasks| tasks.into_iter().find(|t| t.id == task_id)) else {
+        return (StatusCode::NOT_FOUND, "Task Not Found").into_response();
+    };
+    let samples = session
+        .measurements
+        .read()
+        .unwrap()
+        .get(&taskset_id)
+        .and_then(|measurements| measurements.get(&task_id).cloned())
+        .unwrap_or_default();
+
+    let chart = charts::measurement_chart(&task_id, &samples, task.wcet(), &theme);
+    chart_page(format!("Measurements - {task_id}"), &chart, &size)
+}
+
+// Observed maximum response times of the jobs ingested from devices against the analysis of the task set.
+fn observed_response_times(
+    session: &Session,
+    taskset_id: &str,
+    query: &AnalysisQuery,
+) -> Option<Vec<ObservedResponseTime>> {
+    let tasks = session.task_set(taskset_id)?;
+    let results = session.analyze(&tasks, &query.config());
+    Some(compare_observed(&results, &session.observed_response_times(taskset_id)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/{taskset_id}/observed",
+    params(("taskset_id" = String, Path, description = "Stored task set"), AnalysisQuery),
+    responses(
+        (status = 200, description = "Observed maximum response time of every task with ingested jobs against R(t)", body = [ObservedResponseTime]),
+        (status = 404, description = "Unknown task set")
+    )
+)]
+async fn observed_json(
+    extract::Extension(session): extract::Extension<Arc<Session>>,
+    extract::Path(taskset_id): extract::Path<String>,
+    extract::Query(query): extract::Query<AnalysisQuery>,
+) -> impl IntoResponse {
+    match observed_response_times(&session, &taskset_id, &query) {
+        Some(observations) => Json(observations).into_response(),
+        None => (StatusCode::NOT_FOUND, "Task Set Not Found").into_response(),
+    }
+}
+
+// Observed against analytical response times, flagging every task whose measurements exceed the analysis.
+async fn observed_page(
+    extract::State(state): extract::State<Arc<AppState>>,
+    extract::Extension(session): extract::Extension<Arc<Session>>,
+    extract::Path(taskset_id): extract::Path<String>,
+    extract::Query(query): extract::Query<AnalysisQuery>,
+    extract::Query(theme): extract::Query<ThemeQuery>,
+    extract::Query(size): extract::Query<ChartQuery>,
+) -> impl IntoResponse {
+    let Some(observations) = observed_response_times(&session, &taskset_id, &query) else {
+        return (StatusCode::NOT_FOUND, "Task Set Not Found").into_response();
+    };
+    let theme = match requested_theme(&state, &theme) {
+        Ok(theme) => theme,
+        Err(response) => return response,
+    };
+    let chart = charts::observed_chart(&observations, &theme).to_string();
+    HtmlTemplate(ObservedTemplate { taskset_id, observations, chart, size: size.size(900, 500) }).into_response()
+}
+
+#[derive(Template)]
+#[template(path = "observed.html")]
+struct ObservedTemplate {
+    taskset_id: String,
+    observations: Vec<ObservedResponseTime>,
+    size: ChartSize,
+    chart: String,
+}
+
+// Priority inversions in the events ingested from devices for the task set, `None` for an unknown task set.
+fn priority_inversions(session: &Session, taskset_id: &str) -> Option<(Tasks, Vec<PriorityInversion>)> {
+    let tasks = session.task_set(taskset_id)?;
+    let detectors = session.inversions.lock().unwrap();
+    let inversions = detectors.get(taskset_id).map(|detector| detector.inversions().to_vec()).unwrap_or_default();
+    Some((tasks, inversions))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/{taskset_id}/inversions",
+    params(("taskset_id" = String, Path, description = "Stored task set")),
+    responses(
+        (status = 200, description = "Priority inversions in the ingested events, in the order they started", body = [PriorityInversion]),
+        (status = 404, description = "Unknown task set")
+    )
+)]
+async fn inversions_json(
+    extract::Extension(session): extract::Extension<Arc<Session>>,
+    extract::Path(taskset_id): extract::Path<String>,
+) -> impl IntoResponse {
+    match priority_inversions(&session, &taskset_id) {
+        Some((_, inversions)) => Json(inversions).into_response(),
+        None => (StatusCode::NOT_FOUND, "Task Set Not Found").into_response(),
+    }
+}
+
+// Timeline of the intervals in which a higher priority job of the ingested traces waited for a medium one.
+async fn inversions_page(
+    extract::State(state): extract::State<Arc<AppState>>,
+    extract::Extension(session): extract::Extension<Arc<Session>>,
+    extract::Path(taskset_id): extract::Path<String>,
+    extract::Query(theme): extract::Query<ThemeQuery>,
+    extract::Query(size): extract::Query<ChartQuery>,
+) -> impl IntoResponse {
+    let Some((tasks, inversions)) = priority_inversions(&session, &taskset_id) else {
+        return (StatusCode::NOT_FOUND, "Task Set Not Found").into_response();
+    };
+    let theme = match requested_theme(&state, &theme) {
+        Ok(theme) => theme,
+        Err(response) => return response,
+    };
+    let chart = charts::inversion_chart(&tasks, &inversions, &theme).to_string();
+    HtmlTemplate(InversionsTemplate { taskset_id, inversions, chart, size: size.size(1000, 500) }).into_response()
+}
+
+#[derive(Template)]
+#[template(path = "inversions.html")]
+struct InversionsTemplate {
+    taskset_id: String,
+    inversions: Vec<PriorityInversion>,
+    size: ChartSize,
+    chart: String,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/{taskset_id}/ceilings",
+    params(("taskset_id" = String, Path, description = "Stored task set")),
+    responses(
+        (status = 200, description = "Resource ceilings, as a table for text/html or text/plain", body = [ResourceCeiling]),
+        (status = 404, description = "Unknown task set")
+    )
+)]
+async fn ceilings_json(
+    format: Format,
+    extract::Extension(session): extract::Extension<Arc<Session>>,
+    extract::Path(taskset_id): extract::Path<String>,
+) -> impl IntoResponse {
+    let Some(tasks) = session.task_set(&taskset_id) else {
+        return (StatusCode::NOT_FOUND, "Task Set Not Found").into_response();
+    };
+    let ceilings = resource_ceilings(&tasks);
+    Negotiated::new(format, &format!("Resource ceilings of {taskset_id}"), &ceilings, &ceilings).into_response()
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/{taskset_id}/stack",
+    params(("taskset_id" = String, Path, description = "Stored task set")),
+    responses(
+        (status = 200, description = "Worst-case shared stack depth", body = StackAnalysis),
+        (status = 404, description = "Unknown task set")
+    )
+)]
+async fn stack_json(
+    extract::Extension(session): extract::Extension<Arc<Session>>,
+    extract::Path(taskset_id): extract::Path<String>,
+) -> impl IntoResponse {
+    match session.task_set(&taskset_id) {
+        Some(tasks) => Json(stack_depth(&tasks)).into_response(),
+        None => (StatusCode::NOT_FOUND, "Task Set Not Found").into_response(),
+    }
+}
+
+// End-to-end latencies of the posted chains through a stored task set.
+#[utoipa::path(
+    post,
+    path = "/api/{taskset_id}/chains",
+    params(("taskset_id" = String, Path, description = "Stored task set"), AnalysisQuery),
+    request_body(content = [Chain], description = "Chains of task ids in data flow order"),
+    responses(
+        (status = 200, description = "Worst-case end-to-end latency of every chain, as a table for text/html or text/plain", body = [ChainLatency]),
+        (status = 404, description = "Unknown task set")
+    )
+)]
+async fn chains_json(
+    format: Format,
+    extract::Extension(session): extract::Extension<Arc<Session>>,
+    extract::Path(taskset_id): extract::Path<String>,
+    extract::Query(query): extract::Query<AnalysisQuery>,
+    Json(chains): Json<Vec<Chain>>,
+) -> impl IntoResponse {
+    let Some(tasks) = session.task_set(&taskset_id) else {
+        return (StatusCode::NOT_FOUND, "Task Set Not Found").into_response();
+    };
+
+    let config = query.config();
+    let results = srp_analyze_with_progress(&tasks, &config, |_| {});
+    let latencies = chain_latencies(&chains, &results);
+    Negotiated::new(format, &format!("Chain latencies in {taskset_id}"), &latencies, &latencies).into_response()
+}
+
+// Analysis of a posted task set whose tasks are partly scheduled within polling or deferrable servers.
+#[utoipa::path(
+    post,
+    path = "/api/hierarchical",
+    params(AnalysisQuery),
+    request_body(content = HierarchicalTaskSet, description = "Servers, each with the ids of its tasks, and all tasks"),
+    responses(
+        (status = 200, description = "R(s) of every server and R(t) of every task", body = HierarchicalAnalysis),
+        (status = 400, description = "Invalid servers or resources shared across servers")
+    )
+)]
+async fn hierarchical_json(
+    extract::Query(query): extract::Query<AnalysisQuery>,
+    Json(set): Json<HierarchicalTaskSet>,
+) -> impl IntoResponse {
+    match hierarchical_analyze(&set, &query.config()) {
+        Ok(analysis) => Json(analysis).into_response(),
+        Err(e) => (StatusCode::BAD_REQUEST, e).into_response(),
+    }
+}
+
+// Every problem in the traces of the posted task set with its location, e.g. to fix a task set before importing it.
+#[utoipa::path(
+    post,
+    path = "/api/traces/check",
+    request_body(content = [Task], description = "Task set to check"),
+    responses((status = 200, description = "Problems in pre-order, empty if the traces are valid", body = [TraceIssue]))
+)]
+async fn check_task_traces(Json(tasks): Json<Tasks>) -> impl IntoResponse {
+    Json(check_traces(&tasks))
+}
+
+// Skeleton task set of the posted RTIC application source, with placeholder WCETs and periods.
+#[utoipa::path(
+    post,
+    path = "/api/import/rtic",
+    request_body(content = String, description = "Rust source of an RTIC application", content_type = "text/plain"),
+    responses(
+        (status = 200, description = "Skeleton task set", body = [Task]),
+        (status = 400, description = "No RTIC application found")
+    )
+)]
+async fn import_rtic(source: String) -> impl IntoResponse {
+    match io::rtic_task_set(&source) {
+        Ok(tasks) => Json(tasks).into_response(),
+        Err(e) => (StatusCode::BAD_REQUEST, e).into_response(),
+    }
+}
+
+// Task set of a posted research task set, e.g. a published benchmark set, with deadline monotonic priorities.
+#[utoipa::path(
+    post,
+    path = "/api/import/research",
+    request_body(
+        content = String,
+        description = "Tasks {C, T, D} and resource access lists {accesses: [{task, length}]}",
+        content_type = "application/json"
+    ),
+    responses(
+        (status = 200, description = "Task set", body = [Task]),
+        (status = 400, description = "Invalid task set")
+    )
+)]
+async fn import_research(json: String) -> impl IntoResponse {
+    match io::research_task_set(&json) {
+        Ok(tasks) => Json(tasks).into_response(),
+        Err(e) => (StatusCode::BAD_REQUEST, e).into_response(),
+    }
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct DecimalQuery {
+    /// Ticks per time unit of the posted times, e.g. 1000 for times in ms and µs ticks, by default the finest
+    /// decimal place of the times
+    ticks_per_unit: Option<u32>,
+}
+
+// Task set in ticks of a posted task set with fractional times, e.g. measurements in milliseconds, so they do not
+// have to be scaled by hand. The ticks per unit are returned in the `x-ticks-per-unit` header.
+#[utoipa::path(
+    post,
+    path = "/api/import/decimal",
+    params(DecimalQuery),
+    request_body(
+        content = String,
+        description = "Task set whose times are decimal numbers or strings, e.g. {\"deadline\": 2.5}",
+        content_type = "application/json"
+    ),
+    responses(
+        (status = 200, description = "Task set in ticks, times rounded to the safe side", body = [Task],
+            headers(("x-ticks-per-unit" = u32, description = "Ticks per time unit"))),
+        (status = 400, description = "Invalid task set, a time too large, or an inexact offset")
+    )
+)]
+async fn import_decimal(extract::Query(query): extract::Query<DecimalQuery>, json: String) -> impl IntoResponse {
+    match io::decimal_task_set(&json, query.ticks_per_unit) {
+        Ok((tasks, ticks_per_unit)) => {
+            ([("x-ticks-per-unit", ticks_per_unit.to_string())], Json(tasks)).into_response()
+        }
+        Err(e) => (StatusCode::BAD_REQUEST, e).into_response(),
+    }
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct RelativeQuery {
+    /// Merge tasks with the same id, repeated activations of one task, into a single worst-case activation
+    #[serde(default)]
+    merge: bool,
+}
+
+// Task set with relative traces of a posted task set recorded with absolute timestamps, with what was changed.
+#[utoipa::path(
+    post,
+    path = "/api/import/relative",
+    params(RelativeQuery),
+    request_body(content = [Task], description = "Task set whose traces start at their recorded timestamps"),
+    responses(
+        (status = 200, description = "Task set with traces starting at 0", body = TraceNormalization),
+        (status = 400, description = "Activations that cannot be merged, or traces that are still invalid")
+    )
+)]
+async fn import_relative(
+    extract::Query(query): extract::Query<RelativeQuery>,
+    Json(tasks): Json<Tasks>,
+) -> impl IntoResponse {
+    let normalized = normalize_trace_origins(&tasks, query.merge)
+        .and_then(|normalization| validate_task_set(&normalization.tasks).map(|_| normalization));
+    match normalized {
+        Ok(normalization) => Json(normalization).into_response(),
+        Err(e) => (StatusCode::BAD_REQUEST, e).into_response(),
+    }
+}
+
+// Cheddar XML model of a stored task set, to cross-check the analysis with Cheddar.
+#[utoipa::path(
+    get,
+    path = "/api/{taskset_id}/export/cheddar",
+    params(("taskset_id" = String, Path, description = "Stored task set")),
+    responses(
+        (status = 200, description = "Cheddar XML model", body = String, content_type = "application/xml"),
+        (status = 404, description = "Unknown task set"),
+        (status = 422, description = "Task set cannot be expressed in Cheddar")
+    )
+)]
+async fn export_cheddar(
+    extract::Extension(session): extract::Extension<Arc<Session>>,
+    extract::Path(taskset_id): extract::Path<String>,
+) -> impl IntoResponse {
+    let Some(tasks) = session.task_set(&taskset_id) else {
+        return (StatusCode::NOT_FOUND, "Task Set Not Found").into_response();
+    };
+    match io::cheddar_xml(&tasks) {
+        Ok(xml) => ([(header::CONTENT_TYPE, "application/xml")], xml).into_response(),
+        Err(e) => (StatusCode::UNPROCESSABLE_ENTITY, e).into_response(),
+    }
+}
+
+// Charts rendered into an analysis archive, see `analysis_chart`
+const ARCHIVED_CHARTS: &[&str] =
+    &["response_times", "groups", "composition", "utilization", "margins", "ceilings", "resources", "timeline"];
+
+// Task set, measurements, configuration and results of a stored task set, with its report and charts, as a
+// tar archive to be shared and imported again.
+#[utoipa::path(
+    get,
+    path = "/api/{taskset_id}/export/archive",
+    params(("taskset_id" = String, Path, description = "Stored task set"), AnalysisQuery, ThemeQuery),
+    responses(
+        (status = 200, description = "Tar archive of the analysis", body = String, content_type = "application/x-tar"),
+        (status = 400, description = "Unknown theme"),
+        (status = 404, description = "Unknown task set")
+    )
+)]
+async fn export_archive(
+    extract::State(state): extract::State<Arc<AppState>>,
+    extract::Extension(session): extract::Extension<Arc<Session>>,
+    extract::Path(taskset_id): extract::Path<String>,
+    extract::Query(query): extract::Query<AnalysisQuery>,
+    extract::Query(theme): extract::Query<ThemeQuery>,
+) -> impl IntoResponse {
+    let Some(tasks) = session.task_set(&taskset_id) else {
+        return (StatusCode::NOT_FOUND, "Task Set Not Found").into_response();
+    };
+    let theme = match requested_theme(&state, &theme) {
+        Ok(theme) => theme,
+        Err(response) => return response,
+    };
+
+    let config = query.config();
+    let report = ReportTemplate::new(&taskset_id, &tasks, &config, &theme, Locale::default()).render().unwrap();
+    let mut documents = vec![("report.html".to_string(), report)];
+    for name in ARCHIVED_CHARTS {
+        let chart = analysis_chart(name, &tasks, &config, &theme).unwrap();
+        let html = chart_html(format!("{taskset_id} - {name}"), &chart, &ChartQuery::default());
+        documents.push((format!("charts/{name}.html"), html));
+    }
+
+    let archive = io::AnalysisArchive {
+        manifest: io::ArchiveManifest { version: io::ARCHIVE_VERSION, taskset_id: taskset_id.clone(), config },
+        measurements: session.measurements.read().unwrap().get(&taskset_id).cloned().unwrap_or_default(),
+        results: session.analyze(&tasks, &config).as_ref().clone(),
+        tasks,
+        documents,
+    };
+    match archive.to_tar() {
+        Ok(tar) => (
+            [
+                (header::CONTENT_TYPE, "application/x-tar".to_string()),
+                (header::CONTENT_DISPOSITION, attachment_disposition(&taskset_id, "tar")),
+            ],
+            tar,
+        )
+            .into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct ImportQuery {
+    /// Id to store the task set under, defaults to the id in the archive
+    id: Option<String>,
+}
+
+#[derive(Serialize, ToSchema)]
+struct ArchiveImport {
+    taskset_id: String,
+    config: AnalysisConfig,
+    /// Analysis of the imported task set with the archived configuration
+    results: Vec<TaskAnalysis>,
+    /// Whether the analysis reproduces the archived results
+    reproduced: bool,
+}
+
+// Stores the task set and measurements of an exported archive and analyzes it again with the archived options.
+#[utoipa::path(
+    post,
+    path = "/api/import/archive",
+    params(ImportQuery),
+    request_body(content = String, description = "Archive exported from /api/{taskset_id}/export/archive", content_type = "application/x-tar"),
+    responses(
+        (status = 200, description = "Stored task set and its analysis", body = ArchiveImport),
+        (status = 400, description = "Invalid archive")
+    )
+)]
+async fn import_archive(
+    extract::Extension(session): extract::Extension<Arc<Session>>,
+    extract::Query(query): extract::Query<ImportQuery>,
+    body: axum::body::Bytes,
+) -> impl IntoResponse {
+    let archive = match io::AnalysisArchive::from_tar(&body) {
+        Ok(archive) => archive,
+        Err(e) => return (StatusCode::BAD_REQUEST, e).into_response(),
+    };
+
+    let taskset_id = query.id.unwrap_or(archive.manifest.taskset_id);
+    let config = archive.manifest.config;
+    let results = session.analyze(&archive.tasks, &config).as_ref().clone();
+    session.measurements.write().unwrap().insert(taskset_id.clone(), archive.measurements);
+    session.insert_task_set(&taskset_id, archive.tasks);
+    Json(ArchiveImport { reproduced: results == archive.results, taskset_id, config, results }).into_response()
+}
+
+#[derive(Deserialize, IntoParams)]
+struct SimulationQuery {
+    /// Simulated time, defaults to the hyperperiod
+    horizon: Option<u32>,
+}
+
+// Simulated SRP schedule of a stored task set, over at most MAX_SIMULATION_HORIZON time units.
+fn simulate_stored(session: &Session, taskset_id: &str, query: &SimulationQuery) -> Result<(Tasks, Simulation), Response> {
+    let Some(tasks) = session.task_set(taskset_id) else {
+        return Err((StatusCode::NOT_FOUND, "Task Set Not Found").into_response());
+    };
+    let simulation = simulate_requested(&tasks, query);
+    Ok((tasks, simulation))
+}
+
+// Requested horizon of a simulation, the hyperperiod by default, at most MAX_SIMULATION_HORIZON.
+fn simulation_horizon(tasks: &Tasks, query: &SimulationQuery) -> u32 {
+    query.horizon.or_else(|| hyperperiod(tasks)).unwrap_or(MAX_SIMULATION_HORIZON).min(MAX_SIMULATION_HORIZON)
+}
+
+// Simulates the task set over the requested horizon, see `simulation_horizon`.
+fn simulate_requested(tasks: &Tasks, query: &SimulationQuery) -> Simulation {
+    simulate(tasks, simulation_horizon(tasks, query))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/{taskset_id}/simulation",
+    params(("taskset_id" = String, Path, description = "Stored task set"), SimulationQuery),
+    responses(
+        (status = 200, description = "Simulated schedule and system ceiling", body = Simulation),
+        (status = 404, description = "Unknown task set")
+    )
+)]
+async fn simulation_json(
+    extract::Extension(session): extract::Extension<Arc<Session>>,
+    extract::Path(taskset_id): extract::Path<String>,
+    extract::Query(query): extract::Query<SimulationQuery>,
+) -> impl IntoResponse {
+    match simulate_stored(&session, &taskset_id, &query) {
+        Ok((_, simulation)) => Json(simulation).into_response(),
+        Err(response) => response,
+    }
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct StepQuery {
+    /// Time to simulate up to, one time unit past the last step by default, an earlier time starts over
+    until: Option<u32>,
+}
+
+// Advances the simulation of a stored task set kept in the session and returns the events since the last step,
+// for a play/pause/step UI over the timeline. A replaced task set starts over.
+#[utoipa::path(
+    post,
+    path = "/simulate/{taskset_id}/step",
+    params(("taskset_id" = String, Path, description = "Stored task set"), StepQuery),
+    responses(
+        (status = 200, description = "Schedule and events since the last step", body = SimulationStep),
+        (status = 400, description = "Time beyond the longest simulated schedule"),
+        (status = 404, description = "Unknown task set")
+    )
+)]
+async fn simulation_step(
+    extract::Extension(session): extract::Extension<Arc<Session>>,
+    extract::Path(taskset_id): extract::Path<String>,
+    extract::Query(query): extract::Query<StepQuery>,
+) -> impl IntoResponse {
+    let Some(tasks) = session.task_set(&taskset_id) else {
+        return (StatusCode::NOT_FOUND, "Task Set Not Found").into_response();
+    };
+    let mut simulators = session.simulators.lock().unwrap();
+    let resumed = simulators.get(&taskset_id).is_some_and(|simulator| {
+        simulator.tasks() == &tasks && query.until.is_none_or(|until| until >= simulator.time())
+    });
+    if !resumed {
+        simulators.insert(taskset_id.clone(), Simulator::new(tasks));
+    }
+    let simulator = simulators.get_mut(&taskset_id).unwrap();
+    let until = query.until.unwrap_or(simulator.time() + 1);
+    if until > MAX_SIMULATION_HORIZON {
+        let e = format!("Error: Cannot simulate beyond {} time units.", MAX_SIMULATION_HORIZON);
+        return (StatusCode::BAD_REQUEST, e).into_response();
+    }
+    Json(simulator.step(until)).into_response()
+}
+
+// (m, k) constraints of the tasks of a stored task set, simulated over the hyperperiod and the longest deadline
+// for the tasks failing the hard analysis.
+#[utoipa::path(
+    get,
+    path = "/api/{taskset_id}/weakly-hard",
+    params(("taskset_id" = String, Path, description = "Stored task set"), AnalysisQuery),
+    responses(
+        (status = 200, description = "Outcome of every task with an (m, k) constraint", body = [WeaklyHardCheck]),
+        (status = 404, description = "Unknown task set")
+    )
+)]
+async fn weakly_hard(
+    extract::Extension(session): extract::Extension<Arc<Session>>,
+    extract::Path(taskset_id): extract::Path<String>,
+    extract::Query(query): extract::Query<AnalysisQuery>,
+) -> impl IntoResponse {
+    let Some(tasks) = session.task_set(&taskset_id) else {
+        return (StatusCode::NOT_FOUND, "Task Set Not Found").into_response();
+    };
+    let longest_deadline = tasks.iter().map(|task| task.deadline).max().unwrap_or(0);
+    let horizon = hyperperiod(&tasks)
+        .and_then(|h| h.checked_add(longest_deadline))
+        .unwrap_or(MAX_SIMULATION_HORIZON)
+        .min(MAX_SIMULATION_HORIZON);
+    let config = query.config();
+    match tokio::task::spawn_blocking(move || check_weakly_hard(&tasks, &config, horizon)).await {
+        Ok(checks) => Json(checks).into_response(),
+        Err(_) => (StatusCode::INTERNAL_SERVER_ERROR, "Simulation Failed").into_response(),
+    }
+}
+
+// Timeline of the simulated execution and the system ceiling of a stored task set.
+async fn timeline_page(
+    extract::State(state): extract::State<Arc<AppState>>,
+    extract::Extension(session): extract::Extension<Arc<Session>>,
+    extract::Path(taskset_id): extract::Path<String>,
+    extract::Query(query): extract::Query<SimulationQuery>,
+    extract::Query(analysis): extract::Query<AnalysisQuery>,
+    extract::Query(theme): extract::Query<ThemeQuery>,
+    extract::Query(size): extract::Query<ChartQuery>,
+) -> impl IntoResponse {
+    let theme = match requested_theme(&state, &theme) {
+        Ok(theme) => theme,
+        Err(response) => return response,
+    };
+    let (tasks, simulation) = match simulate_stored(&session, &taskset_id, &query) {
+        Ok(simulated) => simulated,
+        Err(response) => return response,
+    };
+    let chart = charts::timeline_chart(&tasks, &simulation, analysis.config().clock, &theme);
+    chart_page(format!("Timeline - {taskset_id}"), &chart, &size)
+}
+
+#[derive(Deserialize, IntoParams)]
+struct ScenarioQuery {
+    /// Simulated time after the release of the task, defaults to 2 * D(t)
+    horizon: Option<u32>,
+}
+
+// Critical instant of a task of a stored task set, simulated over at most MAX_SIMULATION_HORIZON time units.
+fn scenario_stored(
+    session: &Session,
+    taskset_id: &str,
+    task_id: &str,
+    query: &ScenarioQuery,
+) -> Result<(Tasks, Scenario), Response> {
+    let Some(tasks) = session.task_set(taskset_id) else {
+        return Err((StatusCode::NOT_FOUND, "Task Set Not Found").into_response());
+    };
+    let Some(index) = tasks.iter().position(|t| t.id == task_id) else {
+        return Err((StatusCode::NOT_FOUND, "Task Not Found").into_response());
+    };
+    let horizon = query
+        .horizon
+        .unwrap_or(tasks[index].deadline.saturating_mul(2))
+        .min(MAX_SIMULATION_HORIZON);
+    let scenario = critical_instant(&tasks, index, horizon);
+    Ok((tasks, scenario))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/{taskset_id}/scenario/{task_id}",
+    params(
+        ("taskset_id" = String, Path, description = "Stored task set"),
+        ("task_id" = String, Path, description = "Task whose worst case is reconstructed"),
+        ScenarioQuery
+    ),
+    responses(
+        (status = 200, description = "Simulated critical instant of the task", body = Scenario),
+        (status = 404, description = "Unknown task set or task")
+    )
+)]
+async fn scenario_json(
+    extract::Extension(session): extract::Extension<Arc<Session>>,
+    extract::Path((taskset_id, task_id)): extract::Path<(String, String)>,
+    extract::Query(query): extract::Query<ScenarioQuery>,
+) -> impl IntoResponse {
+    match scenario_stored(&session, &taskset_id, &task_id, &query) {
+        Ok((_, scenario)) => Json(scenario).into_response(),
+        Err(response) => response,
+    }
+}
+
+// Derivation of R(t) of a task, every quantity with its formula and operands, e.g. for teaching the analysis.
+#[utoipa::path(
+    get,
+    path = "/api/{taskset_id}/explain/{task_id}",
+    params(
+        ("taskset_id" = String, Path, description = "Stored task set"),
+        ("task_id" = String, Path, description = "Task whose response time is explained"),
+        AnalysisQuery
+    ),
+    responses(
+        (status = 200, description = "Tree of the quantities R(t) is computed from", body = Explanation),
+        (status = 404, description = "Unknown task set or task")
+    )
+)]
+async fn explain_json(
+    extract::Extension(session): extract::Extension<Arc<Session>>,
+    extract::Path((taskset_id, task_id)): extract::Path<(String, String)>,
+    extract::Query(query): extract::Query<AnalysisQuery>,
+) -> impl IntoResponse {
+    let Some(tasks) = session.task_set(&taskset_id) else {
+        return (StatusCode::NOT_FOUND, "Task Set Not Found").into_response();
+    };
+    match explain_response_time(&tasks, &task_id, &query.config()) {
+        Some(explanation) => Json(explanation).into_response(),
+        None => (StatusCode::NOT_FOUND, "Task Not Found").into_response(),
+    }
+}
+
+// Verdict of a stored task set with every intermediate value, to be verified without rerunning the analysis.
+#[utoipa::path(
+    get,
+    path = "/api/{taskset_id}/certificate",
+    params(("taskset_id" = String, Path, description = "Stored task set"), AnalysisQuery),
+    responses(
+        (status = 200, description = "Machine-checkable certificate of the analysis", body = SchedulabilityCertificate),
+        (status = 404, description = "Unknown task set")
+    )
+)]
+async fn certificate_json(
+    extract::Extension(session): extract::Extension<Arc<Session>>,
+    extract::Path(taskset_id): extract::Path<String>,
+    extract::Query(query): extract::Query<AnalysisQuery>,
+) -> impl IntoResponse {
+    let Some(tasks) = session.task_set(&taskset_id) else {
+        return (StatusCode::NOT_FOUND, "Task Set Not Found").into_response();
+    };
+    Json(schedulability_certificate(&tasks, &query.config())).into_response()
+}
+
+// Every inconsistency of a posted certificate, checked step by step without the task set.
+#[utoipa::path(
+    post,
+    path = "/api/certificate/verify",
+    request_body(content = SchedulabilityCertificate, description = "Certificate to verify"),
+    responses((status = 200, description = "Inconsistencies, empty if the certificate holds", body = [String]))
+)]
+async fn verify_certificate_json(Json(certificate): Json<SchedulabilityCertificate>) -> impl IntoResponse {
+    Json(verify_certificate(&certificate))
+}
+
+// R(t) of every task of a stored task set with and without the offsets of the transactions.
+#[utoipa::path(
+    get,
+    path = "/api/{taskset_id}/offsets",
+    params(("taskset_id" = String, Path, description = "Stored task set"), AnalysisQuery),
+    responses(
+        (status = 200, description = "Offset-oblivious and offset-aware bound of every task", body = [OffsetBound]),
+        (status = 404, description = "Unknown task set")
+    )
+)]
+async fn offsets_json(
+    extract::Extension(session): extract::Extension<Arc<Session>>,
+    extract::Path(taskset_id): extract::Path<String>,
+    extract::Query(query): extract::Query<AnalysisQuery>,
+) -> impl IntoResponse {
+    let Some(tasks) = session.task_set(&taskset_id) else {
+        return (StatusCode::NOT_FOUND, "Task Set Not Found").into_response();
+    };
+    Json(offset_bounds(&tasks, &query.config())).into_response()
+}
+
+// WCET slack and permissible release jitter of every task of a stored task set.
+#[utoipa::path(
+    get,
+    path = "/api/{taskset_id}/sensitivity",
+    params(("taskset_id" = String, Path, description = "Stored task set"), AnalysisQuery),
+    responses(
+        (status = 200, description = "How much C(t) and J(t) of every task may grow", body = [TaskSensitivity]),
+        (status = 400, description = "Invalid task set"),
+        (status = 404, description = "Unknown task set")
+    )
+)]
+async fn sensitivity_json(
+    extract::Extension(session): extract::Extension<Arc<Session>>,
+    extract::Path(taskset_id): extract::Path<String>,
+    extract::Query(query): extract::Query<AnalysisQuery>,
+) -> impl IntoResponse {
+    let Some(tasks) = session.task_set(&taskset_id) else {
+        return (StatusCode::NOT_FOUND, "Task Set Not Found").into_response();
+    };
+
+    let config = query.config();
+    match tokio::task::spawn_blocking(move || task_sensitivities(&tasks, &config)).await {
+        Ok(Ok(sensitivities)) => Json(sensitivities).into_response(),
+        Ok(Err(e)) => (StatusCode::BAD_REQUEST, e).into_response(),
+        Err(_) => (StatusCode::INTERNAL_SERVER_ERROR, "Sensitivity Analysis Failed").into_response(),
+    }
+}
+
+// Refactorings of the critical sections of a stored task set, ranked by the blocking they remove.
+#[utoipa::path(
+    get,
+    path = "/api/{taskset_id}/refactorings",
+    params(("taskset_id" = String, Path, description = "Stored task set"), AnalysisQuery),
+    responses(
+        (status = 200, description = "Refactorings removing blocking, the most first", body = [RefactoringSuggestion]),
+        (status = 404, description = "Unknown task set")
+    )
+)]
+async fn refactorings_json(
+    extract::Extension(session): extract::Extension<Arc<Session>>,
+    extract::Path(taskset_id): extract::Path<String>,
+    extract::Query(query): extract::Query<AnalysisQuery>,
+) -> impl IntoResponse {
+    let Some(tasks) = session.task_set(&taskset_id) else {
+        return (StatusCode::NOT_FOUND, "Task Set Not Found").into_response();
+    };
+
+    let config = query.config();
+    match tokio::task::spawn_blocking(move || suggest_refactorings(&tasks, &config)).await {
+        Ok(suggestions) => Json(suggestions).into_response(),
+        Err(_) => (StatusCode::INTERNAL_SERVER_ERROR, "Refactoring Advice Failed").into_response(),
+    }
+}
+
+// Timeline of the critical instant of a task, showing how a deadline miss unfolds.
+async fn scenario_page(
+    extract::State(state): extract::State<Arc<AppState>>,
+    extract::Extension(session): extract::Extension<Arc<Session>>,
+    extract::Path((taskset_id, task_id)): extract::Path<(String, String)>,
+    extract::Query(query): extract::Query<ScenarioQuery>,
+    extract::Query(analysis): extract::Query<AnalysisQuery>,
+    extract::Query(theme): extract::Query<ThemeQuery>,
+    extract::Query(size): extract::Query<ChartQuery>,
+) -> impl IntoResponse {
+    let theme = match requested_theme(&state, &theme) {
+        Ok(theme) => theme,
+        Err(response) => return response,
+    };
+    let (tasks, scenario) = match scenario_stored(&session, &taskset_id, &task_id, &query) {
+        Ok(scenario) => scenario,
+        Err(response) => return response,
+    };
+    let chart = charts::scenario_chart(&tasks, &scenario, analysis.config().clock, &theme);
+    chart_page(format!("Critical instant - {task_id}"), &chart, &size)
+}
+
+// Timeline, response times and resource usage of a stored task set, highlighting a selected task in all of them.
+async fn dashboard_page(
+    extract::State(state): extract::State<Arc<AppState>>,
+    extract::Extension(session): extract::Extension<Arc<Session>>,
+    extract::Path(taskset_id): extract::Path<String>,
+    extract::Query(analysis): extract::Query<AnalysisQuery>,
+    extract::Query(query): extract::Query<SimulationQuery>,
+    extract::Query(theme): extract::Query<ThemeQuery>,
+) -> impl IntoResponse {
+    let theme = match requested_theme(&state, &theme) {
+        Ok(theme) => theme,
+        Err(response) => return response,
+    };
+    let (tasks, simulation) = match simulate_stored(&session, &taskset_id, &query) {
+        Ok(simulated) => simulated,
+        Err(response) => return response,
+    };
+    let config = analysis.config();
+    let previous = session.previous_task_set(&taskset_id).map(|previous| session.analyze(&previous, &config));
+    let previous = previous.as_deref().map(Vec::as_slice);
+    HtmlTemplate(DashboardTemplate::new(&taskset_id, &tasks, &simulation, &config, previous, &theme))
+        .into_response()
+}
+
+// Shares a copy of a stored task set through a read-only token for reviewers and an owner token for edits.
+#[utoipa::path(
+    post,
+    path = "/api/{taskset_id}/share",
+    params(("taskset_id" = String, Path, description = "Stored task set")),
+    responses(
+        (status = 201, description = "Tokens of the shared copy", body = ShareTokens),
+        (status = 404, description = "Unknown task set")
+    )
+)]
+async fn share_task_set(
+    extract::State(state): extract::State<Arc<AppState>>,
+    extract::Extension(session): extract::Extension<Arc<Session>>,
+    extract::Path(taskset_id): extract::Path<String>,
+) -> impl IntoResponse {
+    let Some(tasks) = session.task_set(&taskset_id) else {
+        return (StatusCode::NOT_FOUND, "Task Set Not Found").into_response();
+    };
+    (StatusCode::CREATED, Json(state.shares.share(&taskset_id, tasks))).into_response()
+}
+
+// Dashboard of a shared task set, for anyone with one of its tokens.
+async fn shared_dashboard(
+    extract::State(state): extract::State<Arc<AppState>>,
+    extract::Path(token): extract::Path<String>,
+    extract::Query(analysis): extract::Query<AnalysisQuery>,
+    extract::Query(query): extract::Query<SimulationQuery>,
+    extract::Query(theme): extract::Query<ThemeQuery>,
+) -> impl IntoResponse {
+    let theme = match requested_theme(&state, &theme) {
+        Ok(theme) => theme,
+        Err(response) => return response,
+    };
+    let Some((_, taskset_id, tasks)) = state.shares.get(&token) else {
+        return (StatusCode::NOT_FOUND, "Share Not Found").into_response();
+    };
+    let config = analysis.config();
+    let previous: Option<Vec<TaskAnalysis>> = state
+        .shares
+        .previous(&token)
+        .map(|previous| srp_analyze(&previous, &config).iter().map(TaskAnalysis::from).collect());
+    let simulation = simulate_requested(&tasks, &query);
+    let mut dashboard =
+        DashboardTemplate::new(&taskset_id, &tasks, &simulation, &config, previous.as_deref(), &theme);
+    dashboard.shared = true;
+    HtmlTemplate(dashboard).into_response()
+}
+
+// Replaces a shared task set, only with its owner token.
+#[utoipa::path(
+    put,
+    path = "/share/{token}",
+    params(("token" = String, Path, description = "Owner token of the shared task set")),
+    request_body(content = [Task], description = "New version of the task set"),
+    responses(
+        (status = 204, description = "Replaced, read tokens show the new version"),
+        (status = 400, description = "Invalid task set"),
+        (status = 403, description = "Read-only token"),
+        (status = 404, description = "Unknown token")
+    )
+)]
+async fn replace_shared(
+    extract::State(state): extract::State<Arc<AppState>>,
+    extract::Path(token): extract::Path<String>,
+    Json(tasks): Json<Tasks>,
+) -> impl IntoResponse {
+    match state.shares.get(&token) {
+        None => return (StatusCode::NOT_FOUND, "Share Not Found").into_response(),
+        Some((ShareAccess::Read, ..)) => return (StatusCode::FORBIDDEN, "Read-Only Share Link").into_response(),
+        Some((ShareAccess::Owner, ..)) => {}
+    }
+    if let Err(e) = validate_task_set(&tasks).and_then(|_| state.shares.replace(&token, tasks)) {
+        return (StatusCode::BAD_REQUEST, e).into_response();
+    }
+    StatusCode::NO_CONTENT.into_response()
+}
+
+// Metrics and nested critical sections of a single task of a stored task set.
+async fn task_page(
+    extract::State(state): extract::State<Arc<AppState>>,
+    extract::Extension(session): extract::Extension<Arc<Session>>,
+    extract::Path((taskset_id, task_id)): extract::Path<(String, String)>,
+    extract::Query(analysis): extract::Query<AnalysisQuery>,
+    extract::Query(theme): extract::Query<ThemeQuery>,
+) -> impl IntoResponse {
+    let theme = match requested_theme(&state, &theme) {
+        Ok(theme) => theme,
+        Err(response) => return response,
+    };
+    let Some(tasks) = session.task_set(&taskset_id) else {
+        return (StatusCode::NOT_FOUND, "Task Set Not Found").into_response();
+    };
+    let Some(index) = tasks.iter().position(|t| t.id == task_id) else {
+        return (StatusCode::NOT_FOUND, "Task Not Found").into_response();
+    };
+    let config = analysis.config();
+    let results = session.analyze(&tasks, &config);
+    // Show how a reported deadline miss comes about
+    let scenario = (!results[index].meets_deadline()).then(|| {
+        let horizon = tasks[index].deadline.saturating_mul(2).min(MAX_SIMULATION_HORIZON);
+        critical_instant(&tasks, index, horizon)
+    });
+    HtmlTemplate(TaskDetailTemplate::new(
+        &taskset_id,
+        &tasks,
+        index,
+        &results[index],
+        scenario.as_ref(),
+        &config,
+        &theme,
+    ))
+    .into_response()
+}
+
+// Resources held by a task over its trace as bands, to check the start and end of every section at a glance.
+async fn task_resources_page(
+    extract::State(state): extract::State<Arc<AppState>>,
+    extract::Extension(session): extract::Extension<Arc<Session>>,
+    extract::Path((taskset_id, task_id)): extract::Path<(String, String)>,
+    extract::Query(analysis): extract::Query<AnalysisQuery>,
+    extract::Query(theme): extract::Query<ThemeQuery>,
+    extract::Query(size): extract::Query<ChartQuery>,
+) -> impl IntoResponse {
+    let theme = match requested_theme(&state, &theme) {
+        Ok(theme) => theme,
+        Err(response) => return response,
+    };
+    let Some(task) = session.task_set(&taskset_id).and_then(|tasks| tasks.into_iter().find(|t| t.id == task_id)) else {
+        return (StatusCode::NOT_FOUND, "Task Not Found").into_response();
+    };
+    let chart = charts::resource_timeline_chart(&task, analysis.config().clock, &theme);
+    chart_page(format!("Resource usage - {task_id}"), &chart, &size)
+}
+
+// Iterates of the response time recurrence of every task of a stored task set, see `response_time_convergence`.
+#[utoipa::path(
+    get,
+    path = "/api/{taskset_id}/convergence",
+    params(("taskset_id" = String, Path, description = "Stored task set"), AnalysisQuery),
+    responses(
+        (status = 200, description = "R^n of every task until it converges or exceeds 2 * D(t)", body = [Convergence]),
+        (status = 404, description = "Unknown task set")
+    )
+)]
+async fn convergence_json(
+    extract::Extension(session): extract::Extension<Arc<Session>>,
+    extract::Path(taskset_id): extract::Path<String>,
+    extract::Query(query): extract::Query<AnalysisQuery>,
+) -> impl IntoResponse {
+    let Some(tasks) = session.task_set(&taskset_id) else {
+        return (StatusCode::NOT_FOUND, "Task Set Not Found").into_response();
+    };
+    Json(response_time_convergence(&tasks, &query.config())).into_response()
+}
+
+// Chart of the response time iterates of a task, to see why a borderline task does or does not converge in time.
+async fn convergence_page(
+    extract::State(state): extract::State<Arc<AppState>>,
+    extract::Extension(session): extract::Extension<Arc<Session>>,
+    extract::Path((taskset_id, task_id)): extract::Path<(String, String)>,
+    extract::Query(query): extract::Query<AnalysisQuery>,
+    extract::Query(theme): extract::Query<ThemeQuery>,
+    extract::Query(size): extract::Query<ChartQuery>,
+) -> impl IntoResponse {
+    let theme = match requested_theme(&state, &theme) {
+        Ok(theme) => theme,
+        Err(response) => return response,
+    };
+    let Some(tasks) = session.task_set(&taskset_id) else {
+        return (StatusCode::NOT_FOUND, "Task Set Not Found").into_response();
+    };
+    let convergences = response_time_convergence(&tasks, &query.config());
+    let Some(convergence) = convergences.iter().find(|c| c.task == task_id) else {
+        return (StatusCode::NOT_FOUND, "Task Not Found").into_response();
+    };
+    chart_page(format!("Convergence - {task_id}"), &charts::convergence_chart(convergence, &theme), &size)
+}
+
+// Table and chart of the resource ceilings of a stored task set.
+async fn ceilings_page(
+    extract::State(state): extract::State<Arc<AppState>>,
+    extract::Extension(session): extract::Extension<Arc<Session>>,
+    extract::Path(taskset_id): extract::Path<String>,
+    extract::Query(theme): extract::Query<ThemeQuery>,
+    extract::Query(size): extract::Query<ChartQuery>,
+) -> impl IntoResponse {
+    let Some(tasks) = session.task_set(&taskset_id) else {
+        return (StatusCode::NOT_FOUND, "Task Set Not Found").into_response();
+    };
+    let theme = match requested_theme(&state, &theme) {
+        Ok(theme) => theme,
+        Err(response) => return response,
+    };
+    let ceilings = resource_ceilings(&tasks);
+    let chart = charts::ceiling_chart(&ceilings, &theme).to_string();
+    HtmlTemplate(CeilingsTemplate { taskset_id, ceilings, chart, size: size.size(900, 500) }).into_response()
+}
+
+#[derive(Template)]
+#[template(path = "ceilings.html")]
+struct CeilingsTemplate {
+    taskset_id: String,
+    ceilings: Vec<ResourceCeiling>,
+    size: ChartSize,
+    chart: String,
+}
+
+// Form-based editor of a task set, an unknown id starts a new, empty task set.
+async fn editor_page(
+    extract::Extension(session): extract::Extension<Arc<Session>>,
+    extract::Path(taskset_id): extract::Path<String>,
+) -> impl IntoResponse {
+    let tasks = session.task_set(&taskset_id).unwrap_or_default();
+    HtmlTemplate(EditorTemplate::new(&taskset_id, &tasks))
+}
+
+// Applies an edit to the task set, creating it if needed, and sends the browser back to the editor.
+fn edit_task_set<F>(session: &Session, taskset_id: &str, edit: F) -> Response
+where
+    F: FnOnce(&mut Tasks) -> Result<(), String>,
+{
+    let mut tasks = session.task_set(taskset_id).unwrap_or_default();
+    if let Err(e) = edit(&mut tasks) {
+        return (StatusCode::BAD_REQUEST, e).into_response();
+    }
+    session.insert_task_set(taskset_id, tasks);
+    Redirect::to(&format!("/editor/{}", taskset_id)).into_response()
+}
+
+async fn editor_save_task(
+    extract::Extension(session): extract::Extension<Arc<Session>>,
+    extract::Path(taskset_id): extract::Path<String>,
+    Form(form): Form<TaskForm>,
+) -> impl IntoResponse {
+    edit_task_set(&session, &taskset_id, |tasks| form.apply(tasks))
+}
+
+async fn editor_remove_task(
+    extract::Extension(session): extract::Extension<Arc<Session>>,
+    extract::Path((taskset_id, task)): extract::Path<(String, String)>,
+) -> impl IntoResponse {
+    edit_task_set(&session, &taskset_id, |tasks| remove_task(tasks, &task))
+}
+
+async fn editor_add_section(
+    extract::Extension(session): extract::Extension<Arc<Session>>,
+    extract::Path(taskset_id): extract::Path<String>,
+    Form(form): Form<SectionForm>,
+) -> impl IntoResponse {
+    edit_task_set(&session, &taskset_id, |tasks| form.apply(tasks))
+}
+
+async fn editor_remove_section(
+    extract::Extension(session): extract::Extension<Arc<Session>>,
+    extract::Path(taskset_id): extract::Path<String>,
+    Form(section): Form<SectionRef>,
+) -> impl IntoResponse {
+    edit_task_set(&session, &taskset_id, |tasks| section.remove(tasks))
+}
+
+// Response time chart option of a stored task set, as sent to live clients.
+fn live_chart_event(session: &Session, taskset_id: &str, theme: &Theme) -> Option<Event> {
+    let tasks = session.task_set(taskset_id)?;
+    let config = AnalysisConfig::default();
+    let previous = session.previous_task_set(taskset_id).map(|previous| session.analyze(&previous, &config));
+    let previous = previous.as_deref().map(Vec::as_slice);
+    let chart = charts::response_time_chart(&srp_analyze(&tasks, &config), previous, None, theme);
+    Some(Event::default().event("chart").data(chart.to_string()))
+}
+
+async fn live_page(
+    extract::Path(taskset_id): extract::Path<String>,
+    extract::Query(size): extract::Query<ChartQuery>,
+) -> impl IntoResponse {
+    HtmlTemplate(LiveTemplate { taskset_id, size: size.size(1000, 600) })
+}
+
+// Sends the current chart of the task set, followed by a new one every time the task set is replaced.
+async fn live_events(
+    extract::State(state): extract::State<Arc<AppState>>,
+    extract::Extension(session): extract::Extension<Arc<Session>>,
+    extract::Path(taskset_id): extract::Path<String>,
+    extract::Query(theme): extract::Query<ThemeQuery>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, Response> {
+    let theme = requested_theme(&state, &theme)?;
+    let current = live_chart_event(&session, &taskset_id, &theme);
+    let updates = session
+        .updates()
+        .filter_map(move |id| if id == taskset_id { live_chart_event(&session, &id, &theme) } else { None });
+
+    let stream = tokio_stream::iter(current).chain(updates).map(Ok);
+    Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
+}
+
+#[derive(Template)]
+#[template(path = "live.html")]
+struct LiveTemplate {
+    taskset_id: String,
+    size: ChartSize,
+}
+
+// Execution time statistics of the jobs of devices in the field, only with the `mqtt` feature
+#[cfg(feature = "mqtt")]
+fn field_routes() -> Router<Arc<AppState>> {
+    Router::new().route("/api/:taskset_id/field", get(field_stats))
+}
+
+#[cfg(not(feature = "mqtt"))]
+fn field_routes() -> Router<Arc<AppState>> {
+    Router::new()
+}
+
+#[cfg(feature = "mqtt")]
+async fn field_stats(
+    extract::State(state): extract::State<Arc<AppState>>,
+    extract::Path(taskset_id): extract::Path<String>,
+) -> impl IntoResponse {
+    match state.field.stats(&taskset_id) {
+        Some(stats) => Json(stats).into_response(),
+        None => (StatusCode::NOT_FOUND, "No Jobs From The Field").into_response(),
+    }
+}
+
+// Versioned API with a stable wire format, see `server::TaskV1`. The unversioned /api routes may change with the
+// analysis, these only change with a new version.
+fn api_v1_routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/schema", get(v1_schema))
+        .route("/tasksets", get(v1_task_set_ids))
+        .route("/tasksets/:taskset_id", get(v1_task_set).put(v1_replace_task_set))
+        .route("/tasksets/:taskset_id/analysis", get(v1_analysis))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/schema",
+    responses((status = 200, description = "OpenAPI description of the v1 endpoints and their wire format"))
+)]
+async fn v1_schema() -> impl IntoResponse {
+    Json(ApiV1Doc::openapi())
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/tasksets",
+    responses((status = 200, description = "Ids of the task sets of the session", body = [String]))
+)]
+async fn v1_task_set_ids(extract::Extension(session): extract::Extension<Arc<Session>>) -> impl IntoResponse {
+    Json(session.task_set_ids())
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/tasksets/{taskset_id}",
+    params(("taskset_id" = String, Path, description = "Stored task set")),
+    responses(
+        (status = 200, description = "Task set", body = [TaskV1]),
+        (status = 404, description = "Unknown task set")
+    )
+)]
+async fn v1_task_set(
+    extract::Extension(session): extract::Extension<Arc<Session>>,
+    extract::Path(taskset_id): extract::Path<String>,
+) -> impl IntoResponse {
+    match session.task_set(&taskset_id) {
+        Some(tasks) => Json(tasks.iter().map(TaskV1::from).collect::<Vec<_>>()).into_response(),
+        None => (StatusCode::NOT_FOUND, "Task Set Not Found").into_response(),
+    }
+}
+
+// Stores a task set in the session under the id, replacing a task set stored under it before.
+#[utoipa::path(
+    put,
+    path = "/api/v1/tasksets/{taskset_id}",
+    params(("taskset_id" = String, Path, description = "Id to store the task set under")),
+    request_body(content = [TaskV1], description = "Task set, unknown fields are rejected"),
+    responses(
+        (status = 204, description = "Stored"),
+        (status = 400, description = "Invalid task set"),
+        (status = 422, description = "Malformed task set, e.g. an unknown field")
+    )
+)]
+async fn v1_replace_task_set(
+    extract::Extension(session): extract::Extension<Arc<Session>>,
+    extract::Path(taskset_id): extract::Path<String>,
+    Json(tasks): Json<Vec<TaskV1>>,
+) -> impl IntoResponse {
+    match tasks_from_v1(tasks) {
+        Ok(tasks) => {
+            session.insert_task_set(&taskset_id, tasks);
+            StatusCode::NO_CONTENT.into_response()
+        }
+        Err(e) => (StatusCode::BAD_REQUEST, e).into_response(),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/tasksets/{taskset_id}/analysis",
+    params(("taskset_id" = String, Path, description = "Stored task set"), AnalysisQuery),
+    responses(
+        (status = 200, description = "Analysis of every task of the task set", body = [TaskAnalysisV1]),
+        (status = 404, description = "Unknown task set")
+    )
+)]
+async fn v1_analysis(
+    extract::Extension(session): extract::Extension<Arc<Session>>,
+    extract::Path(taskset_id): extract::Path<String>,
+    extract::Query(query): extract::Query<AnalysisQuery>,
+) -> impl IntoResponse {
+    let Some(tasks) = session.task_set(&taskset_id) else {
+        return (StatusCode::NOT_FOUND, "Task Set Not Found").into_response();
+    };
+    let results = session.analyze(&tasks, &query.config());
+    Json(results.iter().map(TaskAnalysisV1::from).collect::<Vec<_>>()).into_response()
+}
+
+// Description of the v1 endpoints alone, served under /api/v1/schema for consumers of the wire format
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "SRP analysis API", version = "1"),
+    paths(v1_schema, v1_task_set_ids, v1_task_set, v1_replace_task_set, v1_analysis),
+    components(schemas(
+        TaskV1, TraceV1, TaskAnalysisV1, ArrivalModel, TaskKind, Criticality, LockType,
+        WeaklyHard, PreemptionMode, DeadlineModel, JitterModel, OffsetModel, BlockingRefinement
+    ))
+)]
+struct ApiV1Doc;
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        chart_catalog, chart_json, analysis_json, cache_stats, chart_cache_stats, example_catalog, example_task_set, generate_task_set, what_if, ceilings_json, stack_json, chains_json, hierarchical_json, check_task_traces, import_rtic, import_research, import_decimal, import_relative,
+        export_cheddar, export_archive, import_archive, simulation_json, taskset_progress, simulation_step, assign_priorities, priority_normalization, normalize_priorities, wcet_budgets, scale_task_set, balance_cores_json, plugin_catalog, run_plugin, shedding, sweep, fuzz, start_job, job_status, weakly_hard, wcet_check, convergence_json, share_task_set, replace_shared,
+        render_analysis, view, results_csv, add_measurements, merge_task_runs, ingest_trace, observed_json, inversions_json, scenario_json, explain_json, certificate_json, verify_certificate_json, offsets_json, sensitivity_json, refactorings_json,
+        v1_schema, v1_task_set_ids, v1_task_set, v1_replace_task_set, v1_analysis
+    ),
+    components(schemas(
+        ChartInfo, ChartInput, Example, GeneratedTaskSet, Task, TaskKind, Criticality, Trace, LockType, ArrivalModel, PreemptionMode, DeadlineModel, JitterModel, OffsetModel, BlockingRefinement, AnalysisConfig, ClockModel, TaskAnalysis, RealTimeAnalysis, WhatIfResponse, PriorityAssignmentResponse, PriorityNormalization, PriorityLevel, CoreBalanceRequest, CoreBalancing, CoreLoad, CoreMigration,
+        WcetBounds, WcetBudget, WcetConsistency, WcetDiscrepancy, SheddingPlan, SweepPoint, ArchiveImport, ResourceCeiling, StackAnalysis, StackLevel, Chain, ChainLatency,
+        Convergence, Explanation, TraceIssue, TraceIssueKind, SchedulabilityCertificate, TaskCertificate, OffsetBound, TaskSensitivity, Refactoring, RefactoringSuggestion, TraceNormalization, RebasedTrace, MergedActivations, ShareAccess, ShareTokens, Simulation, ExecutionSlice, CeilingStep, SimulatedJob, SimulationStep, JobEvent, JobEventKind, Scenario, WcetStatistic, ExecutionTimeStats,
+        TraceEnvelope, SectionDominance, TraceEvent, TraceEventKind, TraceIngest, ObservedResponseTime, PriorityInversion,
+        ServerKind, Server, HierarchicalTaskSet, HierarchicalAnalysis, PluginReport,
+        WeaklyHard, WeaklyHardCheck, FuzzReport, ParameterFragility, JobRequest,
+        CacheStats, ResultColumn, SortOrder, Job, JobStatus,
+        TaskV1, TraceV1, TaskAnalysisV1
+    ))
+)]
+struct ApiDoc;
+
+async fn openapi_json() -> impl IntoResponse {
+    Json(ApiDoc::openapi())
+}
+
+// Swagger UI for the OpenAPI description
+async fn docs() -> impl IntoResponse {
+    HtmlTemplate(DocsTemplate {})
+}
+
+#[derive(Template)]
+#[template(path = "docs.html")]
+struct DocsTemplate {}
+
+#[derive(Template)]
+#[template(path = "analysis.html")]
+struct AnalysisTemplate {
+    /// Id of the analyzed task set as a JSON string, `null` for the example task set
+    taskset_id: String,
+}
+
+#[derive(Template)]
+#[template(path = "index.html")]
+struct IndexTemplate {
+    collections: Vec<(String, BTreeMap<String, ChartInfo>)>,
+    /// Stored task sets of the session, offered by the view form
+    task_sets: Vec<String>,
+    themes: Vec<String>,
+    locale: Locale,
+}
+
+impl IndexTemplate {
+    fn new(locale: Locale) -> Self {
+        Self {
+            collections: vec![],
+            task_sets: vec![],
+            themes: vec![],
+            locale,
+        }
+    }
+
+    fn analysis_charts(&self) -> &'static [&'static str] {
+        &ANALYSIS_CHARTS
+    }
+
+    fn collection(&mut self, name: &str, charts: BTreeMap<String, ChartInfo>) {
+        self.collections.push((name.to_string(), charts));
+    }
+}
+
+struct HtmlTemplate<T>(T);
+
+impl<T> IntoResponse for HtmlTemplate<T>
+where
+    T: Template,
+{
+    fn into_response(self) -> Response {
+        match self.0.render() {
+            Ok(body) => Html(body).into_response(),
+            Err(e) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Template error: {}", e),
+            )
+                .into_response(),
+        }
+    }
+}
@@ -0,0 +1,403 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+use axum::{
+    extract::State,
+    http::{header, HeaderMap, HeaderValue, Method, Request},
+    middleware::Next,
+    response::Response,
+};
+use tokio::sync::broadcast;
+use tokio_stream::{wrappers::BroadcastStream, Stream, StreamExt};
+
+use crate::srp_analysis::*;
+use super::{
+    load_task_sets, record_analysis, session_namespace, AnalysisCache, AppState, ChartCache, JobTable, StoreWriter,
+    DEFAULT_CACHE_CAPACITY, DEFAULT_CHART_CACHE_CAPACITY,
+};
+
+/// Cookie carrying the session token of a browser.
+pub const SESSION_COOKIE: &str = "session";
+
+/// Header carrying the session token of API clients, and announcing the token of a new session.
+pub const SESSION_HEADER: &str = "x-session-token";
+
+/// Sessions idle for longer are discarded, unless configured otherwise.
+pub const DEFAULT_SESSION_TIMEOUT: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Sessions kept at most, a new session beyond them discards the one idle for the longest.
+pub const DEFAULT_MAX_SESSIONS: usize = 10_000;
+
+/// Task sets by id in memory, notifying subscribers of every insert or replacement, and writing every insert
+/// through to a `TaskSetStore` if persisted.
+pub struct TaskSetCache {
+    // Shared with the store, see `MemoryStore`
+    task_sets: RwLock<BTreeMap<String, Arc<Tasks>>>,
+    // Versions replaced by the latest insert
+    previous: RwLock<BTreeMap<String, Arc<Tasks>>>,
+    updates: broadcast::Sender<String>,
+    // Namespace of the task sets in the store
+    persisted: Option<(String, StoreWriter)>,
+}
+
+impl Default for TaskSetCache {
+    fn default() -> Self {
+        Self {
+            task_sets: RwLock::new(BTreeMap::new()),
+            previous: RwLock::new(BTreeMap::new()),
+            updates: broadcast::channel(16).0,
+            persisted: None,
+        }
+    }
+}
+
+impl TaskSetCache {
+    /// Cache of the task sets stored under `namespace`, starting with those loaded from the store, see
+    /// `load_task_sets`.
+    pub fn persisted(namespace: String, writer: StoreWriter, stored: Vec<(String, Arc<Tasks>)>) -> Self {
+        Self {
+            task_sets: RwLock::new(stored.into_iter().collect()),
+            persisted: Some((namespace, writer)),
+            ..Self::default()
+        }
+    }
+
+    /// Clone of the task set with the given id.
+    pub fn get(&self, id: &str) -> Option<Tasks> {
+        self.task_sets.read().unwrap().get(id).map(|tasks| tasks.as_ref().clone())
+    }
+
+    /// Ids of all stored task sets, in order.
+    pub fn ids(&self) -> Vec<String> {
+        self.task_sets.read().unwrap().keys().cloned().collect()
+    }
+
+    /// Version of the task set with the given id before it was last replaced.
+    pub fn previous(&self, id: &str) -> Option<Tasks> {
+        self.previous.read().unwrap().get(id).map(|tasks| tasks.as_ref().clone())
+    }
+
+    /// Stores the task set under `id`, replacing any earlier version, and notifies subscribers.
+    pub fn insert(&self, id: &str, tasks: Tasks) {
+        let tasks = Arc::new(tasks);
+        if let Some((namespace, writer)) = &self.persisted {
+            writer.put(namespace, id, &tasks);
+        }
+        if let Some(replaced) = self.task_sets.write().unwrap().insert(id.to_string(), tasks) {
+            self.previous.write().unwrap().insert(id.to_string(), replaced);
+        }
+        // Sending only fails when nobody is subscribed
+        let _ = self.updates.send(id.to_string());
+    }
+
+    /// Drops the task set with the given id together with its previous version.
+    pub fn remove(&self, id: &str) {
+        if let Some((namespace, writer)) = &self.persisted {
+            writer.delete(namespace, id);
+        }
+        self.task_sets.write().unwrap().remove(id);
+        self.previous.write().unwrap().remove(id);
+    }
+
+    /// Ids of task sets as they are inserted or replaced.
+    pub fn subscribe(&self) -> impl Stream<Item = String> {
+        BroadcastStream::new(self.updates.subscribe()).filter_map(|updated| updated.ok())
+    }
+}
+
+/// Task sets, measurements and analysis results of one user, so users of a shared server do not overwrite each
+/// other's data.
+///
+/// Task sets stored by the server itself, e.g. the example or a watched file, are visible in every session
+/// until the session stores its own version under the same id.
+///
+/// The session's own task sets are persisted under its `session_namespace`, so a server restarted with a
+/// database backend, or another server sharing the database, restores them on the next request with its token.
+pub struct Session {
+    pub id: String,
+    task_sets: TaskSetCache,
+    shared: Arc<TaskSetCache>,
+    /// Measured execution times by task set id
+    pub measurements: RwLock<BTreeMap<String, Measurements>>,
+    /// Jobs still open in the events ingested from devices, by task set id
+    pub recorders: Mutex<BTreeMap<String, TraceRecorder>>,
+    /// Priority inversions in the events ingested from devices, by task set id
+    pub inversions: Mutex<BTreeMap<String, InversionDetector>>,
+    /// Simulations stepped through by the user, by task set id
+    pub simulators: Mutex<BTreeMap<String, Simulator>>,
+    // Envelope of all ingested jobs of each task, by task set id
+    recorded: RwLock<BTreeMap<String, BTreeMap<String, Trace>>>,
+    // Largest response time of the ingested jobs of each task, by task set id
+    observed: RwLock<BTreeMap<String, BTreeMap<String, u32>>>,
+    pub cache: Mutex<AnalysisCache>,
+    /// Chart pages rendered from the task sets of the session
+    pub charts: Mutex<ChartCache>,
+    /// Background jobs started by the user, see `JobTable`
+    pub jobs: JobTable,
+    last_access: Mutex<Instant>,
+}
+
+impl Session {
+    fn new(id: String, shared: Arc<TaskSetCache>, writer: StoreWriter, stored: Vec<(String, Arc<Tasks>)>) -> Self {
+        Self {
+            task_sets: TaskSetCache::persisted(session_namespace(&id), writer, stored),
+            id,
+            shared,
+            measurements: RwLock::new(BTreeMap::new()),
+            recorders: Mutex::new(BTreeMap::new()),
+            inversions: Mutex::new(BTreeMap::new()),
+            simulators: Mutex::new(BTreeMap::new()),
+            recorded: RwLock::new(BTreeMap::new()),
+            observed: RwLock::new(BTreeMap::new()),
+            cache: Mutex::new(AnalysisCache::new(DEFAULT_CACHE_CAPACITY)),
+            charts: Mutex::new(ChartCache::new(DEFAULT_CHART_CACHE_CAPACITY)),
+            jobs: JobTable::new(),
+            last_access: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Clone of the task set with the given id, the session's own version if it has one.
+    pub fn task_set(&self, id: &str) -> Option<Tasks> {
+        self.task_sets.get(id).or_else(|| self.shared.get(id))
+    }
+
+    /// Version of the task set with the given id before its last replacement, e.g. before the last edit.
+    ///
+    /// The first version a session stores replaces the shared version, if any.
+    pub fn previous_task_set(&self, id: &str) -> Option<Tasks> {
+        if self.task_sets.get(id).is_some() {
+            self.task_sets.previous(id).or_else(|| self.shared.get(id))
+        } else {
+            self.shared.previous(id)
+        }
+    }
+
+    /// Ids of all task sets visible in this session, in order.
+    pub fn task_set_ids(&self) -> Vec<String> {
+        let mut ids = self.task_sets.ids();
+        ids.extend(self.shared.ids());
+        ids.sort();
+        ids.dedup();
+        ids
+    }
+
+    /// Stores the task set in this session only, drops its chart pages, and notifies its subscribers.
+    pub fn insert_task_set(&self, id: &str, tasks: Tasks) {
+        self.task_sets.insert(id, tasks);
+        self.invalidate_charts(id);
+    }
+
+    /// Drops the rendered chart pages of the task set, e.g. when it is replaced.
+    pub fn invalidate_charts(&self, id: &str) {
+        self.charts.lock().unwrap().retain(|key| key.task_set.as_deref() != Some(id));
+    }
+
+    /// Ids of task sets visible in this session as they are inserted or replaced.
+    pub fn updates(&self) -> impl Stream<Item = String> {
+        self.task_sets.subscribe().merge(self.shared.subscribe())
+    }
+
+    /// Adds measured execution times to those already recorded for the task set and returns all of them.
+    pub fn add_measurements(&self, id: &str, measurements: Measurements) -> Measurements {
+        let mut stored = self.measurements.write().unwrap();
+        let recorded = stored.entry(id.to_string()).or_default();
+        for (task, samples) in measurements {
+            recorded.entry(task).or_default().extend(samples);
+        }
+        recorded.clone()
+    }
+
+    /// Merges a job ingested from a device into the envelope of the earlier jobs of its task, see `merge_runs`,
+    /// records its execution time as a measurement and its response time as an observation, and returns the new
+    /// envelope.
+    pub fn record_job(&self, id: &str, job: RecordedJob) -> Result<Trace, String> {
+        let mut stored = self.recorded.write().unwrap();
+        let recorded = stored.entry(id.to_string()).or_default();
+        let envelope = match recorded.get(&job.task) {
+            Some(envelope) => {
+                merge_runs(&job.task, &[envelope.clone(), job.trace.clone()])
+                    .map_err(|_| format!("Error: Job of task '{}' has other sections than its earlier jobs.", job.task))?
+                    .trace
+            }
+            None => job.trace.clone(),
+        };
+        recorded.insert(job.task.clone(), envelope.clone());
+        let mut observed = self.observed.write().unwrap();
+        let max = observed.entry(id.to_string()).or_default().entry(job.task.clone()).or_default();
+        *max = job.response_time.max(*max);
+        self.add_measurements(id, Measurements::from([(job.task, vec![job.trace.end - job.trace.start])]));
+        Ok(envelope)
+    }
+
+    /// Largest response time of the jobs of each task ingested from devices, see `compare_observed`.
+    pub fn observed_response_times(&self, id: &str) -> BTreeMap<String, u32> {
+        self.observed.read().unwrap().get(id).cloned().unwrap_or_default()
+    }
+
+    /// Runs `srp_analyze` on the task set, or returns the cached results of an identical earlier run.
+    pub fn analyze(&self, tasks: &Tasks, config: &AnalysisConfig) -> Arc<Vec<TaskAnalysis>> {
+        let key = super::task_set_key(tasks, config);
+        if let Some(results) = self.cache.lock().unwrap().get(&key) {
+            return results;
+        }
+
+        // The lock is not held while analyzing, so other requests are not blocked by a long analysis
+        let started = Instant::now();
+        let results: Arc<Vec<TaskAnalysis>> = Arc::new(
+            srp_analyze_with_progress(tasks, config, |_| {})
+                .iter()
+                .map(|result| TaskAnalysis::from(result).with_clock(config.clock))
+                .collect(),
+        );
+        record_analysis("full", started);
+        self.cache.lock().unwrap().insert(key, results.clone());
+        results
+    }
+
+    /// Like `analyze`, but only recomputes the tasks of `tasks` affected by its differences to `base`,
+    /// reusing the (cached) analysis of `base` for the rest, see `srp_reanalyze`.
+    pub fn analyze_delta(&self, base: &Tasks, tasks: &Tasks, config: &AnalysisConfig) -> Arc<Vec<TaskAnalysis>> {
+        let key = super::task_set_key(tasks, config);
+        if let Some(results) = self.cache.lock().unwrap().get(&key) {
+            return results;
+        }
+
+        let previous = self.analyze(base, config);
+        let started = Instant::now();
+        let results = Arc::new(srp_reanalyze(base, &previous, tasks, config));
+        record_analysis("delta", started);
+        self.cache.lock().unwrap().insert(key, results.clone());
+        results
+    }
+}
+
+/// Sessions by token, discarding sessions idle for longer than the timeout, or idle for the longest once there
+/// are `capacity` of them, along with their stored task sets.
+pub struct Sessions {
+    sessions: RwLock<HashMap<String, Arc<Session>>>,
+    timeout: Duration,
+    capacity: usize,
+    writer: StoreWriter,
+}
+
+impl Sessions {
+    pub fn new(timeout: Duration, capacity: usize, writer: StoreWriter) -> Self {
+        Self { sessions: RwLock::new(HashMap::new()), timeout, capacity, writer }
+    }
+
+    /// Number of sessions kept, expired or not.
+    pub fn count(&self) -> usize {
+        self.sessions.read().unwrap().len()
+    }
+
+    /// Drops the chart pages of the task set in every session, e.g. when its shared version is replaced.
+    pub fn invalidate_charts(&self, id: &str) {
+        for session in self.sessions.read().unwrap().values() {
+            session.invalidate_charts(id);
+        }
+    }
+
+    /// Whether the token is of a session of this server, expired or not.
+    pub fn contains(&self, token: &str) -> bool {
+        self.sessions.read().unwrap().contains_key(token)
+    }
+
+    /// Session of the token, or a new session if the token is missing, unknown or expired.
+    /// The flag is set for new sessions, whose token has to be sent to the client.
+    ///
+    /// An unknown token with task sets `stored` under it is restored as a session with these task sets. Unless
+    /// `create` is set or there is something to restore, the new session is not kept and its flag not set, so
+    /// requests that only read, e.g. metrics scrapes, do not fill up the sessions.
+    pub fn get_or_create(
+        &self,
+        token: Option<&str>,
+        stored: Vec<(String, Arc<Tasks>)>,
+        shared: &Arc<TaskSetCache>,
+        create: bool,
+    ) -> (Arc<Session>, bool) {
+        let now = Instant::now();
+        if let Some(session) = token.and_then(|token| self.sessions.read().unwrap().get(token).cloned()) {
+            let mut last_access = session.last_access.lock().unwrap();
+            if now.duration_since(*last_access) <= self.timeout {
+                *last_access = now;
+                drop(last_access);
+                return (session, false);
+            }
+        }
+
+        if !create && stored.is_empty() {
+            let id = uuid::Uuid::new_v4().to_string();
+            return (Arc::new(Session::new(id, shared.clone(), self.writer.clone(), stored)), false);
+        }
+
+        let mut sessions = self.sessions.write().unwrap();
+        sessions.retain(|id, session| {
+            let expired = now.duration_since(*session.last_access.lock().unwrap()) > self.timeout;
+            if expired {
+                self.writer.clear(&session_namespace(id));
+            }
+            !expired
+        });
+        if sessions.len() >= self.capacity {
+            let idle = sessions.iter().min_by_key(|(_, session)| *session.last_access.lock().unwrap());
+            if let Some(id) = idle.map(|(id, _)| id.clone()) {
+                self.writer.clear(&session_namespace(&id));
+                sessions.remove(&id);
+            }
+        }
+        let id = match token {
+            Some(token) if !stored.is_empty() && !sessions.contains_key(token) => token.to_string(),
+            _ => uuid::Uuid::new_v4().to_string(),
+        };
+        let session = Arc::new(Session::new(id, shared.clone(), self.writer.clone(), stored));
+        sessions.insert(session.id.clone(), session.clone());
+        (session, true)
+    }
+}
+
+// Token of the request, from the session header or else the session cookie
+fn session_token(headers: &HeaderMap) -> Option<String> {
+    if let Some(token) = headers.get(SESSION_HEADER).and_then(|value| value.to_str().ok()) {
+        return Some(token.to_string());
+    }
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|cookies| cookies.split(';'))
+        .filter_map(|cookie| cookie.trim().split_once('='))
+        .find(|(name, _)| *name == SESSION_COOKIE)
+        .map(|(_, token)| token.to_string())
+}
+
+/// Middleware resolving the session of every request, available to handlers as `Extension<Arc<Session>>`.
+///
+/// Only requests that may store something, i.e. other than GET, HEAD and OPTIONS, start a session, the others
+/// without one get a session of their own that is discarded afterwards. A new session is announced to browsers as
+/// a cookie and to API clients in the `x-session-token` header.
+pub async fn session_layer<B>(State(state): State<Arc<AppState>>, mut request: Request<B>, next: Next<B>) -> Response {
+    let token = session_token(request.headers());
+    // A token unknown to this server, e.g. after a restart, may still have task sets in the store
+    let stored = match token.as_deref() {
+        Some(token) if !state.sessions.contains(token) => {
+            load_task_sets(state.storage.as_ref(), &session_namespace(token)).await.unwrap_or_else(|e| {
+                tracing::warn!("{}", e);
+                vec![]
+            })
+        }
+        _ => vec![],
+    };
+    let writes = !matches!(*request.method(), Method::GET | Method::HEAD | Method::OPTIONS);
+    let (session, created) = state.sessions.get_or_create(token.as_deref(), stored, &state.shared, writes);
+    request.extensions_mut().insert(session.clone());
+
+    let mut response = next.run(request).await;
+    if created {
+        let cookie = format!("{}={}; Path=/; HttpOnly; SameSite=Lax", SESSION_COOKIE, session.id);
+        let headers = response.headers_mut();
+        headers.append(header::SET_COOKIE, HeaderValue::from_str(&cookie).unwrap());
+        headers.insert(SESSION_HEADER, HeaderValue::from_str(&session.id).unwrap());
+    }
+    response
+}
@@ -0,0 +1,135 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Mutex, RwLock};
+
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::srp_analysis::*;
+use super::TaskSetCache;
+
+/// Task sets shared at most, sharing another one drops the one shared first together with its tokens.
+pub const MAX_SHARES: usize = 10_000;
+
+/// What a share token allows, see `ShareTable`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ShareAccess {
+    /// Viewing the dashboards of the shared task set
+    Read,
+    /// Viewing and replacing the shared task set
+    Owner,
+}
+
+/// Tokens of a newly shared task set, as returned by `POST /api/:taskset_id/share`.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ShareTokens {
+    /// Read-only token for reviewers
+    pub read: String,
+    /// Token allowing edits, to be kept by the owner
+    pub owner: String,
+    /// Read-only page of the shared task set, `/share/<read>`
+    pub link: String,
+}
+
+// Task set a token refers to
+struct ShareToken {
+    share: String,
+    taskset_id: String,
+    access: ShareAccess,
+}
+
+/// Task sets shared outside the session they were created in, e.g. with reviewers, through unguessable tokens.
+///
+/// Sharing stores a copy of the task set that every session can see through its tokens. A read token only shows
+/// it, an owner token may also replace it, so reviewers cannot modify the model.
+pub struct ShareTable {
+    // Copies of the shared task sets by share id
+    task_sets: TaskSetCache,
+    tokens: RwLock<HashMap<String, ShareToken>>,
+    // Share ids with their read and owner tokens, in the order they were shared
+    shares: Mutex<VecDeque<(String, [String; 2])>>,
+    capacity: usize,
+}
+
+impl Default for ShareTable {
+    fn default() -> Self {
+        Self::with_capacity(MAX_SHARES)
+    }
+}
+
+impl ShareTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Table keeping at most `capacity` shared task sets.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            task_sets: TaskSetCache::default(),
+            tokens: RwLock::new(HashMap::new()),
+            shares: Mutex::new(VecDeque::new()),
+            capacity,
+        }
+    }
+
+    /// Number of shared task sets.
+    pub fn count(&self) -> usize {
+        self.shares.lock().unwrap().len()
+    }
+
+    /// Number of tokens of all shared task sets.
+    pub fn token_count(&self) -> usize {
+        self.tokens.read().unwrap().len()
+    }
+
+    /// Shares a copy of the task set under two new tokens, dropping the task sets shared first beyond the
+    /// capacity of the table.
+    pub fn share(&self, taskset_id: &str, tasks: Tasks) -> ShareTokens {
+        let share = uuid::Uuid::new_v4().to_string();
+        let mut shares = self.shares.lock().unwrap();
+        let mut tokens = self.tokens.write().unwrap();
+        while shares.len() >= self.capacity {
+            let Some((dropped, dropped_tokens)) = shares.pop_front() else { break };
+            for token in dropped_tokens {
+                tokens.remove(&token);
+            }
+            self.task_sets.remove(&dropped);
+        }
+        self.task_sets.insert(&share, tasks);
+        let read = uuid::Uuid::new_v4().to_string();
+        let owner = uuid::Uuid::new_v4().to_string();
+        shares.push_back((share.clone(), [read.clone(), owner.clone()]));
+        for (token, access) in [(&read, ShareAccess::Read), (&owner, ShareAccess::Owner)] {
+            let entry = ShareToken { share: share.clone(), taskset_id: taskset_id.to_string(), access };
+            tokens.insert(token.clone(), entry);
+        }
+        ShareTokens { link: format!("/share/{}", read), read, owner }
+    }
+
+    /// Access granted by the token, the id the task set was shared under and the shared task set, `None` for
+    /// unknown tokens.
+    pub fn get(&self, token: &str) -> Option<(ShareAccess, String, Tasks)> {
+        let tokens = self.tokens.read().unwrap();
+        let entry = tokens.get(token)?;
+        Some((entry.access, entry.taskset_id.clone(), self.task_sets.get(&entry.share)?))
+    }
+
+    /// Version of the shared task set before its last replacement.
+    pub fn previous(&self, token: &str) -> Option<Tasks> {
+        let tokens = self.tokens.read().unwrap();
+        self.task_sets.previous(&tokens.get(token)?.share)
+    }
+
+    /// Replaces the shared task set, if the token is an owner token.
+    pub fn replace(&self, token: &str, tasks: Tasks) -> Result<(), String> {
+        let tokens = self.tokens.read().unwrap();
+        match tokens.get(token) {
+            Some(entry) if entry.access == ShareAccess::Owner => {
+                self.task_sets.insert(&entry.share, tasks);
+                Ok(())
+            }
+            Some(_) => Err("Error: A read-only share token cannot modify the task set.".to_string()),
+            None => Err("Error: Unknown share token.".to_string()),
+        }
+    }
+}
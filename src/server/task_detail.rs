@@ -0,0 +1,156 @@
+use askama::Template;
+
+use crate::charts;
+use crate::srp_analysis::*;
+use super::filters;
+
+/// Critical section of a task as listed on its detail page, times in ticks followed by microseconds if there is
+/// a clock.
+pub struct SectionRow {
+    /// Indices of the section within the nested sections of the task, joined by '.', see `EditorRow::path`
+    pub path: String,
+    pub depth: usize,
+    pub resource: String,
+    pub start: String,
+    pub end: String,
+    pub duration: String,
+    /// π(r)
+    pub ceiling: u8,
+    pub non_preemptible: bool,
+    pub shared: bool,
+    /// Whether other sections are nested inside, which the page can collapse
+    pub nested: bool,
+}
+
+fn flatten(
+    trace: &Trace,
+    path: &str,
+    depth: usize,
+    context: &AnalysisContext,
+    time: &dyn Fn(u32) -> String,
+    rows: &mut Vec<SectionRow>,
+) {
+    for (i, section) in trace.inner.iter().enumerate() {
+        let path = if path.is_empty() { i.to_string() } else { format!("{}.{}", path, i) };
+        rows.push(SectionRow {
+            path: path.clone(),
+            depth,
+            resource: section.id.clone(),
+            start: time(section.start),
+            end: time(section.end),
+            duration: time(section.wcet()),
+            ceiling: context.ceiling_priority(section),
+            non_preemptible: section.non_preemptible,
+            shared: section.lock == LockType::Shared,
+            nested: !section.inner.is_empty(),
+        });
+        flatten(section, &path, depth + 1, context, time, rows);
+    }
+}
+
+/// Step of the derivation of R(t) as listed on the detail page of a task, see `Explanation`.
+pub struct ExplanationRow {
+    /// Indices of the step within the terms of the steps above it, joined by '.'
+    pub path: String,
+    pub depth: usize,
+    /// See `Explanation::summary`
+    pub summary: String,
+    pub counted: bool,
+    pub note: Option<String>,
+    /// Whether the step has terms, which the page can collapse
+    pub nested: bool,
+}
+
+fn flatten_explanation(explanation: &Explanation, path: String, depth: usize, rows: &mut Vec<ExplanationRow>) {
+    rows.push(ExplanationRow {
+        path: path.clone(),
+        depth,
+        summary: explanation.summary(),
+        counted: explanation.counted,
+        note: explanation.note.clone(),
+        nested: !explanation.terms.is_empty(),
+    });
+    for (i, term) in explanation.terms.iter().enumerate() {
+        flatten_explanation(term, format!("{}.{}", path, i), depth + 1, rows);
+    }
+}
+
+/// Metrics and nested trace of a single task of a stored task set, e.g. to find out why it misses its deadline.
+#[derive(Template)]
+#[template(path = "task.html")]
+pub struct TaskDetailTemplate {
+    pub taskset_id: String,
+    pub task: Task,
+    pub analysis: TaskAnalysis,
+    /// Times of the analysis, formatted like the section times
+    pub deadline: String,
+    pub wcet: String,
+    pub blocking_time: String,
+    /// Critical section behind B(t), see `Task::blocker`
+    pub blocker: Option<String>,
+    pub interference: String,
+    pub response_time: String,
+    pub load_factor: String,
+    /// I_h(t) of every interfering task h, see `TaskSchedulable::interference_breakdown`
+    pub interferers: Vec<(String, String)>,
+    pub sections: Vec<SectionRow>,
+    pub chart: String,
+    /// Sections as bands per resource, see `charts::resource_timeline_chart`
+    pub resources: String,
+    /// Simulated critical instant, if the task misses its deadline, see `charts::scenario_chart`
+    pub scenario: Option<String>,
+    /// Derivation of R(t), see `explain_response_time`
+    pub explanation: Vec<ExplanationRow>,
+}
+
+impl TaskDetailTemplate {
+    /// Detail page of the task at `index` in `tasks`, whose analysis with `config` is `analysis`.
+    pub fn new(
+        taskset_id: &str,
+        tasks: &Tasks,
+        index: usize,
+        analysis: &TaskAnalysis,
+        scenario: Option<&Scenario>,
+        config: &AnalysisConfig,
+        theme: &charts::Theme,
+    ) -> Self {
+        let time = |ticks: u32| config.clock.map_or(ticks.to_string(), |clock| clock.format(ticks));
+        let task = &tasks[index];
+        let prepared = config.prepare(tasks);
+        let context = AnalysisContext::new(tasks);
+
+        let mut sections = vec![];
+        flatten(&task.trace, "", 0, &context, &time, &mut sections);
+        let mut explanation = vec![];
+        if let Some(derivation) = explain_response_time(tasks, &task.id, config) {
+            flatten_explanation(&derivation, "0".to_string(), 0, &mut explanation);
+        }
+
+        Self {
+            taskset_id: taskset_id.to_string(),
+            task: task.clone(),
+            analysis: analysis.clone(),
+            deadline: time(analysis.deadline),
+            wcet: time(analysis.wcet),
+            blocking_time: time(analysis.blocking_time),
+            blocker: prepared[index].blocker(&prepared).map(|blocker| blocker.to_string()),
+            interference: time(analysis.interference),
+            response_time: match &analysis.response_time {
+                Ok(r) => time(*r),
+                Err(e) => e.clone(),
+            },
+            load_factor: task.load_factor().map_or("-".to_string(), |load_factor| format!("{:.3}", load_factor)),
+            interferers: prepared[index]
+                .interference_breakdown(&prepared)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(id, interference)| (id, time(interference)))
+                .collect(),
+            sections,
+            chart: charts::trace_chart(task, config.clock, theme).to_string(),
+            resources: charts::resource_timeline_chart(task, config.clock, theme).to_string(),
+            scenario: scenario.map(|scenario| charts::scenario_chart(tasks, scenario, config.clock, theme).to_string()),
+            explanation,
+        }
+    }
+}
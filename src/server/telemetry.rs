@@ -0,0 +1,40 @@
+use std::time::Instant;
+
+use axum::{extract::MatchedPath, http::Request, middleware::Next, response::Response};
+use metrics_exporter_prometheus::{PrometheusBuilder, PrometheusHandle};
+
+// Histogram buckets in seconds, from cached lookups up to analyses close to the request timeout
+const DURATION_BUCKETS: &[f64] = &[0.001, 0.005, 0.01, 0.05, 0.1, 0.5, 1.0, 5.0, 10.0, 30.0];
+
+/// Installs the global metrics recorder, whose handle renders all metrics in the Prometheus text format.
+pub fn install_metrics_recorder() -> PrometheusHandle {
+    PrometheusBuilder::new()
+        .set_buckets(DURATION_BUCKETS)
+        .unwrap()
+        .install_recorder()
+        .expect("failed to install the metrics recorder")
+}
+
+/// Middleware counting requests and recording their latencies by method, route and status.
+///
+/// Routes are recorded as their pattern, e.g. `/report/:taskset_id`, to keep the number of series bounded.
+pub async fn track_metrics<B>(request: Request<B>, next: Next<B>) -> Response {
+    let started = Instant::now();
+    let path = match request.extensions().get::<MatchedPath>() {
+        Some(path) => path.as_str().to_string(),
+        None => "unmatched".to_string(),
+    };
+    let method = request.method().to_string();
+
+    let response = next.run(request).await;
+
+    let labels = [("method", method), ("path", path), ("status", response.status().as_u16().to_string())];
+    metrics::increment_counter!("http_requests_total", &labels);
+    metrics::histogram!("http_request_duration_seconds", started.elapsed().as_secs_f64(), &labels);
+    response
+}
+
+/// Records the duration of an analysis started at `started`, `kind` is `full` or `delta`.
+pub fn record_analysis(kind: &'static str, started: Instant) {
+    metrics::histogram!("analysis_duration_seconds", started.elapsed().as_secs_f64(), "kind" => kind);
+}
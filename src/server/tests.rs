@@ -0,0 +1,297 @@
+use std::net::IpAddr;
+use std::num::NonZeroU32;
+use std::time::Instant;
+
+use askama::Template;
+use axum::extract::FromRequestParts;
+use axum::http::{header, Request};
+use serde_json::Value;
+
+use super::*;
+use crate::charts;
+
+fn chart_key(task_set: &str, chart: &str) -> ChartKey {
+    ChartKey {
+        task_set: Some(task_set.to_string()),
+        chart: chart.to_string(),
+        data: task_set_key(&simple_example(), &AnalysisConfig::default()),
+        width: 1000,
+        height: 800,
+        renderer: "canvas",
+        theme: String::new(),
+    }
+}
+
+fn sessions(capacity: usize) -> Sessions {
+    Sessions::new(Duration::from_secs(60), capacity, StoreWriter::spawn(Arc::new(MemoryStore::new())))
+}
+
+fn session() -> Arc<Session> {
+    sessions(1).get_or_create(None, vec![], &Arc::new(TaskSetCache::default()), true).0
+}
+
+#[test]
+fn lru_counts_hits_and_misses() {
+    let mut lru = Lru::new(2);
+    assert_eq!(lru.get(&1), None);
+    lru.insert(1, "one");
+    assert_eq!(lru.get(&1), Some("one"));
+    assert_eq!(lru.get(&1), Some("one"));
+
+    let stats = lru.stats();
+    assert_eq!((stats.entries, stats.hits, stats.misses, stats.evictions), (1, 2, 1, 0));
+}
+
+#[test]
+fn lru_evicts_the_least_recently_used_entry() {
+    let mut lru = Lru::new(2);
+    lru.insert(1, "one");
+    lru.insert(2, "two");
+    // Reading 1 makes 2 the least recently used
+    lru.get(&1);
+    lru.insert(3, "three");
+
+    assert_eq!(lru.get(&2), None);
+    assert_eq!(lru.get(&1), Some("one"));
+    assert_eq!(lru.get(&3), Some("three"));
+    assert_eq!(lru.stats().evictions, 1);
+    assert_eq!(lru.stats().entries, 2);
+}
+
+#[test]
+fn lru_without_capacity_stores_nothing() {
+    let mut lru = Lru::new(0);
+    lru.insert(1, "one");
+    assert_eq!(lru.get(&1), None);
+    assert_eq!(lru.stats().entries, 0);
+}
+
+#[tokio::test]
+async fn replacing_a_task_set_drops_only_its_chart_pages() {
+    let session = session();
+    for key in [chart_key("a", "gantt"), chart_key("a", "blocking"), chart_key("b", "gantt")] {
+        session.charts.lock().unwrap().insert(key, Arc::new(String::from("<html></html>")));
+    }
+
+    session.insert_task_set("a", simple_example());
+    let mut charts = session.charts.lock().unwrap();
+    assert_eq!(charts.get(&chart_key("a", "gantt")), None);
+    assert_eq!(charts.get(&chart_key("a", "blocking")), None);
+    assert!(charts.get(&chart_key("b", "gantt")).is_some());
+    // Invalidated pages are not evictions
+    assert_eq!(charts.stats().evictions, 0);
+}
+
+fn client(n: u32) -> IpAddr {
+    IpAddr::from(n.to_be_bytes())
+}
+
+#[test]
+fn rate_limiter_refills_the_bucket_over_time() {
+    let limiter = RateLimiter::new(NonZeroU32::new(2).unwrap());
+    let now = Instant::now();
+    assert_eq!(limiter.check(client(1), now), Ok(()));
+    assert_eq!(limiter.check(client(1), now), Ok(()));
+    // One token refills every 30 seconds
+    assert_eq!(limiter.check(client(1), now), Err(30));
+    assert_eq!(limiter.check(client(2), now), Ok(()));
+    assert_eq!(limiter.check(client(1), now + Duration::from_secs(30)), Ok(()));
+}
+
+#[test]
+fn rate_limiter_tracks_a_bounded_number_of_clients() {
+    let limiter = RateLimiter::new(NonZeroU32::new(1).unwrap());
+    let start = Instant::now();
+    // Every client uses up its bucket, so none of them could be forgotten for having refilled
+    for n in 0..MAX_TRACKED_CLIENTS as u32 + 10 {
+        assert_eq!(limiter.check(client(n), start + Duration::from_millis(n as u64)), Ok(()));
+    }
+    assert_eq!(limiter.tracked_clients(), MAX_TRACKED_CLIENTS);
+
+    // The clients seen longest ago made room, the others are still limited
+    let now = start + Duration::from_secs(1);
+    assert_eq!(limiter.check(client(0), now), Ok(()));
+    assert!(limiter.check(client(MAX_TRACKED_CLIENTS as u32), now).is_err());
+    assert_eq!(limiter.tracked_clients(), MAX_TRACKED_CLIENTS);
+}
+
+#[tokio::test]
+async fn only_requests_that_store_something_start_a_session() {
+    let (sessions, shared) = (sessions(10), Arc::new(TaskSetCache::default()));
+    let (_, created) = sessions.get_or_create(None, vec![], &shared, false);
+    assert!(!created);
+    assert_eq!(sessions.count(), 0);
+
+    let (session, created) = sessions.get_or_create(None, vec![], &shared, true);
+    assert!(created);
+    assert_eq!(sessions.count(), 1);
+    // Reading with the token of the session finds it
+    let (found, created) = sessions.get_or_create(Some(&session.id), vec![], &shared, false);
+    assert!(Arc::ptr_eq(&session, &found) && !created);
+}
+
+#[tokio::test]
+async fn the_session_idle_for_the_longest_makes_room_for_a_new_one() {
+    let (sessions, shared) = (sessions(2), Arc::new(TaskSetCache::default()));
+    let (first, _) = sessions.get_or_create(None, vec![], &shared, true);
+    let (second, _) = sessions.get_or_create(None, vec![], &shared, true);
+    // Using the first session leaves the second idle for the longest
+    std::thread::sleep(Duration::from_millis(1));
+    sessions.get_or_create(Some(&first.id), vec![], &shared, false);
+    let (third, _) = sessions.get_or_create(None, vec![], &shared, true);
+
+    assert_eq!(sessions.count(), 2);
+    assert!(sessions.contains(&first.id) && sessions.contains(&third.id));
+    assert!(!sessions.contains(&second.id));
+}
+
+async fn requested_format(uri: &str, accept: Option<&str>) -> Format {
+    let mut request = Request::builder().uri(uri);
+    if let Some(accept) = accept {
+        request = request.header(header::ACCEPT, accept);
+    }
+    let (mut parts, _) = request.body(()).unwrap().into_parts();
+    Format::from_request_parts(&mut parts, &()).await.unwrap()
+}
+
+#[tokio::test]
+async fn xml_is_selected_by_the_accept_header_or_the_format_parameter() {
+    assert_eq!(requested_format("/report/example", Some("application/xml")).await, Format::Xml);
+    assert_eq!(requested_format("/report/example", Some("text/xml")).await, Format::Xml);
+    assert_eq!(requested_format("/report/example?format=xml", None).await, Format::Xml);
+    // The parameter overrides the header, e.g. for a link opened in a browser
+    assert_eq!(requested_format("/report/example?theme=dark&format=xml", Some("text/html")).await, Format::Xml);
+    assert_eq!(requested_format("/report/example?format=json", Some("application/xml")).await, Format::Json);
+
+    let preferred = Some("text/html;q=0.8, application/xml;q=0.9, */*;q=0.1");
+    assert_eq!(requested_format("/report/example", preferred).await, Format::Xml);
+    assert_eq!(requested_format("/report/example", Some("text/html, application/xml")).await, Format::Html);
+    // Unknown formats fall back to the header, and no header to JSON
+    assert_eq!(requested_format("/report/example?format=yaml", Some("application/xml")).await, Format::Xml);
+    assert_eq!(requested_format("/report/example", None).await, Format::Json);
+}
+
+// Hashes every value alike, so only comparing the keys in full tells them apart
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Colliding(u32);
+
+impl std::hash::Hash for Colliding {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        0.hash(state);
+    }
+}
+
+#[test]
+fn lru_tells_apart_keys_whose_hashes_collide() {
+    let mut lru = Lru::new(4);
+    lru.insert(Colliding(1), "one");
+    assert_eq!(lru.get(&Colliding(2)), None);
+    lru.insert(Colliding(2), "two");
+    assert_eq!((lru.get(&Colliding(1)), lru.get(&Colliding(2))), (Some("one"), Some("two")));
+}
+
+#[tokio::test]
+async fn analysis_cache_keys_hold_the_whole_task_set_and_config() {
+    let (tasks, config) = (simple_example(), AnalysisConfig::default());
+    let mut changed = tasks.clone();
+    changed[0].deadline += 1;
+    assert_eq!(task_set_key(&tasks, &config), task_set_key(&tasks.clone(), &config));
+    assert_ne!(task_set_key(&tasks, &config), task_set_key(&changed, &config));
+    assert_ne!(task_set_key(&tasks, &config), task_set_key(&tasks, &AnalysisConfig::new(PreemptionMode::Exact)));
+
+    let session = session();
+    let analyzed = session.analyze(&tasks, &config);
+    assert!(Arc::ptr_eq(&analyzed, &session.analyze(&tasks, &config)));
+    assert!(!Arc::ptr_eq(&analyzed, &session.analyze(&changed, &config)));
+    let stats = session.cache.lock().unwrap().stats();
+    assert_eq!((stats.entries, stats.hits, stats.misses), (2, 1, 2));
+}
+
+#[tokio::test]
+async fn memory_store_shares_the_task_sets_of_the_cache() {
+    let store = Arc::new(MemoryStore::new());
+    let cache = TaskSetCache::persisted("ns".to_string(), StoreWriter::spawn(store.clone()), vec![]);
+    cache.insert("example", simple_example());
+
+    let mut stored = None;
+    for _ in 0..100 {
+        stored = store.get("ns", "example").await.unwrap();
+        if stored.is_some() {
+            break;
+        }
+        // Lets the writer apply the put
+        tokio::task::yield_now().await;
+    }
+    // Held by the cache, the store and here
+    assert_eq!(Arc::strong_count(&stored.unwrap()), 3);
+}
+
+#[test]
+fn task_v1_keeps_the_trace_id_of_the_task() {
+    let mut tasks = simple_example();
+    tasks[0].trace.id = "main loop".to_string();
+    let encoded: Vec<TaskV1> = tasks.iter().map(TaskV1::from).collect();
+    let json = serde_json::to_value(&encoded).unwrap();
+    assert_eq!(json[0]["trace_id"], "main loop");
+    // Traces named after their task leave it out
+    assert!(json[1].get("trace_id").is_none());
+
+    let decoded = tasks_from_v1(serde_json::from_value(json).unwrap()).unwrap();
+    assert_eq!(decoded, tasks);
+}
+
+#[test]
+fn script_json_keeps_the_value_but_not_the_markup() {
+    let json = serde_json::to_string(&["</script><script>alert(1)</script>", "a & b", "<!--"]).unwrap();
+    let escaped = filters::script_json(&json).unwrap();
+    assert!(!escaped.contains(['<', '>', '&']));
+    assert_eq!(serde_json::from_str::<Value>(&escaped).unwrap(), serde_json::from_str::<Value>(&json).unwrap());
+}
+
+#[test]
+fn task_ids_cannot_close_the_script_of_the_dashboard() {
+    let mut tasks = simple_example();
+    tasks[0].id = "</script><script>alert(1)</script>".to_string();
+    let simulation = simulate(&tasks, 100);
+    let config = AnalysisConfig::default();
+    let html = DashboardTemplate::new("example", &tasks, &simulation, &config, None, &charts::Theme::default());
+    let html = html.render().unwrap();
+    assert!(!html.contains("<script>alert(1)"));
+    assert!(html.contains("\\u003c/script\\u003e\\u003cscript\\u003ealert(1)"));
+}
+
+#[test]
+fn templates_inline_json_only_through_script_json() {
+    let templates = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("templates");
+    for entry in std::fs::read_dir(templates).unwrap() {
+        let path = entry.unwrap().path();
+        let template = std::fs::read_to_string(&path).unwrap();
+        for (line, text) in template.lines().enumerate() {
+            let unescaped = text.matches("|safe").count() - text.matches("|script_json|safe").count();
+            assert_eq!(unescaped, 0, "{}:{} marks JSON safe without script_json", path.display(), line + 1);
+        }
+    }
+}
+
+#[test]
+fn sharing_beyond_the_capacity_drops_the_first_share_and_its_tokens() {
+    let shares = ShareTable::with_capacity(2);
+    let first = shares.share("a", simple_example());
+    let second = shares.share("b", simple_example());
+    let third = shares.share("c", simple_example());
+
+    assert_eq!((shares.count(), shares.token_count()), (2, 4));
+    assert!(shares.get(&first.read).is_none() && shares.get(&first.owner).is_none());
+    assert!(shares.replace(&first.owner, simple_example()).is_err());
+    let shared = |token: &str| shares.get(token).map(|(access, id, _)| (access, id));
+    assert_eq!(shared(&second.read), Some((ShareAccess::Read, "b".to_string())));
+    assert_eq!(shared(&third.owner), Some((ShareAccess::Owner, "c".to_string())));
+}
+
+#[test]
+fn attachment_names_keep_only_safe_characters_of_the_task_set_id() {
+    assert_eq!(attachment_disposition("rtic-app_v1.2", "csv"), "attachment; filename=\"rtic-app_v1.2.csv\"");
+    let disposition = attachment_disposition("a\"; filename=evil.exe\r\nx-injected: 1 ü", "tar");
+    assert_eq!(disposition, "attachment; filename=\"a___filename_evil.exe__x-injected__1__.tar\"");
+    assert!(header::HeaderValue::from_str(&disposition).is_ok());
+}
@@ -0,0 +1,51 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+
+use crate::io::load_task_set;
+use super::AppState;
+
+/// Id a watched task set file is stored under: its file name without extension.
+pub fn watched_task_set_id(path: &Path) -> String {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "watch".to_string())
+}
+
+/// Reloads the task set file into `state` whenever it changes, which notifies all subscribed clients.
+///
+/// The parent directory is watched rather than the file itself, since many editors save by replacing the file.
+/// The returned watcher stops watching when dropped.
+pub fn watch_task_set(state: Arc<AppState>, path: PathBuf) -> notify::Result<RecommendedWatcher> {
+    let id = watched_task_set_id(&path);
+    reload(&state, &path, &id);
+
+    let directory = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+        _ => PathBuf::from("."),
+    };
+    let file_name = path.file_name().map(|name| name.to_owned());
+
+    let watched = path.clone();
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        let Ok(event) = event else { return };
+        let touches_file = event.paths.iter().any(|p| p.file_name().map(|name| name.to_owned()) == file_name);
+        if touches_file && (event.kind.is_create() || event.kind.is_modify()) {
+            reload(&state, &watched, &id);
+        }
+    })?;
+    watcher.watch(&directory, RecursiveMode::NonRecursive)?;
+    Ok(watcher)
+}
+
+fn reload(state: &AppState, path: &Path, id: &str) {
+    match load_task_set(path) {
+        Ok(tasks) => {
+            tracing::info!("Reloaded task set '{}' from {}", id, path.display());
+            state.insert_task_set(id, tasks);
+        }
+        // Keep serving the last valid version while the file is being edited
+        Err(e) => tracing::warn!("{}", e),
+    }
+}
@@ -0,0 +1,61 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::ceil_div;
+
+/// How releases of a task are spread over time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "model", rename_all = "snake_case")]
+pub enum ArrivalModel {
+    /// Released exactly every `period` time units.
+    Periodic { period: u32 },
+    /// Released at most once every `min_inter_arrival` time units.
+    Sporadic { min_inter_arrival: u32 },
+    /// Up to `n` releases arbitrarily close to each other within any `window`.
+    Bursty { n: u32, window: u32 },
+}
+
+impl ArrivalModel {
+    /// A(t), the time over which the arrival pattern repeats.
+    pub fn inter_arrival(&self) -> u32 {
+        match *self {
+            ArrivalModel::Periodic { period } => period,
+            ArrivalModel::Sporadic { min_inter_arrival } => min_inter_arrival,
+            ArrivalModel::Bursty { window, .. } => window,
+        }
+    }
+
+    /// Same arrival model with its inter arrival time A(t) replaced.
+    pub fn with_inter_arrival(&self, inter_arrival: u32) -> Self {
+        match *self {
+            ArrivalModel::Periodic { .. } => ArrivalModel::Periodic { period: inter_arrival },
+            ArrivalModel::Sporadic { .. } => ArrivalModel::Sporadic { min_inter_arrival: inter_arrival },
+            ArrivalModel::Bursty { n, .. } => ArrivalModel::Bursty { n, window: inter_arrival },
+        }
+    }
+
+    /// Number of releases per inter arrival time A(t).
+    pub fn releases_per_inter_arrival(&self) -> u32 {
+        match *self {
+            ArrivalModel::Bursty { n, .. } => n,
+            _ => 1,
+        }
+    }
+
+    /// Earliest release of job `q` (counting from 0) relative to the first release.
+    pub fn earliest_release(&self, q: u32) -> u32 {
+        match *self {
+            ArrivalModel::Bursty { n, window } => (q / n.max(1)) * window,
+            _ => q * self.inter_arrival(),
+        }
+    }
+
+    /// η(Δ), the maximum number of releases within any time interval of length Δ.
+    ///
+    /// η(Δ) = ceiling(Δ / A) for periodic and sporadic tasks, n * ceiling(Δ / window) for bursts, saturating at
+    /// `u32::MAX`.
+    pub fn max_releases(&self, interval: u32) -> u32 {
+        let windows = ceil_div(interval, self.inter_arrival());
+        self.releases_per_inter_arrival().saturating_mul(windows)
+    }
+}
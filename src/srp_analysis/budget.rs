@@ -0,0 +1,75 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::*;
+
+/// Range of WCETs C(t) a task may be given, e.g. from a rough estimate of a software component.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct WcetBounds {
+    pub task: String,
+    pub lower: u32,
+    pub upper: u32,
+}
+
+/// Recommended WCET budget of a task, within its bounds.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct WcetBudget {
+    pub task: String,
+    pub lower: u32,
+    pub upper: u32,
+    /// Largest C(t) the task may use while the task set stays schedulable
+    pub budget: u32,
+}
+
+fn schedulable_with(tasks: &Tasks, config: &AnalysisConfig, task: &str, wcet: u32) -> Result<bool, String> {
+    let mut trial = tasks.clone();
+    ParameterRef { task: task.to_string(), parameter: TaskParameter::Wcet }.apply(&mut trial, wcet)?;
+    Ok(is_schedulable(&srp_analyze(&trial, config)))
+}
+
+/// Greedily allocates WCET budgets, maximizing the total WCET of the bounded tasks while the task set stays
+/// schedulable.
+///
+/// Every bounded task starts at its lower bound. In the order of `bounds`, each budget is then raised to the
+/// largest schedulable value up to its upper bound by binary search, keeping the budgets found so far, which
+/// assumes schedulability does not improve with a larger C(t). Earlier tasks thereby take precedence over later
+/// ones when budgets compete.
+///
+/// Returns `None` if the task set is not schedulable with all bounded tasks at their lower bounds.
+pub fn allocate_wcet_budgets(
+    tasks: &Tasks,
+    bounds: &[WcetBounds],
+    config: &AnalysisConfig,
+) -> Result<Option<Vec<WcetBudget>>, String> {
+    let mut allocated = tasks.clone();
+    for bound in bounds {
+        if bound.lower > bound.upper {
+            return Err(format!(
+                "Error: Lower bound {} of task '{}' exceeds its upper bound {}.",
+                bound.lower, bound.task, bound.upper
+            ));
+        }
+        ParameterRef { task: bound.task.clone(), parameter: TaskParameter::Wcet }.apply(&mut allocated, bound.lower)?;
+    }
+    validate_task_set(&allocated)?;
+    if !is_schedulable(&srp_analyze(&allocated, config)) {
+        return Ok(None);
+    }
+
+    let mut budgets = vec![];
+    for bound in bounds {
+        // Invariant: `low` is schedulable, everything above `high` is not
+        let (mut low, mut high) = (bound.lower, bound.upper);
+        while low < high {
+            let mid = low + ceil_div(high - low, 2);
+            if schedulable_with(&allocated, config, &bound.task, mid)? {
+                low = mid;
+            } else {
+                high = mid - 1;
+            }
+        }
+        ParameterRef { task: bound.task.clone(), parameter: TaskParameter::Wcet }.apply(&mut allocated, low)?;
+        budgets.push(WcetBudget { task: bound.task.clone(), lower: bound.lower, upper: bound.upper, budget: low });
+    }
+    Ok(Some(budgets))
+}
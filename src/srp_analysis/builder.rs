@@ -0,0 +1,313 @@
+use super::*;
+
+/// Fluent construction of a `Task` and its nested critical sections.
+///
+/// ```ignore
+/// let t2 = TaskBuilder::new("T2")
+///     .prio(2)
+///     .period(200)
+///     .span(0, 30)
+///     .section("R1", 10, 20, |s| s.section("R2", 12, 16))
+///     .build()?;
+/// ```
+pub struct TaskBuilder {
+    id: String,
+    prio: u8,
+    deadline: Option<u32>,
+    arrival: Option<ArrivalModel>,
+    group: Option<String>,
+    stack: u32,
+    suspension: u32,
+    jitter: u32,
+    offset: u32,
+    kind: TaskKind,
+    criticality: Criticality,
+    wcet_hi: Option<u32>,
+    weakly_hard: Option<WeaklyHard>,
+    threshold: Option<u8>,
+    start: u32,
+    end: u32,
+    sections: Vec<Trace>,
+}
+
+/// Collects the critical sections nested inside a section.
+#[derive(Default)]
+pub struct SectionBuilder {
+    sections: Vec<Trace>,
+}
+
+impl TaskBuilder {
+    pub fn new(id: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            prio: 1,
+            deadline: None,
+            arrival: None,
+            group: None,
+            stack: 0,
+            suspension: 0,
+            jitter: 0,
+            offset: 0,
+            kind: TaskKind::Software,
+            criticality: Criticality::Hard,
+            wcet_hi: None,
+            weakly_hard: None,
+            threshold: None,
+            start: 0,
+            end: 0,
+            sections: vec![],
+        }
+    }
+
+    pub fn prio(mut self, prio: u8) -> Self {
+        self.prio = prio;
+        self
+    }
+
+    /// Relative deadline, defaults to the inter arrival time.
+    pub fn deadline(mut self, deadline: u32) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    pub fn arrival(mut self, arrival: ArrivalModel) -> Self {
+        self.arrival = Some(arrival);
+        self
+    }
+
+    /// Shorthand for a periodic arrival model.
+    pub fn period(self, period: u32) -> Self {
+        self.arrival(ArrivalModel::Periodic { period })
+    }
+
+    pub fn group(mut self, group: &str) -> Self {
+        self.group = Some(group.to_string());
+        self
+    }
+
+    /// Worst-case stack usage in bytes.
+    pub fn stack(mut self, stack: u32) -> Self {
+        self.stack = stack;
+        self
+    }
+
+    /// Worst-case self-suspension time per job.
+    pub fn suspension(mut self, suspension: u32) -> Self {
+        self.suspension = suspension;
+        self
+    }
+
+    /// Release jitter J(t).
+    pub fn jitter(mut self, jitter: u32) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Offset Φ(t) of the first release.
+    pub fn offset(mut self, offset: u32) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Marks the task as an interrupt handler, see `TaskKind::Interrupt`.
+    pub fn interrupt(mut self) -> Self {
+        self.kind = TaskKind::Interrupt;
+        self
+    }
+
+    pub fn criticality(mut self, criticality: Criticality) -> Self {
+        self.criticality = criticality;
+        self
+    }
+
+    /// Makes the task HI-criticality with WCET C(HI), see `Task::wcet_hi`.
+    pub fn wcet_hi(mut self, wcet_hi: u32) -> Self {
+        self.wcet_hi = Some(wcet_hi);
+        self
+    }
+
+    /// Allows at most `misses` deadline misses in any `window` consecutive jobs, see `WeaklyHard`.
+    pub fn weakly_hard(mut self, misses: u32, window: u32) -> Self {
+        self.weakly_hard = Some(WeaklyHard { misses, window });
+        self
+    }
+
+    /// Preemption threshold γ(t) >= P(t) the task runs at once it started, see `Task::preemption_threshold`.
+    pub fn threshold(mut self, threshold: u8) -> Self {
+        self.threshold = Some(threshold);
+        self
+    }
+
+    /// Start and end of the whole task execution.
+    pub fn span(mut self, start: u32, end: u32) -> Self {
+        self.start = start;
+        self.end = end;
+        self
+    }
+
+    /// Adds a critical section on resource `id`, with its nested sections added by `inner`.
+    pub fn section<F>(mut self, id: &str, start: u32, end: u32, inner: F) -> Self
+    where
+        F: FnOnce(SectionBuilder) -> SectionBuilder,
+    {
+        self.sections.push(section(id, start, end, inner));
+        self
+    }
+
+    /// Adds a non-preemptible section, e.g. a region with interrupts disabled.
+    pub fn non_preemptible(mut self, id: &str, start: u32, end: u32) -> Self {
+        self.sections.push(non_preemptible_section(id, start, end));
+        self
+    }
+
+    /// Adds a read-shared section on resource `id`, see `LockType::Shared`.
+    pub fn read_section(mut self, id: &str, start: u32, end: u32) -> Self {
+        self.sections.push(read_section(id, start, end));
+        self
+    }
+
+    /// Adds the sections of a component called at `start`.
+    pub fn component(mut self, component: &Component, start: u32) -> Self {
+        self.sections.extend(component.instantiate(start));
+        self
+    }
+
+    /// Builds the task, checking that every section lies within its enclosing section, its (m, k) constraint and
+    /// its preemption threshold.
+    pub fn build(self) -> Result<Task, String> {
+        let arrival = self
+            .arrival
+            .ok_or_else(|| format!("Error: Task '{}' has no arrival model.", self.id))?;
+
+        let task = Task {
+            deadline: self.deadline.unwrap_or(arrival.inter_arrival()),
+            id: self.id.clone(),
+            prio: self.prio,
+            arrival,
+            group: self.group,
+            stack: self.stack,
+            suspension: self.suspension,
+            jitter: self.jitter,
+            offset: self.offset,
+            kind: self.kind,
+            criticality: self.criticality,
+            wcet_hi: self.wcet_hi,
+            weakly_hard: self.weakly_hard,
+            threshold: self.threshold,
+            trace: Trace {
+                id: self.id,
+                start: self.start,
+                end: self.end,
+                inner: self.sections,
+                non_preemptible: false,
+                lock: LockType::Exclusive,
+            },
+        };
+        validate_nesting(&task.trace)?;
+        if let Some(constraint) = &task.weakly_hard {
+            constraint.validate(&task.id)?;
+        }
+        task.validate_threshold()?;
+        Ok(task)
+    }
+}
+
+impl SectionBuilder {
+    /// Adds a critical section without further nesting.
+    pub fn section(self, id: &str, start: u32, end: u32) -> Self {
+        self.section_with(id, start, end, |s| s)
+    }
+
+    /// Adds a critical section with its nested sections added by `inner`.
+    pub fn section_with<F>(mut self, id: &str, start: u32, end: u32, inner: F) -> Self
+    where
+        F: FnOnce(SectionBuilder) -> SectionBuilder,
+    {
+        self.sections.push(section(id, start, end, inner));
+        self
+    }
+
+    /// Adds a non-preemptible section, e.g. a region with interrupts disabled.
+    pub fn non_preemptible(mut self, id: &str, start: u32, end: u32) -> Self {
+        self.sections.push(non_preemptible_section(id, start, end));
+        self
+    }
+
+    /// Adds a read-shared section on resource `id`, see `LockType::Shared`.
+    pub fn read_section(mut self, id: &str, start: u32, end: u32) -> Self {
+        self.sections.push(read_section(id, start, end));
+        self
+    }
+
+    /// Adds the sections of a component called at `start`.
+    pub fn component(mut self, component: &Component, start: u32) -> Self {
+        self.sections.extend(component.instantiate(start));
+        self
+    }
+
+    pub(super) fn into_sections(self) -> Vec<Trace> {
+        self.sections
+    }
+}
+
+fn section<F>(id: &str, start: u32, end: u32, inner: F) -> Trace
+where
+    F: FnOnce(SectionBuilder) -> SectionBuilder,
+{
+    Trace {
+        id: id.to_string(),
+        start,
+        end,
+        inner: inner(SectionBuilder::default()).sections,
+        non_preemptible: false,
+        lock: LockType::Exclusive,
+    }
+}
+
+fn read_section(id: &str, start: u32, end: u32) -> Trace {
+    Trace {
+        lock: LockType::Shared,
+        ..section(id, start, end, |s| s)
+    }
+}
+
+fn non_preemptible_section(id: &str, start: u32, end: u32) -> Trace {
+    Trace {
+        non_preemptible: true,
+        ..section(id, start, end, |s| s)
+    }
+}
+
+/// Checks that every trace starts before it ends, lies within its enclosing trace and does not overlap the other
+/// sections of that trace, reporting the first problem, see `check_trace`.
+///
+/// A task executes one section at a time, so overlapping sections would count the same time twice.
+pub fn validate_nesting(trace: &Trace) -> Result<(), String> {
+    match check_trace(&trace.id, trace).first() {
+        Some(issue) => Err(issue.to_string()),
+        None => Ok(()),
+    }
+}
+
+/// Checks that no two tasks share an id and the nesting of every trace, reporting every problem of the traces
+/// on its own line, see `check_traces`.
+///
+/// Task ids identify tasks in results, overrides and chains, and tell equal priority tasks apart.
+pub fn validate_task_set(tasks: &[Task]) -> Result<(), String> {
+    let mut ids = HashSet::new();
+    for task in tasks {
+        if !ids.insert(task.id.as_str()) {
+            return Err(format!("Error: Duplicate task id '{}'.", task.id));
+        }
+        if let Some(constraint) = &task.weakly_hard {
+            constraint.validate(&task.id)?;
+        }
+        task.validate_threshold()?;
+    }
+    // Every problem of every trace at once, so an imported task set can be fixed in one go
+    let issues = check_traces(tasks);
+    if !issues.is_empty() {
+        return Err(issues.iter().map(TraceIssue::to_string).collect::<Vec<_>>().join("\n"));
+    }
+    Ok(())
+}
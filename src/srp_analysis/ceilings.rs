@@ -0,0 +1,54 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::*;
+
+/// Ceiling π(r) of a resource together with the tasks locking it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct ResourceCeiling {
+    pub resource: String,
+    pub ceiling: u8,
+    /// π_r(r) of the read-shared sections on the resource, if there are any
+    pub read_ceiling: Option<u8>,
+    pub tasks: Vec<String>,
+}
+
+/// Ceilings of all resources in the task set as derived by `pre_analysis`, ordered by resource id, with the
+/// tasks locking each resource ordered by task id.
+///
+/// Non-preemptible sections are reported with the highest possible ceiling.
+pub fn resource_ceilings(tasks: &Tasks) -> Vec<ResourceCeiling> {
+    let (ip, tr) = pre_analysis(tasks);
+    let context = AnalysisContext::new(tasks);
+    let non_preemptible: HashSet<&str> = tasks
+        .iter()
+        .flat_map(|t| t.resources())
+        .filter(|r| r.non_preemptible)
+        .map(|r| r.id.as_str())
+        .collect();
+
+    ip.iter()
+        // `IdPrio` also holds the task ids themselves, only keep ids locked by some task
+        .filter(|(id, _)| tr.values().any(|resources| resources.contains(*id)))
+        .map(|(id, ceiling)| {
+            let locked_by: Vec<String> = tr
+                .iter()
+                .filter(|(_, resources)| resources.contains(id))
+                .map(|(task, _)| task.clone())
+                .collect();
+
+            let read_ceiling = tasks
+                .iter()
+                .flat_map(|t| t.resources())
+                .find(|r| r.id == *id && r.lock == LockType::Shared && !r.non_preemptible)
+                .map(|r| context.ceiling_priority(r));
+
+            ResourceCeiling {
+                resource: id.clone(),
+                ceiling: if non_preemptible.contains(id.as_str()) { u8::MAX } else { *ceiling },
+                read_ceiling,
+                tasks: locked_by,
+            }
+        })
+        .collect()
+}
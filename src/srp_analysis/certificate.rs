@@ -0,0 +1,160 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::*;
+
+/// Version of the `SchedulabilityCertificate` format, raised whenever a field changes its meaning.
+pub const CERTIFICATE_VERSION: u32 = 1;
+
+/// Verdict of a task with every value it rests on, see `SchedulabilityCertificate`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct TaskCertificate {
+    pub task: String,
+    pub priority: u8,
+    pub deadline: u32,
+    /// R(t), `None` if it is unbounded
+    pub response_time: Option<u32>,
+    /// R(t) <= D(t)
+    pub schedulable: bool,
+    /// Blocking candidates, interference terms and every other quantity of R(t), see `explain_response_time`
+    pub derivation: Explanation,
+    /// Iterates of the response time recurrence, see `Task::response_time_iterates`
+    pub convergence: Convergence,
+}
+
+/// Verdict of an analysis with all intermediate values, so an independent checker can verify it step by step
+/// without rerunning the analysis, see `verify_certificate`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct SchedulabilityCertificate {
+    /// `CERTIFICATE_VERSION` of the certificate
+    pub version: u32,
+    pub config: AnalysisConfig,
+    pub assumptions: Vec<String>,
+    pub ceilings: Vec<ResourceCeiling>,
+    /// In the order of the task set
+    pub tasks: Vec<TaskCertificate>,
+    /// R(t) <= D(t) for all tasks t
+    pub schedulable: bool,
+}
+
+/// Certificate of the analysis of the task set with `config`.
+pub fn schedulability_certificate(tasks: &Tasks, config: &AnalysisConfig) -> SchedulabilityCertificate {
+    let convergences = response_time_convergence(tasks, config);
+    let certificates: Vec<TaskCertificate> = srp_analyze(tasks, config)
+        .into_iter()
+        .zip(convergences)
+        .map(|((task, response_time, ..), convergence)| {
+            let response_time = response_time.ok();
+            TaskCertificate {
+                task: task.id.clone(),
+                priority: task.prio,
+                deadline: task.deadline,
+                response_time,
+                schedulable: response_time.is_some_and(|r| r <= task.deadline),
+                derivation: explain_response_time(tasks, &task.id, config).expect("task of the task set"),
+                convergence,
+            }
+        })
+        .collect();
+    SchedulabilityCertificate {
+        version: CERTIFICATE_VERSION,
+        config: *config,
+        assumptions: config.assumptions(),
+        ceilings: resource_ceilings(tasks),
+        schedulable: certificates.iter().all(|task| task.schedulable),
+        tasks: certificates,
+    }
+}
+
+fn shown(value: Option<u32>) -> String {
+    value.map_or("unbounded".to_string(), |value| value.to_string())
+}
+
+// Value of the operands of a step: a number, a sum, a maximum, a product or a difference, `None` for anything else
+fn evaluate(operands: &str) -> Option<u32> {
+    let numbers = |text: &str, separator| -> Option<Vec<u32>> {
+        text.split(separator).map(|number: &str| number.trim().parse().ok()).collect()
+    };
+    if let Some(inner) = operands.strip_prefix("max(").and_then(|rest| rest.strip_suffix(')')) {
+        return numbers(inner, ",")?.into_iter().max();
+    }
+    if let Some((a, b)) = operands.split_once(" * ") {
+        return a.trim().parse::<u32>().ok()?.checked_mul(b.trim().parse().ok()?);
+    }
+    if let Some((a, b)) = operands.split_once(" - ") {
+        return a.trim().parse::<u32>().ok()?.checked_sub(b.trim().parse().ok()?);
+    }
+    numbers(operands, "+")?.into_iter().try_fold(0u32, |sum, number| sum.checked_add(number))
+}
+
+// Checks that the operands of every step with a value add up to it
+fn verify_step(task: &str, step: &Explanation, problems: &mut Vec<String>) {
+    if let (Some(value), false) = (step.value, step.operands.is_empty()) {
+        match evaluate(&step.operands) {
+            Some(result) if result == value => {}
+            Some(result) => problems.push(format!(
+                "Error: {} of task '{}' is {} = {}, not {}.",
+                step.quantity, task, step.operands, result, value
+            )),
+            None => problems.push(format!(
+                "Error: Cannot evaluate the operands '{}' of {} of task '{}'.",
+                step.operands, step.quantity, task
+            )),
+        }
+    }
+    for term in &step.terms {
+        verify_step(task, term, problems);
+    }
+}
+
+/// Every inconsistency of a certificate, empty if it holds: ceilings that are not the highest priority of the
+/// tasks locking the resource, steps whose operands do not add up to their value, derivations that do not end at
+/// R(t), decreasing iterates and verdicts that do not follow from R(t) <= D(t).
+///
+/// Only the certificate is checked, the task set is not analyzed again.
+pub fn verify_certificate(certificate: &SchedulabilityCertificate) -> Vec<String> {
+    if certificate.version != CERTIFICATE_VERSION {
+        return vec![format!("Error: Unsupported certificate version {}.", certificate.version)];
+    }
+    let mut problems = vec![];
+    let priorities: HashMap<&str, u8> =
+        certificate.tasks.iter().map(|task| (task.task.as_str(), task.priority)).collect();
+    // Non-preemptible sections have the highest possible ceiling, regardless of the tasks
+    for ceiling in certificate.ceilings.iter().filter(|ceiling| ceiling.ceiling != u8::MAX) {
+        let locking: Option<Vec<u8>> =
+            ceiling.tasks.iter().map(|task| priorities.get(task.as_str()).copied()).collect();
+        if locking.and_then(|priorities| priorities.into_iter().max()) != Some(ceiling.ceiling) {
+            problems.push(format!(
+                "Error: π({}) = {} is not the highest priority of the tasks locking it.",
+                ceiling.resource, ceiling.ceiling
+            ));
+        }
+    }
+
+    for task in &certificate.tasks {
+        if task.derivation.value != task.response_time {
+            problems.push(format!(
+                "Error: The derivation of R({}) ends at {}, not at {}.",
+                task.task,
+                shown(task.derivation.value),
+                shown(task.response_time)
+            ));
+        }
+        verify_step(&task.task, &task.derivation, &mut problems);
+        if task.convergence.iterates.windows(2).any(|pair| pair[1] < pair[0]) {
+            problems.push(format!("Error: The iterates of R({}) decrease.", task.task));
+        }
+        if task.schedulable != task.response_time.is_some_and(|r| r <= task.deadline) {
+            problems.push(format!(
+                "Error: The verdict of task '{}' does not follow from R(t) = {} and D(t) = {}.",
+                task.task,
+                shown(task.response_time),
+                task.deadline
+            ));
+        }
+    }
+    if certificate.schedulable != certificate.tasks.iter().all(|task| task.schedulable) {
+        problems.push("Error: The verdict of the task set does not follow from the verdicts of its tasks.".to_string());
+    }
+    problems
+}
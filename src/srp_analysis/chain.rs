@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::*;
+
+/// Sequence of tasks passing data from one to the next, e.g. sensor → filter → actuator.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct Chain {
+    pub id: String,
+    /// Task ids in data flow order
+    pub tasks: Vec<String>,
+    /// Largest acceptable end-to-end latency D(c)
+    pub deadline: u32,
+}
+
+/// Worst-case end-to-end latency of a `Chain`.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ChainLatency {
+    pub chain: String,
+    pub deadline: u32,
+    /// Either `{"Ok": L(c)}` or `{"Err": reason}`
+    #[schema(value_type = Object)]
+    pub latency: Result<u32, String>,
+}
+
+impl ChainLatency {
+    /// L(c) <= D(c)
+    pub fn meets_deadline(&self) -> bool {
+        matches!(self.latency, Ok(l) if l <= self.deadline)
+    }
+}
+
+/// L(c) = R(t_1) + sum(A(t_i) + R(t_i)) for i = 2..n
+///
+/// Every task after the first may be released just before its predecessor produces its output, so it only
+/// picks up the data on its next release, up to A(t_i) later.
+pub fn chain_latency(chain: &Chain, results: &[AnalysisResult]) -> ChainLatency {
+    let latency = chain
+        .tasks
+        .iter()
+        .enumerate()
+        .try_fold(0u32, |acc, (i, id)| {
+            let (task, response_time, ..) = results
+                .iter()
+                .find(|(task, ..)| task.id == *id)
+                .ok_or_else(|| format!("Unknown task '{}' in chain '{}'", id, chain.id))?;
+            let response_time = response_time
+                .as_ref()
+                .map_err(|e| format!("Task '{}' has no bounded response time: {}", id, e))?;
+            let sampling_delay = if i == 0 { 0 } else { task.arrival.inter_arrival() };
+            Ok(acc + sampling_delay + response_time)
+        });
+
+    ChainLatency {
+        chain: chain.id.clone(),
+        deadline: chain.deadline,
+        latency,
+    }
+}
+
+/// `chain_latency` of every chain, in the given order.
+pub fn chain_latencies(chains: &[Chain], results: &[AnalysisResult]) -> Vec<ChainLatency> {
+    chains.iter().map(|chain| chain_latency(chain, results)).collect()
+}
@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Clock the timing parameters are counted in, e.g. the CPU cycles of measured traces, to report them in real time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+pub struct ClockModel {
+    pub frequency_hz: u64,
+}
+
+impl ClockModel {
+    /// Duration of `ticks` clock cycles in microseconds.
+    pub fn micros(&self, ticks: u32) -> f64 {
+        ticks as f64 * 1_000_000.0 / self.frequency_hz as f64
+    }
+
+    /// Duration of `ticks` in both units, e.g. `480 (6 µs)`.
+    pub fn format(&self, ticks: u32) -> String {
+        format!("{} ({} µs)", ticks, self.micros(ticks))
+    }
+}
@@ -0,0 +1,102 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::*;
+
+/// Reusable trace fragment of a library component, e.g. a driver code path locking a bus, modeled once and
+/// instantiated by every task calling it.
+///
+/// Section times are relative to the start of the component.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct Component {
+    pub id: String,
+    pub sections: Vec<Trace>,
+}
+
+/// Call of a component by a task, starting `offset` time units into the task's trace.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct ComponentUse {
+    pub task: String,
+    pub component: String,
+    pub offset: u32,
+}
+
+/// Task set whose tasks share components, see `expand`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ComponentTaskSet {
+    #[serde(default)]
+    pub components: Vec<Component>,
+    pub tasks: Tasks,
+    #[serde(default)]
+    pub uses: Vec<ComponentUse>,
+}
+
+// Trace moved `offset` time units later
+pub(super) fn shift(trace: &Trace, offset: u32) -> Trace {
+    Trace {
+        id: trace.id.clone(),
+        start: trace.start + offset,
+        end: trace.end + offset,
+        inner: trace.inner.iter().map(|inner| shift(inner, offset)).collect(),
+        non_preemptible: trace.non_preemptible,
+        lock: trace.lock,
+    }
+}
+
+impl Component {
+    /// Component with the sections added by `sections`, see `SectionBuilder`.
+    pub fn new<F>(id: &str, sections: F) -> Result<Self, String>
+    where
+        F: FnOnce(SectionBuilder) -> SectionBuilder,
+    {
+        let component = Component { id: id.to_string(), sections: sections(SectionBuilder::default()).into_sections() };
+        component.validate()?;
+        Ok(component)
+    }
+
+    /// Execution time of the component, from 0 to the end of its last section.
+    pub fn duration(&self) -> u32 {
+        self.sections.iter().map(|section| section.end).max().unwrap_or(0)
+    }
+
+    /// Sections of the component called at `offset`.
+    pub fn instantiate(&self, offset: u32) -> Vec<Trace> {
+        self.sections.iter().map(|section| shift(section, offset)).collect()
+    }
+
+    /// Checks the nesting of every section, see `validate_nesting`.
+    pub fn validate(&self) -> Result<(), String> {
+        self.sections.iter().try_for_each(validate_nesting)
+    }
+}
+
+impl ComponentTaskSet {
+    /// Plain task set with the sections of every used component added to the calling task.
+    pub fn expand(self) -> Result<Tasks, String> {
+        let mut components = HashMap::new();
+        for component in &self.components {
+            component.validate()?;
+            if components.insert(component.id.as_str(), component).is_some() {
+                return Err(format!("Error: Duplicate component id '{}'.", component.id));
+            }
+        }
+
+        let mut tasks = self.tasks;
+        for used in &self.uses {
+            let component = components
+                .get(used.component.as_str())
+                .ok_or_else(|| format!("Error: No component with id '{}'.", used.component))?;
+            let task = tasks
+                .iter_mut()
+                .find(|t| t.id == used.task)
+                .ok_or_else(|| format!("Error: No task with id '{}'.", used.task))?;
+            let offset = task.trace.start + used.offset;
+            task.trace.inner.extend(component.instantiate(offset));
+            task.trace.inner.sort_by_key(|section| section.start);
+        }
+        validate_task_set(&tasks)?;
+        Ok(tasks)
+    }
+}
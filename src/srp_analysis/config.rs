@@ -0,0 +1,241 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::*;
+
+/// Release jitter the analysis accounts for.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum JitterModel {
+    /// Tasks are released exactly at their arrival, J(t) is ignored.
+    #[default]
+    None,
+    /// Releases are delayed by up to J(t) after arrival: η_h(w) = η(w + J(h)) and R(t) includes J(t).
+    Release,
+}
+
+/// Release offsets the analysis accounts for.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum OffsetModel {
+    /// All tasks are released together at the critical instant, Φ(t) is ignored.
+    #[default]
+    Synchronous,
+    /// Periodic tasks of equal period without jitter keep their offsets to each other, Tindell's transactions
+    /// approximation, see `Task::offset_response_time`.
+    Transactions,
+}
+
+/// Critical sections of lower priority tasks that may block a task.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BlockingRefinement {
+    /// SRP: only sections l_r with π(l_r) >= P(t) block t.
+    #[default]
+    Ceiling,
+    /// Every section of a lower priority task blocks t, as if critical sections were non-preemptible.
+    NonPreemptive,
+}
+
+/// Assumptions and options of an analysis run, passed to every analysis entry point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(default)]
+pub struct AnalysisConfig {
+    pub mode: PreemptionMode,
+    pub deadline_model: DeadlineModel,
+    pub jitter_model: JitterModel,
+    pub offset_model: OffsetModel,
+    pub blocking_refinement: BlockingRefinement,
+    /// Timer tick, all timing parameters are conservatively rounded to multiples of it, 1 keeps them exact.
+    pub tick_granularity: u32,
+    /// Clock the timing parameters are counted in, to also report them in microseconds
+    pub clock: Option<ClockModel>,
+}
+
+impl Default for AnalysisConfig {
+    fn default() -> Self {
+        AnalysisConfig {
+            mode: PreemptionMode::Approximate,
+            deadline_model: DeadlineModel::default(),
+            jitter_model: JitterModel::default(),
+            offset_model: OffsetModel::default(),
+            blocking_refinement: BlockingRefinement::default(),
+            tick_granularity: 1,
+            clock: None,
+        }
+    }
+}
+
+// Rounds down to a multiple of the tick, but not below one tick
+fn floor_tick(value: u32, tick: u32) -> u32 {
+    (value / tick * tick).max(tick)
+}
+
+fn ceil_tick(value: u32, tick: u32) -> u32 {
+    ceil_div(value, tick).saturating_mul(tick)
+}
+
+fn round_trace(trace: &mut Trace, tick: u32, non_preemptible: bool) {
+    trace.start = trace.start / tick * tick;
+    trace.end = ceil_tick(trace.end, tick);
+    for section in &mut trace.inner {
+        section.non_preemptible |= non_preemptible;
+        round_trace(section, tick, non_preemptible);
+    }
+}
+
+impl AnalysisConfig {
+    /// Default options in the given mode.
+    pub fn new(mode: PreemptionMode) -> Self {
+        AnalysisConfig { mode, ..Default::default() }
+    }
+
+    /// Copy of the task set as it is analyzed under this configuration.
+    ///
+    /// Execution times, jitters and suspensions grow, inter arrival times and deadlines shrink to whole ticks.
+    pub fn prepare(&self, tasks: &[Task]) -> Tasks {
+        let tick = self.tick_granularity.max(1);
+        let non_preemptible = self.blocking_refinement == BlockingRefinement::NonPreemptive;
+
+        tasks
+            .iter()
+            .map(|task| {
+                let mut task = task.clone();
+                if tick > 1 {
+                    task.deadline = floor_tick(task.deadline, tick);
+                    task.arrival = task.arrival.with_inter_arrival(floor_tick(task.arrival.inter_arrival(), tick));
+                    task.jitter = ceil_tick(task.jitter, tick);
+                    task.suspension = ceil_tick(task.suspension, tick);
+                    task.wcet_hi = task.wcet_hi.map(|wcet_hi| ceil_tick(wcet_hi, tick));
+                }
+                if self.jitter_model == JitterModel::None {
+                    task.jitter = 0;
+                }
+                round_trace(&mut task.trace, tick, non_preemptible);
+                task
+            })
+            .collect()
+    }
+
+    /// R(t) of a task of a prepared task set, see `prepare`.
+    ///
+    /// With transactions, the offset-aware bound replaces the synchronous one where it is lower, in the
+    /// approximate and exact modes under constrained deadlines.
+    pub fn response_time(&self, task: &Task, tasks: &Tasks) -> Result<u32, String> {
+        let response_time = match self.deadline_model {
+            DeadlineModel::Constrained => task.response_time(tasks, &self.mode),
+            DeadlineModel::Arbitrary => task.arbitrary_deadline_response_time(tasks, &self.mode),
+        };
+        let response_time = match (response_time, self.offsets_apply()) {
+            (Ok(synchronous), true) => {
+                Ok(task.offset_response_time(tasks).map_or(synchronous, |aware| aware.min(synchronous)))
+            }
+            // A deadline missed in exact mode may still be met with the offsets
+            (Err(error), true) => match task.offset_response_time(tasks) {
+                Ok(aware) if aware <= task.deadline => Ok(aware),
+                _ => Err(error),
+            },
+            (response_time, false) => response_time,
+        };
+        self.with_jitter(task, response_time)
+    }
+
+    /// R(t) + J(t), measured from the arrival instead of the release of the task.
+    ///
+    /// Exact mode reports a missed deadline as `Err`, which then has to include J(t) as well.
+    pub fn with_jitter(&self, task: &Task, response_time: Result<u32, String>) -> Result<u32, String> {
+        let response_time =
+            response_time?.checked_add(task.jitter).ok_or_else(|| "Busy window overflow".to_string())?;
+        if self.mode == PreemptionMode::Exact && response_time > task.deadline {
+            Err("Deadline missed".to_string())
+        } else {
+            Ok(response_time)
+        }
+    }
+
+    /// Whether `response_time` takes the offsets into account under this configuration.
+    pub fn offsets_apply(&self) -> bool {
+        self.offset_model == OffsetModel::Transactions
+            && self.deadline_model == DeadlineModel::Constrained
+            && matches!(self.mode, PreemptionMode::Approximate | PreemptionMode::Exact)
+    }
+
+    /// Human readable assumptions of an analysis with this configuration.
+    pub fn assumptions(&self) -> Vec<String> {
+        let mut assumptions = vec![
+            "Single core, fixed priority preemptive scheduling under the Stack Resource Policy.".to_string(),
+            match self.deadline_model {
+                DeadlineModel::Constrained => "Constrained deadlines, D(t) <= A(t).".to_string(),
+                DeadlineModel::Arbitrary => {
+                    "Arbitrary deadlines, every job within the level-P(t) busy window is analyzed.".to_string()
+                }
+            },
+            "Execution times are the outer trace durations, end - start.".to_string(),
+            "Non-preemptible sections of lower priority tasks block every higher priority task.".to_string(),
+            "Lower priority tasks with a preemption threshold γ(l) >= P(t) block a task for all of C(l).".to_string(),
+            match self.offset_model {
+                OffsetModel::Synchronous => {
+                    "All tasks are released simultaneously at the critical instant.".to_string()
+                }
+                OffsetModel::Transactions => {
+                    "Periodic tasks of equal period without jitter keep their offsets Φ(t) to each other, one of them \
+                     starts the critical instant; all other tasks are released with it."
+                        .to_string()
+                }
+            },
+            "Equal priority tasks run in FIFO order, each interfering with the others once.".to_string(),
+        ];
+        assumptions.push(match self.mode {
+            PreemptionMode::Approximate => {
+                "Approximate mode: I(t) bounds interference over the busy period Bp(t).".to_string()
+            }
+            PreemptionMode::Exact => {
+                "Exact mode: interference is the response time of every higher priority task.".to_string()
+            }
+            PreemptionMode::SuspensionOblivious => {
+                "Suspension-oblivious mode: self-suspensions S(t) are treated as execution time.".to_string()
+            }
+            PreemptionMode::SuspensionAware => {
+                "Suspension-aware mode: suspensions of higher priority tasks are release jitter R(h) - C(h), \
+                 a suspending task is blocked twice."
+                    .to_string()
+            }
+            PreemptionMode::MixedCriticality => {
+                "Mixed-criticality mode: HI-criticality tasks are analyzed with C(HI) across a mode change that \
+                 drops all LO-criticality tasks (AMC-rtb)."
+                    .to_string()
+            }
+            PreemptionMode::Threshold => {
+                "Threshold mode: once started, a task is only preempted by tasks above its preemption threshold \
+                 γ(t), R(t) is its latest start plus the preemptions after it."
+                    .to_string()
+            }
+        });
+        assumptions.push(match self.jitter_model {
+            JitterModel::None => "Release jitter is ignored.".to_string(),
+            JitterModel::Release => "Releases are delayed by up to J(t), R(t) is measured from arrival.".to_string(),
+        });
+        assumptions.push(match self.blocking_refinement {
+            BlockingRefinement::Ceiling => {
+                "Only critical sections with a ceiling π(r) >= P(t) block a task.".to_string()
+            }
+            BlockingRefinement::NonPreemptive => {
+                "Every critical section of a lower priority task blocks, as if non-preemptible.".to_string()
+            }
+        });
+        if self.tick_granularity > 1 {
+            assumptions.push(format!(
+                "Timing parameters are rounded to whole ticks of {} time units.",
+                self.tick_granularity
+            ));
+        }
+        if let Some(clock) = self.clock {
+            assumptions.push(format!(
+                "Time units are cycles of a {} Hz clock, {} µs each.",
+                clock.frequency_hz,
+                clock.micros(1)
+            ));
+        }
+        assumptions
+    }
+}
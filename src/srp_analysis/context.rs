@@ -0,0 +1,118 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use super::*;
+
+/// Memoized analysis of a single task set.
+///
+/// Computes the same quantities as `TaskSchedulable`, but derives all resource ceilings π(r) once up front
+/// and remembers busy periods and response times, instead of recomputing them per resource and per
+/// recursion step. Enabled in `srp_analyze` by the `perf` feature.
+pub struct AnalysisContext<'a> {
+    tasks: &'a [Task],
+    ceilings: HashMap<&'a str, u8>,
+    // π_r(r) of read-shared sections, from the exclusive sections only
+    read_ceilings: HashMap<&'a str, u8>,
+    // Exact mode response times by task index
+    response_times: RefCell<Vec<Option<Result<u32, String>>>>,
+}
+
+impl<'a> AnalysisContext<'a> {
+    pub fn new(tasks: &'a [Task]) -> Self {
+        let mut ceilings: HashMap<&str, u8> = HashMap::new();
+        let mut read_ceilings: HashMap<&str, u8> = HashMap::new();
+        for task in tasks {
+            for resource in task.resources() {
+                let ceiling = ceilings.entry(resource.id.as_str()).or_insert(task.prio);
+                *ceiling = (*ceiling).max(task.prio);
+                if resource.lock == LockType::Exclusive {
+                    let ceiling = read_ceilings.entry(resource.id.as_str()).or_insert(task.prio);
+                    *ceiling = (*ceiling).max(task.prio);
+                }
+            }
+        }
+
+        Self {
+            tasks,
+            ceilings,
+            read_ceilings,
+            response_times: RefCell::new(vec![None; tasks.len()]),
+        }
+    }
+
+    /// π(r), see `TraceSchedulable::ceiling_priority`.
+    pub fn ceiling_priority(&self, resource: &Trace) -> u8 {
+        if resource.non_preemptible {
+            return u8::MAX;
+        }
+        match resource.lock {
+            LockType::Exclusive => self.ceilings.get(resource.id.as_str()).copied().unwrap_or(1),
+            LockType::Shared => self.read_ceilings.get(resource.id.as_str()).copied().unwrap_or(0),
+        }
+    }
+
+    /// B(t), see `TaskSchedulable::blocking_time`.
+    pub fn blocking_time(&self, task: &Task) -> u32 {
+        self.tasks
+            .iter()
+            .filter(|l| l.level() < task.level())
+            .flat_map(|l| l.resources().filter(move |resource| l.kind == task.kind || resource.non_preemptible))
+            .filter(|resource| self.ceiling_priority(resource) >= task.prio)
+            .map(|resource| resource.wcet())
+            .max()
+            .unwrap_or(0)
+            .max(task.threshold_blocker(&self.tasks).map_or(0, |blocker| blocker.duration))
+    }
+
+    /// I(t), see `TaskSchedulable::interference`.
+    pub fn interference(&self, task: &Task) -> Result<u32, String> {
+        // Bp(t) only depends on the task, not on the interfering task h
+        let busy_period = task.busy_period(&self.tasks)?;
+        self.tasks
+            .iter()
+            .filter(|h| h.level() > task.level())
+            .try_fold(task.equal_priority_interference(&self.tasks), |sum, h| {
+                sum.checked_add(h.wcet().checked_mul(h.max_releases(busy_period))?)
+            })
+            .ok_or_else(|| "Interference overflow".to_string())
+    }
+
+    /// R(t), see `TaskSchedulable::response_time`.
+    ///
+    /// In exact mode the response time of every higher priority task is only computed once.
+    pub fn response_time(&self, index: usize, mode: &PreemptionMode) -> Result<u32, String> {
+        let task = &self.tasks[index];
+        match mode {
+            PreemptionMode::Approximate => {
+                let interference = self.interference(task)?;
+                let own = self.blocking_time(task).checked_add(task.wcet());
+                own.and_then(|own| own.checked_add(interference)).ok_or_else(|| "Interference overflow".to_string())
+            }
+            PreemptionMode::Exact => {
+                if let Some(response_time) = &self.response_times.borrow()[index] {
+                    return response_time.clone();
+                }
+                let response_time = self.exact_response_time(task);
+                self.response_times.borrow_mut()[index] = Some(response_time.clone());
+                response_time
+            }
+            _ => task.response_time(&self.tasks, mode),
+        }
+    }
+
+    fn exact_response_time(&self, task: &Task) -> Result<u32, String> {
+        let mut total_response_time =
+            self.blocking_time(task) + task.wcet() + task.equal_priority_interference(&self.tasks);
+
+        // Recursively calculate interference from higher-priority tasks
+        for (h, _) in self.tasks.iter().enumerate().filter(|(_, h)| h.level() > task.level()) {
+            total_response_time += self.response_time(h, &PreemptionMode::Exact)?;
+        }
+
+        if total_response_time > task.deadline {
+            Err("Deadline missed".to_string())
+        } else {
+            Ok(total_response_time)
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::*;
+
+/// Most iterates `Task::response_time_iterates` records.
+pub const MAX_ITERATES: usize = 1_000;
+
+/// Iterates of the response time recurrence of a task, see `Task::response_time_iterates`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct Convergence {
+    pub task: String,
+    pub deadline: u32,
+    /// R^0 = B(t) + C(t) + sum(C(e)), R^(n+1) = R^0 + sum(η_h(R^n) * C(h)), without the repeated fixed point
+    pub iterates: Vec<u32>,
+    /// The last iterate is the fixed point R(t)
+    pub converged: bool,
+}
+
+impl Task {
+    /// R^0, R^1, ... of the recurrence of `Task::iterative_response_time` from a cold start, until they
+    /// converge, exceed `limit`, overflow or reach `MAX_ITERATES`.
+    ///
+    /// The iterates never decrease, so once one exceeds D(t) the task misses its deadline, converging or not.
+    pub fn response_time_iterates<T>(&self, tasks: &T, limit: u32) -> Convergence
+    where
+        T: std::ops::Deref<Target = [Task]> + Sized,
+    {
+        let own = self.blocking_time(tasks) + self.wcet() + self.equal_priority_interference(tasks);
+        let next = |r: u32| {
+            tasks
+                .iter()
+                .filter(|h| h.level() > self.level())
+                .try_fold(own, |acc, h| acc.checked_add(h.wcet().checked_mul(h.max_releases(r))?))
+        };
+
+        let mut iterates = vec![own];
+        let mut converged = false;
+        while iterates.len() < MAX_ITERATES {
+            let r = *iterates.last().unwrap();
+            if r > limit {
+                break;
+            }
+            match next(r) {
+                Some(n) if n == r => {
+                    converged = true;
+                    break;
+                }
+                Some(n) => iterates.push(n),
+                None => break,
+            }
+        }
+        Convergence { task: self.id.clone(), deadline: self.deadline, iterates, converged }
+    }
+}
+
+/// `Task::response_time_iterates` of every task of the task set as prepared by `config`, each followed up to
+/// 2 * D(t) so a diverging task still shows its trend beyond the deadline.
+pub fn response_time_convergence(tasks: &Tasks, config: &AnalysisConfig) -> Vec<Convergence> {
+    let prepared = config.prepare(tasks);
+    prepared.iter().map(|task| task.response_time_iterates(&prepared, task.deadline.saturating_mul(2))).collect()
+}
@@ -0,0 +1,107 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::*;
+
+// Upper bound on fixed-point iterations before a busy window is considered divergent
+const MAX_ITERATIONS: u32 = 10_000;
+
+/// Relation between deadlines and inter arrival times the analysis may assume.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum DeadlineModel {
+    /// D(t) <= A(t), at most one job of a task is pending at any time.
+    #[default]
+    Constrained,
+    /// D(t) may exceed A(t), several jobs of a task may be pending in the same busy window.
+    Arbitrary,
+}
+
+// Iterates x = f(x) from `start` until it converges
+pub(super) fn fixed_point<F>(start: u32, f: F) -> Result<u32, String>
+where
+    F: Fn(u32) -> Option<u32>,
+{
+    fixed_point_counted(start, f).map(|(x, _)| x)
+}
+
+// Like `fixed_point`, also returning the number of times f was evaluated
+pub(super) fn fixed_point_counted<F>(start: u32, f: F) -> Result<(u32, u32), String>
+where
+    F: Fn(u32) -> Option<u32>,
+{
+    let mut x = start;
+    for iteration in 1..=MAX_ITERATIONS {
+        let next = f(x).ok_or_else(|| "Busy window overflow".to_string())?;
+        if next == x {
+            return Ok((x, iteration));
+        }
+        x = next;
+    }
+    Err("Busy window does not converge".to_string())
+}
+
+impl Task {
+    // sum(η_h(w) * C(h)) for all other tasks h where P(h) >= P(t), `None` on overflow
+    //
+    // Several jobs of an equal priority task may be queued ahead of t under arbitrary deadlines, so they are
+    // accounted for like higher priority tasks.
+    fn higher_priority_demand<T>(&self, tasks: &T, w: u32) -> Option<u32>
+    where
+        T: std::ops::Deref<Target = [Task]> + Sized,
+    {
+        tasks
+            .iter()
+            .filter(|h| h.level() > self.level() || (h.level() == self.level() && h.id != self.id))
+            .try_fold(0u32, |acc, h| acc.checked_add(h.wcet().checked_mul(h.max_releases(w))?))
+    }
+
+    /// L(t) = B(t) + η_t(L(t)) * C(t) + sum(η_h(L(t)) * C(h)) for all other tasks h where P(h) >= P(t)
+    ///
+    /// Length of the longest level-P(t) busy window, solved as a fixed point starting from B(t) + C(t).
+    pub fn level_busy_period<T>(&self, tasks: &T) -> Result<u32, String>
+    where
+        T: std::ops::Deref<Target = [Task]> + Sized,
+    {
+        let b_t = self.blocking_time(tasks);
+        fixed_point(b_t + self.wcet(), |l| {
+            let own = self.wcet().checked_mul(self.max_releases(l))?;
+            b_t.checked_add(own)?.checked_add(self.higher_priority_demand(tasks, l)?)
+        })
+    }
+
+    /// R(t) = max(w(q) - a(q)) for q = 0..Q, where Q = η_t(L(t)) jobs fall inside the busy window
+    ///
+    /// w(q) = B(t) + (q + 1) * C(t) + sum(η_h(w(q)) * C(h)) for all other tasks h where P(h) >= P(t)
+    /// and a(q) is the earliest release of job q relative to the start of the busy window.
+    pub fn arbitrary_deadline_response_time<T>(&self, tasks: &T, mode: &PreemptionMode) -> Result<u32, String>
+    where
+        T: std::ops::Deref<Target = [Task]> + Sized,
+    {
+        if matches!(mode, PreemptionMode::SuspensionOblivious | PreemptionMode::SuspensionAware) {
+            return Err("Error: Self-suspensions are only analyzed under constrained deadlines".to_string());
+        }
+        if *mode == PreemptionMode::MixedCriticality {
+            return Err("Error: Mixed-criticality is only analyzed under constrained deadlines".to_string());
+        }
+        if *mode == PreemptionMode::Threshold {
+            return Err("Error: Preemption thresholds are only analyzed under constrained deadlines".to_string());
+        }
+        let b_t = self.blocking_time(tasks);
+        let c_t = self.wcet();
+        let jobs = self.max_releases(self.level_busy_period(tasks)?).max(1);
+
+        let mut response_time = 0;
+        for q in 0..jobs {
+            let own = (q + 1).checked_mul(c_t).and_then(|own| own.checked_add(b_t));
+            let own = own.ok_or_else(|| "Busy window overflow".to_string())?;
+            let w = fixed_point(own, |w| own.checked_add(self.higher_priority_demand(tasks, w)?))?;
+            response_time = response_time.max(w.saturating_sub(self.arrival.earliest_release(q)));
+        }
+
+        match mode {
+            PreemptionMode::Exact if response_time > self.deadline => Err("Deadline missed".to_string()),
+            _ => Ok(response_time),
+        }
+    }
+}
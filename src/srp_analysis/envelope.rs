@@ -0,0 +1,95 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::component::shift;
+use super::*;
+
+/// Longest duration of a section over all merged runs, and the first run in which it was observed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct SectionDominance {
+    /// Ids from the task down to the section, e.g. `t1/R1/R2`
+    pub section: String,
+    pub duration: u32,
+    /// Index of the dominating run
+    pub run: usize,
+}
+
+/// Conservative composite trace of several recorded runs of a task, see `merge_runs`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct TraceEnvelope {
+    pub trace: Trace,
+    /// Every section of the trace in pre-order, starting with the whole task, i.e. its WCET
+    pub sections: Vec<SectionDominance>,
+}
+
+fn duration(trace: &Trace) -> u32 {
+    trace.end - trace.start
+}
+
+// Largest value of `f` over all runs
+fn max_of<F: Fn(&Trace) -> u32>(runs: &[&Trace], f: F) -> u32 {
+    runs.iter().map(|run| f(run)).max().unwrap_or(0)
+}
+
+// Envelope of the same section in every run, starting at 0
+fn merge(runs: &[&Trace], path: &str, sections: &mut Vec<SectionDominance>) -> Result<Trace, String> {
+    let first = runs[0];
+    for (i, run) in runs.iter().enumerate().skip(1) {
+        let same_sections = run.inner.len() == first.inner.len()
+            && run.inner.iter().zip(&first.inner).all(|(a, b)| a.id == b.id);
+        let same_lock = run.id == first.id && run.non_preemptible == first.non_preemptible && run.lock == first.lock;
+        if !same_lock || !same_sections {
+            return Err(format!("Error: Run {} of '{}' has other sections than run 0.", i, path));
+        }
+    }
+
+    // The first of the longest runs, so ties are reported as the earliest run
+    let (run, longest) = runs
+        .iter()
+        .map(|run| duration(run))
+        .enumerate()
+        .fold((0, 0), |best, (i, d)| if d > best.1 { (i, d) } else { best });
+    sections.push(SectionDominance { section: path.to_string(), duration: longest, run });
+
+    // Inner sections are laid out one after another, each preceded by the longest gap observed before it
+    let mut inner = vec![];
+    let mut cursor = 0;
+    for i in 0..first.inner.len() {
+        let gap = max_of(runs, |run| match i {
+            0 => run.inner[0].start - run.start,
+            _ => run.inner[i].start.saturating_sub(run.inner[i - 1].end),
+        });
+        let section_runs: Vec<&Trace> = runs.iter().map(|run| &run.inner[i]).collect();
+        let section = merge(&section_runs, &format!("{}/{}", path, first.inner[i].id), sections)?;
+        inner.push(shift(&section, cursor + gap));
+        cursor += gap + duration(&section);
+    }
+    let tail = max_of(runs, |run| run.end - run.inner.last().map_or(run.start, |last| last.end.max(run.start)));
+
+    Ok(Trace {
+        id: first.id.clone(),
+        start: 0,
+        end: (cursor + tail).max(longest),
+        inner,
+        non_preemptible: first.non_preemptible,
+        lock: first.lock,
+    })
+}
+
+/// Merges recorded runs of the same task into a trace whose every section lasts at least as long as in any run,
+/// starting where the first run starts.
+///
+/// All runs must have the same sections in the same order. Inner sections keep their order and take their
+/// longest duration, and so does the time before, between and after them, so the composite can exceed the
+/// longest run, but never falls short of it.
+pub fn merge_runs(task: &str, runs: &[Trace]) -> Result<TraceEnvelope, String> {
+    if runs.is_empty() {
+        return Err(format!("Error: No runs of task '{}' to merge.", task));
+    }
+    runs.iter().try_for_each(validate_nesting)?;
+
+    let runs: Vec<&Trace> = runs.iter().collect();
+    let mut sections = vec![];
+    let trace = shift(&merge(&runs, task, &mut sections)?, runs[0].start);
+    Ok(TraceEnvelope { trace, sections })
+}
@@ -0,0 +1,122 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use super::*;
+
+/// Canonical task set for demos and tests, see `examples`.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct Example {
+    /// Name of the example in `/examples/:name`, e.g. `heavy-blocking`
+    pub name: String,
+    pub description: String,
+    pub tasks: Tasks,
+}
+
+/// Task set of the srp_analysis course assignment: three tasks sharing R1, R2 and R3, schedulable under the
+/// approximate analysis only.
+pub fn simple_example() -> Tasks {
+    // Lowest priority, no resource usage
+    let t1 = TaskBuilder::new("T1").prio(1).deadline(100).period(100).group("logging").stack(256).span(0, 10);
+
+    // Middle priority, R1 taken twice with R2 and R3 nested inside
+    let t2 = TaskBuilder::new("T2")
+        .prio(2)
+        .deadline(200)
+        .period(200)
+        .group("control")
+        .stack(512)
+        .span(0, 30)
+        .section("R1", 10, 20, |s| s.section("R2", 12, 16))
+        // R3 is held until 30, so the enclosing R1 section must last at least as long
+        .section("R1", 22, 30, |s| s.section("R3", 23, 30));
+
+    let t3 = TaskBuilder::new("T3")
+        .prio(3)
+        .deadline(50)
+        .period(50)
+        .group("control")
+        .stack(128)
+        .span(0, 30)
+        .section("R2", 10, 20, |s| s)
+        .section("R3", 22, 30, |s| s);
+
+    [t1, t2, t3].into_iter().map(|t| t.build().unwrap()).collect()
+}
+
+/// Data flow through `simple_example`.
+pub fn simple_example_chains() -> Vec<Chain> {
+    vec![
+        // T3 samples, T2 controls
+        Chain { id: "control".to_string(), tasks: vec!["T3".to_string(), "T2".to_string()], deadline: 300 },
+        // T1 logs the control output
+        Chain {
+            id: "logging".to_string(),
+            tasks: vec!["T3".to_string(), "T2".to_string(), "T1".to_string()],
+            deadline: 500,
+        },
+    ]
+}
+
+/// Schedulable task set whose response times are dominated by B(t): a background task holds the bus shared
+/// with the sensor task for most of its execution.
+pub fn heavy_blocking_example() -> Tasks {
+    vec![
+        TaskBuilder::new("sensor").prio(3).period(100).span(0, 10).section("bus", 2, 6, |s| s).build().unwrap(),
+        TaskBuilder::new("control").prio(2).period(200).span(0, 20).build().unwrap(),
+        TaskBuilder::new("flash")
+            .prio(1)
+            .period(1000)
+            .span(0, 80)
+            .section("bus", 10, 70, |s| s)
+            .build()
+            .unwrap(),
+    ]
+}
+
+/// Task set with U > 1, so the lowest priority task misses its deadline.
+pub fn unschedulable_example() -> Tasks {
+    vec![
+        TaskBuilder::new("fast").prio(3).period(10).span(0, 4).build().unwrap(),
+        TaskBuilder::new("medium").prio(2).period(20).span(0, 8).section("R", 2, 6, |s| s).build().unwrap(),
+        TaskBuilder::new("slow").prio(1).period(30).span(0, 12).section("R", 0, 4, |s| s).build().unwrap(),
+    ]
+}
+
+/// Four levels of nested critical sections in one task, with the other tasks taking the inner resources, so
+/// the ceilings and the blocking depend on the depth of the sections.
+pub fn deep_nesting_example() -> Tasks {
+    vec![
+        TaskBuilder::new("a").prio(4).period(50).span(0, 4).section("R4", 1, 2, |s| s).build().unwrap(),
+        TaskBuilder::new("b").prio(3).period(100).span(0, 6).section("R3", 1, 3, |s| s).build().unwrap(),
+        TaskBuilder::new("c").prio(2).period(200).span(0, 8).section("R2", 2, 4, |s| s).build().unwrap(),
+        TaskBuilder::new("d")
+            .prio(1)
+            .period(400)
+            .span(0, 40)
+            .section("R1", 5, 35, |s| {
+                s.section_with("R2", 10, 30, |s| s.section_with("R3", 15, 25, |s| s.section("R4", 18, 22)))
+            })
+            .build()
+            .unwrap(),
+    ]
+}
+
+/// Every canonical task set by name, in the order they are listed.
+pub fn examples() -> Vec<Example> {
+    let example = |name: &str, description: &str, tasks| Example {
+        name: name.to_string(),
+        description: description.to_string(),
+        tasks,
+    };
+    vec![
+        example("simple", "Course assignment with three tasks and nested resources", simple_example()),
+        example("heavy-blocking", "Response times dominated by a long critical section", heavy_blocking_example()),
+        example("unschedulable", "U > 1, the lowest priority task misses its deadline", unschedulable_example()),
+        example("deep-nesting", "Four levels of nested critical sections", deep_nesting_example()),
+    ]
+}
+
+/// Canonical task set by name, see `examples`.
+pub fn example(name: &str) -> Option<Tasks> {
+    examples().into_iter().find(|example| example.name == name).map(|example| example.tasks)
+}
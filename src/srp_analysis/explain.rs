@@ -0,0 +1,228 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::*;
+
+/// Step in the derivation of a computed quantity, with the steps of its operands, see `explain_response_time`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct Explanation {
+    /// e.g. `B(T3)`
+    pub quantity: String,
+    /// e.g. `max(C(l_r)) for all l_r where P(l) < P(t) and π(r) >= P(t)`
+    pub formula: String,
+    /// The formula with its operands filled in, e.g. `max(2, 8)`
+    pub operands: String,
+    /// `None` if the analysis gave up, see `note`
+    pub value: Option<u32>,
+    /// Whether the step counts towards the quantity above it, false for a rejected candidate
+    pub counted: bool,
+    /// Why a candidate counts or not, e.g. `π(R2) = 2 < P(T3) = 3`, or why the analysis gave up
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    pub terms: Vec<Explanation>,
+}
+
+impl Explanation {
+    // `value` is `None` for a quantity that overflows
+    fn new(quantity: String, formula: &str, operands: String, value: impl Into<Option<u32>>) -> Self {
+        Explanation {
+            quantity,
+            formula: formula.to_string(),
+            operands,
+            value: value.into(),
+            counted: true,
+            note: None,
+            terms: vec![],
+        }
+    }
+
+    fn note(mut self, note: String) -> Self {
+        self.note = Some(note);
+        self
+    }
+
+    fn rejected(mut self, note: String) -> Self {
+        self.counted = false;
+        self.note(note)
+    }
+
+    fn terms(mut self, terms: Vec<Explanation>) -> Self {
+        self.terms = terms;
+        self
+    }
+
+    /// `<quantity> = <formula> = <operands> = <value>`, leaving out repetitions.
+    pub fn summary(&self) -> String {
+        let mut summary = self.quantity.clone();
+        for part in [&self.formula, &self.operands] {
+            if !part.is_empty() && !summary.ends_with(part.as_str()) {
+                summary = format!("{} = {}", summary, part);
+            }
+        }
+        match self.value {
+            Some(value) if self.operands != value.to_string() => format!("{} = {}", summary, value),
+            _ => summary,
+        }
+    }
+}
+
+// `a + b + c` of the values of the counted terms, 0 without any
+fn joined(terms: &[Explanation], separator: &str) -> String {
+    let values: Vec<String> =
+        terms.iter().filter(|term| term.counted).filter_map(|term| term.value).map(|v| v.to_string()).collect();
+    if values.is_empty() {
+        "0".to_string()
+    } else {
+        values.join(separator)
+    }
+}
+
+fn explain_wcet(task: &Task) -> Explanation {
+    let operands = format!("{} - {}", task.trace.end, task.trace.start);
+    Explanation::new(format!("C({})", task.id), "end - start", operands, task.wcet())
+}
+
+/// B(t) with every critical section and preemption threshold of a lower priority task as a candidate, counted
+/// or rejected, see `Task::blocker`.
+pub fn explain_blocking(task: &Task, tasks: &Tasks) -> Explanation {
+    let mut candidates = vec![];
+    for l in tasks.iter().filter(|l| l.level() < task.level()) {
+        for resource in l.resources() {
+            let operands = format!("{} - {}", resource.end, resource.start);
+            let candidate =
+                Explanation::new(format!("C({}@{})", resource.id, l.id), "end - start", operands, resource.wcet());
+            let ceiling = resource.ceiling_priority(tasks);
+            candidates.push(if l.kind != task.kind && !resource.non_preemptible {
+                candidate.rejected(format!("{} is preempted by {}, which is of another kind", l.id, task.id))
+            } else if resource.non_preemptible {
+                candidate.note(format!("{} is non-preemptible", resource.id))
+            } else if ceiling >= task.prio {
+                candidate.note(format!("π({}) = {} >= P({}) = {}", resource.id, ceiling, task.id, task.prio))
+            } else {
+                candidate.rejected(format!("π({}) = {} < P({}) = {}", resource.id, ceiling, task.id, task.prio))
+            });
+        }
+        if l.kind == task.kind && l.threshold.is_some() {
+            let threshold = l.preemption_threshold();
+            let candidate = explain_wcet(l);
+            candidates.push(if threshold >= task.prio {
+                candidate.note(format!("γ({}) = {} >= P({}) = {}", l.id, threshold, task.id, task.prio))
+            } else {
+                candidate.rejected(format!("γ({}) = {} < P({}) = {}", l.id, threshold, task.id, task.prio))
+            });
+        }
+    }
+    let operands = format!("max({})", joined(&candidates, ", "));
+    let formula = "max(C(l_r)) for all l_r where P(l) < P(t) and π(r) >= P(t)";
+    Explanation::new(format!("B({})", task.id), formula, operands, task.blocking_time(tasks)).terms(candidates)
+}
+
+/// I(t) with Bp(t) and the contribution I_h(t) of every interfering task, see `TaskSchedulable::interference`.
+pub fn explain_interference(task: &Task, tasks: &Tasks) -> Explanation {
+    let busy_period = task.busy_period(tasks).ok();
+    let levels: Vec<Explanation> = tasks.iter().filter(|h| h.level() >= task.level()).map(explain_wcet).collect();
+    let busy = Explanation::new(
+        format!("Bp({})", task.id),
+        "sum(C(h)) for all tasks h where P(h) >= P(t)",
+        joined(&levels, " + "),
+        busy_period,
+    )
+    .terms(levels);
+
+    let mut contributions = vec![];
+    for h in tasks.iter().filter(|h| h.level() > task.level()) {
+        let releases = busy_period.map(|busy_period| h.max_releases(busy_period));
+        let operands = releases.map_or(String::new(), |releases| format!("{} * {}", releases, h.wcet()));
+        let quantity = format!("I_{}({})", h.id, task.id);
+        let value = releases.and_then(|releases| releases.checked_mul(h.wcet()));
+        contributions.push(Explanation::new(quantity, "η_h(Bp(t)) * C(h)", operands, value));
+    }
+    for e in tasks.iter().filter(|e| e.level() == task.level() && e.id != task.id) {
+        contributions.push(explain_wcet(e).note(format!("{} has the same priority", e.id)));
+    }
+    let operands = joined(&contributions, " + ");
+    let mut terms = vec![busy.note("Window the releases of every h are counted in".to_string())];
+    terms.extend(contributions);
+    let formula = "sum(η_h(Bp(t)) * C(h)) for all tasks h where P(h) > P(t) + sum(C(e)) for all other tasks e \
+        where P(e) = P(t)";
+    Explanation::new(format!("I({})", task.id), formula, operands, task.interference(tasks).ok()).terms(terms)
+}
+
+// R(t) of a task of a prepared task set, with the terms the formula of the mode is made of
+fn explain_prepared(task: &Task, tasks: &Tasks, config: &AnalysisConfig) -> Explanation {
+    let blocking = explain_blocking(task, tasks);
+    let wcet = explain_wcet(task);
+    let (formula, mut terms) = match (config.deadline_model, config.mode) {
+        (DeadlineModel::Arbitrary, _) => {
+            ("max(w(q) - a(q)) for q = 0..Q in the level-P(t) busy window", vec![blocking, wcet])
+        }
+        (_, PreemptionMode::Approximate) => {
+            ("B(t) + C(t) + I(t)", vec![blocking, wcet, explain_interference(task, tasks)])
+        }
+        (_, PreemptionMode::Exact) => {
+            let mut terms = vec![blocking, wcet];
+            terms.extend(tasks.iter().filter(|e| e.level() == task.level() && e.id != task.id).map(explain_wcet));
+            terms.extend(tasks.iter().filter(|h| h.level() > task.level()).map(|h| explain_prepared(h, tasks, config)));
+            (
+                "B(t) + C(t) + sum(C(e)) + sum(R(h)) for all other tasks e where P(e) = P(t) and all tasks h where \
+                 P(h) > P(t)",
+                terms,
+            )
+        }
+        (_, PreemptionMode::SuspensionOblivious) => (
+            "B(t) + C(t) + S(t) + E(t) + sum(η_h(R(t)) * (C(h) + S(h))) for all tasks h where P(h) > P(t)",
+            vec![blocking, wcet],
+        ),
+        (_, PreemptionMode::SuspensionAware) => (
+            "B'(t) + C(t) + S(t) + sum(C(e)) + sum(η_h(R(t) + J(h)) * C(h)) for all tasks h where P(h) > P(t)",
+            vec![blocking, wcet],
+        ),
+        (_, PreemptionMode::MixedCriticality) => {
+            ("max(R_LO(t), R*(t)) of a HI-criticality task, R_LO(t) of a LO-criticality task", vec![blocking, wcet])
+        }
+        (_, PreemptionMode::Threshold) => (
+            "S(t) + C(t) + sum((η_h(F(t)) - η_h(S(t) + 1)) * C(h)) for all tasks h where P(h) > γ(t)",
+            vec![blocking, wcet],
+        ),
+    };
+    let formula = if config.offsets_apply() {
+        format!("min({}, R_Φ(t)) with R_Φ(t) the offset-aware bound of the transactions", formula)
+    } else {
+        formula.to_string()
+    };
+    let formula = if task.jitter > 0 {
+        terms.push(Explanation::new(format!("J({})", task.id), "release jitter", String::new(), task.jitter));
+        format!("{} + J(t)", formula)
+    } else {
+        formula
+    };
+    // Operands only add up for the closed forms, the others are fixed points of their terms
+    let closed = config.deadline_model == DeadlineModel::Constrained
+        && matches!(config.mode, PreemptionMode::Approximate | PreemptionMode::Exact)
+        && !config.offsets_apply();
+    let operands = if closed { joined(&terms, " + ") } else { String::new() };
+
+    let explanation = Explanation {
+        quantity: format!("R({})", task.id),
+        formula,
+        operands,
+        value: None,
+        counted: true,
+        note: None,
+        terms,
+    };
+    match config.response_time(task, tasks) {
+        Ok(r) => Explanation { value: Some(r), ..explanation },
+        Err(e) => explanation.note(e),
+    }
+}
+
+/// Derivation of R(t) of the task with the given id as analyzed with `config`: every quantity with its formula
+/// and operands, e.g. the critical sections B(t) is the longest of and those rejected because π(r) < P(t).
+///
+/// `None` for an unknown task.
+pub fn explain_response_time(tasks: &Tasks, task_id: &str, config: &AnalysisConfig) -> Option<Explanation> {
+    let prepared = config.prepare(tasks);
+    let task = prepared.iter().find(|task| task.id == task_id)?;
+    Some(explain_prepared(task, &prepared, config))
+}
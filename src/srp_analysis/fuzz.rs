@@ -0,0 +1,97 @@
+use rayon::prelude::*;
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use super::*;
+
+/// How strongly the schedulability of a task set depends on one of its parameters, see `fuzz_task_set`.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct ParameterFragility {
+    /// `<task>.<parameter>`, e.g. `T2.wcet`
+    pub parameter: String,
+    /// Mean relative change of the parameter in % over the unschedulable samples minus that over the
+    /// schedulable ones, positive if increasing the parameter breaks the task set
+    pub shift: f64,
+}
+
+/// Schedulability of randomly perturbed variants of a task set, see `fuzz_task_set`.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct FuzzReport {
+    /// Seed the variants were drawn from, the same seed draws the same variants
+    pub seed: u64,
+    pub samples: u32,
+    pub schedulable: u32,
+    /// Fraction of the samples that are schedulable
+    pub fraction: f64,
+    /// Parameters by decreasing |shift|, empty unless some but not all samples are schedulable
+    pub parameters: Vec<ParameterFragility>,
+}
+
+// Perturbed task set and the relative change in % of every perturbed parameter
+fn perturb(tasks: &Tasks, spread: u32, rng: &mut SeededRng) -> (Tasks, Vec<f64>) {
+    let mut draw = |nominal: u32, min: u32| {
+        // Uniform factor in [1 - spread, 1 + spread] percent
+        let percent = rng.below(2 * spread as u64 + 1) as i64 - spread as i64;
+        let value = (nominal as i64 + nominal as i64 * percent / 100).clamp(min as i64, u32::MAX as i64) as u32;
+        let change = if nominal == 0 { 0.0 } else { (value as f64 - nominal as f64) * 100.0 / nominal as f64 };
+        (value, change)
+    };
+
+    let mut perturbed = tasks.clone();
+    let mut changes = vec![];
+    for task in perturbed.iter_mut() {
+        // C(t) never shrinks below the end of the last critical section
+        let (wcet, change) = draw(task.wcet(), sections_wcet(&task.trace).max(1));
+        task.trace.end = task.trace.start + wcet;
+        changes.push(change);
+        let (inter_arrival, change) = draw(task.arrival.inter_arrival(), 1);
+        task.arrival = task.arrival.with_inter_arrival(inter_arrival);
+        changes.push(change);
+        let (deadline, change) = draw(task.deadline, 1);
+        task.deadline = deadline;
+        changes.push(change);
+    }
+    (perturbed, changes)
+}
+
+/// Analyzes `samples` variants of the task set, with the WCET, the inter-arrival time and the deadline of every
+/// task changed by a random amount of at most `spread` percent, drawn from `seed`.
+///
+/// A quick robustness estimate rather than a bound: the fraction of schedulable samples, and the parameters
+/// whose changes separate the unschedulable samples from the schedulable ones the most.
+pub fn fuzz_task_set(tasks: &Tasks, config: &AnalysisConfig, samples: u32, spread: u32, seed: u64) -> FuzzReport {
+    // Every sample draws from a stream of its own, so the variants do not depend on the threading
+    let rng = SeededRng::new(seed);
+    let outcomes: Vec<(bool, Vec<f64>)> = (0..samples)
+        .into_par_iter()
+        .map(|sample| {
+            let (perturbed, changes) = perturb(tasks, spread, &mut rng.fork(sample as u64));
+            (is_schedulable(&srp_analyze(&perturbed, config)), changes)
+        })
+        .collect();
+
+    let schedulable = outcomes.iter().filter(|(schedulable, _)| *schedulable).count() as u32;
+    let mut parameters = vec![];
+    if schedulable > 0 && schedulable < samples {
+        let names = tasks
+            .iter()
+            .flat_map(|task| ["wcet", "period", "deadline"].map(|parameter| format!("{}.{}", task.id, parameter)));
+        for (index, parameter) in names.enumerate() {
+            let mean = |wanted: bool| {
+                let changes: Vec<f64> =
+                    outcomes.iter().filter(|(ok, _)| *ok == wanted).map(|(_, changes)| changes[index]).collect();
+                changes.iter().sum::<f64>() / changes.len() as f64
+            };
+            parameters.push(ParameterFragility { parameter, shift: mean(false) - mean(true) });
+        }
+        parameters.sort_by(|a, b| b.shift.abs().total_cmp(&a.shift.abs()));
+    }
+
+    FuzzReport {
+        seed,
+        samples,
+        schedulable,
+        fraction: if samples == 0 { 0.0 } else { schedulable as f64 / samples as f64 },
+        parameters,
+    }
+}
@@ -0,0 +1,124 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use super::*;
+
+// SplitMix64 finalizer, so every seed draws the same numbers on every platform
+fn splitmix64(state: u64) -> u64 {
+    let mut z = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// Random numbers drawn from an explicit seed, which random outputs record so experiments and bug reports can be
+/// reproduced.
+#[derive(Debug, Clone)]
+pub struct SeededRng {
+    seed: u64,
+    state: u64,
+}
+
+impl SeededRng {
+    pub fn new(seed: u64) -> Self {
+        Self { seed, state: seed }
+    }
+
+    /// Seed the generator started from.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Independent generator of the same seed for stream `stream`, e.g. a sample drawn on another thread, which
+    /// draws the same numbers regardless of the order the streams are drawn in.
+    pub fn fork(&self, stream: u64) -> SeededRng {
+        SeededRng { seed: self.seed, state: splitmix64(self.seed ^ stream) }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = splitmix64(self.state);
+        self.state
+    }
+
+    /// Uniform in 0..n, 0 for n = 0.
+    pub fn below(&mut self, n: u64) -> u64 {
+        if n == 0 { 0 } else { self.next_u64() % n }
+    }
+
+    /// Uniform in [0, 1).
+    pub fn unit(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// Random task set with the seed it was drawn from, see `random_task_set`.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct GeneratedTaskSet {
+    pub seed: u64,
+    pub tasks: Tasks,
+}
+
+/// Deterministic synthetic task set of `n` tasks sharing `resources` resources, for benchmarks and experiments.
+///
+/// Priorities are spread evenly over 1..=255, so sets with more than 255 tasks contain equal priorities.
+/// Every task locks two resources, the second nested inside the first, and the total load stays below 1.
+pub fn synthetic_task_set(n: usize, resources: usize) -> Tasks {
+    let resources = resources.max(1);
+    (0..n)
+        .map(|i| {
+            let period = 1000 * n as u32 + 100 * (i % 10) as u32;
+            let wcet = (period / (2 * n as u32)).max(4);
+            let outer = format!("R{}", i % resources);
+            let nested = format!("R{}", (i + 1) % resources);
+
+            TaskBuilder::new(&format!("T{}", i))
+                .prio((1 + i * 254 / n.max(1)) as u8)
+                .period(period)
+                .span(0, wcet)
+                .section(&outer, 1, wcet - 1, |s| s.section(&nested, 2, wcet - 2))
+                .build()
+                .unwrap()
+        })
+        .collect()
+}
+
+/// Random task set of `n` periodic tasks with implicit deadlines and a total utilization of about `utilization`,
+/// drawn from `rng`.
+///
+/// Utilizations are split by UUniFast, periods are uniform in 10..=1000 and priorities rate monotonic, spread over
+/// 1..=255. With `resources` resources, every task locks a random one for a random part of its execution.
+pub fn random_task_set(n: usize, resources: usize, utilization: f64, rng: &mut SeededRng) -> GeneratedTaskSet {
+    let mut remaining = utilization.max(0.0);
+    let mut shapes = vec![];
+    for i in 0..n {
+        // UUniFast: the utilization left for the other tasks shrinks by a random factor
+        let rest = if i + 1 == n { 0.0 } else { remaining * rng.unit().powf(1.0 / (n - i - 1) as f64) };
+        let period = 10 * (1 + rng.below(100) as u32);
+        let wcet = ((remaining - rest) * period as f64).round().clamp(1.0, period as f64) as u32;
+        remaining = rest;
+        shapes.push((period, wcet));
+    }
+    // Tasks by period, shortest first, equal periods in task order
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by_key(|&i| shapes[i].0);
+    let mut priorities = vec![0; n];
+    for (rank, &i) in order.iter().enumerate() {
+        priorities[i] = (255 - rank * 254 / n) as u8;
+    }
+
+    let mut tasks = vec![];
+    for (i, &(period, wcet)) in shapes.iter().enumerate() {
+        let mut builder = TaskBuilder::new(&format!("T{}", i))
+            .prio(priorities[i])
+            .period(period)
+            .span(0, wcet);
+        if resources > 0 {
+            let start = rng.below(wcet as u64) as u32;
+            let end = start + 1 + rng.below((wcet - start) as u64) as u32;
+            let resource = format!("R{}", rng.below(resources as u64));
+            builder = builder.section(&resource, start, end, |s| s);
+        }
+        tasks.push(builder.build().expect("sections lie within the task"));
+    }
+    GeneratedTaskSet { seed: rng.seed(), tasks }
+}
@@ -0,0 +1,44 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use super::*;
+
+/// Aggregated analysis metrics for all tasks sharing the same `Task::group` tag.
+#[derive(Debug, Clone, Serialize)]
+pub struct GroupSummary {
+    /// Group tag, `None` collects all untagged tasks.
+    pub group: Option<String>,
+    pub tasks: Vec<String>,
+    /// U(g) = sum(L(t)) for all t in g
+    pub utilization: f32,
+    /// max(R(t) / D(t)) for all t in g, `None` if any task in the group has no bounded response time.
+    pub worst_response_ratio: Option<f32>,
+}
+
+/// Aggregates the results of `srp_analyze` per task group, ordered by group tag.
+pub fn group_analysis(results: &[AnalysisResult]) -> Vec<GroupSummary> {
+    let mut groups: BTreeMap<Option<&str>, GroupSummary> = BTreeMap::new();
+
+    for (task, response_time, ..) in results {
+        let summary = groups
+            .entry(task.group.as_deref())
+            .or_insert_with(|| GroupSummary {
+                group: task.group.clone(),
+                tasks: vec![],
+                utilization: 0.0,
+                worst_response_ratio: Some(0.0),
+            });
+
+        summary.tasks.push(task.id.clone());
+        summary.utilization += task.load_factor().unwrap_or(0.0);
+
+        // A single unbounded response time makes the whole group unbounded
+        summary.worst_response_ratio = match (summary.worst_response_ratio, response_time) {
+            (Some(worst), Ok(r)) => Some(worst.max(*r as f32 / task.deadline as f32)),
+            _ => None,
+        };
+    }
+
+    groups.into_values().collect()
+}
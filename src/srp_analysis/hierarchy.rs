@@ -0,0 +1,227 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::deadline::fixed_point;
+use super::*;
+
+/// How a server spends its budget Θ, which it gets back at the start of every period Π.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ServerKind {
+    /// Budget unused while none of its tasks is pending is lost until the next period
+    #[default]
+    Polling,
+    /// Budget is kept until the end of the period, so pending tasks are served at once
+    Deferrable,
+}
+
+/// Time partition of a component: its tasks share a budget Θ every period Π, scheduled at priority P(s)
+/// among the other servers and the tasks outside of any server.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct Server {
+    pub id: String,
+    pub prio: u8,
+    pub budget: u32,
+    pub period: u32,
+    #[serde(default)]
+    pub kind: ServerKind,
+    /// Ids of the tasks scheduled within the server
+    pub tasks: Vec<String>,
+}
+
+/// Task set whose tasks are partly scheduled within servers, see `hierarchical_analyze`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct HierarchicalTaskSet {
+    pub servers: Vec<Server>,
+    pub tasks: Tasks,
+}
+
+/// Results of the servers at the global level and of every task, see `hierarchical_analyze`.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct HierarchicalAnalysis {
+    /// R(s) of every server as a task with C = Θ and D = A = Π, the time it takes to supply its budget
+    pub servers: Vec<TaskAnalysis>,
+    /// Tasks in the order of the task set, I(t) of a task within a server includes the time without supply
+    pub tasks: Vec<TaskAnalysis>,
+}
+
+/// Least supply of a server, `budget` in every `period` after a `blackout` without any supply.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Supply {
+    pub budget: u32,
+    pub period: u32,
+    pub blackout: u32,
+}
+
+impl Supply {
+    /// sbf(t) = k * Θ + min(Θ, t - b - k * Π) where k = floor((t - b) / Π) and b is the blackout, 0 for t < b
+    ///
+    /// Least time supplied in any interval of length t.
+    pub fn bound(&self, t: u32) -> u32 {
+        let Some(supplied) = t.checked_sub(self.blackout) else { return 0 };
+        let k = supplied / self.period;
+        k * self.budget + self.budget.min(supplied - k * self.period)
+    }
+
+    // Shortest interval t with sbf(t) >= w, `None` on overflow
+    fn interval(&self, w: u32) -> Option<u32> {
+        if w == 0 {
+            return Some(0);
+        }
+        // Full budgets before the period that supplies the rest
+        let k = ceil_div(w, self.budget) - 1;
+        self.blackout.checked_add(k.checked_mul(self.period)?)?.checked_add(w - k * self.budget)
+    }
+}
+
+impl Server {
+    /// Supply of the server if it gets its budget within R(s) <= Π of every replenishment.
+    ///
+    /// A job of a polling server may arrive just after the server found nothing to serve and dropped its
+    /// budget, and be served as late as R(s) after the next replenishment, b = Π + R(s) - Θ. A deferrable server
+    /// keeps its budget, so the longest wait follows a budget spent right at a replenishment, b = Π + R(s) - 2Θ.
+    /// Later budgets are supplied as late as possible as well, Π - Θ apart.
+    pub fn supply(&self, response_time: u32) -> Supply {
+        let wait = self.period + response_time - self.budget;
+        let blackout = match self.kind {
+            ServerKind::Polling => wait,
+            ServerKind::Deferrable => wait - self.budget,
+        };
+        Supply { budget: self.budget, period: self.period, blackout }
+    }
+
+    /// The server as a task at the global level, with C = Θ and D = A = Π.
+    ///
+    /// A deferrable server may use its budget at the end of one period and again at the start of the next, so it
+    /// interferes like a task with release jitter J = Π - Θ.
+    pub fn as_task(&self) -> Result<Task, String> {
+        let mut task = TaskBuilder::new(&self.id).prio(self.prio).period(self.period).span(0, self.budget).build()?;
+        if self.kind == ServerKind::Deferrable {
+            task.jitter = self.period - self.budget;
+        }
+        Ok(task)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.budget == 0 || self.budget > self.period {
+            return Err(format!(
+                "Error: Server '{}' needs a budget of at least 1 and at most its period {}.",
+                self.id, self.period
+            ));
+        }
+        Ok(())
+    }
+}
+
+// R(t) of a task within a server, the SRP response time of t among the tasks of the server with the processor
+// only available as the server supplies it: R(t) = min { R | sbf(R) >= W(R) } where
+// W(R) = B(t) + C(t) + sum(C(e)) + sum(η_h(R) * C(h)) for all other tasks e, h of the server where P(e) = P(t)
+// and P(h) > P(t)
+fn served_response_time(task: &Task, tasks: &Tasks, supply: &Supply) -> Result<u32, String> {
+    let own = task.blocking_time(tasks) + task.wcet() + task.equal_priority_interference(tasks);
+    let demand = |r: u32| {
+        tasks
+            .iter()
+            .filter(|h| h.level() > task.level())
+            .try_fold(own, |acc, h| acc.checked_add(h.wcet().checked_mul(h.max_releases(r))?))
+    };
+    let start = supply.interval(own).ok_or_else(|| "Busy window overflow".to_string())?;
+    fixed_point(start, |r| supply.interval(demand(r)?)).map(|r| r + task.jitter)
+}
+
+/// Analyzes a two-level task set: the servers and the tasks outside of any server are analyzed as one task set
+/// under `config`, the tasks of a server among each other with the supply bound function of the server, see
+/// `Server::supply`.
+///
+/// Deadline models other than constrained only apply to the global level, and so does the preemption mode, as
+/// does release jitter to the servers. Resources may only be shared by the tasks of one server, or by tasks
+/// outside of any server, since a server may run out of budget while holding a resource.
+pub fn hierarchical_analyze(
+    set: &HierarchicalTaskSet,
+    config: &AnalysisConfig,
+) -> Result<HierarchicalAnalysis, String> {
+    validate_task_set(&set.tasks)?;
+    // Server of every task within one
+    let mut served: HashMap<&str, usize> = HashMap::new();
+    for (index, server) in set.servers.iter().enumerate() {
+        server.validate()?;
+        if set.tasks.iter().any(|task| task.id == server.id) {
+            return Err(format!("Error: Server '{}' has the id of a task.", server.id));
+        }
+        for id in &server.tasks {
+            if !set.tasks.iter().any(|task| &task.id == id) {
+                return Err(format!("Error: Server '{}' contains no task with id '{}'.", server.id, id));
+            }
+            if served.insert(id, index).is_some() {
+                return Err(format!("Error: Task '{}' is contained in more than one server.", id));
+            }
+        }
+    }
+    let partition = |task: &Task| served.get(task.id.as_str()).copied();
+    let mut owners: HashMap<String, Option<usize>> = HashMap::new();
+    for task in set.tasks.iter() {
+        for resource in task.resources() {
+            if *owners.entry(resource.id.clone()).or_insert(partition(task)) != partition(task) {
+                return Err(format!("Error: Resource '{}' is shared across servers.", resource.id));
+            }
+        }
+    }
+
+    // Global level, with jitter for the deferrable servers
+    let mut global: Tasks = set.tasks.iter().filter(|task| partition(task).is_none()).cloned().collect();
+    for server in &set.servers {
+        global.push(server.as_task()?);
+    }
+    let mut global_config = *config;
+    if set.servers.iter().any(|server| server.kind == ServerKind::Deferrable) {
+        global_config.jitter_model = JitterModel::Release;
+    }
+    // R(s) of a server is the time it takes to supply its budget, without the jitter it has as an interferer
+    let jitters = global_config.prepare(&global);
+    let results: Vec<TaskAnalysis> = srp_analyze(&global, &global_config)
+        .iter()
+        .zip(&jitters)
+        .map(|(result, prepared)| {
+            let mut analysis = TaskAnalysis::from(result);
+            if set.servers.iter().any(|server| server.id == analysis.id) {
+                analysis.response_time = analysis.response_time.map(|r| r - prepared.jitter);
+            }
+            analysis.with_clock(config.clock)
+        })
+        .collect();
+    let global_result = |id: &str| results.iter().find(|result| result.id == id).cloned().unwrap();
+
+    let prepared = config.prepare(&set.tasks);
+    let tasks = set
+        .tasks
+        .iter()
+        .zip(&prepared)
+        .map(|(task, analyzed)| {
+            let Some(index) = partition(task) else { return global_result(&task.id) };
+            let server = &set.servers[index];
+            let local: Tasks = prepared.iter().filter(|t| partition(t) == Some(index)).cloned().collect();
+            let response_time = match global_result(&server.id).response_time {
+                Ok(r) if r <= server.period => served_response_time(analyzed, &local, &server.supply(r)),
+                _ => Err(format!("Server '{}' does not get its budget every period", server.id)),
+            };
+            let blocking_time = analyzed.blocking_time(&local);
+            let wcet = analyzed.wcet();
+            let interference = response_time.as_ref().map_or(0, |r| r.saturating_sub(blocking_time + wcet));
+            TaskAnalysis {
+                id: task.id.clone(),
+                deadline: analyzed.deadline,
+                response_time,
+                blocking_time,
+                wcet,
+                interference,
+                real_time: None,
+            }
+            .with_clock(config.clock)
+        })
+        .collect();
+
+    let servers = set.servers.iter().map(|server| global_result(&server.id)).collect();
+    Ok(HierarchicalAnalysis { servers, tasks })
+}
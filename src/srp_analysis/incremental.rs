@@ -0,0 +1,99 @@
+use std::collections::HashSet;
+
+use super::*;
+
+// Ids of all resources locked by a task
+fn resource_ids(task: &Task) -> HashSet<&str> {
+    task.resources().map(|resource| resource.id.as_str()).collect()
+}
+
+/// Indices of the tasks in `new` whose analysis results may differ from those of `old`.
+///
+/// A changed task c affects
+/// - c itself,
+/// - tasks t with P(t) <= P(c), through Bp(t) and I(t),
+/// - tasks t with P(t) > P(l) for any task l sharing a resource with c, since c may change π(r) and
+///   thereby B(t),
+///
+/// where P(c) is taken both before and after the change. In exact and suspension-aware mode R(t) includes R(h)
+/// of every higher priority task h, so every task below an affected task is affected as well.
+///
+/// Returns `None` if the two task sets do not contain the same task ids in the same order, in which case
+/// everything has to be analyzed again.
+pub fn affected_tasks(old: &[Task], new: &[Task], mode: &PreemptionMode) -> Option<Vec<usize>> {
+    if old.len() != new.len() || old.iter().zip(new).any(|(o, n)| o.id != n.id) {
+        return None;
+    }
+
+    let mut affected: HashSet<usize> = HashSet::new();
+    for (index, (o, n)) in old.iter().zip(new).enumerate().filter(|(_, (o, n))| o != n) {
+        affected.insert(index);
+
+        let shared: HashSet<&str> = resource_ids(o).union(&resource_ids(n)).copied().collect();
+        // Lowest priority among c and the tasks it may share resources with, before or after the change
+        let lowest_sharing = old
+            .iter()
+            .chain(new)
+            .filter(|t| t.resources().any(|r| shared.contains(r.id.as_str())))
+            .map(|t| t.level())
+            .chain([o.level(), n.level()])
+            .min()
+            .unwrap();
+        let highest = o.level().max(n.level());
+
+        affected.extend(
+            new.iter()
+                .enumerate()
+                .filter(|(_, t)| t.level() <= highest || t.level() > lowest_sharing)
+                .map(|(i, _)| i),
+        );
+    }
+
+    if mode.uses_higher_priority_response_times() {
+        if let Some(highest) = affected.iter().map(|&i| new[i].level()).max() {
+            affected.extend(new.iter().enumerate().filter(|(_, t)| t.level() < highest).map(|(i, _)| i));
+        }
+    }
+
+    let mut affected: Vec<usize> = affected.into_iter().collect();
+    affected.sort_unstable();
+    Some(affected)
+}
+
+/// Analysis of `new`, reusing the `previous` results of `old` for all tasks not affected by the change,
+/// see `affected_tasks`.
+///
+/// Falls back to a full `srp_analyze_with_progress` if the task sets are not comparable.
+pub fn srp_reanalyze(
+    old: &[Task],
+    previous: &[TaskAnalysis],
+    new: &Tasks,
+    config: &AnalysisConfig,
+) -> Vec<TaskAnalysis> {
+    let prepared = config.prepare(new);
+    let affected = match affected_tasks(&config.prepare(old), &prepared, &config.mode) {
+        Some(affected) if previous.len() == new.len() => affected,
+        _ => {
+            return srp_analyze_with_progress(new, config, |_| {})
+                .iter()
+                .map(|result| TaskAnalysis::from(result).with_clock(config.clock))
+                .collect()
+        }
+    };
+
+    let mut results = previous.to_vec();
+    for index in affected {
+        let analyzed = &prepared[index];
+        let (response_time, interference) =
+            bounded_interference(config.response_time(analyzed, &prepared), analyzed.interference(&prepared));
+        results[index] = TaskAnalysis::from(&(
+            &new[index],
+            response_time,
+            analyzed.blocking_time(&prepared),
+            analyzed.wcet(),
+            interference,
+        ))
+        .with_clock(config.clock);
+    }
+    results
+}
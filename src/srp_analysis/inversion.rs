@@ -0,0 +1,159 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use super::*;
+
+/// Interval of an ingested trace in which a job waited while a job of lower priority ran, without the running
+/// job holding a resource that keeps the waiting one from starting under SRP.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct PriorityInversion {
+    /// Higher priority task whose job waited
+    pub task: String,
+    /// Medium priority task that ran meanwhile
+    pub running: String,
+    /// Preempted lower priority task holding a resource with π(r) >= P(t), if any, the classic inversion
+    pub blocker: Option<String>,
+    /// Resources held by the blocker with π(r) >= P(t)
+    pub resources: Vec<String>,
+    pub start: u64,
+    pub end: u64,
+}
+
+impl PriorityInversion {
+    pub fn duration(&self) -> u64 {
+        self.end - self.start
+    }
+}
+
+// Started job and the resources it holds, innermost last
+struct ActiveJob {
+    task: String,
+    held: Vec<String>,
+}
+
+/// Finds priority inversions in the events of a single core, one event at a time, see `PriorityInversion`.
+///
+/// Events must be valid for a `TraceRecorder`, so they should only be passed on once it accepted them. Events
+/// of tasks that are not in the task set are ignored for the inversions.
+#[derive(Default)]
+pub struct InversionDetector {
+    jobs: Vec<ActiveJob>,
+    // Tasks with a released job that has not started yet
+    released: Vec<String>,
+    time: u64,
+    inversions: Vec<PriorityInversion>,
+}
+
+impl InversionDetector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Inversions found so far, in the order they started. The last ones may still be growing.
+    pub fn inversions(&self) -> &[PriorityInversion] {
+        &self.inversions
+    }
+
+    /// Forgets the started and released jobs, e.g. when the `TraceRecorder` of the events is discarded, keeping
+    /// the inversions found so far.
+    pub fn discard_open_jobs(&mut self) {
+        self.jobs.clear();
+        self.released.clear();
+    }
+
+    /// Accounts for the time since the previous event, then applies the event.
+    pub fn record(&mut self, event: &TraceEvent, tasks: &Tasks) {
+        if event.time > self.time {
+            self.detect(event.time, tasks);
+        }
+        self.time = event.time;
+
+        match &event.kind {
+            TraceEventKind::Release { task } => {
+                if !self.released.contains(task) {
+                    self.released.push(task.clone());
+                }
+            }
+            TraceEventKind::Start { task } => {
+                self.released.retain(|released| released != task);
+                self.jobs.push(ActiveJob { task: task.clone(), held: vec![] });
+            }
+            TraceEventKind::Stop { .. } => {
+                self.jobs.pop();
+            }
+            TraceEventKind::Lock { resource, .. } => {
+                if let Some(job) = self.jobs.last_mut() {
+                    job.held.push(resource.clone());
+                }
+            }
+            TraceEventKind::Unlock { .. } => {
+                if let Some(job) = self.jobs.last_mut() {
+                    job.held.pop();
+                }
+            }
+        }
+    }
+
+    // Inversions between the previous event and `end`, during which nothing changed
+    fn detect(&mut self, end: u64, tasks: &Tasks) {
+        let task = |id: &str| tasks.iter().find(|t| t.id == id);
+        let Some((running, preempted)) = self.jobs.split_last() else { return };
+        let Some(medium) = task(&running.task) else { return };
+        // Jobs released but not started, and preempted jobs above the running one
+        let waiting: Vec<&Task> = self
+            .released
+            .iter()
+            .chain(preempted.iter().map(|job| &job.task))
+            .filter_map(|id| task(id))
+            .filter(|high| high.level() > medium.level())
+            .collect();
+        if waiting.is_empty() {
+            return;
+        }
+        let ceilings: HashMap<String, u8> =
+            resource_ceilings(tasks).into_iter().map(|ceiling| (ceiling.resource, ceiling.ceiling)).collect();
+        // Whether a resource keeps t from preempting under SRP, π(r) >= P(t) among tasks of the same kind
+        let blocks = |resource: &String, holder: &Task, waiting: &Task| match ceilings.get(resource) {
+            Some(&u8::MAX) => true,
+            Some(&ceiling) => holder.kind == waiting.kind && ceiling >= waiting.prio,
+            None => false,
+        };
+
+        for high in waiting {
+            if running.held.iter().any(|resource| blocks(resource, medium, high)) {
+                continue;
+            }
+            let (blocker, resources) = preempted
+                .iter()
+                .filter_map(|job| {
+                    let low = task(&job.task)?;
+                    let resources: Vec<String> =
+                        job.held.iter().filter(|resource| blocks(resource, low, high)).cloned().collect();
+                    (!resources.is_empty()).then_some((Some(job.task.clone()), resources))
+                })
+                .next()
+                .unwrap_or_default();
+
+            // Extend the inversion of the same jobs up to the previous event
+            let continued = self.inversions.iter_mut().rev().find(|inversion| {
+                inversion.end == self.time
+                    && inversion.task == high.id
+                    && inversion.running == medium.id
+                    && inversion.blocker == blocker
+            });
+            match continued {
+                Some(inversion) => inversion.end = end,
+                None => self.inversions.push(PriorityInversion {
+                    task: high.id.clone(),
+                    running: medium.id.clone(),
+                    blocker,
+                    resources,
+                    start: self.time,
+                    end,
+                }),
+            }
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use super::*;
+
+/// Slack ratio (D(t) - R(t)) / D(t) below which a task has a tight margin, see `MarginQuadrant`.
+pub const TIGHT_SLACK_RATIO: f32 = 0.2;
+
+/// Health of a task by its utilization and its margin to the deadline, see `task_margins`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum MarginQuadrant {
+    /// Below average utilization, comfortable margin
+    Healthy,
+    /// Above average utilization, comfortable margin: a candidate to be shortened should the margins of others
+    /// shrink
+    Heavy,
+    /// Below average utilization, tight margin: delayed by the other tasks rather than by itself
+    Starved,
+    /// Above average utilization, tight margin, or no bounded response time
+    Critical,
+}
+
+impl MarginQuadrant {
+    pub fn label(self) -> &'static str {
+        match self {
+            MarginQuadrant::Healthy => "healthy",
+            MarginQuadrant::Heavy => "heavy",
+            MarginQuadrant::Starved => "starved",
+            MarginQuadrant::Critical => "critical",
+        }
+    }
+}
+
+/// Utilization of a task against its margin to the deadline, a point of the margin scatter chart.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct TaskMargin {
+    pub id: String,
+    /// U(t) = C(t) / A(t)
+    pub utilization: f32,
+    /// (D(t) - R(t)) / D(t), negative for a missed deadline, `None` if R(t) is unbounded
+    pub slack_ratio: Option<f32>,
+    pub quadrant: MarginQuadrant,
+}
+
+/// U(t) and the slack ratio of every task of the results of `srp_analyze`, in order, each in the quadrant split
+/// at the mean U(t) of the set and at `TIGHT_SLACK_RATIO`.
+pub fn task_margins(results: &[AnalysisResult]) -> Vec<TaskMargin> {
+    let utilizations: Vec<f32> = results.iter().map(|(task, ..)| task.load_factor().unwrap_or(0.0)).collect();
+    let mean = mean_utilization(&utilizations);
+    results
+        .iter()
+        .zip(utilizations)
+        .map(|((task, response_time, ..), utilization)| {
+            let slack_ratio = response_time
+                .as_ref()
+                .ok()
+                .map(|&r| (task.deadline as f32 - r as f32) / task.deadline as f32);
+            let heavy = utilization > mean;
+            let quadrant = match slack_ratio {
+                Some(slack) if slack >= TIGHT_SLACK_RATIO && heavy => MarginQuadrant::Heavy,
+                Some(slack) if slack >= TIGHT_SLACK_RATIO => MarginQuadrant::Healthy,
+                Some(_) if !heavy => MarginQuadrant::Starved,
+                _ => MarginQuadrant::Critical,
+            };
+            TaskMargin { id: task.id.clone(), utilization, slack_ratio, quadrant }
+        })
+        .collect()
+}
+
+/// Mean of the utilizations, where `task_margins` splits light from heavy tasks, 0 without any.
+pub fn mean_utilization(utilizations: &[f32]) -> f32 {
+    if utilizations.is_empty() {
+        return 0.0;
+    }
+    utilizations.iter().sum::<f32>() / utilizations.len() as f32
+}
@@ -0,0 +1,11 @@
+/// ceiling(dividend / divisor) in exact integer arithmetic, without the rounding errors of a detour through
+/// floating point for values beyond 2^24.
+///
+/// Nothing fits into a zero divisor, so a positive dividend gives `u32::MAX`, 0 gives 0.
+pub fn ceil_div(dividend: u32, divisor: u32) -> u32 {
+    match (dividend, divisor) {
+        (0, _) => 0,
+        (_, 0) => u32::MAX,
+        _ => dividend.div_ceil(divisor),
+    }
+}
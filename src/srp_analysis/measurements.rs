@@ -0,0 +1,133 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::*;
+
+/// Measured execution times by task id, e.g. read from a cycle counter at the end of every job.
+pub type Measurements = BTreeMap<String, Vec<u32>>;
+
+/// Statistic of the measured execution times used as C(t) in the analysis.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum WcetStatistic {
+    Min,
+    Avg,
+    P50,
+    P90,
+    P99,
+    /// The largest observed execution time, a lower bound of the real WCET
+    #[default]
+    Max,
+}
+
+/// Summary of the measured execution times of a task.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct ExecutionTimeStats {
+    pub task: String,
+    pub samples: usize,
+    pub min: u32,
+    /// Rounded up to whole time units
+    pub avg: u32,
+    pub p50: u32,
+    pub p90: u32,
+    pub p99: u32,
+    pub max: u32,
+}
+
+// Nearest-rank percentile of sorted samples
+fn percentile(sorted: &[u32], p: u32) -> u32 {
+    let rank = (sorted.len() * p as usize).div_ceil(100);
+    sorted[rank.max(1) - 1]
+}
+
+impl ExecutionTimeStats {
+    /// Statistics of the samples, `None` if there are none.
+    pub fn new(task: &str, samples: &[u32]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+        let n = sorted.len();
+        let sum: u64 = sorted.iter().map(|&s| s as u64).sum();
+
+        Some(ExecutionTimeStats {
+            task: task.to_string(),
+            samples: n,
+            min: sorted[0],
+            avg: sum.div_ceil(n as u64) as u32,
+            p50: percentile(&sorted, 50),
+            p90: percentile(&sorted, 90),
+            p99: percentile(&sorted, 99),
+            max: sorted[n - 1],
+        })
+    }
+
+    pub fn get(&self, statistic: WcetStatistic) -> u32 {
+        match statistic {
+            WcetStatistic::Min => self.min,
+            WcetStatistic::Avg => self.avg,
+            WcetStatistic::P50 => self.p50,
+            WcetStatistic::P90 => self.p90,
+            WcetStatistic::P99 => self.p99,
+            WcetStatistic::Max => self.max,
+        }
+    }
+}
+
+/// Statistics of every measured task, ordered by task id, skipping tasks without samples.
+pub fn execution_time_stats(measurements: &Measurements) -> Vec<ExecutionTimeStats> {
+    measurements
+        .iter()
+        .filter_map(|(task, samples)| ExecutionTimeStats::new(task, samples))
+        .collect()
+}
+
+/// Sets C(t) of every measured task to the chosen statistic of its measurements, see `TaskParameter::Wcet`.
+///
+/// Tasks without measurements keep their WCET, measurements of unknown tasks are an error.
+pub fn apply_measured_wcets(
+    tasks: &mut Tasks,
+    measurements: &Measurements,
+    statistic: WcetStatistic,
+) -> Result<(), String> {
+    for stats in execution_time_stats(measurements) {
+        let wcet = ParameterRef { task: stats.task.clone(), parameter: TaskParameter::Wcet };
+        wcet.apply(tasks, stats.get(statistic))?;
+    }
+    Ok(())
+}
+
+/// Largest response time observed on the target, against the analytical worst case R(t).
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct ObservedResponseTime {
+    pub task: String,
+    pub observed: u32,
+    /// Either `{"Ok": R(t)}` or `{"Err": reason}`
+    #[schema(value_type = Object)]
+    pub bound: Result<u32, String>,
+    pub deadline: u32,
+    /// The observation exceeds R(t), so the analysis or the model of the task is wrong
+    pub exceeds_bound: bool,
+}
+
+/// Observed maximum response times by task id compared to the analysis results, in the order of the results.
+///
+/// Tasks without observations are skipped. An unbounded R(t) is never exceeded.
+pub fn compare_observed(results: &[TaskAnalysis], observed: &BTreeMap<String, u32>) -> Vec<ObservedResponseTime> {
+    results
+        .iter()
+        .filter_map(|result| {
+            let &max = observed.get(&result.id)?;
+            Some(ObservedResponseTime {
+                task: result.id.clone(),
+                observed: max,
+                bound: result.response_time.clone(),
+                deadline: result.deadline,
+                exceeds_bound: matches!(result.response_time, Ok(r) if max > r),
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,75 @@
+use super::*;
+
+impl Task {
+    /// Whether the task keeps running after a mode change to HI, i.e. it has a C(HI).
+    pub fn is_hi_criticality(&self) -> bool {
+        self.wcet_hi.is_some()
+    }
+
+    /// C(HI) of a HI-criticality task, never below C(LO) = C(t), C(t) for a LO-criticality task.
+    pub fn hi_wcet(&self) -> u32 {
+        self.wcet_hi.map_or(self.wcet(), |wcet_hi| wcet_hi.max(self.wcet()))
+    }
+
+    // sum(C(e)) of all other tasks e where P(e) = P(t), at C(HI) for HI-criticality tasks if `hi`
+    fn mixed_equal_interference<T>(&self, tasks: &T, hi: bool) -> u32
+    where
+        T: std::ops::Deref<Target = [Task]> + Sized,
+    {
+        tasks
+            .iter()
+            .filter(|e| e.level() == self.level() && e.id != self.id)
+            .map(|e| if hi { e.hi_wcet() } else { e.wcet() })
+            .sum()
+    }
+
+    /// R_LO(t) = B(t) + C(t) + sum(C(e)) + sum(η_h(R_LO(t)) * C(h)) for all other tasks e where P(e) = P(t) and
+    /// all tasks h where P(h) > P(t)
+    ///
+    /// Response time in LO mode, where every task runs with its LO-criticality WCET C(LO) = C(t).
+    pub fn lo_mode_response_time<T>(&self, tasks: &T) -> Result<u32, String>
+    where
+        T: std::ops::Deref<Target = [Task]> + Sized,
+    {
+        self.iterative_response_time(tasks, 0).map(|(r_lo, _)| r_lo)
+    }
+
+    /// R*(t) = B(t) + C_HI(t) + sum(C_HI(e)) + sum(η_h(R*(t)) * C_HI(h)) + sum(η_l(R_LO(t)) * C(l)) for all other
+    /// tasks e where P(e) = P(t), all HI-criticality tasks h and all LO-criticality tasks l where P(h), P(l) > P(t)
+    ///
+    /// AMC-rtb bound on the response time of a HI-criticality task across a mode change: LO-criticality tasks
+    /// are dropped at the change, so they only interfere while t has not yet exceeded R_LO(t). Critical
+    /// sections keep their LO mode lengths, so B(t) is the same in both modes.
+    pub fn hi_mode_response_time<T>(&self, tasks: &T) -> Result<u32, String>
+    where
+        T: std::ops::Deref<Target = [Task]> + Sized,
+    {
+        let r_lo = self.lo_mode_response_time(tasks)?;
+        let dropped = tasks
+            .iter()
+            .filter(|l| l.level() > self.level() && !l.is_hi_criticality())
+            .try_fold(0u32, |acc, l| acc.checked_add(l.wcet().checked_mul(l.max_releases(r_lo))?))
+            .ok_or_else(|| "Busy window overflow".to_string())?;
+
+        let own = self.blocking_time(tasks) + self.hi_wcet() + self.mixed_equal_interference(tasks, true) + dropped;
+        fixed_point(own, |r| {
+            tasks
+                .iter()
+                .filter(|h| h.level() > self.level() && h.is_hi_criticality())
+                .try_fold(own, |acc, h| acc.checked_add(h.hi_wcet().checked_mul(h.max_releases(r))?))
+        })
+    }
+
+    /// R(t) = max(R_LO(t), R*(t)) of a HI-criticality task, R_LO(t) of a LO-criticality task, which is only
+    /// guaranteed in LO mode, see `Task::hi_mode_response_time`.
+    pub fn mixed_criticality_response_time<T>(&self, tasks: &T) -> Result<u32, String>
+    where
+        T: std::ops::Deref<Target = [Task]> + Sized,
+    {
+        let r_lo = self.lo_mode_response_time(tasks)?;
+        if !self.is_hi_criticality() {
+            return Ok(r_lo);
+        }
+        Ok(r_lo.max(self.hi_mode_response_time(tasks)?))
+    }
+}
@@ -1,25 +1,196 @@
 #![allow(unused)]
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+mod arrival;
+mod budget;
+mod builder;
+mod ceilings;
+mod certificate;
+mod chain;
+mod clock;
+mod component;
+mod config;
+mod context;
+mod convergence;
+mod deadline;
+mod envelope;
+mod examples;
+mod explain;
+mod fuzz;
+mod generator;
+mod group;
+mod hierarchy;
+mod math;
+mod incremental;
+mod inversion;
+mod margin;
+mod measurements;
+mod mixed;
+mod normalize;
+mod offset;
+mod origin;
+mod params;
+mod partition;
+mod plugin;
+mod priority;
+mod recorder;
+mod refactoring;
+mod resources;
+mod scale;
+mod scenario;
+mod sensitivity;
+mod shedding;
+mod simulation;
+mod stack;
+mod suspension;
+mod sweep;
+mod threshold;
+mod trace_check;
+mod wcet_check;
+mod weakly_hard;
+
+#[cfg(test)]
+mod proptests;
+#[cfg(test)]
+mod tests;
+
+pub use arrival::*;
+pub use budget::*;
+pub use builder::*;
+pub use ceilings::*;
+pub use certificate::*;
+pub use chain::*;
+pub use clock::*;
+pub use component::*;
+pub use config::*;
+pub use context::*;
+pub use convergence::*;
+pub use deadline::*;
+pub use envelope::*;
+pub use examples::*;
+pub use explain::*;
+pub use fuzz::*;
+pub use generator::*;
+pub use group::*;
+pub use hierarchy::*;
+pub use math::*;
+pub use incremental::*;
+pub use inversion::*;
+pub use margin::*;
+pub use measurements::*;
+pub use normalize::*;
+pub use offset::*;
+pub use origin::*;
+pub use params::*;
+pub use partition::*;
+pub use plugin::*;
+pub use priority::*;
+pub use recorder::*;
+pub use refactoring::*;
+pub use resources::*;
+pub use scale::*;
+pub use scenario::*;
+pub use sensitivity::*;
+pub use shedding::*;
+pub use simulation::*;
+pub use stack::*;
+pub use suspension::*;
+pub use sweep::*;
+pub use threshold::*;
+pub use trace_check::*;
+pub use wcet_check::*;
+pub use weakly_hard::*;
 
 // common data structures
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
 pub struct Task {
     pub id: String,
     pub prio: u8,
     pub deadline: u32,
-    pub inter_arrival: u32,
+    pub arrival: ArrivalModel,
     pub trace: Trace,
+    /// Optional subsystem tag (e.g. an ECU) used to aggregate results per group.
+    pub group: Option<String>,
+    /// Worst-case stack usage of the task in bytes.
+    #[serde(default)]
+    pub stack: u32,
+    /// Worst-case time S(t) a job self-suspends, e.g. waiting for a DMA transfer, outside of critical sections.
+    #[serde(default)]
+    pub suspension: u32,
+    /// Release jitter J(t), the longest delay of a release after the arrival of a job.
+    #[serde(default)]
+    pub jitter: u32,
+    /// Offset Φ(t) of the first release, see `OffsetModel`.
+    #[serde(default)]
+    pub offset: u32,
+    #[serde(default)]
+    pub kind: TaskKind,
+    #[serde(default)]
+    pub criticality: Criticality,
+    /// C(HI), the WCET at the HI criticality level of the mixed-criticality mode, `None` for a LO-criticality
+    /// task, see `Task::hi_mode_response_time`.
+    #[serde(default)]
+    pub wcet_hi: Option<u32>,
+    /// (m, k) constraint of a task that may miss some deadlines, see `check_weakly_hard`, `None` for a task that
+    /// must meet all of them.
+    #[serde(default)]
+    pub weakly_hard: Option<WeaklyHard>,
+    /// Preemption threshold γ(t) >= P(t) the task runs at once it started, `None` to run at P(t), see
+    /// `Task::preemption_threshold`.
+    #[serde(default)]
+    pub threshold: Option<u8>,
+}
+
+/// How a task is scheduled, see `Task::level`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum TaskKind {
+    /// Scheduled by priority under SRP
+    #[default]
+    Software,
+    /// Interrupt handler, e.g. an RTIC hardware task, preempting every software task regardless of ceilings
+    Interrupt,
+}
+
+/// How much a task matters when the task set is overloaded, see `shedding_plans`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Criticality {
+    /// Has to meet its deadline
+    #[default]
+    Hard,
+    /// Should meet its deadline, but is kept running if it does not
+    Soft,
+    /// May be dropped to keep the hard tasks schedulable
+    Optional,
 }
 
-//#[derive(Debug, Clone)]
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
 pub struct Trace {
     pub id: String,
     pub start: u32,
     pub end: u32,
     pub inner: Vec<Trace>,
+    /// Section that cannot be preempted at all (e.g. interrupts disabled), rather than a resource lock.
+    #[serde(default)]
+    pub non_preemptible: bool,
+    #[serde(default)]
+    pub lock: LockType,
+}
+
+/// How a critical section locks its resource, see `TraceSchedulable::ceiling_priority`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum LockType {
+    #[default]
+    Exclusive,
+    /// Read access to a reader/writer lock, shared with other readers, e.g. `&r` in RTIC
+    Shared,
 }
 
 // useful types
@@ -27,16 +198,94 @@ pub struct Trace {
 // Our task set
 pub type Tasks = Vec<Task>;
 
-// A map from Task/Resource identifiers to priority
-pub type IdPrio = HashMap<String, u8>;
+// A map from Task/Resource identifiers to priority, ordered by identifier
+pub type IdPrio = BTreeMap<String, u8>;
+
+// A map from Task identifiers to a set of Resource identifiers, both ordered by identifier
+pub type TaskResources = BTreeMap<String, BTreeSet<String>>;
+
+// Analysis result of a single task: (&Task, R(t), B(t), C(t), I(t))
+pub type AnalysisResult<'a> = (&'a Task, Result<u32, String>, u32, u32, u32);
+
+/// Owned, serializable version of an `AnalysisResult`, not borrowing from the task set.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct TaskAnalysis {
+    pub id: String,
+    pub deadline: u32,
+    /// Either `{"Ok": R(t)}` or `{"Err": reason}`
+    #[schema(value_type = Object)]
+    pub response_time: Result<u32, String>,
+    pub blocking_time: u32,
+    pub wcet: u32,
+    pub interference: u32,
+    /// The same times in microseconds, if the analysis has a clock model
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub real_time: Option<RealTimeAnalysis>,
+}
+
+/// Critical section that blocks a task for B(t), e.g. "T1 via R3 for 8 units".
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct Blocker {
+    /// Lower priority task holding the resource
+    pub task: String,
+    pub resource: String,
+    pub duration: u32,
+}
+
+impl std::fmt::Display for Blocker {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} via {} for {} units", self.task, self.resource, self.duration)
+    }
+}
 
-// A map from Task identifiers to a set of Resource identifiers
-pub type TaskResources = HashMap<String, HashSet<String>>;
+/// Times of a `TaskAnalysis` in microseconds, see `ClockModel`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct RealTimeAnalysis {
+    pub deadline_us: f64,
+    /// Unbounded response times have none
+    pub response_time_us: Option<f64>,
+    pub blocking_time_us: f64,
+    pub wcet_us: f64,
+    pub interference_us: f64,
+}
+
+impl From<&AnalysisResult<'_>> for TaskAnalysis {
+    fn from((task, response_time, blocking_time, wcet, interference): &AnalysisResult) -> Self {
+        TaskAnalysis {
+            id: task.id.clone(),
+            deadline: task.deadline,
+            response_time: response_time.clone(),
+            blocking_time: *blocking_time,
+            wcet: *wcet,
+            interference: *interference,
+            real_time: None,
+        }
+    }
+}
+
+impl TaskAnalysis {
+    /// R(t) <= D(t)
+    pub fn meets_deadline(&self) -> bool {
+        matches!(self.response_time, Ok(r) if r <= self.deadline)
+    }
+
+    /// Adds the times in microseconds of the clock, if any.
+    pub fn with_clock(self, clock: Option<ClockModel>) -> Self {
+        let real_time = clock.map(|clock| RealTimeAnalysis {
+            deadline_us: clock.micros(self.deadline),
+            response_time_us: self.response_time.as_ref().ok().map(|r| clock.micros(*r)),
+            blocking_time_us: clock.micros(self.blocking_time),
+            wcet_us: clock.micros(self.wcet),
+            interference_us: clock.micros(self.interference),
+        });
+        TaskAnalysis { real_time, ..self }
+    }
+}
 
 // Derives the above maps from a set of tasks
 pub fn pre_analysis(tasks: &Tasks) -> (IdPrio, TaskResources) {
-    let mut ip: IdPrio = HashMap::new();
-    let mut tr: TaskResources = HashMap::new();
+    let mut ip: IdPrio = BTreeMap::new();
+    let mut tr: TaskResources = BTreeMap::new();
     for t in tasks {
         update_prio(t.prio, &t.trace, &mut ip);
         for i in &t.trace.inner {
@@ -64,7 +313,7 @@ fn update_tr(s: String, trace: &Trace, trmap: &mut TaskResources) {
     if let Some(seen) = trmap.get_mut(&s) {
         seen.insert(trace.id.clone());
     } else {
-        let mut hs = HashSet::new();
+        let mut hs = BTreeSet::new();
         hs.insert(trace.id.clone());
         trmap.insert(s.clone(), hs);
     }
@@ -72,24 +321,47 @@ fn update_tr(s: String, trace: &Trace, trmap: &mut TaskResources) {
         update_tr(s.clone(), trace, trmap);
     }
 }
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
 pub enum PreemptionMode {
     Exact,
     Approximate,
+    /// Self-suspensions count as execution time, see `Task::suspension_oblivious_response_time`.
+    #[serde(rename = "suspension_oblivious")]
+    SuspensionOblivious,
+    /// Self-suspensions count as release jitter, see `Task::suspension_aware_response_time`.
+    #[serde(rename = "suspension_aware")]
+    SuspensionAware,
+    /// Vestal model with LO and HI WCETs, see `Task::mixed_criticality_response_time`.
+    #[serde(rename = "mixed_criticality")]
+    MixedCriticality,
+    /// Start and finish times under preemption thresholds, see `Task::threshold_response_time`.
+    Threshold,
+}
+
+impl PreemptionMode {
+    /// Whether R(t) depends on R(h) of the higher priority tasks h, not only on which tasks they are.
+    pub fn uses_higher_priority_response_times(&self) -> bool {
+        matches!(self, PreemptionMode::Exact | PreemptionMode::SuspensionAware)
+    }
 }
 
 pub trait Schedulable {
     fn wcet(&self) -> u32;
-    fn resources<'a>(&'a self) -> Box<dyn Iterator<Item = &Trace> + 'a>;
+    fn resources(&self) -> ResourceIter<'_>;
 }
 
 pub trait TaskSchedulable: Schedulable {
     fn blocking_time<T>(&self, tasks: &T) -> u32
     where
         T: std::ops::Deref<Target = [Task]> + Sized;
-    fn busy_period<T>(&self, tasks: &T) -> u32
+    fn busy_period<T>(&self, tasks: &T) -> Result<u32, String>
     where
         T: std::ops::Deref<Target = [Task]> + Sized;
-    fn interference<T>(&self, tasks: &T) -> u32
+    fn interference<T>(&self, tasks: &T) -> Result<u32, String>
+    where
+        T: std::ops::Deref<Target = [Task]> + Sized;
+    fn interference_breakdown<T>(&self, tasks: &T) -> Result<BTreeMap<String, u32>, String>
     where
         T: std::ops::Deref<Target = [Task]> + Sized;
     fn response_time<T>(&self, tasks: &T, mode: &PreemptionMode) -> Result<u32, String>
@@ -109,82 +381,174 @@ impl Schedulable for Task {
     fn wcet(&self) -> u32 {
         self.trace.end - self.trace.start
     }
-    /// Creates an dynamic iterator of all resources within this task recursively.
-    fn resources<'a>(&'a self) -> Box<dyn Iterator<Item = &Trace> + 'a> {
+    /// Iterates over all resources within this task in pre-order, see `ResourceIter`.
+    fn resources(&self) -> ResourceIter<'_> {
         self.trace.resources()
     }
 }
 
-impl Schedulable for Trace {
-    /// C(t)
-    #[inline(always)]
-    fn wcet(&self) -> u32 {
-        self.end - self.start
-    }
-    /// Creates an dynamic iterator of all resources within this trace recursively.
-    fn resources<'a>(&'a self) -> Box<dyn Iterator<Item = &Trace> + 'a> {
-        Box::new(
-        self.inner.iter()
-            .chain(self.inner.iter().flat_map(|n| n.resources())),
-        )
-    }
-}
-
-impl TaskSchedulable for Task {
-    /// B(t) = max(C(l_r)) where P(l) < P(t) and π(l_r) >= P(t)
-    fn blocking_time<T>(&self, tasks: &T) -> u32
+impl Task {
+    /// Lower priority task l and critical section l_r behind B(t), see `TaskSchedulable::blocking_time`, `None`
+    /// if nothing blocks the task.
+    pub fn blocker<T>(&self, tasks: &T) -> Option<Blocker>
     where
         T: std::ops::Deref<Target = [Task]> + Sized
     {
         // Firstly, filter tasks by priority, only including lower priority tasks
         // Secondly, for that task, filter all of its associated resources if their ceiling priorities are larger than the target's task.
-        // Lastly, with an iterator of P(l) < P(t) and π(l_r) >= P(t), take out the max of those blockings.
-        tasks.iter()
-            .filter(|l| l.prio < self.prio)
+        // Lastly, with an iterator of P(l) < P(t) and π(l_r) >= P(t), take out the longest of those blockings.
+        // A lower priority task whose preemption threshold γ(l) >= P(t) blocks as a whole, see `threshold_blocker`.
+        let sections = tasks.iter()
+            .filter(|l| l.level() < self.level())
             .flat_map(|lower_priority_task| {
-                lower_priority_task.resources().filter_map(|resource| {
-
-                    // Critical section duration of a resource
-                    let critical_section = resource.wcet();
+                lower_priority_task.resources().filter_map(move |resource| {
+                    if lower_priority_task.kind != self.kind && !resource.non_preemptible {
+                        return None;
+                    }
 
                     // Compute ceiling priority of the specified resource within all tasks in &[Task]
                     let ceiling_priority = resource.ceiling_priority(tasks);
 
                     // Check if the ceiling priority satisfies the condition
                     if ceiling_priority >= self.prio {
-                        Some(critical_section)
+                        Some(Blocker {
+                            task: lower_priority_task.id.clone(),
+                            resource: resource.id.clone(),
+                            // Critical section duration of a resource
+                            duration: resource.wcet(),
+                        })
                     } else {
                         None
                     }
                 })
-            })
-            .max() // Find the maximum critical section time
-            .unwrap_or(0) // Return 0 if no valid critical section found
+            });
+        sections
+            .chain(self.threshold_blocker(tasks))
+            .max_by_key(|blocker| blocker.duration) // Find the longest critical section
     }
 
-    /// Bp(t)
-    fn busy_period<T>(&self, tasks: &T) -> u32
+    pub fn is_interrupt(&self) -> bool {
+        self.kind == TaskKind::Interrupt
+    }
+
+    /// λ(t) = (kind, P(t)), the level t is scheduled at: interrupts above all software tasks, and by P(t) among
+    /// tasks of the same kind.
+    ///
+    /// "Higher priority" and "equal priority" in the analysis compare levels, so P(h) > P(t) reads λ(h) > λ(t).
+    pub fn level(&self) -> (TaskKind, u8) {
+        (self.kind, self.prio)
+    }
+
+    /// sum(C(e)) for all other tasks e where P(e) = P(t)
+    ///
+    /// Jobs of equal priority run in FIFO order and do not preempt each other, so under constrained deadlines at
+    /// most one pending job of every such task e executes before a job of t.
+    pub fn equal_priority_interference<T>(&self, tasks: &T) -> u32
+    where
+        T: std::ops::Deref<Target = [Task]> + Sized,
+    {
+        tasks
+            .iter()
+            .filter(|e| e.level() == self.level() && e.id != self.id)
+            .map(|e| e.wcet())
+            .sum()
+    }
+
+    /// η_t(w) = η(w + J(t)), the most releases of t within any window of length w, see
+    /// `ArrivalModel::max_releases`.
+    pub fn max_releases(&self, w: u32) -> u32 {
+        self.arrival.max_releases(w.saturating_add(self.jitter))
+    }
+
+    /// L(t) = C(t) * n / A(t), where n is the number of releases per inter arrival time.
+    ///
+    /// Returns `None` on a zero inter arrival time.
+    pub fn load_factor(&self) -> Option<f32> {
+        match self.arrival.inter_arrival() {
+            0 => None,
+            a => Some((self.wcet() * self.arrival.releases_per_inter_arrival()) as f32 / a as f32),
+        }
+    }
+}
+
+impl Schedulable for Trace {
+    /// C(t)
+    #[inline(always)]
+    fn wcet(&self) -> u32 {
+        self.end - self.start
+    }
+    /// Iterates over all resources nested within this trace in pre-order, see `ResourceIter`.
+    fn resources(&self) -> ResourceIter<'_> {
+        ResourceIter::new(self)
+    }
+}
+
+impl TaskSchedulable for Task {
+    /// B(t) = max(C(l_r)) where P(l) < P(t) and π(l_r) >= P(t)
+    ///
+    /// Non-preemptible sections l_r of lower priority tasks always satisfy π(l_r) >= P(t). Interrupts ignore the
+    /// ceilings of software tasks, so only their non-preemptible sections block interrupts.
+    fn blocking_time<T>(&self, tasks: &T) -> u32
+    where
+        T: std::ops::Deref<Target = [Task]> + Sized
+    {
+        self.blocker(tasks).map_or(0, |blocker| blocker.duration)
+    }
+
+    /// Bp(t), `Err` on overflow
+    fn busy_period<T>(&self, tasks: &T) -> Result<u32, String>
     where
         T: std::ops::Deref<Target = [Task]> + Sized
     {
         tasks.iter()
-            .filter(|t| t.prio >= self.prio)
-            .map(|t| t.wcet())
-            .sum()
+            .filter(|t| t.level() >= self.level())
+            .try_fold(0u32, |sum, t| sum.checked_add(t.wcet()))
+            .ok_or_else(|| "Busy period overflow".to_string())
     }
 
-    /// I(t) = sum(C(h) * ceiling(Bp(t) / A(h))) for all tasks h where P(h) > P(t)
-    fn interference<T>(&self, tasks: &T) -> u32
+    /// I(t) = sum(C(h) * η_h(Bp(t))) for all tasks h where P(h) > P(t), plus sum(C(e)) for all other tasks e
+    /// where P(e) = P(t)
+    ///
+    /// where η_h(Bp(t)) = ceiling((Bp(t) + J(h)) / A(h)) for periodic and sporadic tasks, see `Task::max_releases`,
+    /// and equal priority tasks interfere at most once, see `Task::equal_priority_interference`.
+    ///
+    /// `Err` on overflow, e.g. for an interfering task with an inter arrival time of zero, whose releases saturate.
+    fn interference<T>(&self, tasks: &T) -> Result<u32, String>
     where
         T: std::ops::Deref<Target = [Task]> + Sized
     {
+        let busy_period = self.busy_period(tasks)?;
         tasks.iter()
-            .filter(|h| h.prio > self.prio)
-            .map(|h| h.wcet() * (self.busy_period(tasks) as f32 / h.inter_arrival as f32).ceil() as u32)
-            .sum()
+            .filter(|h| h.level() > self.level())
+            .try_fold(self.equal_priority_interference(tasks), |sum, h| {
+                sum.checked_add(h.wcet().checked_mul(h.max_releases(busy_period))?)
+            })
+            .ok_or_else(|| "Interference overflow".to_string())
+    }
+
+    /// I_h(t) = C(h) * η_h(Bp(t)) for every task h where P(h) > P(t), and C(h) for every other task h where
+    /// P(h) = P(t), by task id
+    ///
+    /// The contributions sum up to I(t), `Err` whenever I(t) overflows.
+    fn interference_breakdown<T>(&self, tasks: &T) -> Result<BTreeMap<String, u32>, String>
+    where
+        T: std::ops::Deref<Target = [Task]> + Sized
+    {
+        self.interference(tasks)?;
+        let busy_period = self.busy_period(tasks)?;
+        let mut breakdown = BTreeMap::new();
+        for h in tasks.iter().filter(|h| h.level() > self.level()) {
+            *breakdown.entry(h.id.clone()).or_insert(0) += h.wcet() * h.max_releases(busy_period);
+        }
+        for e in tasks.iter().filter(|e| e.level() == self.level() && e.id != self.id) {
+            *breakdown.entry(e.id.clone()).or_insert(0) += e.wcet();
+        }
+        Ok(breakdown)
     }
 
     /// R(t) = B(t) + C(t) + I(t)
+    ///
+    /// In exact mode I(t) = sum(R(h)) for all tasks h where P(h) > P(t), plus the equal priority interference.
     fn response_time<T>(&self, tasks: &T, mode: &PreemptionMode) -> Result<u32, String>
     where
         T: std::ops::Deref<Target = [Task]> + Sized
@@ -193,18 +557,22 @@ impl TaskSchedulable for Task {
         let c_t = self.wcet();                 // Compute critical time
         match mode {
             PreemptionMode::Approximate => {
-                let i_t = self.interference(tasks);   // Compute interference
-                let response_time = b_t + c_t + i_t;    // R(t) = B(t) + C(t) + I(t)
-                Ok(response_time)
+                let i_t = self.interference(tasks)?;   // Compute interference
+                // R(t) = B(t) + C(t) + I(t)
+                b_t.checked_add(c_t).and_then(|r| r.checked_add(i_t)).ok_or_else(|| "Interference overflow".to_string())
             },
             PreemptionMode::Exact => {
-                let mut total_response_time = b_t + c_t;
+                let mut total_response_time = b_t
+                    .checked_add(c_t)
+                    .and_then(|r| r.checked_add(self.equal_priority_interference(tasks)))
+                    .ok_or_else(|| "Busy window overflow".to_string())?;
 
                 // Recursively calculate interference from higher-priority tasks
-                for higher_priority_task in tasks.iter().filter(|h| h.prio > self.prio) {
+                for higher_priority_task in tasks.iter().filter(|h| h.level() > self.level()) {
                     let interference = higher_priority_task.response_time(tasks, mode)?;
-                    
-                    total_response_time += interference;
+                    total_response_time = total_response_time
+                        .checked_add(interference)
+                        .ok_or_else(|| "Interference overflow".to_string())?;
                 }
 
                 // Check against the deadline
@@ -214,62 +582,153 @@ impl TaskSchedulable for Task {
                     Ok(total_response_time)
                 }
             },
+            PreemptionMode::SuspensionOblivious => self.suspension_oblivious_response_time(tasks),
+            PreemptionMode::SuspensionAware => self.suspension_aware_response_time(tasks),
+            PreemptionMode::MixedCriticality => self.mixed_criticality_response_time(tasks),
+            PreemptionMode::Threshold => self.threshold_response_time(tasks),
         }
     }
 }
 
 impl TraceSchedulable for Trace {
     /// Calculate ceiling priority π(r) of a given resource as a &Trace, against a set of tasks potentially using the given resource.
+    ///
+    /// Non-preemptible sections block every task, so their ceiling is the highest possible priority.
+    ///
+    /// Read-shared sections only conflict with exclusive sections on the same resource, so their ceiling
+    /// π_r(r) is the highest priority of the tasks locking r exclusively, 0 if there are none.
     fn ceiling_priority<T>(&self, tasks: &T) -> u8
     where
         T: std::ops::Deref<Target = [Task]> + Sized
     {
+        if self.non_preemptible {
+            return u8::MAX;
+        }
+        let shared = self.lock == LockType::Shared;
+
         // Iterate through the entire task set, matching any resources id corresponding
         // with the given resource. The set of task matches is transformed into their priorities, then return max value.
         tasks.iter()
-            .filter(|task| task.resources().any(|res| res.id == self.id))
+            .filter(|task| {
+                task.resources().any(|res| res.id == self.id && (!shared || res.lock == LockType::Exclusive))
+            })
             .map(|task| task.prio)
             .max()
-            .unwrap_or(1)
+            .unwrap_or(if shared { 0 } else { 1 })
     }
 }
-/// L_tot = sum(L(T)) where L(t) = C(t) / A(t) for all t in &Tasks.
+/// L(t) = C(t) / A(t) of every task, see `Task::load_factor`.
+pub fn load_factors(tasks: &[Task]) -> Result<Vec<(&Task, f32)>, String> {
+    tasks
+        .iter()
+        .map(|task| match task.load_factor() {
+            Some(load_factor) => Ok((task, load_factor)),
+            // Division by zero
+            None => Err(format!("Error: Task '{}' has an inter_arrival time of zero.", task.id)),
+        })
+        .collect()
+}
+
+/// L_tot = sum(L(T)) where L(t) = C(t) / A(t) for all t in &Tasks, see `load_factors` for the contributions.
 pub fn total_load_factor<T>(tasks: &T) -> Result<f32, String>
 where
     // .iter() returns a slice iterator, so the generic T needs to be able to dereference into a slice.
     // We cannot use .into_iter() because it consumes the collection, making it non-existent afterwards.
     T: std::ops::Deref<Target = [Task]> + Sized
 {
-    tasks.iter()
-        .try_fold(0.0, |acc, task| {
-            // Check for division by zero
-            if task.inter_arrival == 0 {
-                Err(format!("Error: Task '{}' has an inter_arrival time of zero.", task.id))
-            } else {
-                // Calculate the load factor and accumulate the result
-                Ok(acc + task.wcet() as f32 / task.inter_arrival as f32)
-            }
-        })
+    Ok(load_factors(tasks)?.iter().map(|(_, load_factor)| load_factor).sum())
+}
+
+/// Progress notifications emitted while an analysis is running, so long analyses can report back to a client.
+///
+/// R(t) of a task is computed in one go, so there is no progress within a task, e.g. per fixed-point iteration:
+/// the approximate and exact modes do not iterate at all, see `response_time_convergence` for the iterates.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AnalysisProgress {
+    /// Task `id` has been fully analyzed, `analyzed` out of `total` tasks are done.
+    Task { id: String, analyzed: usize, total: usize },
+    /// The schedule is simulated up to `time` out of `horizon`, see `simulate_with_progress`.
+    Simulation { time: u32, horizon: u32 },
 }
 
 /// Performs the stack resource policy analysis on the given task-set and return results in a formatted Vec<>:
 /// 
-/// Vec<&Task, R(t), C(t), B(t), I(t)>
-pub fn srp_analyze<'a, T>(tasks: &'a T, mode: &PreemptionMode) -> Vec<(&'a Task, Result<u32, String>, u32, u32, u32)> 
+/// Vec<&Task, R(t), B(t), C(t), I(t)>
+///
+/// Results are in the order of the tasks, so the output of the same task set is always the same.
+pub fn srp_analyze<'a, T>(tasks: &'a T, config: &AnalysisConfig) -> Vec<AnalysisResult<'a>> 
 where
     T: std::ops::Deref<Target = [Task]> + Sized
+{
+    srp_analyze_with_progress(tasks, config, |_| {})
+}
+
+/// Same as `srp_analyze`, but calls `progress` after each analyzed task.
+///
+/// The task set is analyzed as prepared by `AnalysisConfig::prepare`, results refer to the given tasks.
+/// Under `DeadlineModel::Arbitrary` R(t) is computed with multiple-job busy window analysis.
+pub fn srp_analyze_with_progress<'a, T, F>(
+    tasks: &'a T,
+    config: &AnalysisConfig,
+    mut progress: F,
+) -> Vec<AnalysisResult<'a>> 
+where
+    T: std::ops::Deref<Target = [Task]> + Sized,
+    F: FnMut(AnalysisProgress),
 {
     let mut result_vector = Vec::new();
+    let total = tasks.len();
+    let prepared = config.prepare(tasks);
 
-    for task in tasks.iter() {
+    #[cfg(feature = "perf")]
+    let context = AnalysisContext::new(&prepared);
+
+    for (index, (task, analyzed)) in tasks.iter().zip(&prepared).enumerate() {
         
-        let response_time = task.response_time(tasks, mode);
-        let blocking_time = task.blocking_time(tasks);
-        let critical_time = task.wcet();
-        let interference = task.interference(tasks);
+        #[cfg(not(feature = "perf"))]
+        let (response_time, blocking_time, interference) = (
+            config.response_time(analyzed, &prepared),
+            analyzed.blocking_time(&prepared),
+            analyzed.interference(&prepared),
+        );
+
+        #[cfg(feature = "perf")]
+        let (response_time, blocking_time, interference) = (
+            match config.deadline_model {
+                DeadlineModel::Constrained if !config.offsets_apply() => {
+                    config.with_jitter(analyzed, context.response_time(index, &config.mode))
+                }
+                _ => config.response_time(analyzed, &prepared),
+            },
+            context.blocking_time(analyzed),
+            context.interference(analyzed),
+        );
+        let critical_time = analyzed.wcet();
+        let (response_time, interference) = bounded_interference(response_time, interference);
 
         result_vector.push((task, response_time, blocking_time, critical_time, interference));
+
+        progress(AnalysisProgress::Task { id: task.id.clone(), analyzed: index + 1, total });
     }
 
     result_vector
 }
+
+/// R(t) and I(t) of an analysis result: R(t) is unbounded whenever I(t) overflows, I(t) is then reported as 0.
+pub(crate) fn bounded_interference(
+    response_time: Result<u32, String>,
+    interference: Result<u32, String>,
+) -> (Result<u32, String>, u32) {
+    match interference {
+        Ok(interference) => (response_time, interference),
+        Err(e) => (Err(e), 0),
+    }
+}
+
+/// A task set is schedulable if every task has a bounded response time R(t) <= D(t).
+pub fn is_schedulable(results: &[AnalysisResult]) -> bool {
+    results.iter().all(|(task, response_time, ..)| {
+        matches!(response_time, Ok(r) if *r <= task.deadline)
+    })
+}
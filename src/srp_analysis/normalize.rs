@@ -0,0 +1,90 @@
+use std::collections::BTreeSet;
+
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use super::*;
+
+/// Old and new priority of a priority level, see `NormalizePriorities`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct PriorityLevel {
+    pub from: u8,
+    pub to: u8,
+    /// Tasks at the level, in the order of the task set, several for a tie
+    pub tasks: Vec<String>,
+}
+
+/// Mapping of the priorities of a task set onto dense levels, see `NormalizePriorities`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct PriorityNormalization {
+    /// From the lowest to the highest level
+    pub levels: Vec<PriorityLevel>,
+    /// Levels shared by several tasks of the same kind, which run in FIFO order rather than by priority
+    pub ties: Vec<PriorityLevel>,
+    /// Whether any priority or preemption threshold changes
+    pub changed: bool,
+}
+
+/// Task sets whose priorities can be mapped onto dense levels, e.g. after importing a set with sparse or
+/// conflicting priorities.
+///
+/// P(t) becomes its rank 1..=n among the distinct priorities of the set, so the relative order of all priorities,
+/// ties included, and thereby every ceiling π(r) and analysis result stay the same. γ(t) becomes the number of
+/// levels at or below it, so the same tasks preempt a started task.
+pub trait NormalizePriorities {
+    /// The mapping `normalize_priorities` would apply, without applying it.
+    fn priority_normalization(&self) -> PriorityNormalization;
+
+    /// Replaces every P(t) and γ(t) by its dense level and returns the mapping.
+    fn normalize_priorities(&mut self) -> PriorityNormalization;
+}
+
+// Distinct priorities of the set, from the lowest
+fn distinct_priorities(tasks: &[Task]) -> BTreeSet<u8> {
+    tasks.iter().map(|task| task.prio).collect()
+}
+
+// Number of distinct priorities at or below `prio`, the dense level of a priority of the set
+fn dense_level(priorities: &BTreeSet<u8>, prio: u8) -> u8 {
+    priorities.range(..=prio).count() as u8
+}
+
+// Whether several tasks of the same kind share the priority, as a software task and an interrupt never tie
+fn is_tie(tasks: &[Task], prio: u8) -> bool {
+    [TaskKind::Software, TaskKind::Interrupt]
+        .iter()
+        .any(|&kind| tasks.iter().filter(|task| task.kind == kind && task.prio == prio).count() > 1)
+}
+
+impl NormalizePriorities for Tasks {
+    fn priority_normalization(&self) -> PriorityNormalization {
+        let priorities = distinct_priorities(self);
+        let levels: Vec<PriorityLevel> = priorities
+            .iter()
+            .map(|&from| PriorityLevel {
+                from,
+                to: dense_level(&priorities, from),
+                tasks: self.iter().filter(|task| task.prio == from).map(|task| task.id.clone()).collect(),
+            })
+            .collect();
+        let changed = levels.iter().any(|level| level.from != level.to)
+            || self.iter().any(|task| {
+                task.threshold.is_some_and(|threshold| dense_level(&priorities, threshold) != threshold)
+            });
+        PriorityNormalization {
+            ties: levels.iter().filter(|level| is_tie(self, level.from)).cloned().collect(),
+            levels,
+            changed,
+        }
+    }
+
+    fn normalize_priorities(&mut self) -> PriorityNormalization {
+        let normalization = self.priority_normalization();
+        let priorities = distinct_priorities(self);
+        for task in self.iter_mut() {
+            task.prio = dense_level(&priorities, task.prio);
+            task.threshold = task.threshold.map(|threshold| dense_level(&priorities, threshold));
+        }
+        normalization
+    }
+}
@@ -0,0 +1,122 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use super::deadline::fixed_point;
+use super::*;
+
+/// R(t) of a task with and without the offsets of the transactions, see `offset_bounds`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct OffsetBound {
+    pub task: String,
+    pub deadline: u32,
+    /// Period of the transaction of the task, `None` if it is released independently of the other tasks
+    pub transaction: Option<u32>,
+    /// R(t) with all tasks released together, `None` if unbounded
+    pub synchronous: Option<u32>,
+    /// R(t) with `OffsetModel::Transactions`, never above `synchronous`
+    pub offset_aware: Option<u32>,
+}
+
+// φ_jk = (Φ(j) - Φ(k)) mod T, the release of j relative to the release of k in a transaction of period T
+fn phase(j: &Task, k: &Task, period: u32) -> u32 {
+    let period = period as u64;
+    ((j.offset as u64 % period + period - k.offset as u64 % period) % period) as u32
+}
+
+// W_k(w) = sum(ceiling((w - φ_jk) / T) * C(j)) for the tasks j of a transaction released within a window of length
+// w starting at the release of k, `None` on overflow
+fn transaction_demand(members: &[&Task], k: &Task, period: u32, w: u32) -> Option<u32> {
+    members.iter().try_fold(0u32, |acc, j| {
+        let releases = w.checked_sub(phase(j, k, period)).map_or(0, |rest| ceil_div(rest, period));
+        acc.checked_add(j.wcet().checked_mul(releases)?)
+    })
+}
+
+impl Task {
+    /// Period T of the transaction of the task, `None` if it is released independently of the other tasks.
+    ///
+    /// Periodic tasks without jitter keep their offsets to every other such task of the same period.
+    pub fn transaction_period(&self) -> Option<u32> {
+        match self.arrival {
+            ArrivalModel::Periodic { period } if period > 0 && self.jitter == 0 => Some(period),
+            _ => None,
+        }
+    }
+
+    /// R(t) with the offsets Φ(t) of the transactions, Tindell's approximation under constrained deadlines
+    ///
+    /// A critical instant of t starts at the release of some task k of every transaction, which then interferes
+    /// with W_k(w) = sum(ceiling((w - φ_jk) / T) * C(j)) for its tasks j where P(j) > P(t). Other transactions
+    /// interfere with max(W_k(w)) for all their tasks k, tasks outside of transactions with η_h(w) * C(h):
+    ///
+    /// w_k = B(t) + C(t) + sum(C(e)) + W_k(w_k) + sum(max(W(w_k))) + sum(η_h(w_k) * C(h))
+    ///
+    /// R(t) = max(w_k - φ_tk) for all tasks k of the transaction of t including t, skipping every k whose busy
+    /// window ends before t could have run.
+    pub fn offset_response_time<T>(&self, tasks: &T) -> Result<u32, String>
+    where
+        T: std::ops::Deref<Target = [Task]> + Sized,
+    {
+        let mut transactions: BTreeMap<u32, Vec<&Task>> = BTreeMap::new();
+        let mut independent = vec![];
+        for h in tasks.iter().filter(|h| h.level() > self.level()) {
+            match h.transaction_period() {
+                Some(period) => transactions.entry(period).or_default().push(h),
+                None => independent.push(h),
+            }
+        }
+        let own = self.transaction_period().map(|period| (period, transactions.remove(&period).unwrap_or_default()));
+
+        // Demand of everything but the own transaction in a window of length w, `None` on overflow
+        let others = |w: u32| -> Option<u32> {
+            let independent = independent
+                .iter()
+                .try_fold(0u32, |acc, h| acc.checked_add(h.wcet().checked_mul(h.max_releases(w))?))?;
+            transactions.iter().try_fold(independent, |acc, (period, members)| {
+                let worst = members
+                    .iter()
+                    .try_fold(0u32, |worst, k| Some(worst.max(transaction_demand(members, k, *period, w)?)))?;
+                acc.checked_add(worst)
+            })
+        };
+        let base = self.blocking_time(tasks) + self.wcet() + self.equal_priority_interference(tasks);
+        let Some((period, members)) = own else {
+            return fixed_point(base, |w| base.checked_add(others(w)?));
+        };
+
+        let mut response_time = 0;
+        for k in members.iter().copied().chain([self]) {
+            let w = fixed_point(base, |w| {
+                base.checked_add(transaction_demand(&members, k, period, w)?)?.checked_add(others(w)?)
+            })?;
+            let release = phase(self, k, period);
+            if w >= release.saturating_add(self.wcet()) {
+                response_time = response_time.max(w - release);
+            }
+        }
+        Ok(response_time)
+    }
+}
+
+/// R(t) of every task with all tasks released together and with the offsets of the transactions, in the order of
+/// the task set.
+///
+/// Both are analyzed with `config`, outside of the approximate and exact modes under constrained deadlines the
+/// offsets do not apply and both bounds are the same, see `AnalysisConfig::offsets_apply`.
+pub fn offset_bounds(tasks: &Tasks, config: &AnalysisConfig) -> Vec<OffsetBound> {
+    let synchronous = AnalysisConfig { offset_model: OffsetModel::Synchronous, ..*config };
+    let transactions = AnalysisConfig { offset_model: OffsetModel::Transactions, ..*config };
+    srp_analyze(tasks, &synchronous)
+        .into_iter()
+        .zip(srp_analyze(tasks, &transactions))
+        .map(|((task, synchronous, ..), (_, offset_aware, ..))| OffsetBound {
+            task: task.id.clone(),
+            deadline: task.deadline,
+            transaction: task.transaction_period(),
+            synchronous: synchronous.ok(),
+            offset_aware: offset_aware.ok(),
+        })
+        .collect()
+}
@@ -0,0 +1,105 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use super::trace_check::label;
+use super::*;
+
+/// Task whose trace was moved to start at 0, see `normalize_trace_origins`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct RebasedTrace {
+    pub task: String,
+    /// Timestamp the trace started at, subtracted from every section
+    pub origin: u32,
+}
+
+/// Task whose repeated activations were merged into a single worst-case activation, see `merge_runs`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct MergedActivations {
+    pub task: String,
+    pub activations: usize,
+}
+
+/// Task set with relative traces and what `normalize_trace_origins` changed to get there.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct TraceNormalization {
+    pub tasks: Tasks,
+    pub rebased: Vec<RebasedTrace>,
+    /// Sections moved into their enclosing section, located as by `check_trace` of the rebased trace
+    pub aligned: Vec<String>,
+    pub merged: Vec<MergedActivations>,
+}
+
+// The trace with `origin` subtracted from every timestamp, sections before it start at 0
+fn rebase(trace: &Trace, origin: u32) -> Trace {
+    Trace {
+        id: trace.id.clone(),
+        start: trace.start.saturating_sub(origin),
+        end: trace.end.saturating_sub(origin),
+        inner: trace.inner.iter().map(|inner| rebase(inner, origin)).collect(),
+        non_preemptible: trace.non_preemptible,
+        lock: trace.lock,
+    }
+}
+
+// Clamps every section into the window of its enclosing section, e.g. a lock released after its task completed
+// as recorded by a second clock
+fn align(trace: &mut Trace, location: &str, aligned: &mut Vec<String>) {
+    let (start, end) = (trace.start, trace.end.max(trace.start));
+    for inner in &mut trace.inner {
+        let inner_location = format!("{} > {}", location, label(inner));
+        if inner.start < start || inner.end > end {
+            inner.start = inner.start.clamp(start, end);
+            inner.end = inner.end.clamp(inner.start, end);
+            aligned.push(inner_location.clone());
+        }
+        align(inner, &inner_location, aligned);
+    }
+}
+
+/// Imported task set with traces relative to their release, e.g. recorded with absolute timestamps.
+///
+/// Every trace is rebased to start at 0 and every section clamped into its enclosing section. With `merge`,
+/// tasks with the same id are taken as repeated activations of one task and merged into a single worst-case
+/// activation by `merge_runs`, keeping the parameters of the first; without it, they stay as they are.
+pub fn normalize_trace_origins(tasks: &[Task], merge: bool) -> Result<TraceNormalization, String> {
+    let mut rebased = vec![];
+    let mut aligned = vec![];
+    let mut normalized: Tasks = vec![];
+    for task in tasks {
+        let origin = task.trace.start;
+        if origin > 0 {
+            rebased.push(RebasedTrace { task: task.id.clone(), origin });
+        }
+        let mut trace = rebase(&task.trace, origin);
+        let location = label(&trace);
+        align(&mut trace, &location, &mut aligned);
+        normalized.push(Task { trace, ..task.clone() });
+    }
+    if !merge {
+        return Ok(TraceNormalization { tasks: normalized, rebased, aligned, merged: vec![] });
+    }
+
+    // Activations of every task id, in the order of their first activation
+    let mut activations: Vec<(Task, Vec<Trace>)> = vec![];
+    for task in normalized {
+        match activations.iter_mut().find(|(first, _)| first.id == task.id) {
+            Some((_, runs)) => runs.push(task.trace),
+            None => {
+                let runs = vec![task.trace.clone()];
+                activations.push((task, runs));
+            }
+        }
+    }
+    let mut merged = vec![];
+    let mut tasks = vec![];
+    for (task, runs) in activations {
+        if runs.len() == 1 {
+            tasks.push(task);
+            continue;
+        }
+        let trace = merge_runs(&task.id, &runs)?.trace;
+        merged.push(MergedActivations { task: task.id.clone(), activations: runs.len() });
+        tasks.push(Task { trace, ..task });
+    }
+    Ok(TraceNormalization { tasks, rebased, aligned, merged })
+}
@@ -0,0 +1,75 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use super::*;
+
+/// A tunable timing or scheduling parameter of a task.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskParameter {
+    Wcet,
+    Period,
+    Deadline,
+    Prio,
+    /// J(t), only analyzed with `JitterModel::Release`
+    Jitter,
+}
+
+/// Reference to a parameter of a named task, written as `<task>.<parameter>`, e.g. `T3.wcet`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParameterRef {
+    pub task: String,
+    pub parameter: TaskParameter,
+}
+
+impl FromStr for ParameterRef {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (task, parameter) = s
+            .rsplit_once('.')
+            .ok_or_else(|| format!("Error: '{}' is not of the form <task>.<parameter>.", s))?;
+
+        let parameter = match parameter {
+            "wcet" => TaskParameter::Wcet,
+            "period" | "inter_arrival" => TaskParameter::Period,
+            "deadline" => TaskParameter::Deadline,
+            "prio" => TaskParameter::Prio,
+            "jitter" => TaskParameter::Jitter,
+            other => return Err(format!("Error: Unknown task parameter '{}'.", other)),
+        };
+
+        Ok(ParameterRef { task: task.to_string(), parameter })
+    }
+}
+
+impl ParameterRef {
+    /// Overrides the referenced parameter in `tasks` with `value`.
+    ///
+    /// Changing the WCET moves the end of the task's trace, keeping its start and inner sections.
+    pub fn apply(&self, tasks: &mut Tasks, value: u32) -> Result<(), String> {
+        let task = tasks
+            .iter_mut()
+            .find(|t| t.id == self.task)
+            .ok_or_else(|| format!("Error: No task with id '{}'.", self.task))?;
+
+        match self.parameter {
+            TaskParameter::Wcet => task.trace.end = task.trace.start + value,
+            TaskParameter::Period => task.arrival = task.arrival.with_inter_arrival(value),
+            TaskParameter::Deadline => task.deadline = value,
+            TaskParameter::Prio => {
+                task.prio = u8::try_from(value)
+                    .map_err(|_| format!("Error: Priority {} is out of range.", value))?
+            }
+            TaskParameter::Jitter => task.jitter = value,
+        }
+        Ok(())
+    }
+}
+
+/// Applies a set of overrides such as `{"T2.wcet": 40}` to the task set.
+pub fn apply_overrides(tasks: &mut Tasks, overrides: &BTreeMap<String, u32>) -> Result<(), String> {
+    for (parameter, value) in overrides {
+        parameter.parse::<ParameterRef>()?.apply(tasks, *value)?;
+    }
+    Ok(())
+}
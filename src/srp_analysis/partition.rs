@@ -0,0 +1,188 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use super::*;
+
+/// Core of every task of a partitioned task set by task id, tasks missing in it run on core 0.
+pub type CoreAssignment = BTreeMap<String, u32>;
+
+/// Tasks of a core of a partitioned task set, see `core_loads`.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct CoreLoad {
+    pub core: u32,
+    pub tasks: Vec<String>,
+    /// U(c) = sum(U(t)) for all tasks t on c
+    pub utilization: f32,
+    /// R(t) <= D(t) for all tasks t on c
+    pub schedulable: bool,
+}
+
+/// Task moved to another core by `balance_cores`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct CoreMigration {
+    pub task: String,
+    pub from: u32,
+    pub to: u32,
+}
+
+/// Migrations suggested by `balance_cores`, with the cores before and after them.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct CoreBalancing {
+    /// In the order they are applied
+    pub moves: Vec<CoreMigration>,
+    pub before: Vec<CoreLoad>,
+    pub after: Vec<CoreLoad>,
+    /// Core of every task after all moves
+    pub assignment: BTreeMap<String, u32>,
+}
+
+impl CoreBalancing {
+    /// max(U(c)) for all cores c before and after the moves.
+    pub fn peak_utilization(&self) -> (f32, f32) {
+        (peak(&self.before).utilization, peak(&self.after).utilization)
+    }
+}
+
+fn core_of(assignment: &CoreAssignment, task: &Task) -> u32 {
+    assignment.get(&task.id).copied().unwrap_or(0)
+}
+
+// Resources of the task shared with other tasks, non-preemptible sections are local to a core
+fn shared_resources(task: &Task) -> BTreeSet<&str> {
+    task.resources().filter(|resource| !resource.non_preemptible).map(|resource| resource.id.as_str()).collect()
+}
+
+fn validate_assignment(tasks: &Tasks, assignment: &CoreAssignment, cores: u32) -> Result<(), String> {
+    if cores == 0 {
+        return Err("Error: A partitioned task set needs at least one core.".to_string());
+    }
+    if let Some(id) = assignment.keys().find(|id| !tasks.iter().any(|task| &task.id == *id)) {
+        return Err(format!("Error: No task with id '{}'.", id));
+    }
+    if let Some((id, core)) = assignment.iter().find(|(_, &core)| core >= cores) {
+        return Err(format!("Error: Task '{}' is assigned to core {} of {} cores.", id, core, cores));
+    }
+    // Global resources would need a multiprocessor protocol such as MSRP, which the analysis does not model
+    let mut owners: BTreeMap<&str, &Task> = BTreeMap::new();
+    for task in tasks {
+        for resource in shared_resources(task) {
+            let owner = *owners.entry(resource).or_insert(task);
+            if core_of(assignment, owner) != core_of(assignment, task) {
+                return Err(format!(
+                    "Error: Resource '{}' is shared by '{}' and '{}' on different cores.",
+                    resource, owner.id, task.id
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+// Tasks of the core, analyzed on their own
+fn core_load(tasks: &Tasks, assignment: &CoreAssignment, core: u32, config: &AnalysisConfig) -> CoreLoad {
+    let local: Tasks = tasks.iter().filter(|task| core_of(assignment, task) == core).cloned().collect();
+    let schedulable =
+        srp_analyze(&local, config).iter().all(|(task, r, ..)| matches!(r, Ok(r) if *r <= task.deadline));
+    CoreLoad {
+        core,
+        tasks: local.iter().map(|task| task.id.clone()).collect(),
+        // Folded from 0 rather than summed, as an empty f32 sum is -0
+        utilization: local.iter().fold(0.0, |sum, task| sum + task.load_factor().unwrap_or(0.0)),
+        schedulable,
+    }
+}
+
+fn peak(loads: &[CoreLoad]) -> &CoreLoad {
+    loads.iter().max_by(|a, b| a.utilization.total_cmp(&b.utilization)).expect("at least one core")
+}
+
+/// Partitioned analysis: every core analyzed on its own under SRP with the tasks assigned to it.
+///
+/// Tasks sharing a resource have to be on the same core.
+pub fn core_loads(
+    tasks: &Tasks,
+    assignment: &CoreAssignment,
+    cores: u32,
+    config: &AnalysisConfig,
+) -> Result<Vec<CoreLoad>, String> {
+    validate_assignment(tasks, assignment, cores)?;
+    Ok((0..cores).map(|core| core_load(tasks, assignment, core, config)).collect())
+}
+
+// Ids of the tasks connected through shared resources, which have to migrate together
+fn resource_clusters(tasks: &Tasks) -> Vec<Vec<String>> {
+    let mut clusters: Vec<(BTreeSet<&str>, Vec<String>)> = vec![];
+    for task in tasks {
+        let mut resources = shared_resources(task);
+        let mut members = vec![task.id.clone()];
+        // Merges every cluster sharing a resource with the task into its own
+        clusters.retain_mut(|(shared, ids)| {
+            if shared.is_disjoint(&resources) {
+                return true;
+            }
+            resources.append(shared);
+            members.append(ids);
+            false
+        });
+        clusters.push((resources, members));
+    }
+    clusters.into_iter().map(|(_, ids)| ids).collect()
+}
+
+/// Suggests migrations of tasks between cores that lower max(U(c)), the utilization of the busiest core.
+///
+/// Greedily moves the tasks of the busiest core, a cluster of tasks sharing resources at a time, to the core where
+/// the peak of the two cores ends up lowest, as long as the peak drops and the target core stays schedulable.
+pub fn balance_cores(
+    tasks: &Tasks,
+    assignment: &CoreAssignment,
+    cores: u32,
+    config: &AnalysisConfig,
+) -> Result<CoreBalancing, String> {
+    let before = core_loads(tasks, assignment, cores, config)?;
+    let clusters = resource_clusters(tasks);
+    let mut current: CoreAssignment = tasks.iter().map(|task| (task.id.clone(), core_of(assignment, task))).collect();
+    let mut loads = before.clone();
+    let mut moves = vec![];
+
+    loop {
+        let busiest = peak(&loads).clone();
+        // Lowest peak of the busiest and the target core, the assignment and the two cores after the move
+        let mut best: Option<(f32, &Vec<String>, CoreAssignment, CoreLoad, CoreLoad)> = None;
+        for cluster in clusters.iter().filter(|cluster| current[&cluster[0]] == busiest.core) {
+            for target in loads.iter().filter(|load| load.core != busiest.core) {
+                let moved_load: f32 = tasks
+                    .iter()
+                    .filter(|task| cluster.contains(&task.id))
+                    .map(|task| task.load_factor().unwrap_or(0.0))
+                    .sum();
+                let estimate = (busiest.utilization - moved_load).max(target.utilization + moved_load);
+                if estimate >= busiest.utilization || best.as_ref().is_some_and(|best| estimate >= best.0) {
+                    continue;
+                }
+                let mut moved = current.clone();
+                for id in cluster {
+                    moved.insert(id.clone(), target.core);
+                }
+                let to = core_load(tasks, &moved, target.core, config);
+                if to.schedulable {
+                    let from = core_load(tasks, &moved, busiest.core, config);
+                    best = Some((estimate, cluster, moved, from, to));
+                }
+            }
+        }
+
+        let Some((_, cluster, moved, from, to)) = best else { break };
+        for task in tasks.iter().filter(|task| cluster.contains(&task.id)) {
+            moves.push(CoreMigration { task: task.id.clone(), from: from.core, to: to.core });
+        }
+        let (source, target) = (from.core as usize, to.core as usize);
+        loads[source] = from;
+        loads[target] = to;
+        current = moved;
+    }
+
+    Ok(CoreBalancing { moves, before, after: loads, assignment: current })
+}
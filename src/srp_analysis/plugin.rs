@@ -0,0 +1,139 @@
+use std::sync::{Arc, RwLock};
+
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use super::*;
+
+/// Outcome of a schedulability test, see `AnalysisPlugin`.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct PluginReport {
+    pub plugin: String,
+    /// `None` if the test is inconclusive, e.g. a sufficient test that does not pass
+    pub schedulable: Option<bool>,
+    /// Results of the tasks the test bounds individually, e.g. by their response times
+    pub tasks: Vec<TaskAnalysis>,
+    /// Findings of the test, e.g. the bound a task set exceeds
+    pub notes: Vec<String>,
+}
+
+/// Schedulability test that can be selected per request next to `srp_analyze`, e.g. an experimental blocking
+/// bound, registered in a `PluginRegistry`.
+pub trait AnalysisPlugin: Send + Sync {
+    fn name(&self) -> &str;
+    fn description(&self) -> &str;
+    fn run(&self, tasks: &Tasks, config: &AnalysisConfig) -> PluginReport;
+}
+
+/// Schedulability tests by name.
+///
+/// Tests register themselves at startup, so adding one needs no change to `srp_analyze` or the routes.
+#[derive(Default)]
+pub struct PluginRegistry {
+    plugins: RwLock<BTreeMap<String, Arc<dyn AnalysisPlugin>>>,
+}
+
+impl PluginRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `plugin` under its name, replacing any plugin of the same name.
+    pub fn register<P>(&self, plugin: P)
+    where
+        P: AnalysisPlugin + 'static,
+    {
+        self.plugins.write().unwrap().insert(plugin.name().to_string(), Arc::new(plugin));
+    }
+
+    /// Runs plugin `name` on the task set.
+    ///
+    /// The registry is not locked while the plugin runs.
+    pub fn run(&self, name: &str, tasks: &Tasks, config: &AnalysisConfig) -> Result<PluginReport, String> {
+        let plugin = self
+            .plugins
+            .read()
+            .unwrap()
+            .get(name)
+            .cloned()
+            .ok_or_else(|| format!("Error: Unknown analysis plugin '{}'", name))?;
+        Ok(plugin.run(tasks, config))
+    }
+
+    /// Description of every registered plugin by name.
+    pub fn catalog(&self) -> BTreeMap<String, String> {
+        self.plugins
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(name, plugin)| (name.clone(), plugin.description().to_string()))
+            .collect()
+    }
+}
+
+/// `srp_analyze` as a plugin, exact for the chosen `AnalysisConfig`.
+pub struct SrpPlugin;
+
+impl AnalysisPlugin for SrpPlugin {
+    fn name(&self) -> &str {
+        "srp"
+    }
+
+    fn description(&self) -> &str {
+        "Response time analysis under the Stack Resource Policy"
+    }
+
+    fn run(&self, tasks: &Tasks, config: &AnalysisConfig) -> PluginReport {
+        let results = srp_analyze(tasks, config);
+        PluginReport {
+            plugin: self.name().to_string(),
+            schedulable: Some(is_schedulable(&results)),
+            tasks: results.iter().map(|result| TaskAnalysis::from(result).with_clock(config.clock)).collect(),
+            notes: config.assumptions(),
+        }
+    }
+}
+
+/// Utilization bound of rate monotonic scheduling with blocking, a sufficient test for tasks with D(t) = A(t).
+pub struct UtilizationBoundPlugin;
+
+impl AnalysisPlugin for UtilizationBoundPlugin {
+    fn name(&self) -> &str {
+        "utilization_bound"
+    }
+
+    fn description(&self) -> &str {
+        "Sufficient test sum(C(h) / A(h)) + B(t) / A(t) <= n(2^(1/n) - 1) over t and the n - 1 tasks h above it"
+    }
+
+    fn run(&self, tasks: &Tasks, config: &AnalysisConfig) -> PluginReport {
+        let prepared = config.prepare(tasks);
+        let mut notes = vec![];
+        for task in &prepared {
+            if task.deadline < task.arrival.inter_arrival() {
+                notes.push(format!("{}: D(t) < A(t), the bound does not apply", task.id));
+                continue;
+            }
+            let higher: Vec<&Task> = prepared.iter().filter(|h| h.level() >= task.level()).collect();
+            let n = higher.len() as f64;
+            let utilization: f64 = higher.iter().filter_map(|h| h.load_factor()).map(f64::from).sum();
+            let blocking = task.blocking_time(&prepared) as f64 / task.arrival.inter_arrival().max(1) as f64;
+            let bound = n * (2f64.powf(1.0 / n) - 1.0);
+            if utilization + blocking > bound {
+                notes.push(format!("{}: {:.3} exceeds the bound {:.3}", task.id, utilization + blocking, bound));
+            }
+        }
+        PluginReport {
+            plugin: self.name().to_string(),
+            schedulable: if notes.is_empty() { Some(true) } else { None },
+            tasks: vec![],
+            notes,
+        }
+    }
+}
+
+/// Registers the schedulability tests of this crate.
+pub fn register_builtin_plugins(registry: &PluginRegistry) {
+    registry.register(SrpPlugin);
+    registry.register(UtilizationBoundPlugin);
+}
@@ -0,0 +1,79 @@
+use std::collections::BTreeMap;
+
+use super::*;
+
+/// Audsley's optimal priority assignment: assigns priorities 1..=n from the lowest level up, each time to some
+/// task that meets its deadline with all still unassigned tasks at higher priorities.
+///
+/// In approximate mode B(t), I(t) and thereby R(t) only depend on which tasks have a higher priority, not on
+/// their relative order, so a schedulable assignment is found whenever one exists, and likewise in
+/// suspension-oblivious mode. Exact and suspension-aware mode include R(h) of every higher priority task h and
+/// are rejected. So are tasks with preemption thresholds, as γ(t) is an absolute level that would have to move
+/// along with P(t).
+///
+/// Returns the task set with the new priorities, analyzed again with `config` to be valid and schedulable, or
+/// `None` if no assignment is schedulable.
+pub fn optimal_priority_assignment(tasks: &Tasks, config: &AnalysisConfig) -> Result<Option<Tasks>, String> {
+    if config.mode.uses_higher_priority_response_times() {
+        return Err(format!(
+            "Error: {:?} mode response times depend on the order of higher priority tasks",
+            config.mode
+        ));
+    }
+    if let Some(task) = tasks.iter().find(|task| task.threshold.is_some()) {
+        return Err(format!(
+            "Error: Task '{}' has a preemption threshold, which the priority assignment cannot keep",
+            task.id
+        ));
+    }
+    if tasks.len() >= u8::MAX as usize {
+        return Err(format!("Error: {} tasks exceed the number of priority levels", tasks.len()));
+    }
+
+    // Priorities are assigned on the task set as analyzed, and copied over to the given tasks at the end
+    let mut trial = config.prepare(tasks);
+    let mut unassigned: Vec<usize> = (0..tasks.len()).collect();
+    // Tasks with longer deadlines are tried first at the lower levels, as in deadline monotonic order
+    unassigned.sort_by_key(|&i| std::cmp::Reverse(tasks[i].deadline));
+
+    for level in 1..=tasks.len() as u8 {
+        let mut assigned = None;
+        for (candidate, &index) in unassigned.iter().enumerate() {
+            for &other in &unassigned {
+                trial[other].prio = level + 1;
+            }
+            trial[index].prio = level;
+
+            // R(t) <= D(t) for the candidate task
+            if matches!(config.response_time(&trial[index], &trial), Ok(r) if r <= tasks[index].deadline) {
+                assigned = Some(candidate);
+                break;
+            }
+        }
+
+        match assigned {
+            Some(candidate) => {
+                let index = unassigned.remove(candidate);
+                trial[index].prio = level;
+            }
+            None => return Ok(None),
+        }
+    }
+
+    let mut assigned = tasks.clone();
+    for (task, analyzed) in assigned.iter_mut().zip(&trial) {
+        task.prio = analyzed.prio;
+    }
+    // The assignment is only as good as the analysis of the whole task set agrees, e.g. the per-task test above
+    // does not see a task set that became invalid
+    validate_task_set(&assigned)?;
+    if !is_schedulable(&srp_analyze(&assigned, config)) {
+        return Err("Error: The priority assignment is not schedulable when the task set is analyzed again".to_string());
+    }
+    Ok(Some(assigned))
+}
+
+/// Priority of every task by id.
+pub fn priorities(tasks: &Tasks) -> BTreeMap<String, u8> {
+    tasks.iter().map(|task| (task.id.clone(), task.prio)).collect()
+}
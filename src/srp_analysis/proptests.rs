@@ -0,0 +1,133 @@
+use proptest::prelude::*;
+
+use super::*;
+
+// Random task sets of up to 6 tasks, each optionally locking a resource with another one nested inside.
+fn task_set() -> impl Strategy<Value = Tasks> {
+    let task = (1u8..=8, 10u32..200, 1u32..50, prop::option::of((0usize..4, 0usize..3)));
+    prop::collection::vec(task, 1..6).prop_map(|tasks| {
+        tasks
+            .into_iter()
+            .enumerate()
+            .map(|(i, (prio, period, wcet, resources))| {
+                let wcet = wcet.min(period);
+                let builder = TaskBuilder::new(&format!("T{}", i)).prio(prio).period(period).span(0, wcet);
+                let builder = match resources {
+                    Some((outer, nested)) => {
+                        let nested = format!("R{}", (outer + 1 + nested) % 4);
+                        builder.section(&format!("R{}", outer), 0, wcet, |s| s.section(&nested, 0, wcet / 2))
+                    }
+                    None => builder,
+                };
+                builder.build().unwrap()
+            })
+            .collect()
+    })
+}
+
+// Task sets with the index of some task and the index of a strictly higher priority task, if any.
+fn task_set_with_higher_priority_task() -> impl Strategy<Value = (Tasks, usize, usize)> {
+    task_set()
+        .prop_flat_map(|tasks| {
+            let n = tasks.len();
+            (Just(tasks), 0..n, 0..n)
+        })
+        .prop_filter("needs a higher priority task", |(tasks, t, h)| tasks[*h].prio > tasks[*t].prio)
+}
+
+// Task sets with a changed copy, where one task got a new priority and WCET.
+fn task_set_with_change() -> impl Strategy<Value = (Tasks, Tasks)> {
+    task_set()
+        .prop_flat_map(|tasks| {
+            let n = tasks.len();
+            (Just(tasks), 0..n, 1u8..=8, 1u32..50)
+        })
+        .prop_map(|(tasks, index, prio, wcet)| {
+            let mut changed = tasks.clone();
+            changed[index].prio = prio;
+            changed[index].trace.end = changed[index].trace.start + wcet;
+            (tasks, changed)
+        })
+}
+
+proptest! {
+    #[test]
+    fn approximate_response_time_covers_blocking_and_wcet(tasks in task_set()) {
+        for task in &tasks {
+            let r = task.response_time(&tasks, &PreemptionMode::Approximate).unwrap();
+            prop_assert!(r >= task.blocking_time(&tasks) + task.wcet());
+        }
+    }
+
+    #[test]
+    fn exact_response_time_covers_blocking_and_wcet(tasks in task_set()) {
+        for task in &tasks {
+            if let Ok(r) = task.response_time(&tasks, &PreemptionMode::Exact) {
+                prop_assert!(r >= task.blocking_time(&tasks) + task.wcet());
+            }
+        }
+    }
+
+    #[test]
+    fn removing_higher_priority_task_never_increases_response_time(
+        (tasks, t, h) in task_set_with_higher_priority_task()
+    ) {
+        let mut reduced = tasks.clone();
+        reduced.remove(h);
+        let task = &tasks[t];
+
+        for mode in [PreemptionMode::Approximate, PreemptionMode::Exact] {
+            if let Ok(before) = task.response_time(&tasks, &mode) {
+                let after = task.response_time(&reduced, &mode);
+                prop_assert!(matches!(after, Ok(after) if after <= before), "{:?}: {:?} > {}", mode, after, before);
+            }
+        }
+    }
+
+    #[test]
+    fn memoized_context_matches_direct_analysis(tasks in task_set()) {
+        let context = AnalysisContext::new(&tasks);
+        for (i, task) in tasks.iter().enumerate() {
+            prop_assert_eq!(context.blocking_time(task), task.blocking_time(&tasks));
+            prop_assert_eq!(context.interference(task), task.interference(&tasks));
+            for mode in [PreemptionMode::Approximate, PreemptionMode::Exact] {
+                prop_assert_eq!(context.response_time(i, &mode), task.response_time(&tasks, &mode));
+            }
+        }
+    }
+
+    #[test]
+    fn incremental_analysis_matches_full_analysis((old, new) in task_set_with_change()) {
+        for mode in [PreemptionMode::Approximate, PreemptionMode::Exact] {
+            for deadline_model in [DeadlineModel::Constrained, DeadlineModel::Arbitrary] {
+                let config = AnalysisConfig { mode, deadline_model, ..Default::default() };
+                let analyze = |tasks: &Tasks| -> Vec<TaskAnalysis> {
+                    srp_analyze(tasks, &config).iter().map(TaskAnalysis::from).collect()
+                };
+                let previous = analyze(&old);
+                let incremental = srp_reanalyze(&old, &previous, &new, &config);
+                prop_assert_eq!(format!("{:?}", incremental), format!("{:?}", analyze(&new)));
+            }
+        }
+    }
+
+    #[test]
+    fn optimal_priority_assignment_is_found_for_schedulable_sets(mut tasks in task_set()) {
+        // Tasks of equal priority interfere with each other only once, which no assignment of distinct priorities
+        // can match, so ties are broken by index first
+        let mut order: Vec<usize> = (0..tasks.len()).collect();
+        order.sort_by_key(|&i| (tasks[i].prio, i));
+        for (rank, &i) in order.iter().enumerate() {
+            tasks[i].prio = rank as u8 + 1;
+        }
+
+        let config = AnalysisConfig::default();
+        let assigned = optimal_priority_assignment(&tasks, &config).unwrap();
+        if is_schedulable(&srp_analyze(&tasks, &config)) {
+            prop_assert!(assigned.is_some());
+        }
+        if let Some(assigned) = assigned {
+            prop_assert!(is_schedulable(&srp_analyze(&assigned, &config)));
+        }
+    }
+}
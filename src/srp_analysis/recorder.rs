@@ -0,0 +1,182 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::*;
+
+/// What happened on the device at the time of a `TraceEvent`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "event", rename_all = "lowercase")]
+pub enum TraceEventKind {
+    /// The next job of the task is released, it may start later, e.g. when higher priority jobs completed
+    Release { task: String },
+    /// A job of the task starts, preempting the running job, if any
+    Start { task: String },
+    /// The running job, which must be of the task, completes
+    Stop { task: String },
+    /// The running job locks the resource
+    Lock {
+        resource: String,
+        #[serde(default)]
+        lock: LockType,
+    },
+    /// The running job unlocks the resource it locked last
+    Unlock { resource: String },
+}
+
+/// Timestamped event of a device under test, e.g. `{"time": 120, "event": "lock", "resource": "R1"}`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct TraceEvent {
+    /// Time on the clock of the device, never decreasing
+    pub time: u64,
+    #[serde(flatten)]
+    pub kind: TraceEventKind,
+}
+
+/// Job completed on the device, with its sections in execution time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordedJob {
+    pub task: String,
+    /// Starts at 0 and ends at the execution time of the job, excluding the time it was preempted
+    pub trace: Trace,
+    /// Time from the release of the job, or its start if its release was not recorded, until it stopped
+    pub response_time: u32,
+}
+
+// Section locked by a running job, and the sections completed within it
+struct OpenSection {
+    resource: String,
+    lock: LockType,
+    start: u32,
+    inner: Vec<Trace>,
+}
+
+// Job started but not yet stopped, preempted unless it is the last one
+struct OpenJob {
+    task: String,
+    released: u64,
+    executed: u32,
+    resumed: u64,
+    sections: Vec<OpenSection>,
+    inner: Vec<Trace>,
+}
+
+impl OpenJob {
+    // Execution time up to `time`, if the job has been running since it was last resumed
+    fn offset(&self, time: u64) -> Result<u32, String> {
+        u32::try_from(time - self.resumed)
+            .ok()
+            .and_then(|running| self.executed.checked_add(running))
+            .ok_or_else(|| format!("Error: Execution time of task '{}' overflows.", self.task))
+    }
+}
+
+/// Builds the traces of jobs from the events of a single core, one event at a time.
+///
+/// A started job preempts the running one, which resumes when it stops, so preemptions must nest. Sections
+/// are measured in execution time, the time a job spends preempted counts for neither its sections nor its
+/// WCET.
+#[derive(Default)]
+pub struct TraceRecorder {
+    jobs: Vec<OpenJob>,
+    // Release times of the jobs that have not started yet, by task
+    releases: BTreeMap<String, u64>,
+    time: u64,
+}
+
+impl TraceRecorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Tasks of the started jobs that have not stopped yet, the running one last.
+    pub fn running(&self) -> Vec<String> {
+        self.jobs.iter().map(|job| job.task.clone()).collect()
+    }
+
+    /// Records the event, returning the job it completes, if any.
+    pub fn record(&mut self, event: &TraceEvent) -> Result<Option<RecordedJob>, String> {
+        if event.time < self.time {
+            return Err(format!("Error: Event at {} is earlier than the previous event at {}.", event.time, self.time));
+        }
+        self.time = event.time;
+
+        if let TraceEventKind::Release { task } = &event.kind {
+            self.releases.insert(task.clone(), event.time);
+            return Ok(None);
+        }
+        if let TraceEventKind::Start { task } = &event.kind {
+            if let Some(preempted) = self.jobs.last_mut() {
+                preempted.executed = preempted.offset(event.time)?;
+            }
+            self.jobs.push(OpenJob {
+                task: task.clone(),
+                released: self.releases.remove(task).unwrap_or(event.time),
+                executed: 0,
+                resumed: event.time,
+                sections: vec![],
+                inner: vec![],
+            });
+            return Ok(None);
+        }
+
+        let job = self.jobs.last_mut().ok_or_else(|| format!("Error: No job is running at {}.", event.time))?;
+        let offset = job.offset(event.time)?;
+        match &event.kind {
+            TraceEventKind::Release { .. } | TraceEventKind::Start { .. } => unreachable!(),
+            TraceEventKind::Lock { resource, lock } => {
+                let section = OpenSection { resource: resource.clone(), lock: *lock, start: offset, inner: vec![] };
+                job.sections.push(section);
+                Ok(None)
+            }
+            TraceEventKind::Unlock { resource } => {
+                let section = match job.sections.pop() {
+                    Some(section) if &section.resource == resource => section,
+                    _ => {
+                        return Err(format!(
+                            "Error: Task '{}' unlocks '{}' at {}, which it did not lock last.",
+                            job.task, resource, event.time
+                        ))
+                    }
+                };
+                let trace = Trace {
+                    id: section.resource,
+                    start: section.start,
+                    end: offset,
+                    inner: section.inner,
+                    non_preemptible: false,
+                    lock: section.lock,
+                };
+                match job.sections.last_mut() {
+                    Some(outer) => outer.inner.push(trace),
+                    None => job.inner.push(trace),
+                }
+                Ok(None)
+            }
+            TraceEventKind::Stop { task } => {
+                if &job.task != task {
+                    let time = event.time;
+                    return Err(format!("Error: Task '{}' stops at {}, but '{}' is running.", task, time, job.task));
+                }
+                if let Some(section) = job.sections.last() {
+                    let resource = &section.resource;
+                    return Err(format!("Error: Task '{}' stops at {} holding '{}'.", task, event.time, resource));
+                }
+                let job = self.jobs.pop().unwrap();
+                if let Some(preempted) = self.jobs.last_mut() {
+                    preempted.resumed = event.time;
+                }
+                let trace = Trace {
+                    id: job.task.clone(),
+                    start: 0,
+                    end: offset,
+                    inner: job.inner,
+                    non_preemptible: false,
+                    lock: LockType::Exclusive,
+                };
+                let response_time = u32::try_from(event.time - job.released)
+                    .map_err(|_| format!("Error: Response time of task '{}' overflows.", job.task))?;
+                Ok(Some(RecordedJob { task: job.task, trace, response_time }))
+            }
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use super::*;
+
+/// Change to the resource sharing of a task set, see `suggest_refactorings`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Refactoring {
+    /// Releases and locks the resource again at `at`, so the section [start, end] of the task becomes two
+    SplitSection { task: String, resource: String, start: u32, end: u32, at: u32 },
+    /// Stops the task from sharing the resource, e.g. by handing it a copy or passing messages, so π(r) drops to
+    /// the highest priority of the other tasks locking it
+    NarrowScope { task: String, resource: String, ceiling: u8, narrowed_ceiling: u8 },
+}
+
+/// Refactoring with the blocking it removes in the analysis of the refactored task set.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct RefactoringSuggestion {
+    pub refactoring: Refactoring,
+    /// sum(B(t)) of all tasks before less after the refactoring
+    pub blocking_removed: u32,
+    /// Decrease of B(t) of every task it decreases for
+    pub relieved: BTreeMap<String, u32>,
+    /// R(t) <= D(t) for all tasks after the refactoring
+    pub schedulable: bool,
+}
+
+// Splits the `n`-th section of the trace in pre-order, see `ResourceIter`, into two at its middle
+fn split_section(trace: &mut Trace, n: &mut usize) -> Option<u32> {
+    for i in 0..trace.inner.len() {
+        if *n == 0 {
+            let section = &mut trace.inner[i];
+            let at = section.start + ceil_div(section.end - section.start, 2);
+            let mut rest = section.clone();
+            (section.end, rest.start) = (at, at);
+            trace.inner.insert(i + 1, rest);
+            return Some(at);
+        }
+        *n -= 1;
+        if let Some(at) = split_section(&mut trace.inner[i], n) {
+            return Some(at);
+        }
+    }
+    None
+}
+
+// Renames every section of the trace on `resource` to `private`
+fn rename_sections(trace: &mut Trace, resource: &str, private: &str) {
+    for section in &mut trace.inner {
+        if section.id == resource {
+            section.id = private.to_string();
+        }
+        rename_sections(section, resource, private);
+    }
+}
+
+// Every refactoring with the task set it results in
+fn candidates(tasks: &Tasks) -> Vec<(Refactoring, Tasks)> {
+    let mut candidates = vec![];
+    for (i, task) in tasks.iter().enumerate() {
+        // Only sections without nested sections can be split without breaking the nesting
+        for (n, section) in task.resources().enumerate() {
+            if !section.inner.is_empty() || section.end - section.start < 2 {
+                continue;
+            }
+            let (mut refactored, mut nth) = (tasks.clone(), n);
+            let Some(at) = split_section(&mut refactored[i].trace, &mut nth) else {
+                continue;
+            };
+            let refactoring = Refactoring::SplitSection {
+                task: task.id.clone(),
+                resource: section.id.clone(),
+                start: section.start,
+                end: section.end,
+                at,
+            };
+            candidates.push((refactoring, refactored));
+        }
+    }
+
+    for ceiling in resource_ceilings(tasks).into_iter().filter(|ceiling| ceiling.ceiling != u8::MAX) {
+        for id in &ceiling.tasks {
+            let others = tasks.iter().filter(|t| t.id != *id && ceiling.tasks.contains(&t.id));
+            let Some(narrowed_ceiling) = others.map(|t| t.prio).max() else {
+                continue;
+            };
+            let mut refactored = tasks.clone();
+            let task = refactored.iter_mut().find(|t| t.id == *id).expect("task locking the resource");
+            rename_sections(&mut task.trace, &ceiling.resource, &format!("{}@{}", ceiling.resource, id));
+            let refactoring = Refactoring::NarrowScope {
+                task: id.clone(),
+                resource: ceiling.resource.clone(),
+                ceiling: ceiling.ceiling,
+                narrowed_ceiling,
+            };
+            candidates.push((refactoring, refactored));
+        }
+    }
+    candidates
+}
+
+/// Refactorings of the critical sections that remove blocking, the most removed blocking first and otherwise in
+/// the order they are tried.
+///
+/// Every section without nested sections is tried split at its middle, and every task sharing a resource with
+/// others is tried without access to it. Each refactored task set is analyzed with `config`, refactorings that
+/// remove no blocking are left out. Non-preemptible sections are split but never narrowed, they block everything
+/// regardless of the tasks sharing them.
+pub fn suggest_refactorings(tasks: &Tasks, config: &AnalysisConfig) -> Vec<RefactoringSuggestion> {
+    let blocking: HashMap<String, u32> = srp_analyze(tasks, config)
+        .into_iter()
+        .map(|(task, _, blocking_time, ..)| (task.id.clone(), blocking_time))
+        .collect();
+
+    let mut suggestions: Vec<RefactoringSuggestion> = candidates(tasks)
+        .into_iter()
+        .filter_map(|(refactoring, refactored)| {
+            let results = srp_analyze(&refactored, config);
+            let relieved: BTreeMap<String, u32> = results
+                .iter()
+                .filter_map(|(task, _, blocking_time, ..)| {
+                    let removed = blocking.get(&task.id)?.saturating_sub(*blocking_time);
+                    (removed > 0).then(|| (task.id.clone(), removed))
+                })
+                .collect();
+            let blocking_removed = relieved.values().sum();
+            (blocking_removed > 0).then(|| RefactoringSuggestion {
+                refactoring,
+                blocking_removed,
+                relieved,
+                schedulable: is_schedulable(&results),
+            })
+        })
+        .collect();
+    suggestions.sort_by_key(|suggestion| std::cmp::Reverse(suggestion.blocking_removed));
+    suggestions
+}
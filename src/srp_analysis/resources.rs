@@ -0,0 +1,41 @@
+use super::*;
+
+/// Depth-first, pre-order iterator over the critical sections nested inside a trace.
+///
+/// Every section is yielded before the sections nested inside it, and sibling sections in the order of
+/// `Trace::inner`. The trace the iterator was created from is not yielded itself.
+///
+/// Traces are walked with an explicit stack of the enclosing sections instead of boxed, recursively chained
+/// iterators, so no allocation happens for traces without nested sections and at most one per additional
+/// nesting level otherwise.
+pub struct ResourceIter<'a> {
+    // Remaining siblings of the current section
+    current: std::slice::Iter<'a, Trace>,
+    // Remaining siblings of every enclosing section
+    enclosing: Vec<std::slice::Iter<'a, Trace>>,
+}
+
+impl<'a> ResourceIter<'a> {
+    pub fn new(trace: &'a Trace) -> Self {
+        ResourceIter { current: trace.inner.iter(), enclosing: vec![] }
+    }
+}
+
+impl<'a> Iterator for ResourceIter<'a> {
+    type Item = &'a Trace;
+
+    fn next(&mut self) -> Option<&'a Trace> {
+        loop {
+            if let Some(section) = self.current.next() {
+                if !section.inner.is_empty() {
+                    let siblings = std::mem::replace(&mut self.current, section.inner.iter());
+                    self.enclosing.push(siblings);
+                }
+                return Some(section);
+            }
+            self.current = self.enclosing.pop()?;
+        }
+    }
+}
+
+impl std::iter::FusedIterator for ResourceIter<'_> {}
@@ -0,0 +1,48 @@
+use super::*;
+
+/// Task sets whose timing can be converted to another time unit, e.g. from ms to µs with a factor of 1000.
+pub trait Scalable {
+    /// Copy with every timing quantity multiplied by `factor`: C(t) and all section times, A(t), D(t), J(t),
+    /// S(t) and C(HI). Counts such as the releases of a burst stay the same, so all ratios are kept.
+    fn scaled(&self, factor: u32) -> Result<Tasks, String>;
+}
+
+fn scale_trace(trace: &Trace, factor: u32) -> Option<Trace> {
+    Some(Trace {
+        id: trace.id.clone(),
+        start: trace.start.checked_mul(factor)?,
+        end: trace.end.checked_mul(factor)?,
+        inner: trace.inner.iter().map(|inner| scale_trace(inner, factor)).collect::<Option<_>>()?,
+        non_preemptible: trace.non_preemptible,
+        lock: trace.lock,
+    })
+}
+
+fn scale_task(task: &Task, factor: u32) -> Option<Task> {
+    Some(Task {
+        deadline: task.deadline.checked_mul(factor)?,
+        arrival: task.arrival.with_inter_arrival(task.arrival.inter_arrival().checked_mul(factor)?),
+        trace: scale_trace(&task.trace, factor)?,
+        suspension: task.suspension.checked_mul(factor)?,
+        jitter: task.jitter.checked_mul(factor)?,
+        wcet_hi: match task.wcet_hi {
+            Some(wcet_hi) => Some(wcet_hi.checked_mul(factor)?),
+            None => None,
+        },
+        ..task.clone()
+    })
+}
+
+impl Scalable for Tasks {
+    fn scaled(&self, factor: u32) -> Result<Tasks, String> {
+        if factor == 0 {
+            return Err("Error: Cannot scale a task set by 0.".to_string());
+        }
+        self.iter()
+            .map(|task| {
+                scale_task(task, factor)
+                    .ok_or_else(|| format!("Error: Scaling task '{}' by {} overflows.", task.id, factor))
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,85 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use super::*;
+
+/// Worst-case arrival pattern of a task, constructed from its analysis, and the schedule it leads to.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct Scenario {
+    pub task: String,
+    /// Lower priority task whose critical section blocks the task for B(t), if any
+    pub blocker: Option<String>,
+    /// Resource locked by the blocker
+    pub resource: Option<String>,
+    /// Job of the task released at the critical instant
+    pub job: SimulatedJob,
+    pub simulation: Simulation,
+}
+
+impl Scenario {
+    /// Whether the job released at the critical instant misses its absolute deadline.
+    pub fn misses_deadline(&self) -> bool {
+        self.job.completion.is_none_or(|completion| completion > self.job.deadline)
+    }
+}
+
+/// Simulates the critical instant of the task at `index`, for `horizon` time units after its release.
+///
+/// The lower priority task l with the longest section l_r blocking the task, see `AnalysisContext::blocking_time`,
+/// is released at 0 and locks l_r just as the task is released. Every task h with P(h) > P(t) is released together
+/// with the task, and again as early as its jitter J(h) allows, so that its first releases are packed as densely as
+/// η_h(w) assumes. Tasks of equal priority are released together with the task as well, but queued before it.
+/// All other tasks are never released, they can not delay the task beyond B(t).
+pub fn critical_instant(tasks: &Tasks, index: usize, horizon: u32) -> Scenario {
+    let task = &tasks[index];
+    let context = AnalysisContext::new(tasks);
+
+    let blocking = tasks
+        .iter()
+        .enumerate()
+        .filter(|(_, l)| l.level() < task.level())
+        .flat_map(|(i, l)| {
+            l.resources()
+                .filter(move |resource| l.kind == task.kind || resource.non_preemptible)
+                .map(move |resource| (i, resource))
+        })
+        .filter(|(_, resource)| context.ceiling_priority(resource) >= task.prio)
+        .max_by_key(|(_, resource)| resource.wcet());
+    // The blocker executes its trace up to the lock before the task is released
+    let release = blocking.map_or(0, |(i, resource)| resource.start - tasks[i].trace.start);
+
+    // The task itself comes last, so that the simulation queues it after the equal priority tasks
+    let mut order: Vec<usize> = (0..tasks.len()).filter(|&i| i != index).collect();
+    order.push(index);
+    let ordered: Tasks = order.iter().map(|&i| tasks[i].clone()).collect();
+    let releases: Vec<Option<Release>> = order
+        .iter()
+        .map(|&i| {
+            let other = &tasks[i];
+            if blocking.is_some_and(|(blocker, _)| blocker == i) {
+                Some(Release { offset: 0, jitter: 0 })
+            } else if other.level() > task.level() {
+                Some(Release { offset: release, jitter: other.jitter })
+            } else if other.level() == task.level() {
+                Some(Release { offset: release, jitter: 0 })
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    let simulation = simulate_releases(&ordered, &releases, release.saturating_add(horizon.max(1)));
+    let job = simulation
+        .jobs
+        .iter()
+        .find(|job| job.task == task.id && job.job == 0)
+        .cloned()
+        .expect("the task is released at the critical instant");
+    Scenario {
+        task: task.id.clone(),
+        blocker: blocking.map(|(i, _)| tasks[i].id.clone()),
+        resource: blocking.map(|(_, resource)| resource.id.clone()),
+        job,
+        simulation,
+    }
+}
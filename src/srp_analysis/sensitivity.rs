@@ -0,0 +1,78 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use super::*;
+
+/// How much C(t) and J(t) of a task may grow before some deadline is missed, see `task_sensitivities`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct TaskSensitivity {
+    pub task: String,
+    pub wcet: u32,
+    /// Largest increase of C(t) that keeps the task set schedulable, `None` if it is not schedulable as is
+    pub wcet_slack: Option<u32>,
+    pub jitter: u32,
+    /// Largest J(t) that keeps the task set schedulable with `JitterModel::Release`, `None` if it is not
+    /// schedulable with the current jitters
+    pub max_jitter: Option<u32>,
+}
+
+// Largest value of `parameter` in `low..=high` that keeps the task set schedulable, given that `low` does
+fn largest_schedulable(
+    tasks: &Tasks,
+    config: &AnalysisConfig,
+    parameter: &ParameterRef,
+    (mut low, mut high): (u32, u32),
+) -> Result<u32, String> {
+    // Invariant: `low` is schedulable, everything above `high` is not
+    while low < high {
+        let mid = low + ceil_div(high - low, 2);
+        let mut trial = tasks.clone();
+        parameter.apply(&mut trial, mid)?;
+        if is_schedulable(&srp_analyze(&trial, config)) {
+            low = mid;
+        } else {
+            high = mid - 1;
+        }
+    }
+    Ok(low)
+}
+
+/// WCET slack and permissible release jitter of every task, in the order of the task set, each found by binary
+/// search with all other tasks as they are.
+///
+/// Since R(t) >= C(t) and, with release jitter, R(t) >= J(t) + C(t), neither C(t) nor J(t) can exceed D(t). The
+/// jitter is analyzed with `JitterModel::Release` regardless of `config`. Both searches assume schedulability does
+/// not improve with a larger C(t) or J(t).
+pub fn task_sensitivities(tasks: &Tasks, config: &AnalysisConfig) -> Result<Vec<TaskSensitivity>, String> {
+    validate_task_set(tasks)?;
+    let jittery = AnalysisConfig { jitter_model: JitterModel::Release, ..*config };
+    let schedulable = is_schedulable(&srp_analyze(tasks, config));
+    let schedulable_with_jitter = is_schedulable(&srp_analyze(tasks, &jittery));
+
+    tasks
+        .iter()
+        .map(|task| {
+            let wcet_slack = if schedulable {
+                let wcet = ParameterRef { task: task.id.clone(), parameter: TaskParameter::Wcet };
+                let range = (task.wcet(), task.deadline.max(task.wcet()));
+                Some(largest_schedulable(tasks, config, &wcet, range)? - task.wcet())
+            } else {
+                None
+            };
+            let max_jitter = if schedulable_with_jitter {
+                let jitter = ParameterRef { task: task.id.clone(), parameter: TaskParameter::Jitter };
+                let range = (task.jitter, task.deadline.max(task.jitter));
+                Some(largest_schedulable(tasks, &jittery, &jitter, range)?)
+            } else {
+                None
+            };
+            Ok(TaskSensitivity {
+                task: task.id.clone(),
+                wcet: task.wcet(),
+                wcet_slack,
+                jitter: task.jitter,
+                max_jitter,
+            })
+        })
+        .collect()
+}
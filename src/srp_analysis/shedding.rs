@@ -0,0 +1,64 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use super::*;
+
+// Every subset of the optional tasks is analyzed, 2^n task sets
+const MAX_OPTIONAL_TASKS: usize = 16;
+
+/// Optional tasks to drop so that every hard task meets its deadline.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct SheddingPlan {
+    /// Ids of the dropped optional tasks
+    pub shed: Vec<String>,
+    /// L_tot of the remaining tasks
+    pub load_factor: f32,
+    /// Ids of the remaining soft and optional tasks that still miss their deadlines
+    pub misses: Vec<String>,
+}
+
+/// Smallest sets of optional tasks whose removal makes every hard task meet its deadline.
+///
+/// Every plan drops the same, minimal number of optional tasks, and the plans with the fewest remaining deadline
+/// misses come first, then those leaving the lowest load L_tot. An empty plan means no task needs to be dropped.
+///
+/// Returns no plans if the hard tasks miss their deadlines even with all optional tasks dropped.
+pub fn shedding_plans(tasks: &Tasks, config: &AnalysisConfig) -> Result<Vec<SheddingPlan>, String> {
+    let optional: Vec<usize> = (0..tasks.len())
+        .filter(|&i| tasks[i].criticality == Criticality::Optional)
+        .collect();
+    if optional.len() > MAX_OPTIONAL_TASKS {
+        return Err(format!(
+            "Error: {} optional tasks exceed the {} that can be searched for shedding plans.",
+            optional.len(),
+            MAX_OPTIONAL_TASKS
+        ));
+    }
+
+    // Subsets of the optional tasks as bit masks, the smallest ones first
+    let mut subsets: Vec<u32> = (0..1u32 << optional.len()).collect();
+    subsets.sort_by_key(|subset| subset.count_ones());
+
+    let mut plans = vec![];
+    for subset in subsets {
+        if plans.first().is_some_and(|plan: &SheddingPlan| plan.shed.len() < subset.count_ones() as usize) {
+            break;
+        }
+        let shed = |i: usize| optional.iter().position(|&o| o == i).is_some_and(|bit| subset & (1 << bit) != 0);
+        let remaining: Tasks = (0..tasks.len()).filter(|&i| !shed(i)).map(|i| tasks[i].clone()).collect();
+
+        let results = srp_analyze(&remaining, config);
+        let meets = |(task, response_time, ..): &&AnalysisResult| matches!(response_time, Ok(r) if *r <= task.deadline);
+        if !results.iter().filter(|(task, ..)| task.criticality == Criticality::Hard).all(|result| meets(&result)) {
+            continue;
+        }
+        plans.push(SheddingPlan {
+            shed: (0..tasks.len()).filter(|&i| shed(i)).map(|i| tasks[i].id.clone()).collect(),
+            load_factor: total_load_factor(&remaining)?,
+            misses: results.iter().filter(|result| !meets(result)).map(|(task, ..)| task.id.clone()).collect(),
+        });
+    }
+
+    plans.sort_by(|a, b| a.misses.len().cmp(&b.misses.len()).then(a.load_factor.total_cmp(&b.load_factor)));
+    Ok(plans)
+}
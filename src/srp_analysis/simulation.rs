@@ -0,0 +1,363 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use super::*;
+
+/// Progress notifications of `simulate_with_progress` over the whole horizon.
+pub const SIMULATION_PROGRESS_STEPS: u32 = 100;
+
+/// Interval `start..end` in which job `job` of `task` was executing.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct ExecutionSlice {
+    pub task: String,
+    pub job: u32,
+    pub start: u32,
+    pub end: u32,
+}
+
+/// System ceiling Π(t) from `time` on, until the next step.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct CeilingStep {
+    pub time: u32,
+    /// Π(t) = max(π(r)) of all currently locked resources r, 0 if no resource is locked
+    pub ceiling: u8,
+}
+
+/// Release and completion of a single job.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct SimulatedJob {
+    pub task: String,
+    pub job: u32,
+    pub release: u32,
+    /// Absolute deadline, release + D(t)
+    pub deadline: u32,
+    /// `None` if the job did not complete within the simulated horizon
+    pub completion: Option<u32>,
+}
+
+impl SimulatedJob {
+    /// Completion - release, if the job completed.
+    pub fn response_time(&self) -> Option<u32> {
+        self.completion.map(|completion| completion - self.release)
+    }
+}
+
+/// Schedule of a task set under SRP over `0..horizon`.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct Simulation {
+    pub horizon: u32,
+    pub slices: Vec<ExecutionSlice>,
+    pub ceiling: Vec<CeilingStep>,
+    pub jobs: Vec<SimulatedJob>,
+}
+
+/// What happens to a job in a `JobEvent`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum JobEventKind {
+    Released,
+    /// First dispatched, from then on it only waits for preempting jobs
+    Started,
+    Completed,
+    /// Still incomplete at its absolute deadline
+    DeadlineMissed,
+}
+
+/// Release, start, completion or deadline miss of a job at `time`, see `Simulator::step`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct JobEvent {
+    pub time: u32,
+    pub task: String,
+    pub job: u32,
+    pub kind: JobEventKind,
+}
+
+impl JobEvent {
+    fn new(time: u32, job: &SimulatedJob, kind: JobEventKind) -> Self {
+        JobEvent { time, task: job.task.clone(), job: job.job, kind }
+    }
+}
+
+/// Schedule of `from..until` as simulated by a `Simulator::step`.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct SimulationStep {
+    pub from: u32,
+    pub until: u32,
+    /// Execution within `from..until`, a slice running since before `from` starts at `from`
+    pub slices: Vec<ExecutionSlice>,
+    /// Changes of Π(t) within `from..until`
+    pub ceiling: Vec<CeilingStep>,
+    /// In the order they happen
+    pub events: Vec<JobEvent>,
+}
+
+// Pending or running job of `tasks[task]`, `executed` time units into its trace
+struct ActiveJob {
+    index: usize,
+    task: usize,
+    started: bool,
+    executed: u32,
+}
+
+// Resources held by a job of the task at its current execution offset
+fn held<'a>(task: &'a Task, job: &ActiveJob) -> impl Iterator<Item = &'a Trace> + 'a {
+    let now = task.trace.start + job.executed;
+    task.resources().filter(move |r| r.start <= now && now < r.end)
+}
+
+/// Simulates the task set under SRP from a synchronous release of all tasks at time 0 until `horizon`.
+///
+/// Every job executes its full WCET C(t), locking resources as laid out in its trace. A released job may only
+/// start once its priority is higher than both the running job and the system ceiling Π(t); once started it
+/// runs until completion unless preempted by such a job, so it never blocks on a resource. Interrupts only
+/// respect the ceilings of resources held by other interrupts and of non-preemptible sections, see
+/// `Task::level`.
+///
+/// Periodic and sporadic tasks are released as often as possible, bursty tasks release all n jobs at the start
+/// of every window.
+pub fn simulate(tasks: &Tasks, horizon: u32) -> Simulation {
+    simulate_releases(tasks, &vec![Some(Release::default()); tasks.len()], horizon)
+}
+
+/// Like `simulate`, notifying `progress` of the simulated time at every `SIMULATION_PROGRESS_STEPS`-th of the
+/// horizon.
+pub fn simulate_with_progress<F: FnMut(AnalysisProgress)>(tasks: &Tasks, horizon: u32, mut progress: F) -> Simulation {
+    let mut simulator = Simulator::new(tasks.clone());
+    let step = ceil_div(horizon, SIMULATION_PROGRESS_STEPS).max(1);
+    while simulator.time() < horizon {
+        simulator.advance(simulator.time().saturating_add(step).min(horizon));
+        progress(AnalysisProgress::Simulation { time: simulator.time(), horizon });
+    }
+    simulator.simulation
+}
+
+/// First release of a task in a simulation, and the release jitter J by which every later release comes early.
+#[derive(Debug, Clone, Copy, Default)]
+pub(super) struct Release {
+    pub offset: u32,
+    pub jitter: u32,
+}
+
+impl Release {
+    // Whether a job is released at `time`, at offset + k * A - J for k > 0
+    fn at(&self, time: u32, inter_arrival: u32) -> bool {
+        let jitter = self.jitter.min(inter_arrival - 1);
+        time == self.offset || (time > self.offset && (time - self.offset + jitter).is_multiple_of(inter_arrival))
+    }
+}
+
+/// Like `simulate`, but with the release of every task given by `releases`, `None` for tasks never released.
+pub(super) fn simulate_releases(tasks: &Tasks, releases: &[Option<Release>], horizon: u32) -> Simulation {
+    let mut simulator = Simulator::with_releases(tasks.clone(), releases.to_vec());
+    simulator.advance(horizon);
+    simulator.simulation
+}
+
+/// Schedule of `simulate` advanced a step at a time, e.g. by a play/pause/step UI over the timeline.
+///
+/// The schedule of `0..t` is the same however many steps it took to get there.
+pub struct Simulator {
+    tasks: Tasks,
+    releases: Vec<Option<Release>>,
+    active: Vec<ActiveJob>,
+    // Jobs released so far of every task
+    released: Vec<u32>,
+    // Events since the last step
+    events: Vec<JobEvent>,
+    simulation: Simulation,
+}
+
+impl Simulator {
+    /// Simulator at time 0 of a synchronous release of all tasks.
+    pub fn new(tasks: Tasks) -> Self {
+        let releases = vec![Some(Release::default()); tasks.len()];
+        Self::with_releases(tasks, releases)
+    }
+
+    pub(super) fn with_releases(tasks: Tasks, releases: Vec<Option<Release>>) -> Self {
+        Self {
+            released: vec![0; tasks.len()],
+            tasks,
+            releases,
+            active: vec![],
+            events: vec![],
+            simulation: Simulation { horizon: 0, slices: vec![], ceiling: vec![], jobs: vec![] },
+        }
+    }
+
+    /// Task set being simulated.
+    pub fn tasks(&self) -> &Tasks {
+        &self.tasks
+    }
+
+    /// Time simulated so far.
+    pub fn time(&self) -> u32 {
+        self.simulation.horizon
+    }
+
+    /// Schedule of `0..time()`.
+    pub fn simulation(&self) -> &Simulation {
+        &self.simulation
+    }
+
+    /// Simulates `time()..until` and returns what happened in between, nothing if `until` is not later.
+    pub fn step(&mut self, until: u32) -> SimulationStep {
+        let from = self.time();
+        let ceiling = self.simulation.ceiling.len();
+        self.advance(until);
+
+        let mut slices: Vec<ExecutionSlice> = self
+            .simulation
+            .slices
+            .iter()
+            .rev()
+            .take_while(|slice| slice.end > from)
+            .map(|slice| ExecutionSlice { start: slice.start.max(from), ..slice.clone() })
+            .collect();
+        slices.reverse();
+        SimulationStep {
+            from,
+            until: self.time(),
+            slices,
+            ceiling: self.simulation.ceiling[ceiling..].to_vec(),
+            events: std::mem::take(&mut self.events),
+        }
+    }
+
+    fn advance(&mut self, until: u32) {
+        let Simulator { tasks, releases, active, released, events, simulation } = self;
+        let tasks: &Tasks = tasks;
+        let ceilings = AnalysisContext::new(tasks);
+
+        for time in simulation.horizon..until {
+            for job in active.iter() {
+                let info = &simulation.jobs[job.index];
+                if info.deadline == time {
+                    events.push(JobEvent::new(time, info, JobEventKind::DeadlineMissed));
+                }
+            }
+
+            for (t, task) in tasks.iter().enumerate() {
+                let inter_arrival = task.arrival.inter_arrival().max(1);
+                if !releases[t].is_some_and(|release| release.at(time, inter_arrival)) {
+                    continue;
+                }
+                for _ in 0..task.arrival.releases_per_inter_arrival() {
+                    // Jobs without any execution time complete right away
+                    let completion = if task.wcet() == 0 { Some(time) } else { None };
+                    if completion.is_none() {
+                        active.push(ActiveJob { index: simulation.jobs.len(), task: t, started: false, executed: 0 });
+                    }
+                    let job = SimulatedJob {
+                        task: task.id.clone(),
+                        job: released[t],
+                        release: time,
+                        deadline: time + task.deadline,
+                        completion,
+                    };
+                    events.push(JobEvent::new(time, &job, JobEventKind::Released));
+                    if completion.is_some() {
+                        events.push(JobEvent::new(time, &job, JobEventKind::Completed));
+                    }
+                    simulation.jobs.push(job);
+                    released[t] += 1;
+                }
+            }
+
+            // Π(t) before the next job is dispatched
+            let system_ceiling = |active: &[ActiveJob]| -> u8 {
+                active
+                    .iter()
+                    .filter(|job| job.started)
+                    .flat_map(|job| held(&tasks[job.task], job))
+                    .map(|resource| ceilings.ceiling_priority(resource))
+                    .max()
+                    .unwrap_or(0)
+            };
+
+            // Π(t) as seen by a job of the given kind
+            let blocking_ceiling = |active: &[ActiveJob], kind: TaskKind| -> u8 {
+                active
+                    .iter()
+                    .filter(|job| job.started)
+                    .flat_map(|job| {
+                        let holder = &tasks[job.task];
+                        held(holder, job).filter(move |resource| holder.kind == kind || resource.non_preemptible)
+                    })
+                    .map(|resource| ceilings.ceiling_priority(resource))
+                    .max()
+                    .unwrap_or(0)
+            };
+
+            // The running job is the highest priority started job, the earliest released one on equal priorities
+            let running_level = active.iter().filter(|job| job.started).map(|job| tasks[job.task].level()).max();
+            let dispatch = active
+                .iter()
+                .enumerate()
+                .filter(|(_, job)| !job.started)
+                .filter(|(_, job)| {
+                    let task = &tasks[job.task];
+                    task.prio > blocking_ceiling(active, task.kind) && Some(task.level()) > running_level
+                })
+                .max_by_key(|(i, job)| (tasks[job.task].level(), std::cmp::Reverse(*i)))
+                .map(|(i, _)| i);
+            if let Some(i) = dispatch {
+                active[i].started = true;
+                events.push(JobEvent::new(time, &simulation.jobs[active[i].index], JobEventKind::Started));
+            }
+
+            let running = active
+                .iter()
+                .enumerate()
+                .filter(|(_, job)| job.started)
+                .max_by_key(|(i, job)| (tasks[job.task].level(), std::cmp::Reverse(*i)))
+                .map(|(i, _)| i);
+
+            if let Some(i) = running {
+                let info = &simulation.jobs[active[i].index];
+                match simulation.slices.last_mut() {
+                    Some(slice) if slice.end == time && slice.task == info.task && slice.job == info.job => {
+                        slice.end += 1
+                    }
+                    _ => simulation.slices.push(ExecutionSlice {
+                        task: info.task.clone(),
+                        job: info.job,
+                        start: time,
+                        end: time + 1,
+                    }),
+                }
+
+                // The ceiling of this time unit includes the resources the running job holds while executing it
+                let ceiling = system_ceiling(active);
+                if simulation.ceiling.last().map(|step| step.ceiling) != Some(ceiling) {
+                    simulation.ceiling.push(CeilingStep { time, ceiling });
+                }
+
+                let job = &mut active[i];
+                job.executed += 1;
+                if job.executed >= tasks[job.task].wcet() {
+                    let info = &mut simulation.jobs[job.index];
+                    info.completion = Some(time + 1);
+                    events.push(JobEvent::new(time + 1, info, JobEventKind::Completed));
+                    active.remove(i);
+                }
+            } else if simulation.ceiling.last().map(|step| step.ceiling) != Some(0) {
+                simulation.ceiling.push(CeilingStep { time, ceiling: 0 });
+            }
+        }
+        simulation.horizon = simulation.horizon.max(until);
+    }
+}
+
+/// H = lcm(A(t)) over all tasks, after which the schedule of a synchronous release repeats.
+///
+/// Returns `None` if H does not fit into a u32.
+pub fn hyperperiod(tasks: &Tasks) -> Option<u32> {
+    fn gcd(a: u32, b: u32) -> u32 {
+        if b == 0 { a } else { gcd(b, a % b) }
+    }
+    tasks.iter().try_fold(1u32, |h, task| {
+        let a = task.arrival.inter_arrival().max(1);
+        (h / gcd(h, a)).checked_mul(a)
+    })
+}
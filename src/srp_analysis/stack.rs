@@ -0,0 +1,57 @@
+use std::cmp::Reverse;
+
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use super::*;
+
+/// Task of the deepest preemption chain at a priority level.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct StackLevel {
+    pub prio: u8,
+    pub task: String,
+    pub stack: u32,
+}
+
+/// Worst-case depth of the single shared stack under SRP.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct StackAnalysis {
+    /// Deepest preemption chain, at most one task per priority level from lowest to highest priority.
+    pub levels: Vec<StackLevel>,
+    /// S_tot = sum(S(t)) over the deepest preemption chain, where S(t) is the stack usage of t.
+    pub total: u32,
+}
+
+/// Computes the worst-case shared stack depth under SRP.
+///
+/// Once running a task never blocks and is only preempted by tasks h where P(h) > γ(t), so every priority level
+/// contributes at most one stack frame to any preemption chain. Levels compare as in `Task::level`, interrupt
+/// handlers stack on top of every software task. Without preemption thresholds the chain takes the
+/// largest frame of every level, S_tot = sum(max(S(t))), thresholds of γ(t) > P(t) skip the levels in between.
+pub fn stack_depth(tasks: &Tasks) -> StackAnalysis {
+    // Highest priority first, so every task that can preempt a task comes before it
+    let mut order: Vec<&Task> = tasks.iter().collect();
+    order.sort_by_key(|task| Reverse(task.level()));
+
+    // Depth of the deepest chain starting at each task and the task preempting it in that chain, preferring the
+    // lowest priority and then the first task on ties
+    let mut deepest: Vec<(u32, Option<usize>)> = Vec::with_capacity(order.len());
+    for (i, task) in order.iter().enumerate() {
+        let next = (0..i)
+            .filter(|&h| order[h].level() > (task.kind, task.preemption_threshold()))
+            .max_by_key(|&h| (deepest[h].0, Reverse(order[h].level()), Reverse(h)));
+        deepest.push((task.stack + next.map_or(0, |h| deepest[h].0), next));
+    }
+
+    let mut levels = vec![];
+    let mut current = (0..order.len()).max_by_key(|&t| (deepest[t].0, Reverse(order[t].level()), Reverse(t)));
+    while let Some(t) = current {
+        levels.push(StackLevel { prio: order[t].prio, task: order[t].id.clone(), stack: order[t].stack });
+        current = deepest[t].1;
+    }
+
+    StackAnalysis {
+        total: levels.iter().map(|l| l.stack).sum(),
+        levels,
+    }
+}
@@ -0,0 +1,67 @@
+use super::*;
+
+impl Task {
+    /// B(t), charged once more after the self-suspension of a suspending task.
+    ///
+    /// A job resuming from a suspension may find a lower priority task locking a resource it started in the
+    /// meantime, so every suspension adds another blocking interval.
+    fn suspension_blocking_time<T>(&self, tasks: &T) -> u32
+    where
+        T: std::ops::Deref<Target = [Task]> + Sized,
+    {
+        let blocking_time = self.blocking_time(tasks);
+        if self.suspension > 0 { 2 * blocking_time } else { blocking_time }
+    }
+
+    /// R(t) = B(t) + C(t) + S(t) + E(t) + sum(η_h(R(t)) * (C(h) + S(h))) for all tasks h where P(h) > P(t)
+    ///
+    /// Suspension-oblivious bound: every suspension S is treated as execution time, as if the processor was
+    /// kept busy while waiting, E(t) = sum(C(e) + S(e)) for all other tasks e where P(e) = P(t). Solved as a
+    /// fixed point starting from B(t) + C(t) + S(t) + E(t).
+    pub fn suspension_oblivious_response_time<T>(&self, tasks: &T) -> Result<u32, String>
+    where
+        T: std::ops::Deref<Target = [Task]> + Sized,
+    {
+        let equal: u32 = tasks
+            .iter()
+            .filter(|e| e.level() == self.level() && e.id != self.id)
+            .map(|e| e.wcet() + e.suspension)
+            .sum();
+        let own = self.blocking_time(tasks) + self.wcet() + self.suspension + equal;
+        fixed_point(own, |r| {
+            tasks
+                .iter()
+                .filter(|h| h.level() > self.level())
+                .try_fold(own, |acc, h| {
+                    acc.checked_add((h.wcet() + h.suspension).checked_mul(h.max_releases(r))?)
+                })
+        })
+    }
+
+    /// R(t) = B'(t) + C(t) + S(t) + sum(C(e)) + sum(η_h(R(t) + J(h)) * C(h)) for all other tasks e where
+    /// P(e) = P(t) and all tasks h where P(h) > P(t)
+    ///
+    /// Suspension-aware bound: the suspensions of a higher priority task h only shift its execution, modelled as
+    /// release jitter J(h) = R(h) - C(h). B'(t) = 2 * B(t) if t suspends, B(t) otherwise. Suspensions are
+    /// assumed to happen outside of critical sections, as required by SRP.
+    pub fn suspension_aware_response_time<T>(&self, tasks: &T) -> Result<u32, String>
+    where
+        T: std::ops::Deref<Target = [Task]> + Sized,
+    {
+        let jitters = tasks
+            .iter()
+            .filter(|h| h.level() > self.level())
+            .map(|h| Ok((h, h.suspension_aware_response_time(tasks)? - h.wcet())))
+            .collect::<Result<Vec<_>, String>>()?;
+
+        let own = self.suspension_blocking_time(tasks)
+            + self.wcet()
+            + self.suspension
+            + self.equal_priority_interference(tasks);
+        fixed_point(own, |r| {
+            jitters.iter().try_fold(own, |acc, (h, jitter)| {
+                acc.checked_add(h.wcet().checked_mul(h.max_releases(r.checked_add(*jitter)?))?)
+            })
+        })
+    }
+}
@@ -0,0 +1,167 @@
+use std::collections::BTreeMap;
+
+use rayon::prelude::*;
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use super::*;
+
+/// Most values of a single swept parameter, see `SweepAxis::values`.
+pub const MAX_SWEEP_POINTS: usize = 10_000;
+/// Most combinations analyzed for a schedulability region, see `schedulability_region`.
+pub const MAX_REGION_POINTS: usize = 100_000;
+
+/// Range of values `from..=to` in steps of `step` swept for a single parameter.
+#[derive(Debug, Clone)]
+pub struct SweepAxis {
+    pub parameter: ParameterRef,
+    pub from: u32,
+    pub to: u32,
+    pub step: u32,
+}
+
+impl SweepAxis {
+    /// Number of swept values, 0 if `from` is above `to`.
+    pub fn count(&self) -> usize {
+        self.to.checked_sub(self.from).map_or(0, |range| (range / self.step.max(1)) as usize + 1)
+    }
+
+    /// The swept values, or an error for an axis of more than `MAX_SWEEP_POINTS` values.
+    pub fn values(&self) -> Result<Vec<u32>, String> {
+        if self.count() > MAX_SWEEP_POINTS {
+            return Err(format!(
+                "Error: Sweeping {} values exceeds the limit of {} values per parameter.",
+                self.count(),
+                MAX_SWEEP_POINTS
+            ));
+        }
+        Ok((self.from..=self.to).step_by(self.step.max(1) as usize).collect())
+    }
+}
+
+/// Schedulability of a task set over a grid of two parameters, `schedulable[y][x]`.
+#[derive(Debug, Clone, Serialize)]
+pub struct SchedulabilityRegion {
+    pub x: Vec<u32>,
+    pub y: Vec<u32>,
+    pub schedulable: Vec<Vec<bool>>,
+}
+
+/// Sweeps the two parameters over their ranges, analyzing every combination in parallel.
+///
+/// Returns an error for more than `MAX_REGION_POINTS` combinations.
+pub fn schedulability_region(
+    tasks: &Tasks,
+    config: &AnalysisConfig,
+    x: &SweepAxis,
+    y: &SweepAxis,
+) -> Result<SchedulabilityRegion, String> {
+    let xs = x.values()?;
+    let ys = y.values()?;
+    if xs.len() * ys.len() > MAX_REGION_POINTS {
+        return Err(format!(
+            "Error: A region of {} x {} values exceeds the limit of {} combinations.",
+            xs.len(),
+            ys.len(),
+            MAX_REGION_POINTS
+        ));
+    }
+
+    let schedulable = ys
+        .par_iter()
+        .map(|&y_value| {
+            xs.iter()
+                .map(|&x_value| {
+                    let mut tasks = tasks.clone();
+                    x.parameter.apply(&mut tasks, x_value)?;
+                    y.parameter.apply(&mut tasks, y_value)?;
+                    Ok(is_schedulable(&srp_analyze(&tasks, config)))
+                })
+                .collect::<Result<Vec<_>, String>>()
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    Ok(SchedulabilityRegion { x: xs, y: ys, schedulable })
+}
+
+impl Task {
+    /// R(t) = B(t) + C(t) + sum(C(e)) + sum(η_h(R(t)) * C(h)) for all other tasks e where P(e) = P(t) and all
+    /// tasks h where P(h) > P(t), together with the number of fixed-point iterations it took
+    ///
+    /// The iteration starts from max(B(t) + C(t) + sum(C(e)), `seed`). A seed of at most R(t), e.g. R(t) before
+    /// an execution time or release rate grew, converges to the same R(t) in fewer iterations, 0 starts cold.
+    pub fn iterative_response_time<T>(&self, tasks: &T, seed: u32) -> Result<(u32, u32), String>
+    where
+        T: std::ops::Deref<Target = [Task]> + Sized,
+    {
+        let own = self.blocking_time(tasks) + self.wcet() + self.equal_priority_interference(tasks);
+        fixed_point_counted(own.max(seed), |r| {
+            tasks
+                .iter()
+                .filter(|h| h.level() > self.level())
+                .try_fold(own, |acc, h| acc.checked_add(h.wcet().checked_mul(h.max_releases(r))?))
+        })
+    }
+}
+
+/// R(t) of every task at one value of a swept parameter, see `response_time_sweep`.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct SweepPoint {
+    pub value: u32,
+    /// Either `{"Ok": R(t)}` or `{"Err": reason}` by task id
+    #[schema(value_type = Object)]
+    pub response_times: BTreeMap<String, Result<u32, String>>,
+    /// Fixed-point iterations summed over all tasks, only reported for debugging
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub iterations: Option<u32>,
+}
+
+// Whether R(t) can only have grown from the task set with `parameter` at `previous` to the one at `next`
+fn only_grows(parameter: TaskParameter, previous: u32, next: u32) -> bool {
+    match parameter {
+        TaskParameter::Wcet | TaskParameter::Jitter => next >= previous,
+        TaskParameter::Period => next <= previous,
+        // D(t) does not enter the recurrence
+        TaskParameter::Deadline => true,
+        // Priorities change which tasks interfere and block
+        TaskParameter::Prio => false,
+    }
+}
+
+/// R(t) of every task, see `Task::iterative_response_time`, at every value of `axis` in order.
+///
+/// With `warm`, every iteration is seeded with R(t) at the previous value whenever the step can only have grown
+/// it: a larger C(t) or J(t), a smaller A(t) or any D(t). Otherwise, and for priority steps, it starts cold from
+/// B(t) + C(t). `debug` reports the iterations of every point, to compare warm and cold sweeps.
+pub fn response_time_sweep(
+    tasks: &Tasks,
+    config: &AnalysisConfig,
+    axis: &SweepAxis,
+    warm: bool,
+    debug: bool,
+) -> Result<Vec<SweepPoint>, String> {
+    let mut points: Vec<SweepPoint> = vec![];
+    for value in axis.values()? {
+        let mut swept = tasks.clone();
+        axis.parameter.apply(&mut swept, value)?;
+        let swept = config.prepare(&swept);
+
+        let previous = points
+            .last()
+            .filter(|previous| warm && only_grows(axis.parameter.parameter, previous.value, value));
+        let mut iterations = 0;
+        let response_times = swept
+            .iter()
+            .map(|task| {
+                let seed = previous.and_then(|previous| previous.response_times[&task.id].clone().ok()).unwrap_or(0);
+                let response_time = task.iterative_response_time(&swept, seed).map(|(r, n)| {
+                    iterations += n;
+                    r
+                });
+                (task.id.clone(), response_time)
+            })
+            .collect();
+        points.push(SweepPoint { value, response_times, iterations: debug.then_some(iterations) });
+    }
+    Ok(points)
+}
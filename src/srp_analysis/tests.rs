@@ -0,0 +1,997 @@
+use super::*;
+
+// Rate monotonic textbook example without resources, (C, T) = (1, 4), (2, 6), (3, 10)
+fn textbook_rate_monotonic() -> Tasks {
+    [(3, 4, 1), (2, 6, 2), (1, 10, 3)]
+        .into_iter()
+        .enumerate()
+        .map(|(i, (prio, period, wcet))| {
+            TaskBuilder::new(&format!("t{}", i + 1)).prio(prio).period(period).span(0, wcet).build().unwrap()
+        })
+        .collect()
+}
+
+// Nested resources A and B, and a non-preemptible section in the lowest priority task
+fn nested_resources() -> Tasks {
+    vec![
+        TaskBuilder::new("t1").prio(3).period(20).span(0, 5).section("A", 1, 3, |s| s).build().unwrap(),
+        TaskBuilder::new("t2").prio(2).period(40).span(0, 10).section("B", 2, 6, |s| s).build().unwrap(),
+        TaskBuilder::new("t3")
+            .prio(1)
+            .period(100)
+            .span(0, 20)
+            .section("A", 5, 9, |s| s.section("B", 6, 8))
+            .non_preemptible("irq", 12, 18)
+            .build()
+            .unwrap(),
+    ]
+}
+
+fn blocking_times(tasks: &Tasks) -> Vec<u32> {
+    tasks.iter().map(|t| t.blocking_time(tasks)).collect()
+}
+
+fn interferences(tasks: &Tasks) -> Vec<u32> {
+    tasks.iter().map(|t| t.interference(tasks).unwrap()).collect()
+}
+
+fn response_times(tasks: &Tasks, mode: &PreemptionMode) -> Vec<Result<u32, String>> {
+    tasks.iter().map(|t| t.response_time(tasks, mode)).collect()
+}
+
+#[test]
+fn course_assignment_blocking_and_interference() {
+    let tasks = simple_example();
+    // T3 is blocked by R3 of T2, π(R3) = 3, R1 of T2 has π(R1) = 2
+    assert_eq!(blocking_times(&tasks), vec![0, 0, 7]);
+    assert_eq!(tasks.iter().map(|t| t.busy_period(&tasks).unwrap()).collect::<Vec<_>>(), vec![70, 60, 30]);
+    assert_eq!(interferences(&tasks), vec![90, 60, 0]);
+}
+
+#[test]
+fn course_assignment_response_times() {
+    let tasks = simple_example();
+    assert_eq!(response_times(&tasks, &PreemptionMode::Approximate), vec![Ok(100), Ok(90), Ok(37)]);
+    // R(T1) = 10 + R(T2) + R(T3) = 114 > D(T1)
+    assert_eq!(
+        response_times(&tasks, &PreemptionMode::Exact),
+        vec![Err("Deadline missed".to_string()), Ok(67), Ok(37)]
+    );
+}
+
+#[test]
+fn textbook_rate_monotonic_response_times() {
+    let tasks = textbook_rate_monotonic();
+    assert_eq!(blocking_times(&tasks), vec![0, 0, 0]);
+    assert_eq!(interferences(&tasks), vec![0, 1, 4]);
+    assert_eq!(response_times(&tasks, &PreemptionMode::Approximate), vec![Ok(1), Ok(3), Ok(7)]);
+    assert_eq!(response_times(&tasks, &PreemptionMode::Exact), vec![Ok(1), Ok(3), Ok(7)]);
+}
+
+#[test]
+fn textbook_rate_monotonic_busy_window_matches_textbook() {
+    // The iterative response time analysis gives R = 1, 3 and 10
+    let tasks = textbook_rate_monotonic();
+    let busy_window: Vec<_> = tasks
+        .iter()
+        .map(|t| t.arbitrary_deadline_response_time(&tasks, &PreemptionMode::Approximate))
+        .collect();
+    assert_eq!(busy_window, vec![Ok(1), Ok(3), Ok(10)]);
+    assert_eq!(response_times(&tasks, &PreemptionMode::SuspensionOblivious), vec![Ok(1), Ok(3), Ok(10)]);
+}
+
+#[test]
+fn nested_resources_and_non_preemptible_sections() {
+    let tasks = nested_resources();
+    // The non-preemptible section of t3 blocks t1 and t2 longer than A with B nested inside
+    assert_eq!(blocking_times(&tasks), vec![6, 6, 0]);
+    assert_eq!(interferences(&tasks), vec![0, 5, 20]);
+    assert_eq!(response_times(&tasks, &PreemptionMode::Approximate), vec![Ok(11), Ok(21), Ok(40)]);
+    assert_eq!(response_times(&tasks, &PreemptionMode::Exact), vec![Ok(11), Ok(27), Ok(58)]);
+}
+
+#[test]
+fn equal_priority_tasks_interfere_once() {
+    let tasks: Tasks = [("a", 20, 4), ("b", 30, 6), ("c", 10, 1)]
+        .into_iter()
+        .map(|(id, period, wcet)| {
+            let prio = if id == "c" { 3 } else { 2 };
+            TaskBuilder::new(id).prio(prio).period(period).span(0, wcet).build().unwrap()
+        })
+        .collect();
+    // Bp(a) = Bp(b) = 11, η_c(11) = 2
+    assert_eq!(interferences(&tasks), vec![6 + 2, 4 + 2, 0]);
+    assert_eq!(response_times(&tasks, &PreemptionMode::Exact), vec![Ok(11), Ok(11), Ok(1)]);
+    let breakdown = BTreeMap::from([("b".to_string(), 6), ("c".to_string(), 2)]);
+    assert_eq!(tasks[0].interference_breakdown(&tasks), Ok(breakdown));
+}
+
+#[test]
+fn duplicate_task_ids_are_rejected() {
+    let mut tasks = textbook_rate_monotonic();
+    assert!(validate_task_set(&tasks).is_ok());
+    tasks[2].id = "t1".to_string();
+    assert_eq!(validate_task_set(&tasks), Err("Error: Duplicate task id 't1'.".to_string()));
+}
+
+#[test]
+fn resources_are_iterated_in_pre_order() {
+    let task = TaskBuilder::new("t")
+        .period(100)
+        .span(0, 50)
+        .section("A", 0, 20, |s| s.section_with("B", 2, 10, |s| s.section("C", 3, 5)).section("D", 12, 18))
+        .section("E", 25, 30, |s| s)
+        .section("F", 30, 45, |s| s.section("G", 31, 40))
+        .build()
+        .unwrap();
+    let ids: Vec<&str> = task.resources().map(|r| r.id.as_str()).collect();
+    assert_eq!(ids, vec!["A", "B", "C", "D", "E", "F", "G"]);
+
+    let flat = TaskBuilder::new("flat").period(100).span(0, 10).build().unwrap();
+    assert_eq!(flat.resources().next(), None);
+}
+
+#[test]
+fn pre_analysis_is_ordered_by_id() {
+    let mut tasks = nested_resources();
+    tasks.reverse();
+    let (ip, tr) = pre_analysis(&tasks);
+    assert_eq!(ip.keys().collect::<Vec<_>>(), vec!["A", "B", "irq", "t1", "t2", "t3"]);
+    assert_eq!(tr["t3"].iter().collect::<Vec<_>>(), vec!["A", "B", "irq"]);
+
+    let ceilings: Vec<(String, Vec<String>)> =
+        resource_ceilings(&tasks).into_iter().map(|c| (c.resource, c.tasks)).collect();
+    assert_eq!(ceilings[0], ("A".to_string(), vec!["t1".to_string(), "t3".to_string()]));
+}
+
+fn configured_response_times(tasks: &Tasks, config: &AnalysisConfig) -> Vec<Result<u32, String>> {
+    srp_analyze(tasks, config).into_iter().map(|(_, response_time, ..)| response_time).collect()
+}
+
+#[test]
+fn release_jitter_is_only_analyzed_under_the_release_model() {
+    let mut tasks = textbook_rate_monotonic();
+    tasks[0].jitter = 3;
+    let config = AnalysisConfig::default();
+    assert_eq!(configured_response_times(&tasks, &config), vec![Ok(1), Ok(3), Ok(7)]);
+
+    // R(t1) = J(t1) + C(t1), η_t1(Bp(t2)) = ceiling((3 + 3) / 4), η_t1(Bp(t3)) = ceiling((6 + 3) / 4)
+    let config = AnalysisConfig { jitter_model: JitterModel::Release, ..config };
+    assert_eq!(configured_response_times(&tasks, &config), vec![Ok(4), Ok(4), Ok(8)]);
+}
+
+#[test]
+fn timing_parameters_are_rounded_to_ticks() {
+    let config = AnalysisConfig { tick_granularity: 2, ..Default::default() };
+    let tasks = textbook_rate_monotonic();
+    // C(t1) = 2 and C(t3) = 4
+    let wcets: Vec<u32> = srp_analyze(&tasks, &config).iter().map(|(.., wcet, _)| *wcet).collect();
+    assert_eq!(wcets, vec![2, 2, 4]);
+    assert_eq!(configured_response_times(&tasks, &config), vec![Ok(2), Ok(4), Ok(12)]);
+}
+
+#[test]
+fn non_preemptive_blocking_ignores_ceilings() {
+    let tasks = simple_example();
+    let config = AnalysisConfig { blocking_refinement: BlockingRefinement::NonPreemptive, ..Default::default() };
+    // R1 of T2 now blocks T3 as well
+    let blocking: Vec<u32> = srp_analyze(&tasks, &config).iter().map(|(_, _, b, ..)| *b).collect();
+    assert_eq!(blocking, vec![0, 0, 10]);
+}
+
+#[test]
+fn wcet_budgets_are_raised_greedily_in_order() {
+    let tasks = textbook_rate_monotonic();
+    let bounds = |task: &str, lower, upper| WcetBounds { task: task.to_string(), lower, upper };
+    let config = AnalysisConfig::default();
+    // C(t3) = 4 gives R(t3) = D(t3) = 10, with C(t1) = 2 R(t3) would be 12
+    let budgets = allocate_wcet_budgets(&tasks, &[bounds("t3", 1, 10), bounds("t1", 1, 3)], &config).unwrap().unwrap();
+    assert_eq!(budgets.iter().map(|b| b.budget).collect::<Vec<_>>(), vec![4, 1]);
+
+    assert_eq!(allocate_wcet_budgets(&tasks, &[bounds("t1", 4, 5)], &config), Ok(None));
+    assert!(allocate_wcet_budgets(&tasks, &[bounds("t1", 3, 2)], &config).is_err());
+}
+
+#[test]
+fn components_are_instantiated_at_their_offsets() {
+    let spi = Component::new("spi_transfer", |s| s.section_with("SPI", 0, 6, |s| s.section("DMA", 2, 4))).unwrap();
+    assert_eq!(spi.duration(), 6);
+
+    let task_set = ComponentTaskSet {
+        components: vec![spi.clone()],
+        tasks: vec![
+            TaskBuilder::new("sensor").prio(2).period(50).span(0, 10).build().unwrap(),
+            TaskBuilder::new("logger").prio(1).period(100).span(5, 25).build().unwrap(),
+        ],
+        uses: vec![
+            ComponentUse { task: "sensor".to_string(), component: "spi_transfer".to_string(), offset: 2 },
+            ComponentUse { task: "logger".to_string(), component: "spi_transfer".to_string(), offset: 10 },
+        ],
+    };
+    let tasks = task_set.expand().unwrap();
+    let logger = TaskBuilder::new("logger").prio(1).period(100).span(5, 25).component(&spi, 15).build().unwrap();
+    assert_eq!(tasks[1], logger);
+    assert_eq!(tasks[0].trace.inner[0].inner[0].start, 4);
+    // The SPI section of the logger blocks the sensor
+    assert_eq!(blocking_times(&tasks), vec![6, 0]);
+}
+
+#[test]
+fn clock_model_reports_times_in_microseconds() {
+    let tasks = textbook_rate_monotonic();
+    let clock = ClockModel { frequency_hz: 2_000_000 };
+    let results = srp_reanalyze(&tasks, &[], &tasks, &AnalysisConfig { clock: Some(clock), ..Default::default() });
+    let real_time = results[2].real_time.clone().unwrap();
+    assert_eq!((real_time.response_time_us, real_time.deadline_us, real_time.interference_us), (Some(3.5), 5.0, 2.0));
+    assert_eq!(clock.format(7), "7 (3.5 µs)");
+}
+
+#[test]
+fn merged_runs_take_the_longest_duration_of_every_section() {
+    let run = |end, start_r1, end_r1| {
+        TaskBuilder::new("t").prio(1).period(50).span(0, end).section("R1", start_r1, end_r1, |s| s).build().unwrap().trace
+    };
+    let envelope = merge_runs("t", &[run(10, 2, 5), run(9, 1, 6)]).unwrap();
+    // Longest lead 2, R1 5 and tail 5
+    assert_eq!((envelope.trace.inner[0].start, envelope.trace.inner[0].end, envelope.trace.end), (2, 7, 12));
+    let dominance: Vec<_> = envelope.sections.iter().map(|s| (s.section.as_str(), s.duration, s.run)).collect();
+    assert_eq!(dominance, vec![("t", 10, 0), ("t/R1", 5, 1)]);
+
+    let other = TaskBuilder::new("t").prio(1).period(50).span(0, 10).build().unwrap().trace;
+    assert!(merge_runs("t", &[run(10, 2, 5), other]).is_err());
+}
+
+#[test]
+fn interrupts_preempt_tasks_regardless_of_ceilings() {
+    let tasks = vec![
+        TaskBuilder::new("isr").prio(1).interrupt().period(20).span(0, 2).build().unwrap(),
+        TaskBuilder::new("hi").prio(3).period(50).span(0, 5).section("R", 1, 3, |s| s).build().unwrap(),
+        TaskBuilder::new("lo")
+            .prio(1)
+            .period(100)
+            .span(0, 10)
+            .section("R", 2, 8, |s| s)
+            .non_preemptible("N", 8, 9)
+            .build()
+            .unwrap(),
+    ];
+    // Only the non-preemptible section of lo blocks the interrupt, which interferes with both tasks
+    assert_eq!(blocking_times(&tasks), vec![1, 6, 0]);
+    assert_eq!(interferences(&tasks), vec![0, 2, 7]);
+    assert_eq!(response_times(&tasks, &PreemptionMode::Approximate)[..2], [Ok(3), Ok(13)]);
+}
+
+#[test]
+fn readers_of_a_shared_lock_do_not_block_each_other() {
+    let tasks = vec![
+        TaskBuilder::new("a").prio(3).period(50).span(0, 2).read_section("R", 0, 2).build().unwrap(),
+        TaskBuilder::new("b").prio(2).period(100).span(0, 10).read_section("R", 0, 8).build().unwrap(),
+        TaskBuilder::new("w").prio(1).period(200).span(0, 10).section("R", 0, 4, |s| s).build().unwrap(),
+    ];
+    // π_r(R) = P(w) = 1, so only the writer blocks the readers
+    assert_eq!(blocking_times(&tasks), vec![4, 4, 0]);
+    assert_eq!(resource_ceilings(&tasks)[0].read_ceiling, Some(1));
+
+    let exclusive: Tasks = tasks
+        .into_iter()
+        .map(|mut task| {
+            task.trace.inner[0].lock = LockType::Exclusive;
+            task
+        })
+        .collect();
+    assert_eq!(blocking_times(&exclusive), vec![8, 4, 0]);
+}
+
+#[test]
+fn critical_instant_releases_the_task_as_the_blocker_locks() {
+    let tasks = vec![
+        TaskBuilder::new("h").prio(3).period(10).span(0, 3).build().unwrap(),
+        TaskBuilder::new("t").prio(2).period(20).deadline(12).span(0, 4).section("R", 0, 1, |s| s).build().unwrap(),
+        TaskBuilder::new("l").prio(1).period(100).span(0, 20).section("R", 5, 15, |s| s).build().unwrap(),
+    ];
+    let scenario = critical_instant(&tasks, 1, 40);
+
+    assert_eq!(scenario.blocker.as_deref(), Some("l"));
+    // h preempts l twice while l holds R, t only starts once R is released
+    assert_eq!((scenario.job.release, scenario.job.deadline, scenario.job.completion), (5, 17, Some(25)));
+    assert!(scenario.misses_deadline());
+}
+
+#[test]
+fn shedding_drops_the_fewest_optional_tasks() {
+    let tasks = vec![
+        TaskBuilder::new("hard").prio(1).period(10).span(0, 3).build().unwrap(),
+        TaskBuilder::new("big").prio(3).period(10).span(0, 5).criticality(Criticality::Optional).build().unwrap(),
+        TaskBuilder::new("small").prio(2).period(10).span(0, 3).criticality(Criticality::Optional).build().unwrap(),
+        TaskBuilder::new("soft").prio(4).period(10).span(0, 2).criticality(Criticality::Soft).build().unwrap(),
+    ];
+    let plans = shedding_plans(&tasks, &AnalysisConfig::default()).unwrap();
+
+    // Dropping either optional task suffices, dropping the larger one leaves the lower load
+    let shed: Vec<Vec<String>> = plans.iter().map(|plan| plan.shed.clone()).collect();
+    assert_eq!(shed, vec![vec!["big".to_string()], vec!["small".to_string()]]);
+    assert!(plans.iter().all(|plan| plan.misses.is_empty()));
+}
+
+#[test]
+fn amc_drops_lo_tasks_after_their_lo_response_time() {
+    let tasks = vec![
+        TaskBuilder::new("hi").prio(1).period(20).span(0, 4).wcet_hi(8).build().unwrap(),
+        TaskBuilder::new("lo").prio(2).period(10).span(0, 3).build().unwrap(),
+        TaskBuilder::new("top").prio(3).period(10).span(0, 2).wcet_hi(3).build().unwrap(),
+    ];
+    // R_LO(hi) = 9, so lo interferes once, top with C(HI) = 3 twice within R*(hi) = 8 + 3 + 2 * 3
+    assert_eq!(tasks[0].lo_mode_response_time(&tasks), Ok(9));
+    assert_eq!(response_times(&tasks, &PreemptionMode::MixedCriticality), vec![Ok(17), Ok(5), Ok(3)]);
+}
+
+#[test]
+fn warm_sweeps_find_the_same_response_times_in_fewer_iterations() {
+    let tasks = vec![
+        TaskBuilder::new("low").prio(1).period(1000).span(0, 50).build().unwrap(),
+        TaskBuilder::new("mid").prio(2).period(40).span(0, 7).build().unwrap(),
+        TaskBuilder::new("high").prio(3).period(15).span(0, 4).build().unwrap(),
+    ];
+    let axis = SweepAxis { parameter: "low.wcet".parse().unwrap(), from: 50, to: 90, step: 10 };
+    let config = AnalysisConfig::default();
+    let warm = response_time_sweep(&tasks, &config, &axis, true, true).unwrap();
+    let cold = response_time_sweep(&tasks, &config, &axis, false, true).unwrap();
+
+    let response_times = |points: &[SweepPoint]| points.iter().map(|p| p.response_times.clone()).collect::<Vec<_>>();
+    assert_eq!(response_times(&warm), response_times(&cold));
+    let iterations = |points: &[SweepPoint]| points.iter().map(|p| p.iterations.unwrap()).sum::<u32>();
+    assert!(iterations(&warm) < iterations(&cold));
+}
+
+#[test]
+fn sweeps_and_regions_are_limited_in_size() {
+    let axis = |from, to, step| SweepAxis { parameter: "t3.wcet".parse().unwrap(), from, to, step };
+    assert_eq!(axis(0, 0, 1).count(), 1);
+    assert_eq!(axis(5, 4, 1).count(), 0);
+    assert_eq!(axis(0, u32::MAX, 0).count(), u32::MAX as usize + 1);
+    assert_eq!(axis(1, MAX_SWEEP_POINTS as u32, 1).values().unwrap().len(), MAX_SWEEP_POINTS);
+    assert!(axis(0, MAX_SWEEP_POINTS as u32, 1).values().is_err());
+    assert!(axis(0, u32::MAX, 1).values().is_err());
+
+    // Both axes are within the limit, but not all their combinations
+    let (tasks, config) = (textbook_rate_monotonic(), AnalysisConfig::default());
+    let side = MAX_SWEEP_POINTS as u32;
+    assert!(schedulability_region(&tasks, &config, &axis(1, side, 1), &axis(1, side, 1)).is_err());
+    assert!(schedulability_region(&tasks, &config, &axis(1, 3, 1), &axis(1, 3, 1)).is_ok());
+    assert!(response_time_sweep(&tasks, &config, &axis(0, u32::MAX, 1), true, false).is_err());
+}
+
+#[test]
+fn recorded_jobs_exclude_the_time_they_were_preempted() {
+    let events: Vec<TraceEvent> = serde_json::from_str(
+        r#"[
+            {"time": 100, "event": "start", "task": "low"},
+            {"time": 102, "event": "lock", "resource": "R1"},
+            {"time": 104, "event": "start", "task": "high"},
+            {"time": 107, "event": "stop", "task": "high"},
+            {"time": 110, "event": "unlock", "resource": "R1"},
+            {"time": 111, "event": "stop", "task": "low"}
+        ]"#,
+    )
+    .unwrap();
+    let mut recorder = TraceRecorder::new();
+    let jobs: Vec<RecordedJob> = events.iter().filter_map(|event| recorder.record(event).unwrap()).collect();
+
+    assert_eq!(jobs[0].task, "high");
+    assert_eq!(jobs[0].trace.end, 3);
+    // low ran for 11 - 3, holding R1 from 2 to 10 - 3
+    let low = TaskBuilder::new("low").period(100).span(0, 8).section("R1", 2, 7, |s| s).build().unwrap();
+    assert_eq!(jobs[1].trace, low.trace);
+    assert!(recorder.running().is_empty());
+}
+
+#[test]
+fn observed_response_times_beyond_the_analysis_are_flagged() {
+    let tasks = simple_example();
+    let results: Vec<TaskAnalysis> =
+        srp_analyze(&tasks, &AnalysisConfig::default()).iter().map(TaskAnalysis::from).collect();
+    let bound = |id: &str| *results.iter().find(|r| r.id == id).unwrap().response_time.as_ref().unwrap();
+
+    // A job released at 0 that only starts at 5 responds at its stop
+    let mut recorder = TraceRecorder::new();
+    let events: Vec<TraceEvent> = serde_json::from_str(
+        r#"[
+            {"time": 0, "event": "release", "task": "T1"},
+            {"time": 5, "event": "start", "task": "T1"},
+            {"time": 15, "event": "stop", "task": "T1"}
+        ]"#,
+    )
+    .unwrap();
+    let job = events.iter().find_map(|event| recorder.record(event).unwrap()).unwrap();
+    assert_eq!((job.trace.end, job.response_time), (10, 15));
+
+    let observed = BTreeMap::from([("T1".to_string(), job.response_time), ("T3".to_string(), bound("T3") + 1)]);
+    let observations = compare_observed(&results, &observed);
+    let flagged: Vec<(&str, bool)> = observations.iter().map(|o| (o.task.as_str(), o.exceeds_bound)).collect();
+    assert_eq!(flagged, vec![("T1", false), ("T3", true)]);
+}
+
+#[test]
+fn medium_jobs_running_while_a_higher_one_waits_are_inversions() {
+    let tasks = vec![
+        TaskBuilder::new("high").prio(3).period(100).span(0, 2).section("R", 0, 1, |s| s).build().unwrap(),
+        TaskBuilder::new("medium").prio(2).period(100).span(0, 5).build().unwrap(),
+        TaskBuilder::new("low").prio(1).period(100).span(0, 4).section("R", 1, 3, |s| s).build().unwrap(),
+    ];
+    // high only waits for low holding R until medium preempts low, as SRP would not allow
+    let events: Vec<TraceEvent> = serde_json::from_str(
+        r#"[
+            {"time": 0, "event": "start", "task": "low"},
+            {"time": 1, "event": "lock", "resource": "R"},
+            {"time": 2, "event": "release", "task": "high"},
+            {"time": 3, "event": "start", "task": "medium"},
+            {"time": 8, "event": "stop", "task": "medium"},
+            {"time": 9, "event": "unlock", "resource": "R"},
+            {"time": 9, "event": "start", "task": "high"},
+            {"time": 11, "event": "stop", "task": "high"},
+            {"time": 12, "event": "stop", "task": "low"}
+        ]"#,
+    )
+    .unwrap();
+    let mut detector = InversionDetector::new();
+    for event in &events {
+        detector.record(event, &tasks);
+    }
+
+    let inversion = PriorityInversion {
+        task: "high".to_string(),
+        running: "medium".to_string(),
+        blocker: Some("low".to_string()),
+        resources: vec!["R".to_string()],
+        start: 3,
+        end: 8,
+    };
+    assert_eq!(detector.inversions(), [inversion]);
+}
+
+#[test]
+fn weakly_hard_constraints_tolerate_sparse_misses() {
+    let high = TaskBuilder::new("high").prio(2).period(7).span(0, 3).build().unwrap();
+    let low = |window| TaskBuilder::new("low").prio(1).period(10).span(0, 5).weakly_hard(1, window).build().unwrap();
+    // R(low) = 11 > D(low), yet over H = 70 only the jobs released at 0 and 70 miss
+    let horizon = hyperperiod(&vec![high.clone(), low(5)]).unwrap() + 10;
+    let config = AnalysisConfig::default();
+
+    let checks = check_weakly_hard(&vec![high.clone(), low(5)], &config, horizon);
+    assert_eq!(checks.len(), 1);
+    let check = &checks[0];
+    assert!(!check.hard);
+    assert_eq!((check.jobs, check.misses, check.window_start, check.satisfied), (8, 1, Some(0), true));
+
+    let checks = check_weakly_hard(&vec![high, low(8)], &config, horizon);
+    assert_eq!((checks[0].misses, checks[0].satisfied), (2, false));
+    assert!(TaskBuilder::new("t").period(10).span(0, 1).weakly_hard(2, 1).build().is_err());
+}
+
+#[test]
+fn fuzzing_finds_the_parameters_without_slack() {
+    // R(T1) = D(T1), so T1 is the first to break
+    let tasks = simple_example();
+    let config = AnalysisConfig::default();
+    let report = fuzz_task_set(&tasks, &config, 200, 10, 1);
+
+    assert_eq!(report, fuzz_task_set(&tasks, &config, 200, 10, 1));
+    assert!(0 < report.schedulable && report.schedulable < report.samples);
+    // Shorter deadlines of T1 break the set
+    assert_eq!(report.parameters[0].parameter, "T1.deadline");
+    assert!(report.parameters[0].shift < 0.0);
+}
+
+#[test]
+fn served_tasks_wait_for_the_supply_of_their_server() {
+    let mut set = HierarchicalTaskSet {
+        servers: vec![Server {
+            id: "s".to_string(),
+            prio: 3,
+            budget: 4,
+            period: 10,
+            kind: ServerKind::Polling,
+            tasks: vec!["a".to_string(), "b".to_string()],
+        }],
+        tasks: vec![
+            TaskBuilder::new("a").prio(2).period(50).span(0, 3).build().unwrap(),
+            TaskBuilder::new("b").prio(1).period(100).span(0, 2).build().unwrap(),
+            TaskBuilder::new("g").prio(1).period(40).span(0, 5).build().unwrap(),
+        ],
+    };
+    let response_times = |analysis: HierarchicalAnalysis| {
+        analysis.tasks.into_iter().map(|task| task.response_time.unwrap()).collect::<Vec<_>>()
+    };
+
+    // Without supply for Π + R(s) - Θ = 10, b needs the budgets of two periods
+    let polling = hierarchical_analyze(&set, &AnalysisConfig::default()).unwrap();
+    assert_eq!(polling.servers[0].response_time, Ok(4));
+    assert_eq!(response_times(polling), vec![13, 21, 9]);
+
+    // A deferrable server waits less, but hits g twice in a row
+    set.servers[0].kind = ServerKind::Deferrable;
+    let deferrable = hierarchical_analyze(&set, &AnalysisConfig::default()).unwrap();
+    assert_eq!(response_times(deferrable), vec![9, 17, 13]);
+}
+
+#[test]
+fn blocking_is_attributed_to_the_longest_blocking_section() {
+    let tasks = simple_example();
+    let blocker = tasks[2].blocker(&tasks).unwrap();
+    assert_eq!(blocker.to_string(), "T2 via R3 for 7 units");
+    assert_eq!(blocker.duration, tasks[2].blocking_time(&tasks));
+    assert_eq!(tasks[1].blocker(&tasks), None);
+}
+
+#[test]
+fn releases_are_counted_exactly_beyond_f32_precision() {
+    // 2^24 + 1 rounds to 2^24 in f32, so a float ceiling would count a single release
+    let arrival = ArrivalModel::Periodic { period: 1 << 24 };
+    assert_eq!(arrival.max_releases((1 << 24) + 1), 2);
+    assert_eq!(arrival.max_releases(u32::MAX), 256);
+    assert_eq!(ArrivalModel::Bursty { n: 3, window: 0 }.max_releases(1), u32::MAX);
+    assert_eq!(ceil_div(0, 0), 0);
+}
+
+#[test]
+fn scaling_a_task_set_scales_its_response_times() {
+    let tasks = simple_example();
+    let scaled = tasks.scaled(1000).unwrap();
+    let config = AnalysisConfig::default();
+    let response_times = |tasks: &Tasks| {
+        srp_analyze(tasks, &config).into_iter().map(|(_, r, ..)| r.unwrap()).collect::<Vec<_>>()
+    };
+    let expected: Vec<u32> = response_times(&tasks).iter().map(|r| r * 1000).collect();
+    assert_eq!(response_times(&scaled), expected);
+    assert!(tasks.scaled(u32::MAX).is_err());
+}
+
+#[test]
+fn plugins_are_run_by_name() {
+    let registry = PluginRegistry::new();
+    register_builtin_plugins(&registry);
+    let tasks = textbook_rate_monotonic();
+    let config = AnalysisConfig::default();
+
+    // U = 0.883 exceeds the bound 0.780 of three tasks, but every task meets its deadline
+    let bound = registry.run("utilization_bound", &tasks, &config).unwrap();
+    assert_eq!(bound.schedulable, None);
+    assert_eq!(registry.run("srp", &tasks, &config).unwrap().schedulable, Some(true));
+    assert!(registry.run("unknown", &tasks, &config).is_err());
+}
+
+#[test]
+fn canonical_examples_are_valid_and_analyzed_as_described() {
+    let config = AnalysisConfig::default();
+    for canonical in examples() {
+        assert!(validate_task_set(&canonical.tasks).is_ok(), "{}", canonical.name);
+        assert_eq!(example(&canonical.name), Some(canonical.tasks.clone()));
+        let schedulable = is_schedulable(&srp_analyze(&canonical.tasks, &config));
+        assert_eq!(schedulable, canonical.name != "unschedulable", "{}", canonical.name);
+    }
+    // The flash write on the bus blocks the sensor longer than it executes
+    let tasks = heavy_blocking_example();
+    assert!(tasks[0].blocking_time(&tasks) > tasks[0].wcet());
+    assert_eq!(example("missing"), None);
+}
+
+#[test]
+fn wcets_are_checked_against_sections_and_measurements() {
+    let overlapping =
+        TaskBuilder::new("t").prio(1).period(50).span(0, 20).section("A", 2, 8, |s| s).section("B", 6, 10, |s| s);
+    assert_eq!(
+        overlapping.build().map(|_| ()),
+        Err("Error: t [0, 20] > B [6, 10] overlaps its sibling A [2, 8].".to_string())
+    );
+
+    let mut tasks = nested_resources();
+    // C(t3) = 8 cuts off the non-preemptible section ending at 18
+    apply_overrides(&mut tasks, &BTreeMap::from([("t3.wcet".to_string(), 8)])).unwrap();
+    let measurements = Measurements::from([("t1".to_string(), vec![4, 6]), ("t2".to_string(), vec![5])]);
+    let checks = check_wcets(&tasks, &measurements);
+    assert_eq!(checks.iter().map(|c| c.sections).collect::<Vec<_>>(), vec![3, 6, 18]);
+    assert_eq!(checks[0].discrepancies, vec![WcetDiscrepancy::MeasuredAboveWcet]);
+    assert_eq!(checks[1].discrepancies, vec![WcetDiscrepancy::MeasuredBelowSections]);
+    assert_eq!(checks[2].discrepancies, vec![WcetDiscrepancy::SectionsBeyondWcet]);
+    assert_eq!(checks[2].measured, None);
+}
+
+#[test]
+fn response_time_iterates_converge_or_stop_beyond_the_limit() {
+    let tasks = textbook_rate_monotonic();
+    let convergence = tasks[2].response_time_iterates(&tasks, 100);
+    assert_eq!(convergence.iterates, vec![3, 6, 7, 9, 10]);
+    assert!(convergence.converged);
+
+    // U > 1, the lowest priority task converges only after its deadline
+    let tasks = unschedulable_example();
+    let convergences = response_time_convergence(&tasks, &AnalysisConfig::default());
+    assert_eq!(convergences[2].iterates, vec![12, 28, 40, 44, 56, 60]);
+    assert!(convergences[2].converged);
+    let stopped = tasks[2].response_time_iterates(&tasks, 30);
+    assert_eq!(stopped.iterates, vec![12, 28, 40]);
+    assert!(!stopped.converged);
+}
+
+#[test]
+fn trace_checks_report_every_problem_with_its_location() {
+    let mut task = TaskBuilder::new("t").prio(1).period(50).span(0, 20).section("A", 2, 8, |s| s).build().unwrap();
+    let released = Trace { start: 7, end: 5, ..task.trace.inner[0].clone() };
+    task.trace.inner[0].inner.push(released);
+    task.trace.inner.push(Trace { id: "B".to_string(), start: 6, end: 25, inner: vec![], ..task.trace.clone() });
+
+    let issues = check_traces(std::slice::from_ref(&task));
+    let kinds: Vec<_> = issues.iter().map(|issue| (issue.location.as_str(), issue.kind.clone())).collect();
+    assert_eq!(
+        kinds,
+        vec![
+            ("t [0, 20] > A [2, 8] > A [7, 5]", TraceIssueKind::ReleasedBeforeAcquired),
+            ("t [0, 20] > B [6, 25]", TraceIssueKind::OutsideEnclosing),
+            ("t [0, 20] > B [6, 25]", TraceIssueKind::Overlapping { other: "A [2, 8]".to_string() }),
+        ]
+    );
+    assert_eq!(validate_task_set(&[task]).unwrap_err().lines().count(), 3);
+}
+
+#[test]
+fn preemption_thresholds_trade_blocking_for_fewer_preemptions() {
+    let mut tasks = textbook_rate_monotonic();
+    assert_eq!(response_times(&tasks, &PreemptionMode::Threshold), vec![Ok(1), Ok(3), Ok(10)]);
+    for (task, stack) in tasks.iter_mut().zip([100, 200, 300]) {
+        task.stack = stack;
+    }
+    assert_eq!(stack_depth(&tasks).total, 600);
+
+    // Once started t3 runs at γ = 2, so only t1 preempts it while it blocks t2 for all of C(t3)
+    tasks[2].threshold = Some(2);
+    assert_eq!(blocking_times(&tasks), vec![0, 3, 0]);
+    assert_eq!(response_times(&tasks, &PreemptionMode::Threshold), vec![Ok(1), Ok(7), Ok(7)]);
+    let stack = stack_depth(&tasks);
+    assert_eq!(stack.levels.iter().map(|l| l.task.as_str()).collect::<Vec<_>>(), vec!["t3", "t1"]);
+    assert_eq!(stack.total, 400);
+
+    tasks[2].threshold = Some(0);
+    assert_eq!(
+        validate_task_set(&tasks),
+        Err("Error: Task 't3' has a preemption threshold 0 below its priority 1.".to_string())
+    );
+}
+
+#[test]
+fn normalized_priorities_are_dense_and_keep_the_analysis() {
+    let mut tasks = nested_resources();
+    for (task, prio) in tasks.iter_mut().zip([200, 30, 30]) {
+        task.prio = prio;
+    }
+    tasks[2].threshold = Some(100);
+    let config = AnalysisConfig::default();
+    let before: Vec<_> = tasks.iter().map(|t| config.response_time(t, &tasks)).collect();
+
+    let preview = tasks.priority_normalization();
+    let mapping: Vec<_> = preview.levels.iter().map(|level| (level.from, level.to)).collect();
+    assert_eq!(mapping, vec![(30, 1), (200, 2)]);
+    assert_eq!(preview.ties.len(), 1);
+    assert_eq!(preview.ties[0].tasks, vec!["t2", "t3"]);
+    assert_eq!(tasks[0].prio, 200);
+
+    assert_eq!(tasks.normalize_priorities(), preview);
+    let normalized: Vec<_> = tasks.iter().map(|t| (t.prio, t.threshold)).collect();
+    assert_eq!(normalized, vec![(2, None), (1, None), (1, Some(1))]);
+    assert_eq!(tasks.iter().map(|t| config.response_time(t, &tasks)).collect::<Vec<_>>(), before);
+    assert!(!tasks.priority_normalization().changed);
+}
+
+#[test]
+fn explanations_derive_the_response_time_step_by_step() {
+    let tasks = nested_resources();
+    let config = AnalysisConfig::default();
+    let explanation = explain_response_time(&tasks, "t1", &config).unwrap();
+    assert_eq!(explanation.value, Some(11));
+    assert_eq!(explanation.operands, "6 + 5 + 0");
+
+    // B is only locked by tasks of priority 2, below P(t1) = 3
+    let blocking = &explanation.terms[0];
+    assert_eq!(
+        blocking.summary(),
+        "B(t1) = max(C(l_r)) for all l_r where P(l) < P(t) and π(r) >= P(t) = max(4, 6) = 6"
+    );
+    let rejected: Vec<_> = blocking.terms.iter().filter(|c| !c.counted).map(|c| c.quantity.as_str()).collect();
+    assert_eq!(rejected, vec!["C(B@t2)", "C(B@t3)"]);
+    assert_eq!(blocking.terms[1].note.as_deref(), Some("π(A) = 3 >= P(t1) = 3"));
+
+    let explanation = explain_response_time(&tasks, "t3", &config).unwrap();
+    assert_eq!(explanation.operands, "0 + 20 + 20");
+    assert_eq!(explanation.terms[2].terms[1].summary(), "I_t1(t3) = η_h(Bp(t)) * C(h) = 2 * 5 = 10");
+    assert_eq!(explain_response_time(&tasks, "t4", &config), None);
+}
+
+#[test]
+fn task_margins_split_at_the_mean_utilization_and_a_tight_slack() {
+    let mut tasks = nested_resources();
+    let config = AnalysisConfig::default();
+    let margins = task_margins(&srp_analyze(&tasks, &config));
+    let points: Vec<_> = margins.iter().map(|m| (m.utilization, m.slack_ratio, m.quadrant)).collect();
+    assert_eq!(
+        points,
+        vec![
+            (0.25, Some(0.45), MarginQuadrant::Heavy),
+            (0.25, Some(0.475), MarginQuadrant::Heavy),
+            (0.2, Some(0.6), MarginQuadrant::Healthy),
+        ]
+    );
+
+    // R(t1) = 11 and R(t3) = 40 leave less than a fifth of the deadlines
+    tasks[0].deadline = 12;
+    tasks[2].deadline = 45;
+    let quadrants: Vec<_> = task_margins(&srp_analyze(&tasks, &config)).iter().map(|m| m.quadrant).collect();
+    assert_eq!(quadrants, vec![MarginQuadrant::Critical, MarginQuadrant::Heavy, MarginQuadrant::Starved]);
+}
+
+#[test]
+fn balanced_cores_keep_resource_sharing_tasks_together() {
+    let mut tasks = nested_resources();
+    tasks.push(TaskBuilder::new("t4").prio(4).period(10).span(0, 4).build().unwrap());
+    let config = AnalysisConfig::default();
+
+    let balancing = balance_cores(&tasks, &CoreAssignment::new(), 2, &config).unwrap();
+    let (before, after) = balancing.peak_utilization();
+    assert!((before - 1.1).abs() < 1e-6 && (after - 0.7).abs() < 1e-6);
+    assert!(balancing.before[1].tasks.is_empty());
+    // t1, t2 and t3 share A and B, so they migrate together
+    let moved: Vec<_> = balancing.moves.iter().map(|m| (m.task.as_str(), m.from, m.to)).collect();
+    assert_eq!(moved, vec![("t1", 0, 1), ("t2", 0, 1), ("t3", 0, 1)]);
+    assert!(balancing.after.iter().all(|load| load.schedulable));
+    assert_eq!(balancing.after[0].tasks, vec!["t4"]);
+
+    let split = CoreAssignment::from([("t2".to_string(), 1)]);
+    assert_eq!(
+        core_loads(&tasks, &split, 2, &config),
+        Err("Error: Resource 'B' is shared by 't2' and 't3' on different cores.".to_string())
+    );
+    let beyond = CoreAssignment::from([("t4".to_string(), 2)]);
+    assert_eq!(
+        balance_cores(&tasks, &beyond, 2, &config),
+        Err("Error: Task 't4' is assigned to core 2 of 2 cores.".to_string())
+    );
+}
+
+#[test]
+fn imported_traces_are_rebased_aligned_and_merged_per_task() {
+    let activation = |start, end, start_r1, end_r1| {
+        let builder = TaskBuilder::new("t").prio(1).period(50).span(start, end);
+        builder.section("R1", start_r1, end_r1, |s| s).build().unwrap()
+    };
+    let mut late = activation(1000, 1009, 1001, 1006);
+    // Lock released after the task completed, as recorded by another clock
+    late.trace.inner[0].end = 1012;
+    let tasks = vec![activation(500, 510, 502, 505), late];
+
+    let normalization = normalize_trace_origins(&tasks, false).unwrap();
+    let origins: Vec<_> = normalization.rebased.iter().map(|r| (r.task.as_str(), r.origin)).collect();
+    assert_eq!(origins, vec![("t", 500), ("t", 1000)]);
+    assert_eq!(normalization.aligned, vec!["t [0, 9] > R1 [1, 12]"]);
+    let r1 = &normalization.tasks[1].trace.inner[0];
+    assert_eq!((r1.start, r1.end), (1, 9));
+    assert!(normalization.merged.is_empty() && normalization.tasks.len() == 2);
+
+    // Same envelope as merging the relative activations directly
+    let merged = normalize_trace_origins(&tasks, true).unwrap();
+    assert_eq!(merged.merged, vec![MergedActivations { task: "t".to_string(), activations: 2 }]);
+    let expected = merge_runs("t", &[activation(0, 10, 2, 5).trace, activation(0, 9, 1, 9).trace]).unwrap().trace;
+    assert_eq!(merged.tasks.len(), 1);
+    assert_eq!(merged.tasks[0].trace, expected);
+}
+
+#[test]
+fn certificates_hold_until_a_value_is_tampered_with() {
+    let tasks = nested_resources();
+    for mode in [PreemptionMode::Approximate, PreemptionMode::Exact] {
+        let certificate = schedulability_certificate(&tasks, &AnalysisConfig::new(mode));
+        assert_eq!(verify_certificate(&certificate), Vec::<String>::new());
+    }
+
+    let mut certificate = schedulability_certificate(&tasks, &AnalysisConfig::default());
+    let blocking = &mut certificate.tasks[0].derivation.terms[0];
+    blocking.value = blocking.value.map(|b| b + 1);
+    certificate.schedulable = !certificate.schedulable;
+    let problems = verify_certificate(&certificate);
+    assert_eq!(problems.len(), 2);
+    assert!(problems[0].starts_with("Error: B(t1) of task 't1' is max("));
+    assert!(problems[1].starts_with("Error: The verdict of the task set"));
+}
+
+#[test]
+fn stepped_simulation_matches_a_single_run() {
+    let mut tasks = textbook_rate_monotonic();
+    tasks[2].deadline = 5;
+    let mut simulator = Simulator::new(tasks.clone());
+    let steps: Vec<SimulationStep> = [7, 7, 25, 60].into_iter().map(|until| simulator.step(until)).collect();
+    assert_eq!(simulator.simulation(), &simulate(&tasks, 60));
+
+    assert_eq!((steps[1].from, steps[1].until), (7, 7));
+    assert!(steps[1].slices.is_empty() && steps[1].events.is_empty());
+    // The second job of t2 runs 6..8, across the start of the third step
+    let first = &steps[2].slices[0];
+    assert_eq!((first.task.as_str(), first.job, first.start, first.end), ("t2", 1, 7, 8));
+    let miss = JobEvent { time: 5, task: "t3".to_string(), job: 0, kind: JobEventKind::DeadlineMissed };
+    assert!(steps[0].events.contains(&miss));
+    let releases = steps.iter().flat_map(|step| &step.events).filter(|e| e.kind == JobEventKind::Released).count();
+    assert_eq!(releases, simulator.simulation().jobs.len());
+}
+
+#[test]
+fn offsets_of_a_transaction_tighten_the_synchronous_bound() {
+    let task = |id: &str, prio, offset, wcet| {
+        TaskBuilder::new(id).prio(prio).period(10).offset(offset).span(0, wcet).build().unwrap()
+    };
+    let mut tasks = vec![task("t1", 3, 0, 2), task("t2", 2, 5, 2), task("t3", 1, 0, 3)];
+    let config = AnalysisConfig { offset_model: OffsetModel::Transactions, ..Default::default() };
+    let bounds = offset_bounds(&tasks, &config);
+    let pairs: Vec<(Option<u32>, Option<u32>)> = bounds.iter().map(|b| (b.synchronous, b.offset_aware)).collect();
+    assert_eq!(pairs, vec![(Some(2), Some(2)), (Some(4), Some(2)), (Some(7), Some(5))]);
+    assert!(bounds.iter().all(|bound| bound.transaction == Some(10)));
+    let certificate = schedulability_certificate(&tasks, &config);
+    assert_eq!(verify_certificate(&certificate), Vec::<String>::new());
+
+    // Jitter takes t2 out of the transaction, so it may be released together with t3 again
+    tasks[1].jitter = 1;
+    let config = AnalysisConfig { jitter_model: JitterModel::Release, ..config };
+    assert_eq!(offset_bounds(&tasks, &config)[2].offset_aware, Some(7));
+}
+
+#[test]
+fn random_task_sets_are_reproducible_from_their_seed() {
+    let generated = random_task_set(8, 2, 0.6, &mut SeededRng::new(42));
+    assert_eq!(generated.seed, 42);
+    assert_eq!(generated, random_task_set(8, 2, 0.6, &mut SeededRng::new(42)));
+    assert_ne!(generated.tasks, random_task_set(8, 2, 0.6, &mut SeededRng::new(43)).tasks);
+    assert_eq!(generated.tasks.len(), 8);
+    let utilization = total_load_factor(&generated.tasks).unwrap();
+    assert!((utilization - 0.6).abs() < 0.1, "U = {}", utilization);
+
+    assert_eq!(fuzz_task_set(&generated.tasks, &AnalysisConfig::default(), 20, 10, 7).seed, 7);
+}
+
+#[test]
+fn permissible_jitter_is_found_alongside_the_wcet_slack() {
+    let task = |id: &str, prio, period, wcet| TaskBuilder::new(id).prio(prio).period(period).span(0, wcet).build();
+    let mut tasks = vec![task("t1", 2, 10, 2).unwrap(), task("t2", 1, 20, 4).unwrap()];
+    let sensitivities = task_sensitivities(&tasks, &AnalysisConfig::default()).unwrap();
+    let found: Vec<_> = sensitivities.iter().map(|s| (s.wcet_slack, s.max_jitter)).collect();
+    // J(t1) = 8 delays t1 to its deadline, while t2 is still only preempted twice: R(t2) = 4 + 2 * 2
+    assert_eq!(found, vec![(Some(6), Some(8)), (Some(12), Some(14))]);
+
+    tasks[1].jitter = 17;
+    let sensitivities = task_sensitivities(&tasks, &AnalysisConfig::default()).unwrap();
+    assert_eq!(sensitivities[1].wcet_slack, Some(12));
+    assert!(sensitivities.iter().all(|s| s.max_jitter.is_none()));
+}
+
+#[test]
+fn refactorings_are_ranked_by_the_blocking_they_remove() {
+    let tasks = vec![
+        TaskBuilder::new("t1").prio(3).period(20).span(0, 2).section("R", 0, 1, |s| s).build().unwrap(),
+        TaskBuilder::new("t2").prio(2).period(40).span(0, 4).build().unwrap(),
+        TaskBuilder::new("t3").prio(1).period(100).span(0, 10).section("R", 2, 8, |s| s).build().unwrap(),
+    ];
+    let suggestions = suggest_refactorings(&tasks, &AnalysisConfig::default());
+    let ranked: Vec<(&Refactoring, u32)> = suggestions.iter().map(|s| (&s.refactoring, s.blocking_removed)).collect();
+    let narrow = |task: &str, narrowed_ceiling| Refactoring::NarrowScope {
+        task: task.to_string(),
+        resource: "R".to_string(),
+        ceiling: 3,
+        narrowed_ceiling,
+    };
+    let (task, resource) = ("t3".to_string(), "R".to_string());
+    let split = Refactoring::SplitSection { task, resource, start: 2, end: 8, at: 5 };
+    // Without sharing R, neither t1 nor t2 are blocked, the halves of the split section still block both for 3
+    assert_eq!(ranked, vec![(&narrow("t1", 1), 12), (&narrow("t3", 3), 12), (&split, 6)]);
+    assert_eq!(suggestions[2].relieved, BTreeMap::from([("t1".to_string(), 3), ("t2".to_string(), 3)]));
+}
+
+#[test]
+fn interrupts_stack_on_top_of_higher_priority_software_tasks() {
+    let task = |id: &str, prio, stack| TaskBuilder::new(id).prio(prio).period(100).span(0, 1).stack(stack);
+    let tasks = vec![
+        task("t1", 5, 100).build().unwrap(),
+        task("isr", 1, 50).interrupt().build().unwrap(),
+        task("t0", 1, 10).build().unwrap(),
+    ];
+    // The handler preempts t1 although P(t1) is numerically higher, so all three frames add up
+    let stack = stack_depth(&tasks);
+    assert_eq!(stack.levels.iter().map(|l| l.task.as_str()).collect::<Vec<_>>(), vec!["t0", "t1", "isr"]);
+    assert_eq!(stack.total, 160);
+}
+
+#[test]
+fn interference_of_zero_or_tiny_periods_overflows_into_an_error() {
+    let task = |id: &str, prio, period, wcet| TaskBuilder::new(id).prio(prio).period(period).span(0, wcet).build();
+    let mut tasks = vec![task("h", 2, 1, 100_000).unwrap(), task("l", 1, 1_000_000, 100_000).unwrap()];
+    // η_h(Bp(l)) * C(h) = 200_000 * 100_000 does not fit into u32
+    assert_eq!(tasks[1].interference(&tasks), Err("Interference overflow".to_string()));
+    assert!(tasks[1].interference_breakdown(&tasks).is_err());
+    let results = srp_analyze(&tasks, &AnalysisConfig::default());
+    assert_eq!((&results[1].1, results[1].4), (&Err("Interference overflow".to_string()), 0));
+    assert!(!is_schedulable(&results));
+
+    // Releases of a zero period saturate, so any execution time overflows
+    tasks[0].arrival = ArrivalModel::Periodic { period: 0 };
+    tasks[0].trace.end = 1;
+    assert!(tasks[1].response_time(&tasks, &PreemptionMode::Approximate).is_err());
+    assert_eq!(AnalysisContext::new(&tasks).interference(&tasks[1]), tasks[1].interference(&tasks));
+}
+
+#[test]
+fn exact_response_times_near_the_largest_tick_overflow_into_an_error() {
+    let half = u32::MAX / 2 + 1;
+    let task = |id: &str, prio| TaskBuilder::new(id).prio(prio).period(u32::MAX).span(0, half).build().unwrap();
+    // C(l) + R(h) and C(l) + C(e) each exceed u32::MAX
+    let tasks = vec![task("h", 2), task("l", 1)];
+    assert_eq!(
+        response_times(&tasks, &PreemptionMode::Exact),
+        vec![Ok(half), Err("Interference overflow".to_string())]
+    );
+    let tasks = vec![task("e", 1), task("l", 1)];
+    assert_eq!(response_times(&tasks, &PreemptionMode::Exact)[1], Err("Busy window overflow".to_string()));
+}
+
+#[test]
+fn priority_assignment_rejects_preemption_thresholds() {
+    let mut tasks = textbook_rate_monotonic();
+    tasks[2].threshold = Some(tasks[0].prio);
+    // Raising P(t3) above γ(t3) would leave an invalid task set behind
+    let error = optimal_priority_assignment(&tasks, &AnalysisConfig::default()).unwrap_err();
+    assert!(error.starts_with("Error: Task 't3' has a preemption threshold"), "{}", error);
+}
+
+#[test]
+fn assigned_priorities_are_schedulable_under_the_same_config() {
+    // Rate monotonic order misses the deadline of t1, which only deadline monotonic order meets
+    let mut tasks = textbook_rate_monotonic();
+    tasks[0].deadline = 2;
+    tasks[1].deadline = 1;
+    tasks[1].trace.end = 1;
+    tasks[2].jitter = 1;
+    let config = AnalysisConfig { jitter_model: JitterModel::Release, ..Default::default() };
+    assert!(!is_schedulable(&srp_analyze(&tasks, &config)));
+
+    let assigned = optimal_priority_assignment(&tasks, &config).unwrap().unwrap();
+    let expected = [("t1", 2), ("t2", 3), ("t3", 1)].map(|(task, prio)| (task.to_string(), prio));
+    assert_eq!(priorities(&assigned), BTreeMap::from(expected));
+    assert!(is_schedulable(&srp_analyze(&assigned, &config)));
+    assert_eq!(validate_task_set(&assigned), Ok(()));
+}
+
+#[test]
+fn release_jitter_counts_towards_a_missed_deadline_in_exact_mode() {
+    let mut tasks = textbook_rate_monotonic();
+    let (mode, jitter_model) = (PreemptionMode::Exact, JitterModel::Release);
+    let config = AnalysisConfig { mode, jitter_model, ..Default::default() };
+    tasks[2].jitter = 3;
+    assert_eq!(srp_analyze(&tasks, &config)[2].1, Ok(10));
+
+    // R(t3) = 7 still meets D(t3) = 10 on its own, but not 4 ticks after the arrival
+    tasks[2].jitter = 4;
+    let results = srp_analyze(&tasks, &config);
+    assert_eq!(results[2].1, Err("Deadline missed".to_string()));
+    assert!(!is_schedulable(&results));
+}
+
+#[test]
+fn simulation_progress_reports_the_simulated_time_up_to_the_horizon() {
+    let tasks = textbook_rate_monotonic();
+    let mut times = vec![];
+    let simulation = simulate_with_progress(&tasks, 250, |progress| match progress {
+        AnalysisProgress::Simulation { time, horizon } => times.push((time, horizon)),
+        AnalysisProgress::Task { .. } => panic!("no task is analyzed"),
+    });
+    assert_eq!(simulation, simulate(&tasks, 250));
+    // Steps of ceil(250 / 100) = 3
+    assert_eq!(times.len(), 84);
+    assert_eq!(times[..2], [(3, 250), (6, 250)]);
+    assert_eq!(times.last(), Some(&(250, 250)));
+
+    let mut analyzed = vec![];
+    srp_analyze_with_progress(&tasks, &AnalysisConfig::default(), |progress| analyzed.push(progress));
+    assert_eq!(analyzed.len(), tasks.len());
+}
@@ -0,0 +1,63 @@
+use super::*;
+
+impl Task {
+    /// γ(t), the priority t runs at once it started: only tasks h where P(h) > γ(t) preempt it, P(t) for a task
+    /// without a threshold.
+    pub fn preemption_threshold(&self) -> u8 {
+        self.threshold.map_or(self.prio, |threshold| threshold.max(self.prio))
+    }
+
+    pub(super) fn validate_threshold(&self) -> Result<(), String> {
+        match self.threshold {
+            Some(threshold) if threshold < self.prio => Err(format!(
+                "Error: Task '{}' has a preemption threshold {} below its priority {}.",
+                self.id, threshold, self.prio
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Longest lower priority task l of the same kind where γ(l) >= P(t), which cannot be preempted by t once it
+    /// started and so blocks t for C(l), `None` if there is none.
+    pub fn threshold_blocker<T>(&self, tasks: &T) -> Option<Blocker>
+    where
+        T: std::ops::Deref<Target = [Task]> + Sized,
+    {
+        tasks
+            .iter()
+            .filter(|l| l.level() < self.level() && l.kind == self.kind && l.preemption_threshold() >= self.prio)
+            .map(|l| Blocker { task: l.id.clone(), resource: "threshold".to_string(), duration: l.wcet() })
+            .max_by_key(|blocker| blocker.duration)
+    }
+
+    /// R(t) = F(t) under preemption threshold scheduling, where
+    ///
+    /// S(t) = B(t) + sum(C(e)) + sum(η_h(S(t) + 1) * C(h)) for all other tasks e where P(e) = P(t) and all tasks h
+    /// where P(h) > P(t), the latest start of a job, and
+    /// F(t) = S(t) + C(t) + sum((η_h(F(t)) - η_h(S(t) + 1)) * C(h)) for all tasks h where P(h) > γ(t), its latest
+    /// finish, as only those preempt it once it started.
+    ///
+    /// B(t) includes the lower priority tasks whose threshold keeps t from preempting them, see
+    /// `Task::threshold_blocker`. Without thresholds R(t) is that of `Task::iterative_response_time`.
+    pub fn threshold_response_time<T>(&self, tasks: &T) -> Result<u32, String>
+    where
+        T: std::ops::Deref<Target = [Task]> + Sized,
+    {
+        let own = self.blocking_time(tasks) + self.equal_priority_interference(tasks);
+        let start = fixed_point(own, |s| {
+            tasks
+                .iter()
+                .filter(|h| h.level() > self.level())
+                .try_fold(own, |acc, h| acc.checked_add(h.wcet().checked_mul(h.max_releases(s.checked_add(1)?))?))
+        })?;
+
+        let running = (self.kind, self.preemption_threshold());
+        let started = start.checked_add(self.wcet()).ok_or_else(|| "Busy window overflow".to_string())?;
+        fixed_point(started, |f| {
+            tasks.iter().filter(|h| h.level() > running).try_fold(started, |acc, h| {
+                let later = h.max_releases(f).saturating_sub(h.max_releases(start + 1));
+                acc.checked_add(h.wcet().checked_mul(later)?)
+            })
+        })
+    }
+}
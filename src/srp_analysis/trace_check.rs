@@ -0,0 +1,85 @@
+use std::fmt;
+
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use super::*;
+
+/// What is wrong with a section of a trace, see `check_trace`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TraceIssueKind {
+    /// end < start, the lock is released before it is acquired
+    ReleasedBeforeAcquired,
+    /// The section starts before or ends after the section enclosing it
+    OutsideEnclosing,
+    /// The section overlaps a sibling section, which a single core cannot execute at the same time
+    Overlapping {
+        /// The sibling, as `<id> [<start>, <end>]`
+        other: String,
+    },
+}
+
+/// Problem in the trace of a task with its precise location, see `check_trace`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct TraceIssue {
+    pub task: String,
+    /// Path from the task down to the offending section, e.g. `T2 [0, 30] > R1 [10, 20] > R2 [12, 22]`
+    pub location: String,
+    #[serde(flatten)]
+    pub kind: TraceIssueKind,
+}
+
+impl fmt::Display for TraceIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            TraceIssueKind::ReleasedBeforeAcquired => {
+                write!(f, "Error: {} is released before it is acquired.", self.location)
+            }
+            TraceIssueKind::OutsideEnclosing => write!(f, "Error: {} is not within its enclosing section.", self.location),
+            TraceIssueKind::Overlapping { other } => write!(f, "Error: {} overlaps its sibling {}.", self.location, other),
+        }
+    }
+}
+
+pub(super) fn label(trace: &Trace) -> String {
+    format!("{} [{}, {}]", trace.id, trace.start, trace.end)
+}
+
+fn check_section(task: &str, trace: &Trace, location: &str, issues: &mut Vec<TraceIssue>) {
+    let issue = |location: &str, kind| TraceIssue { task: task.to_string(), location: location.to_string(), kind };
+    if trace.start > trace.end {
+        issues.push(issue(location, TraceIssueKind::ReleasedBeforeAcquired));
+    }
+    for inner in &trace.inner {
+        let inner_location = format!("{} > {}", location, label(inner));
+        if inner.start < trace.start || inner.end > trace.end {
+            issues.push(issue(&inner_location, TraceIssueKind::OutsideEnclosing));
+        }
+        check_section(task, inner, &inner_location, issues);
+    }
+
+    let mut siblings: Vec<&Trace> = trace.inner.iter().collect();
+    siblings.sort_by_key(|section| (section.start, section.end));
+    for pair in siblings.windows(2) {
+        if pair[1].start < pair[0].end {
+            let location = format!("{} > {}", location, label(pair[1]));
+            issues.push(issue(&location, TraceIssueKind::Overlapping { other: label(pair[0]) }));
+        }
+    }
+}
+
+/// Every problem in the trace of a task, in pre-order: sections released before they are acquired, sections
+/// outside their enclosing section and overlapping sibling sections.
+///
+/// Unlike `validate_nesting`, checking goes on after the first problem, so an imported trace can be fixed in one go.
+pub fn check_trace(task: &str, trace: &Trace) -> Vec<TraceIssue> {
+    let mut issues = vec![];
+    check_section(task, trace, &label(trace), &mut issues);
+    issues
+}
+
+/// `check_trace` of every task, in the order of the task set.
+pub fn check_traces(tasks: &[Task]) -> Vec<TraceIssue> {
+    tasks.iter().flat_map(|task| check_trace(&task.id, &task.trace)).collect()
+}
@@ -0,0 +1,65 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use super::*;
+
+/// Least C(t) the trace tree allows: the time from the start of the task to the end of its last section, 0
+/// without sections.
+///
+/// Sections of a validated trace do not overlap, see `validate_nesting`, so C(t) is at least this long.
+pub fn sections_wcet(trace: &Trace) -> u32 {
+    trace.inner.iter().map(|section| section.end.saturating_sub(trace.start)).max().unwrap_or(0)
+}
+
+/// Disagreement between C(t) of a task, its trace tree and its measured execution times, see `check_wcets`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum WcetDiscrepancy {
+    /// C(t) ends before the last section does, e.g. after setting C(t) to a measured statistic
+    SectionsBeyondWcet,
+    /// A measured job ran longer than C(t), so C(t) is not a bound
+    MeasuredAboveWcet,
+    /// Every measured job finished before the last section of the trace ends, so the sections are too long
+    MeasuredBelowSections,
+}
+
+/// C(t) of a task as declared by its trace and as derived from its sections and measurements.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct WcetConsistency {
+    pub task: String,
+    /// end - start of the trace, the C(t) the analysis uses
+    pub declared: u32,
+    /// Least C(t) the sections allow, see `sections_wcet`
+    pub sections: u32,
+    /// Largest measured execution time, if the task was measured
+    pub measured: Option<u32>,
+    /// Empty if the three agree
+    pub discrepancies: Vec<WcetDiscrepancy>,
+}
+
+/// Checks C(t) of every task against its trace tree and, for measured tasks, against its largest measured
+/// execution time, in the order of the task set.
+pub fn check_wcets(tasks: &Tasks, measurements: &Measurements) -> Vec<WcetConsistency> {
+    tasks
+        .iter()
+        .map(|task| {
+            let declared = task.wcet();
+            let sections = sections_wcet(&task.trace);
+            let measured = measurements.get(&task.id).and_then(|samples| samples.iter().max().copied());
+
+            let mut discrepancies = vec![];
+            if declared < sections {
+                discrepancies.push(WcetDiscrepancy::SectionsBeyondWcet);
+            }
+            if let Some(measured) = measured {
+                if measured > declared {
+                    discrepancies.push(WcetDiscrepancy::MeasuredAboveWcet);
+                }
+                if measured < sections {
+                    discrepancies.push(WcetDiscrepancy::MeasuredBelowSections);
+                }
+            }
+            WcetConsistency { task: task.id.clone(), declared, sections, measured, discrepancies }
+        })
+        .collect()
+}
@@ -0,0 +1,108 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::*;
+
+/// (m, k) constraint of a task tolerating occasional deadline misses, e.g. a control loop: at most m of any k
+/// consecutive jobs may miss their deadline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct WeaklyHard {
+    /// m, the misses tolerated within a window
+    pub misses: u32,
+    /// k, the number of consecutive jobs in a window
+    pub window: u32,
+}
+
+impl WeaklyHard {
+    pub(super) fn validate(&self, task: &str) -> Result<(), String> {
+        if self.window == 0 || self.misses > self.window {
+            return Err(format!(
+                "Error: Task '{}' has an invalid (m, k) = ({}, {}) constraint, it needs 0 <= m <= k and k >= 1.",
+                task, self.misses, self.window
+            ));
+        }
+        Ok(())
+    }
+
+    /// Largest number of misses within any k consecutive entries of `missed`, the job outcomes in release
+    /// order, and the index of the first job of the earliest window with that many. Fewer than k jobs form a
+    /// single window.
+    pub fn worst_window(&self, missed: &[bool]) -> (u32, usize) {
+        let k = (self.window as usize).clamp(1, missed.len().max(1));
+        let mut count = missed.iter().take(k).filter(|&&miss| miss).count() as u32;
+        let mut worst = (count, 0);
+        for start in 1..=missed.len().saturating_sub(k) {
+            count = count + missed[start + k - 1] as u32 - missed[start - 1] as u32;
+            if count > worst.0 {
+                worst = (count, start);
+            }
+        }
+        worst
+    }
+}
+
+/// Outcome of the (m, k) constraint of a task, see `check_weakly_hard`.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct WeaklyHardCheck {
+    pub task: String,
+    pub constraint: WeaklyHard,
+    /// R(t) <= D(t), every job meets its deadline and the constraint holds without simulation
+    pub hard: bool,
+    /// Simulated jobs whose deadline lies within the horizon, 0 if `hard`
+    pub jobs: u32,
+    /// Largest number of misses within any k consecutive simulated jobs
+    pub misses: u32,
+    /// Number of the first job of the earliest window with that many misses, if there is a miss
+    pub window_start: Option<u32>,
+    pub satisfied: bool,
+}
+
+/// Checks the (m, k) constraint of every task that has one, in the order of the task set.
+///
+/// A task passing the hard analysis under `config` satisfies its constraint. Otherwise the task set is
+/// simulated from a synchronous release over `horizon`, e.g. the hyperperiod plus the longest deadline, and the
+/// jobs with a deadline within it are checked. Late jobs keep running, delaying later jobs. The simulation only
+/// covers one release pattern, so a satisfied constraint is evidence rather than a guarantee for sporadic
+/// tasks or release jitter.
+pub fn check_weakly_hard(tasks: &Tasks, config: &AnalysisConfig, horizon: u32) -> Vec<WeaklyHardCheck> {
+    let results = srp_analyze(tasks, config);
+    let mut simulation: Option<Simulation> = None;
+
+    tasks
+        .iter()
+        .zip(&results)
+        .filter_map(|(task, (_, response_time, ..))| {
+            let constraint = task.weakly_hard?;
+            if matches!(response_time, Ok(r) if *r <= task.deadline) {
+                let check = WeaklyHardCheck {
+                    task: task.id.clone(),
+                    constraint,
+                    hard: true,
+                    jobs: 0,
+                    misses: 0,
+                    window_start: None,
+                    satisfied: true,
+                };
+                return Some(check);
+            }
+
+            let simulation = simulation.get_or_insert_with(|| simulate(&config.prepare(tasks), horizon));
+            let missed: Vec<bool> = simulation
+                .jobs
+                .iter()
+                .filter(|job| job.task == task.id && job.deadline <= horizon)
+                .map(|job| job.completion.is_none_or(|completion| completion > job.deadline))
+                .collect();
+            let (misses, start) = constraint.worst_window(&missed);
+            Some(WeaklyHardCheck {
+                task: task.id.clone(),
+                constraint,
+                hard: false,
+                jobs: missed.len() as u32,
+                misses,
+                window_start: (misses > 0).then_some(start as u32),
+                satisfied: misses <= constraint.misses,
+            })
+        })
+        .collect()
+}